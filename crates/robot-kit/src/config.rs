@@ -20,11 +20,15 @@ pub struct RobotConfig {
 
     /// Safety limits
     pub safety: SafetyConfig,
+
+    /// Simulated world (used by the "sim" drive backend and LIDAR type)
+    #[serde(default)]
+    pub sim: SimConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DriveConfig {
-    /// "ros2", "gpio", "serial", or "mock"
+    /// "ros2", "gpio", "serial", "sim", or "mock"
     pub backend: String,
 
     /// ROS2 topic for cmd_vel (if using ROS2)
@@ -82,7 +86,7 @@ pub struct SensorConfig {
     /// LIDAR device (e.g., "/dev/ttyUSB0")
     pub lidar_port: String,
 
-    /// LIDAR type ("rplidar", "ydlidar", "mock")
+    /// LIDAR type ("rplidar", "ydlidar", "sim", "mock")
     pub lidar_type: String,
 
     /// GPIO pins for motion sensors (BCM numbering)
@@ -153,6 +157,37 @@ pub struct SafetyConfig {
     pub blind_mode_speed_limit: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimConfig {
+    /// Occupancy grid map file (see `sim` module docs for the format)
+    /// Default: none (built-in empty 4m x 4m room)
+    pub map_path: Option<PathBuf>,
+
+    /// Start pose override (x meters, y meters, heading degrees)
+    /// Default: the map's 'R' cell, or the map center, facing +x
+    pub start_pose: Option<(f64, f64, f64)>,
+
+    /// Robot footprint radius for collision checks (meters)
+    /// Default: 0.15m
+    pub robot_radius: f64,
+
+    /// Maximum simulated LIDAR / ultrasonic range (meters)
+    /// Default: 8.0m
+    pub lidar_max_range: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            map_path: None,
+            start_pose: None,
+            robot_radius: 0.15,
+            lidar_max_range: 8.0,
+        }
+    }
+}
+
 impl Default for RobotConfig {
     fn default() -> Self {
         Self {
@@ -197,6 +232,7 @@ impl Default for RobotConfig {
                 sensor_timeout_secs: 5,       // Block if sensors stale 5s
                 blind_mode_speed_limit: 0.2,  // 20% speed without sensors
             },
+            sim: SimConfig::default(),
         }
     }
 }
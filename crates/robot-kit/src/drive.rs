@@ -4,9 +4,11 @@
 //! - ROS2: Publishes geometry_msgs/Twist to cmd_vel topic
 //! - GPIO: Direct PWM control via rppal
 //! - Serial: Arduino/motor controller via serial commands
+//! - Sim: Integrates pose in a simulated 2D world (see [`crate::sim`])
 //! - Mock: Logs commands for testing
 
 use crate::config::RobotConfig;
+use crate::sim::SimWorld;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -26,8 +28,7 @@ trait DriveBackend: Send + Sync {
         duration_ms: u64,
     ) -> Result<()>;
     async fn stop(&self) -> Result<()>;
-    #[allow(dead_code)]
-    async fn get_odometry(&self) -> Result<(f64, f64, f64)>; // x, y, theta - reserved for future odometry integration
+    async fn get_odometry(&self) -> Result<(f64, f64, f64)>; // x, y, theta
 }
 
 /// Mock backend for testing
@@ -63,6 +64,42 @@ impl DriveBackend for MockDrive {
     }
 }
 
+/// Sim backend - drives the robot around a [`SimWorld`]
+struct SimDrive {
+    world: Arc<SimWorld>,
+}
+
+#[async_trait]
+impl DriveBackend for SimDrive {
+    async fn move_robot(
+        &self,
+        linear_x: f64,
+        linear_y: f64,
+        angular_z: f64,
+        duration_ms: u64,
+    ) -> Result<()> {
+        let outcome = self.world.drive(linear_x, linear_y, angular_z, duration_ms);
+        if outcome.collided {
+            anyhow::bail!(
+                "SIM: collision, robot stopped at ({:.2}, {:.2})",
+                outcome.pose.x,
+                outcome.pose.y
+            );
+        }
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        tracing::info!("SIM DRIVE: STOP");
+        Ok(())
+    }
+
+    async fn get_odometry(&self) -> Result<(f64, f64, f64)> {
+        let pose = self.world.pose();
+        Ok((pose.x, pose.y, pose.theta))
+    }
+}
+
 /// ROS2 backend - shells out to ros2 topic pub
 struct Ros2Drive {
     topic: String,
@@ -185,14 +222,36 @@ pub struct DriveTool {
 }
 
 impl DriveTool {
+    /// Drive tool without a sim world.
+    ///
+    /// A "sim" backend needs a world shared with `SenseTool`, so it falls back
+    /// to the mock backend here with a warning; use [`DriveTool::with_sim`] or
+    /// [`crate::create_tools`] to drive a simulated robot.
     pub fn new(config: RobotConfig) -> Self {
-        let backend: Arc<dyn DriveBackend> = match config.drive.backend.as_str() {
-            "ros2" => Arc::new(Ros2Drive {
+        Self::build(config, None)
+    }
+
+    /// Drive tool using an existing sim world (shared with `SenseTool`)
+    /// when `drive.backend` is "sim"
+    pub fn with_sim(config: RobotConfig, world: Arc<SimWorld>) -> Self {
+        Self::build(config, Some(world))
+    }
+
+    fn build(config: RobotConfig, world: Option<Arc<SimWorld>>) -> Self {
+        let backend: Arc<dyn DriveBackend> = match (config.drive.backend.as_str(), world) {
+            ("ros2", _) => Arc::new(Ros2Drive {
                 topic: config.drive.ros2_topic.clone(),
             }),
-            "serial" => Arc::new(SerialDrive {
+            ("serial", _) => Arc::new(SerialDrive {
                 port: config.drive.serial_port.clone(),
             }),
+            ("sim", Some(world)) => Arc::new(SimDrive { world }),
+            ("sim", None) => {
+                tracing::warn!(
+                    "drive.backend is \"sim\" but no sim world was provided; using the mock drive"
+                );
+                Arc::new(MockDrive)
+            }
             // "gpio" => Arc::new(GpioDrive::new(&config)), // Would use rppal
             _ => Arc::new(MockDrive),
         };
@@ -213,7 +272,7 @@ impl Tool for DriveTool {
 
     fn description(&self) -> &str {
         "Move the robot. Supports omni-directional movement (forward, backward, strafe left/right, rotate). \
-         Use 'stop' action to halt immediately. Distance is in meters, rotation in degrees."
+         Use 'stop' action to halt immediately, 'odometry' to read the current pose. Distance is in meters, rotation in degrees."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -222,7 +281,7 @@ impl Tool for DriveTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["forward", "backward", "left", "right", "rotate_left", "rotate_right", "stop", "custom"],
+                    "enum": ["forward", "backward", "left", "right", "rotate_left", "rotate_right", "stop", "odometry", "custom"],
                     "description": "Movement action. 'left'/'right' are strafe (omni wheels). 'rotate_*' spins in place."
                 },
                 "distance": {
@@ -255,6 +314,21 @@ impl Tool for DriveTool {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
 
+        // Odometry is read-only, so it bypasses the rate limit
+        if action == "odometry" {
+            let (x, y, theta) = self.backend.get_odometry().await?;
+            return Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Odometry: x={:.2}m, y={:.2}m, heading={:.1}°",
+                    x,
+                    y,
+                    theta.to_degrees()
+                ),
+                error: None,
+            });
+        }
+
         // Safety: check max drive duration
         {
             let mut last = self.last_command.lock().await;
@@ -419,6 +493,26 @@ mod tests {
         assert!(result.output.contains("stopped"));
     }
 
+    #[tokio::test]
+    async fn drive_sim_updates_odometry() {
+        let mut config = RobotConfig::default();
+        config.drive.backend = "sim".to_string();
+        let world = SimWorld::shared(&config.sim).unwrap();
+        let tool = DriveTool::with_sim(config, world);
+
+        let before = tool.execute(json!({"action": "odometry"})).await.unwrap();
+        assert!(before.output.contains("x=2.00m"));
+
+        let result = tool
+            .execute(json!({"action": "forward", "distance": 0.5}))
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let after = tool.execute(json!({"action": "odometry"})).await.unwrap();
+        assert!(after.output.contains("x=2.50m"), "{}", after.output);
+    }
+
     #[tokio::test]
     async fn drive_unknown_action() {
         let tool = DriveTool::new(RobotConfig::default());
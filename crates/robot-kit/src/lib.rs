@@ -11,6 +11,7 @@
 //! - **Sense**: LIDAR, motion sensors, ultrasonic distance
 //! - **Emote**: LED matrix expressions and sound effects
 //! - **Safety**: Independent safety monitor (collision avoidance, E-stop, watchdog)
+//! - **Sim**: 2D world model (occupancy grid, ray-cast LIDAR, collisions) for hardware-free testing
 //!
 //! ## Architecture
 //!
@@ -95,6 +96,7 @@ pub mod emote;
 pub mod listen;
 pub mod look;
 pub mod sense;
+pub mod sim;
 pub mod speak;
 
 #[cfg(feature = "safety")]
@@ -112,6 +114,7 @@ pub use emote::EmoteTool;
 pub use listen::ListenTool;
 pub use look::LookTool;
 pub use sense::SenseTool;
+pub use sim::SimWorld;
 pub use speak::SpeakTool;
 
 #[cfg(feature = "safety")]
@@ -124,31 +127,52 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 ///
 /// Returns a Vec of boxed tools ready for use with an agent.
 pub fn create_tools(config: &RobotConfig) -> Vec<Box<dyn Tool>> {
+    let (drive, sense) = drive_and_sense(config);
     vec![
-        Box::new(DriveTool::new(config.clone())),
+        Box::new(drive),
         Box::new(LookTool::new(config.clone())),
         Box::new(ListenTool::new(config.clone())),
         Box::new(SpeakTool::new(config.clone())),
-        Box::new(SenseTool::new(config.clone())),
+        Box::new(sense),
         Box::new(EmoteTool::new(config.clone())),
     ]
 }
 
+/// Build drive and sense tools, sharing one sim world when either uses it
+fn drive_and_sense(config: &RobotConfig) -> (DriveTool, SenseTool) {
+    let uses_sim = config.drive.backend == "sim" || config.sensors.lidar_type == "sim";
+    if uses_sim {
+        match SimWorld::shared(&config.sim) {
+            Ok(world) => {
+                return (
+                    DriveTool::with_sim(config.clone(), world.clone()),
+                    SenseTool::with_sim(config.clone(), world),
+                );
+            }
+            Err(e) => tracing::warn!("Sim world unavailable, using mock backends: {e:#}"),
+        }
+    }
+    (
+        DriveTool::new(config.clone()),
+        SenseTool::new(config.clone()),
+    )
+}
+
 /// Create all robot tools with safety wrapper on drive
 #[cfg(feature = "safety")]
 pub fn create_safe_tools(
     config: &RobotConfig,
     safety: std::sync::Arc<SafetyMonitor>,
 ) -> Vec<Box<dyn Tool>> {
-    let drive = std::sync::Arc::new(DriveTool::new(config.clone()));
-    let safe_drive = SafeDrive::new(drive, safety);
+    let (drive, sense) = drive_and_sense(config);
+    let safe_drive = SafeDrive::new(std::sync::Arc::new(drive), safety);
 
     vec![
         Box::new(safe_drive),
         Box::new(LookTool::new(config.clone())),
         Box::new(ListenTool::new(config.clone())),
         Box::new(SpeakTool::new(config.clone())),
-        Box::new(SenseTool::new(config.clone())),
+        Box::new(sense),
         Box::new(EmoteTool::new(config.clone())),
     ]
 }
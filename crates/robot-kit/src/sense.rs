@@ -1,9 +1,10 @@
 //! Sense Tool - LIDAR, motion sensors, ultrasonic distance
 //!
 //! Provides environmental awareness through various sensors.
-//! Supports multiple backends: direct GPIO, ROS2 topics, or mock.
+//! Supports multiple backends: direct GPIO, ROS2 topics, a simulated world, or mock.
 
 use crate::config::RobotConfig;
+use crate::sim::SimWorld;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -32,18 +33,45 @@ pub struct MotionResult {
 pub struct SenseTool {
     config: RobotConfig,
     last_scan: Arc<Mutex<Option<LidarScan>>>,
+    /// Simulated world for LIDAR and ultrasonic readings (lidar_type "sim")
+    sim: Option<Arc<SimWorld>>,
 }
 
 impl SenseTool {
+    /// Sense tool without a sim world.
+    ///
+    /// A "sim" lidar needs a world shared with `DriveTool`, so it reads mock
+    /// data here with a warning; use [`SenseTool::with_sim`] or
+    /// [`crate::create_tools`] to sense a simulated robot.
     pub fn new(config: RobotConfig) -> Self {
+        if config.sensors.lidar_type == "sim" {
+            tracing::warn!(
+                "sensors.lidar_type is \"sim\" but no sim world was provided; using mock readings"
+            );
+        }
+        Self {
+            config,
+            last_scan: Arc::new(Mutex::new(None)),
+            sim: None,
+        }
+    }
+
+    /// Sense tool reading from an existing sim world (shared with `DriveTool`)
+    /// when `sensors.lidar_type` is "sim"
+    pub fn with_sim(config: RobotConfig, world: Arc<SimWorld>) -> Self {
+        let sim = (config.sensors.lidar_type == "sim").then_some(world);
         Self {
             config,
             last_scan: Arc::new(Mutex::new(None)),
+            sim,
         }
     }
 
     /// Read LIDAR scan
     async fn scan_lidar(&self) -> Result<LidarScan> {
+        if let Some(world) = &self.sim {
+            return Ok(self.scan_sim(world));
+        }
         match self.config.sensors.lidar_type.as_str() {
             "rplidar" => self.scan_rplidar().await,
             "ros2" => self.scan_ros2().await,
@@ -51,6 +79,29 @@ impl SenseTool {
         }
     }
 
+    /// Ray-cast LIDAR against the simulated world
+    fn scan_sim(&self, world: &SimWorld) -> LidarScan {
+        let ranges = world.lidar_ranges();
+
+        let nearest = ranges
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, &d)| (d, i as u16))
+            .unwrap_or((999.0, 0));
+
+        let forward_clear = ranges[0..30]
+            .iter()
+            .chain(ranges[330..360].iter())
+            .all(|&d| d > self.config.safety.min_obstacle_distance);
+
+        LidarScan {
+            ranges,
+            nearest,
+            forward_clear,
+        }
+    }
+
     /// Mock LIDAR for testing
     async fn scan_mock(&self) -> Result<LidarScan> {
         // Simulate a room with walls
@@ -205,6 +256,10 @@ impl SenseTool {
 
     /// Read ultrasonic distance sensor
     async fn check_distance(&self) -> Result<f64> {
+        if let Some(world) = &self.sim {
+            return Ok(world.forward_distance());
+        }
+
         let Some((trigger, echo)) = self.config.sensors.ultrasonic_pins else {
             return Ok(999.0); // No sensor configured
         };
@@ -445,6 +500,23 @@ mod tests {
         assert!(result.output.contains("Forward"));
     }
 
+    #[tokio::test]
+    async fn sense_sim_reads_world() {
+        let mut config = RobotConfig::default();
+        config.sensors.lidar_type = "sim".to_string();
+        config.sim.start_pose = Some((3.7, 2.0, 0.0));
+        let world = SimWorld::shared(&config.sim).unwrap();
+        let tool = SenseTool::with_sim(config, world);
+
+        // Default room: east wall inner face at x = 3.9
+        let scan = tool.scan_lidar().await.unwrap();
+        assert!((scan.ranges[0] - 0.2).abs() < 0.05);
+        assert!(!scan.forward_clear);
+
+        let distance = tool.check_distance().await.unwrap();
+        assert!((distance - 0.05).abs() < 0.05);
+    }
+
     #[tokio::test]
     async fn sense_clear_ahead() {
         let tool = SenseTool::new(RobotConfig::default());
@@ -0,0 +1,514 @@
+//! Simulation backend - 2D world model for hardware-free development
+//!
+//! Backs the drive, sense and safety layers with a simulated robot:
+//! - Occupancy grid map loaded from a plain-text map file
+//! - Robot pose integrated from drive commands (omni-directional kinematics)
+//! - Ray-cast LIDAR and forward ultrasonic distance readings
+//! - Collision detection against occupied cells (circular footprint)
+//!
+//! ## Map format
+//!
+//! ```text
+//! ; comment lines start with ';'
+//! resolution 0.1
+//! ##########
+//! #R.......#
+//! #....##..#
+//! ##########
+//! ```
+//!
+//! `#` is an occupied cell, `.` (or space) is free, `R` marks the start cell.
+//! The first grid row is the top of the map (largest y); the robot starts
+//! facing +x. Everything outside the grid counts as occupied.
+
+use crate::config::SimConfig;
+use anyhow::{Context, Result};
+use std::f64::consts::TAU;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Integration step for drive commands (seconds)
+const SIM_STEP_SECS: f64 = 0.02;
+
+/// Points sampled on the robot footprint for collision checks
+const FOOTPRINT_SAMPLES: usize = 16;
+
+/// Side length of the built-in room when no map file is configured (meters)
+const DEFAULT_ROOM_SIZE: f64 = 4.0;
+const DEFAULT_RESOLUTION: f64 = 0.1;
+
+/// Robot pose in world coordinates (meters, radians)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub x: f64,
+    pub y: f64,
+    pub theta: f64,
+}
+
+/// 2D occupancy grid
+#[derive(Debug, Clone)]
+pub struct OccupancyGrid {
+    /// Cell size in meters
+    pub resolution: f64,
+    pub width: usize,
+    pub height: usize,
+    /// Row-major, row 0 = bottom of the map
+    cells: Vec<bool>,
+    /// Center of the `R` cell, if the map marks one
+    start: Option<(f64, f64)>,
+}
+
+impl OccupancyGrid {
+    /// Parse a text map (see module docs for the format)
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut resolution = DEFAULT_RESOLUTION;
+        let mut rows: Vec<&str> = Vec::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim_end();
+            if trimmed.trim_start().starts_with(';') {
+                continue;
+            }
+            if let Some(value) = trimmed.trim_start().strip_prefix("resolution") {
+                resolution = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid map resolution: {}", value.trim()))?;
+                continue;
+            }
+            if trimmed.is_empty() && rows.is_empty() {
+                continue;
+            }
+            rows.push(trimmed);
+        }
+        while rows.last().is_some_and(|r| r.is_empty()) {
+            rows.pop();
+        }
+
+        if !(resolution.is_finite() && resolution > 0.0) {
+            anyhow::bail!("Map resolution must be positive");
+        }
+        if rows.is_empty() {
+            anyhow::bail!("Map has no grid rows");
+        }
+
+        let height = rows.len();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        let mut cells = vec![false; width * height];
+        let mut start = None;
+
+        for (file_row, line) in rows.iter().enumerate() {
+            let row = height - 1 - file_row;
+            for (col, ch) in line.chars().enumerate() {
+                match ch {
+                    '#' => cells[row * width + col] = true,
+                    '.' | ' ' => {}
+                    'R' => {
+                        start = Some((
+                            (col as f64 + 0.5) * resolution,
+                            (row as f64 + 0.5) * resolution,
+                        ));
+                    }
+                    other => anyhow::bail!("Unknown map cell '{other}' at row {file_row}"),
+                }
+            }
+        }
+
+        Ok(Self {
+            resolution,
+            width,
+            height,
+            cells,
+            start,
+        })
+    }
+
+    /// Load a map file
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read sim map {}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// Empty square room with walls on the border
+    pub fn empty_room(size_m: f64, resolution: f64) -> Self {
+        let cells_per_side = ((size_m / resolution).round() as usize).max(3);
+        let mut cells = vec![false; cells_per_side * cells_per_side];
+        for i in 0..cells_per_side {
+            cells[i] = true;
+            cells[(cells_per_side - 1) * cells_per_side + i] = true;
+            cells[i * cells_per_side] = true;
+            cells[i * cells_per_side + cells_per_side - 1] = true;
+        }
+        Self {
+            resolution,
+            width: cells_per_side,
+            height: cells_per_side,
+            cells,
+            start: None,
+        }
+    }
+
+    /// Is the world point (x, y) occupied? Points off the map are occupied.
+    pub fn is_occupied(&self, x: f64, y: f64) -> bool {
+        if x < 0.0 || y < 0.0 {
+            return true;
+        }
+        let col = (x / self.resolution) as usize;
+        let row = (y / self.resolution) as usize;
+        if col >= self.width || row >= self.height {
+            return true;
+        }
+        self.cells[row * self.width + col]
+    }
+
+    /// Distance from (x, y) along `angle` to the first occupied cell,
+    /// capped at `max_range`
+    pub fn raycast(&self, x: f64, y: f64, angle: f64, max_range: f64) -> f64 {
+        let step = self.resolution / 4.0;
+        let (sin, cos) = angle.sin_cos();
+        let mut travelled = 0.0;
+        while travelled < max_range {
+            if self.is_occupied(x + cos * travelled, y + sin * travelled) {
+                return travelled;
+            }
+            travelled += step;
+        }
+        max_range
+    }
+
+    fn width_m(&self) -> f64 {
+        self.width as f64 * self.resolution
+    }
+
+    fn height_m(&self) -> f64 {
+        self.height as f64 * self.resolution
+    }
+}
+
+/// Result of integrating a drive command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepOutcome {
+    pub pose: Pose,
+    /// The command was cut short by a collision
+    pub collided: bool,
+}
+
+#[derive(Debug)]
+struct SimState {
+    pose: Pose,
+    collisions: u32,
+    /// Set on collision, cleared once read by a sensor feed
+    bump_pending: bool,
+}
+
+/// Simulated robot in a 2D world, shared between the sim backends
+#[derive(Debug)]
+pub struct SimWorld {
+    grid: OccupancyGrid,
+    robot_radius: f64,
+    lidar_max_range: f64,
+    state: Mutex<SimState>,
+}
+
+impl SimWorld {
+    pub fn new(grid: OccupancyGrid, start: Pose, robot_radius: f64, lidar_max_range: f64) -> Self {
+        Self {
+            grid,
+            robot_radius: robot_radius.max(0.0),
+            lidar_max_range: lidar_max_range.max(0.1),
+            state: Mutex::new(SimState {
+                pose: start,
+                collisions: 0,
+                bump_pending: false,
+            }),
+        }
+    }
+
+    /// Build a world from config: map file (or built-in room) and start pose
+    pub fn from_config(config: &SimConfig) -> Result<Self> {
+        let grid = match &config.map_path {
+            Some(path) => OccupancyGrid::load(path)?,
+            None => OccupancyGrid::empty_room(DEFAULT_ROOM_SIZE, DEFAULT_RESOLUTION),
+        };
+        let start = match config.start_pose {
+            Some((x, y, theta_deg)) => Pose {
+                x,
+                y,
+                theta: theta_deg.to_radians(),
+            },
+            None => {
+                let (x, y) = grid
+                    .start
+                    .unwrap_or((grid.width_m() / 2.0, grid.height_m() / 2.0));
+                Pose { x, y, theta: 0.0 }
+            }
+        };
+        let world = Self::new(grid, start, config.robot_radius, config.lidar_max_range);
+        if world.footprint_collides(&start) {
+            anyhow::bail!(
+                "Sim start pose ({:.2}, {:.2}) collides with the map",
+                start.x,
+                start.y
+            );
+        }
+        Ok(world)
+    }
+
+    /// Shared world for wiring into several tools
+    pub fn shared(config: &SimConfig) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self::from_config(config)?))
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, SimState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub fn grid(&self) -> &OccupancyGrid {
+        &self.grid
+    }
+
+    pub fn pose(&self) -> Pose {
+        self.lock_state().pose
+    }
+
+    /// Teleport the robot (ignores collisions)
+    pub fn set_pose(&self, pose: Pose) {
+        self.lock_state().pose = pose;
+    }
+
+    /// Total collisions since the world was created
+    pub fn collisions(&self) -> u32 {
+        self.lock_state().collisions
+    }
+
+    /// Returns true once per collision (for bump sensor feeds)
+    pub fn take_bump(&self) -> bool {
+        std::mem::take(&mut self.lock_state().bump_pending)
+    }
+
+    fn footprint_collides(&self, pose: &Pose) -> bool {
+        if self.grid.is_occupied(pose.x, pose.y) {
+            return true;
+        }
+        (0..FOOTPRINT_SAMPLES).any(|i| {
+            let angle = TAU * i as f64 / FOOTPRINT_SAMPLES as f64;
+            self.grid.is_occupied(
+                pose.x + self.robot_radius * angle.cos(),
+                pose.y + self.robot_radius * angle.sin(),
+            )
+        })
+    }
+
+    /// Integrate a velocity command (robot frame: m/s, m/s, rad/s) for
+    /// `duration_ms`. Stops at the last collision-free pose on impact.
+    pub fn drive(
+        &self,
+        linear_x: f64,
+        linear_y: f64,
+        angular_z: f64,
+        duration_ms: u64,
+    ) -> StepOutcome {
+        let mut state = self.lock_state();
+        let mut remaining = duration_ms as f64 / 1000.0;
+
+        while remaining > 0.0 {
+            let dt = remaining.min(SIM_STEP_SECS);
+            remaining -= dt;
+
+            let pose = state.pose;
+            let (sin, cos) = pose.theta.sin_cos();
+            let next = Pose {
+                x: pose.x + (linear_x * cos - linear_y * sin) * dt,
+                y: pose.y + (linear_x * sin + linear_y * cos) * dt,
+                theta: (pose.theta + angular_z * dt).rem_euclid(TAU),
+            };
+
+            if self.footprint_collides(&next) {
+                state.collisions += 1;
+                state.bump_pending = true;
+                tracing::warn!("SIM: collision at ({:.2}, {:.2}), stopping", next.x, next.y);
+                return StepOutcome {
+                    pose: state.pose,
+                    collided: true,
+                };
+            }
+            state.pose = next;
+        }
+
+        StepOutcome {
+            pose: state.pose,
+            collided: false,
+        }
+    }
+
+    /// 360 LIDAR ranges (1 per degree, robot frame: 0° forward, 90° left)
+    pub fn lidar_ranges(&self) -> Vec<f64> {
+        let pose = self.pose();
+        (0..360)
+            .map(|deg| {
+                let angle = pose.theta + f64::from(deg).to_radians();
+                self.grid
+                    .raycast(pose.x, pose.y, angle, self.lidar_max_range)
+            })
+            .collect()
+    }
+
+    /// Forward-facing ultrasonic distance from the robot's edge
+    pub fn forward_distance(&self) -> f64 {
+        let pose = self.pose();
+        let center = self
+            .grid
+            .raycast(pose.x, pose.y, pose.theta, self.lidar_max_range);
+        (center - self.robot_radius).max(0.0)
+    }
+}
+
+/// Stream simulated sensor readings into a [`crate::SafetyMonitor`]
+///
+/// Sends the nearest LIDAR return every `interval` and a bump reading after
+/// each collision. Stops when the receiver is dropped.
+#[cfg(feature = "safety")]
+pub fn spawn_safety_feed(
+    world: Arc<SimWorld>,
+    tx: tokio::sync::mpsc::Sender<crate::safety::SensorReading>,
+    interval: std::time::Duration,
+) -> tokio::task::JoinHandle<()> {
+    use crate::safety::SensorReading;
+
+    tokio::spawn(async move {
+        loop {
+            let ranges = world.lidar_ranges();
+            let (angle, distance) = ranges
+                .iter()
+                .copied()
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((0, f64::MAX));
+            let reading = SensorReading::Lidar {
+                distance,
+                angle: angle as u16,
+            };
+            if tx.send(reading).await.is_err() {
+                break;
+            }
+            if world.take_bump() {
+                let bump = SensorReading::Bump {
+                    sensor: "sim".to_string(),
+                };
+                if tx.send(bump).await.is_err() {
+                    break;
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRIDOR: &str = "\
+; 2m x 0.6m corridor with the robot at the west end
+resolution 0.1
+######################
+#R...................#
+#....................#
+#....................#
+#....................#
+######################
+";
+
+    fn corridor_world() -> SimWorld {
+        let grid = OccupancyGrid::parse(CORRIDOR).unwrap();
+        let (x, y) = grid.start.unwrap();
+        SimWorld::new(
+            grid,
+            Pose {
+                x: x + 0.1,
+                y: y - 0.15,
+                theta: 0.0,
+            },
+            0.1,
+            5.0,
+        )
+    }
+
+    #[test]
+    fn parse_map_reads_resolution_and_start() {
+        let grid = OccupancyGrid::parse(CORRIDOR).unwrap();
+        assert_eq!(grid.width, 22);
+        assert_eq!(grid.height, 6);
+        assert!((grid.resolution - 0.1).abs() < f64::EPSILON);
+        let (x, y) = grid.start.unwrap();
+        assert!((x - 0.15).abs() < 1e-9);
+        assert!((y - 0.45).abs() < 1e-9);
+        assert!(grid.is_occupied(0.05, 0.05));
+        assert!(!grid.is_occupied(1.0, 0.3));
+        assert!(grid.is_occupied(-1.0, 0.3));
+    }
+
+    #[test]
+    fn parse_map_rejects_unknown_cells() {
+        assert!(OccupancyGrid::parse("###\n#?#\n###").is_err());
+    }
+
+    #[test]
+    fn drive_integrates_pose_in_robot_frame() {
+        let world = corridor_world();
+        let start = world.pose();
+        let outcome = world.drive(0.5, 0.0, 0.0, 1000);
+        assert!(!outcome.collided);
+        assert!((outcome.pose.x - start.x - 0.5).abs() < 1e-6);
+        assert!((outcome.pose.y - start.y).abs() < 1e-6);
+
+        world.drive(0.0, 0.0, std::f64::consts::FRAC_PI_2, 1000);
+        let turned = world.pose();
+        assert!((turned.theta - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn drive_stops_at_wall_and_counts_collision() {
+        let world = corridor_world();
+        let outcome = world.drive(1.0, 0.0, 0.0, 5000);
+        assert!(outcome.collided);
+        assert_eq!(world.collisions(), 1);
+        assert!(world.take_bump());
+        assert!(!world.take_bump());
+        // East wall starts at x = 2.1; robot edge must stay clear of it.
+        assert!(outcome.pose.x + 0.1 <= 2.1);
+        assert!(outcome.pose.x > 1.8);
+    }
+
+    #[test]
+    fn lidar_ranges_follow_robot_heading() {
+        let world = corridor_world();
+        let pose = world.pose();
+        let ranges = world.lidar_ranges();
+        assert_eq!(ranges.len(), 360);
+        // Forward: east wall at x = 2.1
+        assert!((ranges[0] - (2.1 - pose.x)).abs() < 0.05);
+        // Behind: west wall ends at x = 0.1
+        assert!((ranges[180] - (pose.x - 0.1)).abs() < 0.05);
+
+        world.set_pose(Pose {
+            theta: std::f64::consts::PI,
+            ..pose
+        });
+        let flipped = world.lidar_ranges();
+        assert!((flipped[0] - ranges[180]).abs() < 0.05);
+    }
+
+    #[test]
+    fn from_config_rejects_start_inside_wall() {
+        let config = SimConfig {
+            start_pose: Some((0.0, 0.0, 0.0)),
+            ..SimConfig::default()
+        };
+        assert!(SimWorld::from_config(&config).is_err());
+        assert!(SimWorld::from_config(&SimConfig::default()).is_ok());
+    }
+}
@@ -235,10 +235,10 @@ impl Agent {
             Arc::from(observability::create_observer(&config.observability));
         let runtime: Arc<dyn runtime::RuntimeAdapter> =
             Arc::from(runtime::create_runtime(&config.runtime)?);
-        let security = Arc::new(SecurityPolicy::from_config(
-            &config.autonomy,
-            &config.workspace_dir,
-        ));
+        let security = Arc::new(
+            SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+                .with_egress(&config.security.egress),
+        );
 
         let memory: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
             &config.memory,
@@ -479,6 +479,7 @@ impl Agent {
                         } else {
                             None
                         },
+                        response_schema: None,
                     },
                     &effective_model,
                     self.temperature,
@@ -518,6 +519,7 @@ impl Agent {
             self.history.push(ConversationMessage::AssistantToolCalls {
                 text: response.text.clone(),
                 tool_calls: response.tool_calls.clone(),
+                reasoning: response.reasoning.clone(),
             });
 
             let results = self.execute_tools(&calls).await;
@@ -651,6 +653,7 @@ mod tests {
                     text: Some("done".into()),
                     tool_calls: vec![],
                     usage: None,
+                    reasoning: None,
                 });
             }
             Ok(guard.remove(0))
@@ -689,6 +692,7 @@ mod tests {
                 text: Some("hello".into()),
                 tool_calls: vec![],
                 usage: None,
+                reasoning: None,
             }]),
         });
 
@@ -728,11 +732,13 @@ mod tests {
                         arguments: "{}".into(),
                     }],
                     usage: None,
+                    reasoning: None,
                 },
                 crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
                     usage: None,
+                    reasoning: None,
                 },
             ]),
         });
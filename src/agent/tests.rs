@@ -32,8 +32,8 @@ use crate::config::{AgentConfig, MemoryConfig};
 use crate::memory::{self, Memory};
 use crate::observability::{NoopObserver, Observer};
 use crate::providers::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ToolCall,
-    ToolResultMessage,
+    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ReasoningContent,
+    ToolCall, ToolResultMessage,
 };
 use crate::tools::{Tool, ToolResult};
 use anyhow::Result;
@@ -94,6 +94,7 @@ impl Provider for ScriptedProvider {
                 text: Some("done".into()),
                 tool_calls: vec![],
                 usage: None,
+                reasoning: None,
             });
         }
         Ok(guard.remove(0))
@@ -330,6 +331,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
         usage: None,
+        reasoning: None,
     }
 }
 
@@ -339,6 +341,7 @@ fn text_response(text: &str) -> ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     }
 }
 
@@ -350,6 +353,7 @@ fn xml_tool_response(name: &str, args: &str) -> ChatResponse {
         )),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     }
 }
 
@@ -739,6 +743,7 @@ async fn turn_handles_empty_text_response() {
         text: Some(String::new()),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -753,6 +758,7 @@ async fn turn_handles_none_text_response() {
         text: None,
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -777,6 +783,7 @@ async fn turn_preserves_text_alongside_tool_calls() {
                 arguments: r#"{"message": "hi"}"#.into(),
             }],
             usage: None,
+            reasoning: None,
         },
         text_response("Here are the results"),
     ]));
@@ -1014,6 +1021,7 @@ async fn native_dispatcher_handles_stringified_arguments() {
             arguments: r#"{"message": "hello"}"#.into(),
         }],
         usage: None,
+        reasoning: None,
     };
 
     let (_, calls) = dispatcher.parse_response(&response);
@@ -1040,6 +1048,7 @@ fn xml_dispatcher_handles_nested_json() {
         ),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1058,6 +1067,7 @@ fn xml_dispatcher_handles_empty_tool_call_tag() {
         text: Some("<tool_call>\n</tool_call>\nSome text".into()),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1072,6 +1082,7 @@ fn xml_dispatcher_handles_unclosed_tool_call() {
         text: Some("Before\n<tool_call>\n{\"name\": \"shell\"}".into()),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1097,6 +1108,10 @@ fn conversation_message_serialization_roundtrip() {
                 name: "shell".into(),
                 arguments: "{}".into(),
             }],
+            reasoning: Some(ReasoningContent {
+                text: "need to look".into(),
+                provider_blocks: vec![serde_json::json!({"type": "thinking", "signature": "sig"})],
+            }),
         },
         ConversationMessage::ToolResults(vec![ToolResultMessage {
             tool_call_id: "tc1".into(),
@@ -1119,12 +1134,15 @@ fn conversation_message_serialization_roundtrip() {
                 ConversationMessage::AssistantToolCalls {
                     text: a_text,
                     tool_calls: a_calls,
+                    reasoning: a_reasoning,
                 },
                 ConversationMessage::AssistantToolCalls {
                     text: b_text,
                     tool_calls: b_calls,
+                    reasoning: b_reasoning,
                 },
             ) => {
+                assert_eq!(a_reasoning, b_reasoning);
                 assert_eq!(a_text, b_text);
                 assert_eq!(a_calls.len(), b_calls.len());
             }
@@ -1219,6 +1237,8 @@ fn xml_dispatcher_converts_history_to_provider_messages() {
                 name: "shell".into(),
                 arguments: "{}".into(),
             }],
+
+            reasoning: None,
         },
         ConversationMessage::ToolResults(vec![ToolResultMessage {
             tool_call_id: "tc1".into(),
@@ -1255,6 +1275,29 @@ fn native_dispatcher_converts_tool_results_to_tool_messages() {
     assert_eq!(messages[1].role, "tool");
 }
 
+#[test]
+fn native_dispatcher_replays_reasoning_with_tool_calls() {
+    let dispatcher = NativeToolDispatcher;
+    let block = serde_json::json!({"type": "thinking", "thinking": "hmm", "signature": "sig"});
+    let history = vec![ConversationMessage::AssistantToolCalls {
+        text: None,
+        tool_calls: vec![ToolCall {
+            id: "tc1".into(),
+            name: "shell".into(),
+            arguments: "{}".into(),
+        }],
+        reasoning: Some(ReasoningContent {
+            text: "hmm".into(),
+            provider_blocks: vec![block.clone()],
+        }),
+    }];
+
+    let messages = dispatcher.to_provider_messages(&history);
+    let payload: serde_json::Value = serde_json::from_str(&messages[0].content).unwrap();
+    assert_eq!(payload["reasoning"]["provider_blocks"][0], block);
+    assert_eq!(payload["tool_calls"][0]["id"], "tc1");
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // 23. XML tool instructions generation
 // ═══════════════════════════════════════════════════════════════════════════
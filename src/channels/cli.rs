@@ -48,6 +48,8 @@ impl Channel for CliChannel {
                     .unwrap_or_default()
                     .as_secs(),
                 thread_ts: None,
+                group: None,
+                voice: false,
             };
 
             if tx.send(msg).await.is_err() {
@@ -111,6 +113,8 @@ mod tests {
             channel: "cli".into(),
             timestamp: 1_234_567_890,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -130,6 +134,8 @@ mod tests {
             channel: "ch".into(),
             timestamp: 0,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -275,6 +275,8 @@ impl Channel for DingTalkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group: None,
+                        voice: false,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
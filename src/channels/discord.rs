@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::markup::{render_markdown, split_markdown, MarkupFormat};
+use super::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
+use super::tts::SpeechAudio;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -15,6 +17,7 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    transcription: Option<crate::config::TranscriptionConfig>,
 }
 
 impl DiscordChannel {
@@ -32,9 +35,18 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handles: Mutex::new(HashMap::new()),
+            transcription: None,
         }
     }
 
+    /// Configure transcription of incoming voice messages.
+    pub fn with_transcription(mut self, config: crate::config::TranscriptionConfig) -> Self {
+        if config.enabled {
+            self.transcription = Some(config);
+        }
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -51,6 +63,68 @@ impl DiscordChannel {
         let part = token.split('.').next()?;
         base64_decode(part)
     }
+
+    /// Download and transcribe the audio of a voice message. `None` when
+    /// transcription is off, the message has no voice clip, or it fails.
+    async fn transcribe_voice_message(&self, d: &serde_json::Value) -> Option<String> {
+        let config = self.transcription.as_ref()?;
+        let (url, file_name) = voice_message_attachment(d)?;
+
+        let audio = match self.http_client().get(url).send().await {
+            Ok(resp) if resp.status().is_success() => resp.bytes().await.ok()?,
+            Ok(resp) => {
+                tracing::warn!(status = %resp.status(), "discord voice message fetch failed");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "discord voice message fetch error");
+                return None;
+            }
+        };
+
+        match super::transcription::transcribe_audio(audio.to_vec(), file_name, config).await {
+            Ok(text) if !text.trim().is_empty() => Some(text),
+            Ok(_) => {
+                tracing::info!("Discord voice transcription returned empty text, skipping");
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Discord voice transcription failed: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Message flag Discord sets on (and requires for) voice messages.
+const DISCORD_VOICE_MESSAGE_FLAG: u64 = 1 << 13;
+
+/// URL and file name of the audio clip in a voice message.
+fn voice_message_attachment(d: &serde_json::Value) -> Option<(&str, &str)> {
+    let flags = d
+        .get("flags")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0);
+    if flags & DISCORD_VOICE_MESSAGE_FLAG == 0 {
+        return None;
+    }
+    let attachment = d.get("attachments")?.as_array()?.iter().find(|att| {
+        att.get("content_type")
+            .and_then(|v| v.as_str())
+            .is_some_and(|ct| ct.starts_with("audio/"))
+    })?;
+    let url = attachment.get("url")?.as_str()?;
+    let file_name = attachment
+        .get("filename")
+        .and_then(|v| v.as_str())
+        .unwrap_or("voice-message.ogg");
+    Some((url, file_name))
+}
+
+/// Voice messages must carry a waveform; synthesized replies get a flat one
+/// (base64 of 255 mid-level samples).
+fn flat_voice_waveform() -> String {
+    "gICA".repeat(85)
 }
 
 /// Process Discord message attachments and return a string to append to the
@@ -109,51 +183,9 @@ const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstu
 const DISCORD_MAX_MESSAGE_LENGTH: usize = 2000;
 
 /// Split a message into chunks that respect Discord's 2000-character limit.
-/// Tries to split at word boundaries when possible.
+/// Breaks fall between Markdown blocks, then lines, then words.
 fn split_message_for_discord(message: &str) -> Vec<String> {
-    if message.chars().count() <= DISCORD_MAX_MESSAGE_LENGTH {
-        return vec![message.to_string()];
-    }
-
-    let mut chunks = Vec::new();
-    let mut remaining = message;
-
-    while !remaining.is_empty() {
-        // Find the byte offset for the 2000th character boundary.
-        // If there are fewer than 2000 chars left, we can emit the tail directly.
-        let hard_split = remaining
-            .char_indices()
-            .nth(DISCORD_MAX_MESSAGE_LENGTH)
-            .map_or(remaining.len(), |(idx, _)| idx);
-
-        let chunk_end = if hard_split == remaining.len() {
-            hard_split
-        } else {
-            // Try to find a good break point (newline, then space)
-            let search_area = &remaining[..hard_split];
-
-            // Prefer splitting at newline
-            if let Some(pos) = search_area.rfind('\n') {
-                // Don't split if the newline is too close to the end
-                if search_area[..pos].chars().count() >= DISCORD_MAX_MESSAGE_LENGTH / 2 {
-                    pos + 1
-                } else {
-                    // Try space as fallback
-                    search_area.rfind(' ').map_or(hard_split, |space| space + 1)
-                }
-            } else if let Some(pos) = search_area.rfind(' ') {
-                pos + 1
-            } else {
-                // Hard split at the limit
-                hard_split
-            }
-        };
-
-        chunks.push(remaining[..chunk_end].to_string());
-        remaining = &remaining[chunk_end..];
-    }
-
-    chunks
+    split_markdown(message, DISCORD_MAX_MESSAGE_LENGTH)
 }
 
 /// URL-encode a Unicode emoji for use in Discord reaction API paths.
@@ -182,6 +214,48 @@ fn contains_bot_mention(content: &str, bot_user_id: &str) -> bool {
     content.contains(&tags[0]) || content.contains(&tags[1])
 }
 
+/// Room metadata for guild channel messages; DMs carry no `guild_id`.
+fn guild_group_context(
+    d: &serde_json::Value,
+    bot_user_id: &str,
+    channel_id: &str,
+) -> Option<GroupContext> {
+    d.get("guild_id")?;
+    let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
+    let mentioned = contains_bot_mention(content, bot_user_id)
+        || d.get("mentions")
+            .and_then(|m| m.as_array())
+            .is_some_and(|mentions| {
+                mentions
+                    .iter()
+                    .any(|u| u.get("id").and_then(|i| i.as_str()) == Some(bot_user_id))
+            });
+    let replied_to_bot = d
+        .get("referenced_message")
+        .and_then(|r| r.get("author"))
+        .and_then(|a| a.get("id"))
+        .and_then(|i| i.as_str())
+        == Some(bot_user_id);
+    let author = d.get("author");
+    let sender_name = author
+        .and_then(|a| a.get("global_name"))
+        .and_then(|n| n.as_str())
+        .or_else(|| {
+            author
+                .and_then(|a| a.get("username"))
+                .and_then(|n| n.as_str())
+        })
+        .unwrap_or("unknown")
+        .to_string();
+
+    Some(GroupContext {
+        room: channel_id.to_string(),
+        sender_name,
+        mentioned,
+        replied_to_bot,
+    })
+}
+
 fn normalize_incoming_content(
     content: &str,
     mention_only: bool,
@@ -256,6 +330,7 @@ impl Channel for DiscordChannel {
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let content = super::strip_tool_call_tags(&message.content);
+        let content = render_markdown(&content, MarkupFormat::Markdown);
         let chunks = split_message_for_discord(&content);
 
         for (i, chunk) in chunks.iter().enumerate() {
@@ -455,10 +530,21 @@ impl Channel for DiscordChannel {
                     }
 
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                    let Some(clean_content) =
-                        normalize_incoming_content(content, self.mention_only, &bot_user_id)
-                    else {
-                        continue;
+                    let (clean_content, voice) = if let Some(text) = self.transcribe_voice_message(d).await {
+                        // Voice messages have no text to @-mention the bot in; a reply to it counts.
+                        let addressed = guild_group_context(d, &bot_user_id, "")
+                            .is_none_or(|group| group.mentioned || group.replied_to_bot);
+                        if self.mention_only && !addressed {
+                            continue;
+                        }
+                        (format!("[Voice] {text}"), true)
+                    } else {
+                        let Some(clean_content) =
+                            normalize_incoming_content(content, self.mention_only, &bot_user_id)
+                        else {
+                            continue;
+                        };
+                        (clean_content, false)
                     };
 
                     let attachment_text = {
@@ -497,6 +583,8 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group: guild_group_context(d, &bot_user_id, &channel_id),
+                        voice,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -509,6 +597,67 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    async fn send_voice_reply(
+        &self,
+        message: &SendMessage,
+        audio: &SpeechAudio,
+    ) -> anyhow::Result<()> {
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+
+        // Only Ogg/Opus can be posted as a native voice message; other formats
+        // go out as a regular file attachment.
+        let (file_name, payload) = if audio.is_voice_note() {
+            let file_name = "voice-message.ogg".to_string();
+            let payload = json!({
+                "flags": DISCORD_VOICE_MESSAGE_FLAG,
+                "attachments": [{
+                    "id": 0,
+                    "filename": file_name,
+                    "duration_secs": audio.duration_secs().unwrap_or(1.0),
+                    "waveform": flat_voice_waveform(),
+                }],
+            });
+            (file_name, payload)
+        } else {
+            let file_name = audio.file_name();
+            let payload = json!({ "attachments": [{ "id": 0, "filename": file_name }] });
+            (file_name, payload)
+        };
+
+        let part = reqwest::multipart::Part::bytes(audio.bytes.clone())
+            .file_name(file_name)
+            .mime_str(audio.format.mime_type())?;
+        let form = reqwest::multipart::Form::new()
+            .text("payload_json", payload.to_string())
+            .part("files[0]", part);
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord voice message failed ({status}): {err}");
+        }
+
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         self.http_client()
             .get("https://discord.com/api/v10/users/@me")
@@ -736,6 +885,55 @@ mod tests {
         assert!(cleaned.is_none());
     }
 
+    #[test]
+    fn guild_group_context_detects_mentions_and_replies() {
+        let d = serde_json::json!({
+            "guild_id": "g1",
+            "content": "hey <@12345> status?",
+            "author": { "id": "7", "username": "alice", "global_name": "Alice" }
+        });
+        let group = guild_group_context(&d, "12345", "c1").unwrap();
+        assert_eq!(group.room, "c1");
+        assert_eq!(group.sender_name, "Alice");
+        assert!(group.mentioned);
+        assert!(!group.replied_to_bot);
+
+        let reply = serde_json::json!({
+            "guild_id": "g1",
+            "content": "thanks",
+            "author": { "id": "7", "username": "alice" },
+            "referenced_message": { "author": { "id": "12345" } }
+        });
+        let group = guild_group_context(&reply, "12345", "c1").unwrap();
+        assert_eq!(group.sender_name, "alice");
+        assert!(group.replied_to_bot);
+
+        let dm = serde_json::json!({ "content": "hi", "author": { "id": "7" } });
+        assert!(guild_group_context(&dm, "12345", "c1").is_none());
+    }
+
+    #[test]
+    fn voice_message_attachment_requires_voice_flag_and_audio() {
+        let voice = serde_json::json!({
+            "flags": 8192,
+            "attachments": [
+                { "url": "https://cdn/x.txt", "content_type": "text/plain", "filename": "x.txt" },
+                { "url": "https://cdn/voice-message.ogg", "content_type": "audio/ogg", "filename": "voice-message.ogg" }
+            ]
+        });
+        assert_eq!(
+            voice_message_attachment(&voice),
+            Some(("https://cdn/voice-message.ogg", "voice-message.ogg"))
+        );
+
+        let plain_audio = serde_json::json!({
+            "flags": 0,
+            "attachments": [{ "url": "https://cdn/song.mp3", "content_type": "audio/mpeg" }]
+        });
+        assert!(voice_message_attachment(&plain_audio).is_none());
+        assert_eq!(flat_voice_waveform().len(), 340);
+    }
+
     #[test]
     fn normalize_incoming_content_strips_mentions_and_trims() {
         let cleaned = normalize_incoming_content("  <@!12345> run status  ", true, "12345");
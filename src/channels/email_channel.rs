@@ -14,7 +14,7 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::MultiPart;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -22,9 +22,11 @@ use rustls::{ClientConfig, RootCertStore};
 use rustls_pki_types::DnsName;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::{sleep, timeout};
@@ -33,6 +35,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::markup::{render_markdown, MarkupFormat};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 
 /// Email channel configuration
@@ -114,10 +117,77 @@ impl Default for EmailConfig {
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// Attachments larger than this are listed but not saved.
+const MAX_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Threads remembered for reply headers; the oldest are forgotten first.
+const MAX_THREADS: usize = 1024;
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    /// Known threads keyed by root Message-ID (the inbound `thread_ts`).
+    threads: Arc<Mutex<ThreadCache>>,
+    workspace_dir: Option<PathBuf>,
+}
+
+/// Headers needed to reply inside an existing email thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmailThread {
+    /// Message-ID of the newest message in the thread (the reply parent).
+    last_message_id: String,
+    /// Full Message-ID chain, oldest first, for the `References` header.
+    references: Vec<String>,
+    subject: String,
+}
+
+/// Bounded map of known threads, evicted in insertion order.
+#[derive(Debug, Default)]
+struct ThreadCache {
+    order: VecDeque<String>,
+    by_root: HashMap<String, EmailThread>,
+}
+
+impl ThreadCache {
+    fn get(&self, root: &str) -> Option<&EmailThread> {
+        self.by_root.get(root)
+    }
+
+    fn get_or_insert_with(
+        &mut self,
+        root: &str,
+        default: impl FnOnce() -> EmailThread,
+    ) -> &mut EmailThread {
+        if !self.by_root.contains_key(root) {
+            self.track(root);
+        }
+        self.by_root.entry(root.to_string()).or_insert_with(default)
+    }
+
+    fn insert(&mut self, root: &str, thread: EmailThread) {
+        if self.by_root.insert(root.to_string(), thread).is_none() {
+            self.track(root);
+        }
+    }
+
+    /// Record a new root and evict the oldest one past `MAX_THREADS`.
+    fn track(&mut self, root: &str) {
+        self.order.push_back(root.to_string());
+        if self.order.len() > MAX_THREADS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_root.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// An inbound attachment extracted from a MIME message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmailAttachment {
+    name: String,
+    content_type: String,
+    data: Vec<u8>,
 }
 
 impl EmailChannel {
@@ -125,9 +195,17 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            threads: Arc::new(Mutex::new(ThreadCache::default())),
+            workspace_dir: None,
         }
     }
 
+    /// Configure workspace directory for saving inbound attachments.
+    pub fn with_workspace_dir(mut self, dir: PathBuf) -> Self {
+        self.workspace_dir = Some(dir);
+        self
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
         if self.config.allowed_senders.is_empty() {
@@ -189,7 +267,10 @@ impl EmailChannel {
             return text.to_string();
         }
         if let Some(html) = parsed.body_html(0) {
-            return Self::strip_html(html.as_ref());
+            // Keeps paragraphs, line breaks and list items, unlike strip_html.
+            return mail_parser::decoders::html::html_to_text(html.as_ref())
+                .trim()
+                .to_string();
         }
         for part in parsed.attachments() {
             let part: &mail_parser::MessagePart = part;
@@ -205,6 +286,101 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Message-ID chain for an inbound email, oldest first and ending with
+    /// its own id. The first entry is the thread root.
+    fn thread_chain(parsed: &mail_parser::Message, msg_id: &str) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        for id in parsed
+            .references()
+            .as_text_list()
+            .into_iter()
+            .flatten()
+            .chain(parsed.in_reply_to().as_text_list().into_iter().flatten())
+        {
+            let id = id.trim().trim_start_matches('<').trim_end_matches('>');
+            if !id.is_empty() && !chain.iter().any(|known| known == id) {
+                chain.push(id.to_string());
+            }
+        }
+        if !chain.iter().any(|known| known == msg_id) {
+            chain.push(msg_id.to_string());
+        }
+        chain
+    }
+
+    /// Collect non-inline MIME attachments.
+    fn extract_attachments(parsed: &mail_parser::Message) -> Vec<EmailAttachment> {
+        parsed
+            .attachments()
+            .enumerate()
+            .filter(|(_, part)| !part.is_message())
+            .map(|(index, part)| {
+                let content_type = MimeHeaders::content_type(part)
+                    .map(|ct| match ct.subtype() {
+                        Some(sub) => format!("{}/{}", ct.ctype(), sub),
+                        None => ct.ctype().to_string(),
+                    })
+                    .unwrap_or_else(|| "application/octet-stream".into());
+                let name = MimeHeaders::attachment_name(part)
+                    .map(sanitize_attachment_name)
+                    .filter(|n| !n.is_empty())
+                    .unwrap_or_else(|| format!("attachment-{}", index + 1));
+                EmailAttachment {
+                    name,
+                    content_type,
+                    data: part.contents().to_vec(),
+                }
+            })
+            .collect()
+    }
+
+    /// Save attachments under `<workspace>/email_files` and describe them
+    /// with the same markers other channels use (`[IMAGE:path]` for images).
+    async fn store_attachments(&self, uid: u32, attachments: &[EmailAttachment]) -> Vec<String> {
+        let save_dir = self.workspace_dir.as_ref().map(|ws| ws.join("email_files"));
+        if let Some(ref dir) = save_dir {
+            if let Err(e) = tokio::fs::create_dir_all(dir).await {
+                warn!("Failed to create email_files directory: {}", e);
+            }
+        }
+
+        let mut lines = Vec::with_capacity(attachments.len());
+        for attachment in attachments {
+            let summary = format!(
+                "[Attachment: {} ({}, {} bytes)]",
+                attachment.name,
+                attachment.content_type,
+                attachment.data.len()
+            );
+            let Some(ref dir) = save_dir else {
+                lines.push(summary);
+                continue;
+            };
+            if attachment.data.len() > MAX_ATTACHMENT_BYTES {
+                lines.push(format!("{} not saved: too large", summary));
+                continue;
+            }
+            let path = match write_new_file(dir, uid, &attachment.name, &attachment.data).await {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!("Failed to save attachment {}: {}", attachment.name, e);
+                    lines.push(summary);
+                    continue;
+                }
+            };
+            if attachment.content_type.starts_with("image/") {
+                lines.push(format!("[IMAGE:{}]", path.display()));
+            } else {
+                lines.push(format!(
+                    "[Document: {}] {}",
+                    attachment.name,
+                    path.display()
+                ));
+            }
+        }
+        lines
+    }
+
     /// Connect to IMAP server with TLS and authenticate
     async fn connect_imap(&self) -> Result<ImapSession> {
         let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
@@ -265,11 +441,19 @@ impl EmailChannel {
                     let sender = Self::extract_sender(&parsed);
                     let subject = parsed.subject().unwrap_or("(no subject)").to_string();
                     let body_text = Self::extract_text(&parsed);
-                    let content = format!("Subject: {}\n\n{}", subject, body_text);
+                    let mut content = format!("Subject: {}\n\n{}", subject, body_text);
+                    let attachments = self
+                        .store_attachments(uid, &Self::extract_attachments(&parsed))
+                        .await;
+                    if !attachments.is_empty() {
+                        content.push_str("\n\n");
+                        content.push_str(&attachments.join("\n"));
+                    }
                     let msg_id = parsed
                         .message_id()
                         .map(|s| s.to_string())
                         .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+                    let references = Self::thread_chain(&parsed, &msg_id);
 
                     #[allow(clippy::cast_sign_loss)]
                     let ts = parsed
@@ -300,7 +484,9 @@ impl EmailChannel {
                         _uid: uid,
                         msg_id,
                         sender,
+                        subject,
                         content,
+                        references,
                         timestamp: ts,
                     });
                 }
@@ -452,6 +638,21 @@ impl EmailChannel {
                 continue;
             }
 
+            // The thread root keys both reply headers and conversation history.
+            let thread_root = email
+                .references
+                .first()
+                .cloned()
+                .unwrap_or_else(|| email.msg_id.clone());
+            self.threads.lock().await.insert(
+                &thread_root,
+                EmailThread {
+                    last_message_id: email.msg_id.clone(),
+                    references: email.references,
+                    subject: email.subject,
+                },
+            );
+
             let msg = ChannelMessage {
                 id: email.msg_id,
                 reply_target: email.sender.clone(),
@@ -459,7 +660,9 @@ impl EmailChannel {
                 content: email.content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
-                thread_ts: None,
+                thread_ts: Some(thread_root),
+                group: None,
+                voice: false,
             };
 
             if tx.send(msg).await.is_err() {
@@ -493,10 +696,74 @@ struct ParsedEmail {
     _uid: u32,
     msg_id: String,
     sender: String,
+    subject: String,
     content: String,
+    /// Message-ID chain, oldest first, ending with `msg_id`.
+    references: Vec<String>,
     timestamp: u64,
 }
 
+/// Replace path separators and control characters so an attachment name
+/// is safe to use as a file name.
+fn sanitize_attachment_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    base.chars()
+        .map(|c| if c.is_control() { '_' } else { c })
+        .collect::<String>()
+        .trim()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+/// Render Markdown to an HTML document. Raw HTML in the source is
+/// escaped rather than passed through.
+fn markdown_to_html(markdown: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"></head><body>{}</body></html>",
+        render_markdown(markdown, MarkupFormat::Html)
+    )
+}
+
+/// Write `data` to `<dir>/<uid>_<name>`, adding a counter before the name
+/// when that file exists so earlier attachments are never overwritten.
+async fn write_new_file(dir: &Path, uid: u32, name: &str, data: &[u8]) -> std::io::Result<PathBuf> {
+    let mut n = 0u32;
+    loop {
+        let file_name = if n == 0 {
+            format!("{uid}_{name}")
+        } else {
+            format!("{uid}_{n}_{name}")
+        };
+        let path = dir.join(file_name);
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(mut file) => {
+                file.write_all(data).await?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Prefix a subject with `Re:` unless it already has one.
+fn reply_subject(subject: &str) -> String {
+    let trimmed = subject.trim();
+    if trimmed
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"))
+    {
+        trimmed.to_string()
+    } else {
+        format!("Re: {}", trimmed)
+    }
+}
+
 /// Result from waiting on IDLE
 enum IdleWaitResult {
     NewMail,
@@ -511,7 +778,14 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
+        let thread = match message.thread_ts {
+            Some(ref root) => self.threads.lock().await.get(root).cloned(),
+            None => None,
+        };
+        let thread_subject = thread.as_ref().map(|t| reply_subject(&t.subject));
+
+        // Use explicit subject if provided, otherwise fall back to legacy parsing,
+        // the thread's subject, or a default
         let (subject, body) = if let Some(ref subj) = message.subject {
             (subj.as_str(), message.content.as_str())
         } else if message.content.starts_with("Subject: ") {
@@ -520,19 +794,61 @@ impl Channel for EmailChannel {
             } else {
                 ("ZeroClaw Message", message.content.as_str())
             }
+        } else if let Some(ref subj) = thread_subject {
+            (subj.as_str(), message.content.as_str())
         } else {
             ("ZeroClaw Message", message.content.as_str())
         };
 
-        let email = Message::builder()
+        let domain = self
+            .config
+            .from_address
+            .rsplit_once('@')
+            .map_or("localhost", |(_, d)| d.trim_end_matches('>'));
+        let own_id = format!("{}@{}", Uuid::new_v4(), domain);
+
+        let mut builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
             .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .message_id(Some(format!("<{}>", own_id)));
+
+        // Thread the reply under the newest message we know of; fall back to
+        // the root id alone after a restart.
+        if let Some(ref root) = message.thread_ts {
+            let (parent, references) = match thread {
+                Some(ref t) => (t.last_message_id.clone(), t.references.clone()),
+                None => (root.clone(), vec![root.clone()]),
+            };
+            let references = references
+                .iter()
+                .map(|id| format!("<{}>", id))
+                .collect::<Vec<_>>()
+                .join(" ");
+            builder = builder
+                .in_reply_to(format!("<{}>", parent))
+                .references(references);
+        }
+
+        let email = builder.multipart(MultiPart::alternative_plain_html(
+            body.to_string(),
+            markdown_to_html(body),
+        ))?;
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
         info!("Email sent to {}", message.recipient);
+
+        if let Some(ref root) = message.thread_ts {
+            let mut threads = self.threads.lock().await;
+            let entry = threads.get_or_insert_with(root, || EmailThread {
+                last_message_id: root.clone(),
+                references: vec![root.clone()],
+                subject: subject.to_string(),
+            });
+            entry.last_message_id = own_id.clone();
+            entry.references.push(own_id);
+        }
         Ok(())
     }
 
@@ -974,4 +1290,178 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("imap.debug.com"));
     }
+
+    const THREADED_REPLY: &str = "From: Alice <alice@example.com>\r\n\
+To: bot@example.com\r\n\
+Subject: Re: Order status\r\n\
+Message-ID: <m3@example.com>\r\n\
+In-Reply-To: <m2@example.com>\r\n\
+References: <m1@example.com> <m2@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/html; charset=utf-8\r\n\
+\r\n\
+<p>First line</p><p>Second line</p>\r\n\
+--b1\r\n\
+Content-Type: application/pdf; name=\"../invoice.pdf\"\r\n\
+Content-Disposition: attachment; filename=\"../invoice.pdf\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0xLjQ=\r\n\
+--b1\r\n\
+Content-Type: image/png; name=\"photo.png\"\r\n\
+Content-Disposition: attachment; filename=\"photo.png\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+iVBORw0KGgo=\r\n\
+--b1--\r\n";
+
+    #[test]
+    fn thread_chain_orders_references_and_ends_with_own_id() {
+        let parsed = MessageParser::default()
+            .parse(THREADED_REPLY.as_bytes())
+            .unwrap();
+        let chain = EmailChannel::thread_chain(&parsed, parsed.message_id().unwrap());
+        assert_eq!(
+            chain,
+            vec!["m1@example.com", "m2@example.com", "m3@example.com"]
+        );
+    }
+
+    #[test]
+    fn thread_chain_for_new_message_is_own_id() {
+        let raw = "From: a@example.com\r\nMessage-ID: <solo@example.com>\r\n\r\nhi\r\n";
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+        assert_eq!(
+            EmailChannel::thread_chain(&parsed, "solo@example.com"),
+            vec!["solo@example.com"]
+        );
+    }
+
+    #[test]
+    fn extract_text_keeps_html_paragraphs() {
+        let parsed = MessageParser::default()
+            .parse(THREADED_REPLY.as_bytes())
+            .unwrap();
+        let text = EmailChannel::extract_text(&parsed);
+        assert!(text.contains("First line"));
+        assert!(text.contains('\n'));
+        assert!(!text.contains("<p>"));
+    }
+
+    #[test]
+    fn extract_attachments_reads_names_types_and_data() {
+        let parsed = MessageParser::default()
+            .parse(THREADED_REPLY.as_bytes())
+            .unwrap();
+        let attachments = EmailChannel::extract_attachments(&parsed);
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].name, "invoice.pdf");
+        assert_eq!(attachments[0].content_type, "application/pdf");
+        assert!(attachments[0].data.starts_with(b"%PDF"));
+        assert_eq!(attachments[1].content_type, "image/png");
+    }
+
+    #[tokio::test]
+    async fn store_attachments_saves_files_with_markers() {
+        let workspace = tempfile::tempdir().unwrap();
+        let channel =
+            EmailChannel::new(EmailConfig::default()).with_workspace_dir(workspace.path().into());
+        let attachments = vec![
+            EmailAttachment {
+                name: "invoice.pdf".into(),
+                content_type: "application/pdf".into(),
+                data: b"%PDF".to_vec(),
+            },
+            EmailAttachment {
+                name: "photo.png".into(),
+                content_type: "image/png".into(),
+                data: vec![1, 2, 3],
+            },
+        ];
+
+        let lines = channel.store_attachments(7, &attachments).await;
+        let saved = workspace.path().join("email_files").join("7_invoice.pdf");
+        assert_eq!(
+            lines[0],
+            format!("[Document: invoice.pdf] {}", saved.display())
+        );
+        assert!(saved.exists());
+        assert!(lines[1].starts_with("[IMAGE:"));
+
+        // Same UID and name again (another folder or a UIDVALIDITY reset).
+        let again = channel.store_attachments(7, &attachments[..1]).await;
+        let renamed = workspace.path().join("email_files").join("7_1_invoice.pdf");
+        assert_eq!(
+            again[0],
+            format!("[Document: invoice.pdf] {}", renamed.display())
+        );
+        assert_eq!(std::fs::read(&saved).unwrap(), b"%PDF");
+    }
+
+    #[tokio::test]
+    async fn store_attachments_without_workspace_lists_only() {
+        let channel = EmailChannel::new(EmailConfig::default());
+        let lines = channel
+            .store_attachments(
+                1,
+                &[EmailAttachment {
+                    name: "a.txt".into(),
+                    content_type: "text/plain".into(),
+                    data: b"hi".to_vec(),
+                }],
+            )
+            .await;
+        assert_eq!(lines, vec!["[Attachment: a.txt (text/plain, 2 bytes)]"]);
+    }
+
+    #[test]
+    fn sanitize_attachment_name_strips_paths() {
+        assert_eq!(sanitize_attachment_name("../../etc/passwd"), "passwd");
+        assert_eq!(
+            sanitize_attachment_name("C:\\tmp\\report.doc"),
+            "report.doc"
+        );
+        assert_eq!(sanitize_attachment_name(".hidden"), "hidden");
+    }
+
+    #[test]
+    fn markdown_to_html_renders_and_escapes_raw_html() {
+        let html = markdown_to_html("**bold** and `code`\n\n<script>alert(1)</script>");
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<code>code</code>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn reply_subject_adds_prefix_once() {
+        assert_eq!(reply_subject("Order status"), "Re: Order status");
+        assert_eq!(reply_subject("RE: Order status"), "RE: Order status");
+        assert_eq!(
+            reply_subject("Ärger mit Bestellung"),
+            "Re: Ärger mit Bestellung"
+        );
+        assert_eq!(reply_subject("日本語"), "Re: 日本語");
+    }
+
+    #[test]
+    fn thread_cache_forgets_oldest_threads() {
+        let mut cache = ThreadCache::default();
+        for i in 0..=MAX_THREADS {
+            cache.insert(
+                &format!("<{i}@x>"),
+                EmailThread {
+                    last_message_id: format!("<{i}@x>"),
+                    references: Vec::new(),
+                    subject: "s".into(),
+                },
+            );
+        }
+        assert_eq!(cache.by_root.len(), MAX_THREADS);
+        assert!(cache.get("<0@x>").is_none());
+        assert!(cache.get(&format!("<{MAX_THREADS}@x>")).is_some());
+    }
 }
@@ -0,0 +1,162 @@
+//! Group-chat handling shared by all room-capable channels.
+//!
+//! Channels tag room messages with a [`GroupContext`]; the runtime then
+//! decides per [`GroupPolicyConfig`] whether the agent replies, keeps one
+//! shared history per room with speaker attribution, and caps replies per
+//! room per minute.
+
+use super::traits::{ChannelMessage, GroupContext};
+use crate::config::{GroupPolicyConfig, GroupReplyMode};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Prompt note added to the system prompt for room conversations.
+pub const GROUP_PROMPT_NOTE: &str = "## Group Chat\n\n\
+You are in a group chat with several people. Each user turn starts with the \
+speaker's name in brackets and may include earlier messages from the room that \
+were not addressed to you. Reply to the latest message only; do not answer \
+earlier lines unless asked.";
+
+/// Conversation key shared by everyone in a room.
+pub fn room_history_key(msg: &ChannelMessage, group: &GroupContext) -> String {
+    format!("{}_room_{}", msg.channel, group.room)
+}
+
+/// Room line with the speaker's name, as stored in the shared history.
+pub fn attributed_content(group: &GroupContext, content: &str) -> String {
+    format!("[{}] {content}", group.sender_name)
+}
+
+/// Whether a room message should get a reply under `policy`.
+pub fn triggers_reply(policy: &GroupPolicyConfig, group: &GroupContext, content: &str) -> bool {
+    let addressed = group.mentioned || group.replied_to_bot;
+    match policy.reply {
+        GroupReplyMode::Always => true,
+        GroupReplyMode::Mention => addressed,
+        GroupReplyMode::Keywords => addressed || contains_keyword(&policy.keywords, content),
+    }
+}
+
+fn contains_keyword(keywords: &[String], content: &str) -> bool {
+    let lowered = content.to_lowercase();
+    let words: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-')
+        .filter(|w| !w.is_empty())
+        .collect();
+    keywords.iter().any(|keyword| {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() {
+            false
+        } else if keyword.contains(char::is_whitespace) {
+            lowered.contains(&keyword)
+        } else {
+            words.iter().any(|w| *w == keyword)
+        }
+    })
+}
+
+/// Sliding one-minute reply counter per room
+#[derive(Default)]
+pub struct RoomRateLimiter {
+    replies: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RoomRateLimiter {
+    /// Record a reply in `room` unless `per_minute` replies already happened
+    /// in the last minute. `per_minute == 0` never limits.
+    pub fn try_acquire(&self, room: &str, per_minute: u32, now: Instant) -> bool {
+        if per_minute == 0 {
+            return true;
+        }
+        let mut replies = self.replies.lock();
+        let recent = replies.entry(room.to_string()).or_default();
+        while recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= per_minute as usize {
+            return false;
+        }
+        recent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(mentioned: bool, replied_to_bot: bool) -> GroupContext {
+        GroupContext {
+            room: "room-1".into(),
+            sender_name: "alice".into(),
+            mentioned,
+            replied_to_bot,
+        }
+    }
+
+    fn policy(reply: GroupReplyMode, keywords: &[&str]) -> GroupPolicyConfig {
+        GroupPolicyConfig {
+            reply,
+            keywords: keywords.iter().map(|k| (*k).to_string()).collect(),
+            max_replies_per_minute: 0,
+        }
+    }
+
+    #[test]
+    fn always_mode_replies_to_everything() {
+        let p = policy(GroupReplyMode::Always, &[]);
+        assert!(triggers_reply(&p, &group(false, false), "hello all"));
+    }
+
+    #[test]
+    fn mention_mode_requires_mention_or_reply() {
+        let p = policy(GroupReplyMode::Mention, &[]);
+        assert!(!triggers_reply(&p, &group(false, false), "hello all"));
+        assert!(triggers_reply(&p, &group(true, false), "hello"));
+        assert!(triggers_reply(&p, &group(false, true), "thanks"));
+    }
+
+    #[test]
+    fn keyword_mode_matches_whole_words_and_phrases() {
+        let p = policy(GroupReplyMode::Keywords, &["deploy", "on call"]);
+        assert!(triggers_reply(
+            &p,
+            &group(false, false),
+            "Can we Deploy now?"
+        ));
+        assert!(triggers_reply(
+            &p,
+            &group(false, false),
+            "who is on call today"
+        ));
+        assert!(!triggers_reply(
+            &p,
+            &group(false, false),
+            "redeployment done"
+        ));
+        assert!(triggers_reply(&p, &group(true, false), "hi"));
+    }
+
+    #[test]
+    fn attributed_content_prefixes_speaker() {
+        assert_eq!(attributed_content(&group(false, false), "hi"), "[alice] hi");
+    }
+
+    #[test]
+    fn rate_limiter_caps_replies_per_room_per_minute() {
+        let limiter = RoomRateLimiter::default();
+        let start = Instant::now();
+        assert!(limiter.try_acquire("a", 2, start));
+        assert!(limiter.try_acquire("a", 2, start + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire("a", 2, start + Duration::from_secs(2)));
+        assert!(limiter.try_acquire("b", 2, start + Duration::from_secs(2)));
+        assert!(limiter.try_acquire("a", 2, start + Duration::from_secs(61)));
+        assert!(limiter.try_acquire("a", 0, start));
+    }
+}
@@ -0,0 +1,638 @@
+//! Cross-channel identity links.
+//!
+//! A channel sender (`telegram` + `123456789`, `email` + `alice@example.com`)
+//! can be linked to a canonical user. Linked senders share conversation
+//! history, memory keys, model routes and preferences, and channel
+//! allowlists may list `user:<id>` to admit every identity linked to that
+//! user. Links live in `{workspace}/identity/links.db`.
+//!
+//! Linking from chat is a two-step code flow: `/link` on an account that is
+//! already allowed issues a short-lived 6-digit code, and `/link <code>` on
+//! another account attaches that account to the same user.
+
+use crate::config::ChannelsConfig;
+use crate::security::pairing::{constant_time_eq, generate_code};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Allowlist prefix that refers to a canonical user instead of a sender.
+pub const USER_ALLOWLIST_PREFIX: &str = "user:";
+
+/// How long a link code stays valid.
+pub const LINK_CODE_TTL: Duration = Duration::from_secs(600);
+
+/// Wrong codes one sender may try before being locked out for a TTL.
+const MAX_LINK_ATTEMPTS: u32 = 5;
+
+/// Wrong codes across all senders before every pending code is invalidated.
+/// A code matches any user's pending link, so spreading guesses over many
+/// senders must not add up to a brute force.
+const MAX_TOTAL_LINK_FAILURES: u32 = 20;
+
+/// Maximum length of a user id or preference key.
+const MAX_NAME_CHARS: usize = 64;
+
+/// Maximum length of a preference value.
+const MAX_PREFERENCE_VALUE_CHARS: usize = 500;
+
+/// One channel account linked to a canonical user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedIdentity {
+    pub channel: String,
+    pub sender: String,
+}
+
+/// A canonical user with its linked accounts and preferences
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityUser {
+    pub id: String,
+    pub identities: Vec<LinkedIdentity>,
+    pub preferences: BTreeMap<String, String>,
+}
+
+/// Result of redeeming a link code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkOutcome {
+    Linked { user_id: String },
+    InvalidCode,
+    TooManyAttempts,
+}
+
+struct PendingLink {
+    code: String,
+    user_id: String,
+    expires_at: Instant,
+}
+
+struct FailedAttempts {
+    count: u32,
+    reset_at: Instant,
+}
+
+#[derive(Default)]
+struct LinkCodes {
+    pending: Vec<PendingLink>,
+    failures: HashMap<(String, String), FailedAttempts>,
+    /// Wrong codes since the pending codes were last cleared
+    total_failures: u32,
+}
+
+impl LinkCodes {
+    fn purge_expired(&mut self, now: Instant) {
+        self.pending.retain(|p| p.expires_at > now);
+        self.failures.retain(|_, f| f.reset_at > now);
+        if self.pending.is_empty() {
+            self.total_failures = 0;
+        }
+    }
+}
+
+/// SQLite-backed registry of canonical users and their channel identities
+pub struct IdentityRegistry {
+    conn: Mutex<Connection>,
+    codes: Mutex<LinkCodes>,
+}
+
+/// Check that a user id or preference key is a short plain token.
+pub fn validate_name(kind: &str, value: &str) -> Result<()> {
+    if value.is_empty() || value.chars().count() > MAX_NAME_CHARS {
+        anyhow::bail!("{kind} must be 1-{MAX_NAME_CHARS} characters");
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        anyhow::bail!("{kind} may only contain letters, digits, '_', '-' and '.'");
+    }
+    Ok(())
+}
+
+impl IdentityRegistry {
+    /// Open (or create) `{workspace_dir}/identity/links.db`.
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("identity").join("links.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create identity directory: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open identity DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA foreign_keys = ON;
+             CREATE TABLE IF NOT EXISTS identity_users (
+                id         TEXT PRIMARY KEY,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS identity_links (
+                channel   TEXT NOT NULL,
+                sender    TEXT NOT NULL,
+                user_id   TEXT NOT NULL,
+                linked_at TEXT NOT NULL,
+                PRIMARY KEY (channel, sender),
+                FOREIGN KEY (user_id) REFERENCES identity_users(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_identity_links_user
+                ON identity_links(user_id);
+
+            CREATE TABLE IF NOT EXISTS identity_preferences (
+                user_id TEXT NOT NULL,
+                key     TEXT NOT NULL,
+                value   TEXT NOT NULL,
+                PRIMARY KEY (user_id, key),
+                FOREIGN KEY (user_id) REFERENCES identity_users(id) ON DELETE CASCADE
+            );",
+        )
+        .context("Failed to initialize identity schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            codes: Mutex::new(LinkCodes::default()),
+        })
+    }
+
+    /// Canonical user a channel sender is linked to, if any.
+    pub fn resolve(&self, channel: &str, sender: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock();
+        conn.query_row(
+            "SELECT user_id FROM identity_links WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to resolve identity")
+    }
+
+    /// Link a channel sender to `user_id`, creating the user if needed.
+    ///
+    /// A sender that was linked to another user moves to `user_id`; a user
+    /// left without identities is removed with its preferences.
+    pub fn link(&self, user_id: &str, channel: &str, sender: &str) -> Result<()> {
+        validate_name("User id", user_id)?;
+        if channel.trim().is_empty() || sender.trim().is_empty() {
+            anyhow::bail!("Channel and sender cannot be empty");
+        }
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR IGNORE INTO identity_users (id, created_at) VALUES (?1, ?2)",
+            params![user_id, now],
+        )?;
+        tx.execute(
+            "INSERT INTO identity_links (channel, sender, user_id, linked_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(channel, sender) DO UPDATE SET
+                user_id = excluded.user_id,
+                linked_at = excluded.linked_at",
+            params![channel, sender, user_id, now],
+        )?;
+        delete_orphan_users(&tx)?;
+        tx.commit().context("Failed to save identity link")?;
+        Ok(())
+    }
+
+    /// Remove a sender's link. Returns `false` when it was not linked.
+    pub fn unlink(&self, channel: &str, sender: &str) -> Result<bool> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let removed = tx.execute(
+            "DELETE FROM identity_links WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+        )?;
+        delete_orphan_users(&tx)?;
+        tx.commit().context("Failed to remove identity link")?;
+        Ok(removed > 0)
+    }
+
+    /// Channel accounts linked to `user_id`.
+    pub fn identities(&self, user_id: &str) -> Result<Vec<LinkedIdentity>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT channel, sender FROM identity_links
+             WHERE user_id = ?1 ORDER BY channel, sender",
+        )?;
+        let rows = stmt.query_map(params![user_id], |row| {
+            Ok(LinkedIdentity {
+                channel: row.get(0)?,
+                sender: row.get(1)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list identities")
+    }
+
+    /// All canonical users with their identities and preferences.
+    pub fn users(&self) -> Result<Vec<IdentityUser>> {
+        let ids: Vec<String> = {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare("SELECT id FROM identity_users ORDER BY id")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        ids.into_iter()
+            .map(|id| {
+                Ok(IdentityUser {
+                    identities: self.identities(&id)?,
+                    preferences: self.preferences(&id)?,
+                    id,
+                })
+            })
+            .collect()
+    }
+
+    /// Preferences stored for `user_id`.
+    pub fn preferences(&self, user_id: &str) -> Result<BTreeMap<String, String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT key, value FROM identity_preferences WHERE user_id = ?1 ORDER BY key",
+        )?;
+        let rows = stmt.query_map(params![user_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<BTreeMap<_, _>>>()
+            .context("Failed to read preferences")
+    }
+
+    /// Set one preference for an existing user.
+    pub fn set_preference(&self, user_id: &str, key: &str, value: &str) -> Result<()> {
+        validate_name("Preference key", key)?;
+        let value = value.trim();
+        if value.is_empty() {
+            anyhow::bail!("Preference value cannot be empty");
+        }
+        if value.chars().count() > MAX_PREFERENCE_VALUE_CHARS {
+            anyhow::bail!(
+                "Preference value must be at most {MAX_PREFERENCE_VALUE_CHARS} characters"
+            );
+        }
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO identity_preferences (user_id, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(user_id, key) DO UPDATE SET value = excluded.value",
+            params![user_id, key, value],
+        )
+        .context("Failed to save preference")?;
+        Ok(())
+    }
+
+    /// Remove one preference. Returns `false` when it was not set.
+    pub fn remove_preference(&self, user_id: &str, key: &str) -> Result<bool> {
+        let conn = self.conn.lock();
+        let removed = conn.execute(
+            "DELETE FROM identity_preferences WHERE user_id = ?1 AND key = ?2",
+            params![user_id, key],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Canonical user for a sender, linking it to a fresh user if needed.
+    pub fn ensure_user(&self, channel: &str, sender: &str) -> Result<String> {
+        if let Some(user_id) = self.resolve(channel, sender)? {
+            return Ok(user_id);
+        }
+        let user_id = format!("u-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+        self.link(&user_id, channel, sender)?;
+        Ok(user_id)
+    }
+
+    /// Issue a link code for the user behind `channel`/`sender`.
+    ///
+    /// Any earlier code for the same user is replaced.
+    pub fn start_link(&self, channel: &str, sender: &str) -> Result<(String, String)> {
+        let user_id = self.ensure_user(channel, sender)?;
+        let code = generate_code();
+        let now = Instant::now();
+        let mut codes = self.codes.lock();
+        codes.purge_expired(now);
+        codes.pending.retain(|p| p.user_id != user_id);
+        codes.pending.push(PendingLink {
+            code: code.clone(),
+            user_id: user_id.clone(),
+            expires_at: now + LINK_CODE_TTL,
+        });
+        Ok((user_id, code))
+    }
+
+    /// Redeem a link code from `channel`/`sender`.
+    pub fn complete_link(&self, channel: &str, sender: &str, code: &str) -> Result<LinkOutcome> {
+        let now = Instant::now();
+        let attempt_key = (channel.to_string(), sender.to_string());
+        let pending = {
+            let mut codes = self.codes.lock();
+            codes.purge_expired(now);
+            if codes
+                .failures
+                .get(&attempt_key)
+                .is_some_and(|f| f.count >= MAX_LINK_ATTEMPTS)
+            {
+                return Ok(LinkOutcome::TooManyAttempts);
+            }
+            let code = code.trim();
+            match codes
+                .pending
+                .iter()
+                .position(|p| constant_time_eq(&p.code, code))
+            {
+                Some(idx) => {
+                    codes.failures.remove(&attempt_key);
+                    codes.pending.swap_remove(idx)
+                }
+                None => {
+                    let entry = codes.failures.entry(attempt_key).or_insert(FailedAttempts {
+                        count: 0,
+                        reset_at: now + LINK_CODE_TTL,
+                    });
+                    entry.count += 1;
+                    codes.total_failures += 1;
+                    if codes.total_failures >= MAX_TOTAL_LINK_FAILURES {
+                        tracing::warn!(
+                            "Too many wrong link codes; invalidating {} pending link code(s)",
+                            codes.pending.len()
+                        );
+                        codes.pending.clear();
+                        codes.total_failures = 0;
+                    }
+                    return Ok(LinkOutcome::InvalidCode);
+                }
+            }
+        };
+
+        self.link(&pending.user_id, channel, sender)?;
+        Ok(LinkOutcome::Linked {
+            user_id: pending.user_id,
+        })
+    }
+
+    /// Replace `user:<id>` entries with the senders linked on `channel`.
+    pub fn expand_allowlist(&self, channel: &str, entries: &[String]) -> Vec<String> {
+        let mut expanded = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let Some(user_id) = entry.strip_prefix(USER_ALLOWLIST_PREFIX) else {
+                expanded.push(entry.clone());
+                continue;
+            };
+            match self.identities(user_id.trim()) {
+                Ok(identities) => expanded.extend(
+                    identities
+                        .into_iter()
+                        .filter(|identity| identity.channel == channel)
+                        .map(|identity| identity.sender),
+                ),
+                Err(err) => {
+                    tracing::warn!("Failed to expand allowlist entry {entry} for {channel}: {err}");
+                }
+            }
+        }
+        expanded.dedup();
+        expanded
+    }
+
+    /// Expand `user:<id>` entries in every channel allowlist.
+    pub fn expand_channel_allowlists(&self, channels: &mut ChannelsConfig) {
+        let expand = |channel: &str, entries: &mut Vec<String>| {
+            if entries
+                .iter()
+                .any(|entry| entry.starts_with(USER_ALLOWLIST_PREFIX))
+            {
+                *entries = self.expand_allowlist(channel, entries);
+            }
+        };
+
+        if let Some(c) = channels.telegram.as_mut() {
+            expand("telegram", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.discord.as_mut() {
+            expand("discord", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.slack.as_mut() {
+            expand("slack", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.mattermost.as_mut() {
+            expand("mattermost", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.zulip.as_mut() {
+            expand("zulip", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.imessage.as_mut() {
+            expand("imessage", &mut c.allowed_contacts);
+        }
+        if let Some(c) = channels.matrix.as_mut() {
+            expand("matrix", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.signal.as_mut() {
+            expand("signal", &mut c.allowed_from);
+        }
+        if let Some(c) = channels.whatsapp.as_mut() {
+            expand("whatsapp", &mut c.allowed_numbers);
+        }
+        if let Some(c) = channels.linq.as_mut() {
+            expand("linq", &mut c.allowed_senders);
+        }
+        if let Some(c) = channels.nextcloud_talk.as_mut() {
+            expand("nextcloud_talk", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.email.as_mut() {
+            expand("email", &mut c.allowed_senders);
+        }
+        if let Some(c) = channels.irc.as_mut() {
+            expand("irc", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.xmpp.as_mut() {
+            expand("xmpp", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.lark.as_mut() {
+            expand("lark", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.dingtalk.as_mut() {
+            expand("dingtalk", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.qq.as_mut() {
+            expand("qq", &mut c.allowed_users);
+        }
+        if let Some(c) = channels.nostr.as_mut() {
+            expand("nostr", &mut c.allowed_pubkeys);
+        }
+    }
+}
+
+fn delete_orphan_users(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute(
+        "DELETE FROM identity_users
+         WHERE id NOT IN (SELECT DISTINCT user_id FROM identity_links)",
+        [],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn registry() -> (TempDir, IdentityRegistry) {
+        let tmp = TempDir::new().unwrap();
+        let registry = IdentityRegistry::open(tmp.path()).unwrap();
+        (tmp, registry)
+    }
+
+    #[test]
+    fn link_and_resolve_round_trip() {
+        let (_tmp, reg) = registry();
+        reg.link("alice", "telegram", "123").unwrap();
+        reg.link("alice", "email", "alice@example.com").unwrap();
+
+        assert_eq!(
+            reg.resolve("telegram", "123").unwrap().as_deref(),
+            Some("alice")
+        );
+        assert_eq!(reg.resolve("slack", "U1").unwrap(), None);
+        assert_eq!(reg.identities("alice").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn relinking_moves_sender_and_drops_orphan_user() {
+        let (_tmp, reg) = registry();
+        reg.link("old", "slack", "U1").unwrap();
+        reg.set_preference("old", "language", "de").unwrap();
+        reg.link("new", "slack", "U1").unwrap();
+
+        let users = reg.users().unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, "new");
+        assert!(reg.preferences("old").unwrap().is_empty());
+    }
+
+    #[test]
+    fn unlink_reports_whether_link_existed() {
+        let (_tmp, reg) = registry();
+        reg.link("alice", "irc", "alice").unwrap();
+        assert!(reg.unlink("irc", "alice").unwrap());
+        assert!(!reg.unlink("irc", "alice").unwrap());
+        assert!(reg.users().unwrap().is_empty());
+    }
+
+    #[test]
+    fn link_rejects_invalid_user_ids() {
+        let (_tmp, reg) = registry();
+        assert!(reg.link("", "irc", "alice").is_err());
+        assert!(reg.link("has space", "irc", "alice").is_err());
+        assert!(reg.link("alice", "irc", " ").is_err());
+    }
+
+    #[test]
+    fn preferences_set_update_and_remove() {
+        let (_tmp, reg) = registry();
+        reg.link("alice", "discord", "42").unwrap();
+        reg.set_preference("alice", "language", "en").unwrap();
+        reg.set_preference("alice", "language", "fr").unwrap();
+        reg.set_preference("alice", "tone", "brief").unwrap();
+
+        let prefs = reg.preferences("alice").unwrap();
+        assert_eq!(prefs.get("language").map(String::as_str), Some("fr"));
+        assert_eq!(prefs.len(), 2);
+        assert!(reg.remove_preference("alice", "tone").unwrap());
+        assert!(!reg.remove_preference("alice", "tone").unwrap());
+        assert!(reg.set_preference("alice", "bad key", "x").is_err());
+    }
+
+    #[test]
+    fn link_code_flow_links_second_identity() {
+        let (_tmp, reg) = registry();
+        let (user_id, code) = reg.start_link("telegram", "123").unwrap();
+        assert_eq!(code.len(), 6);
+
+        let outcome = reg
+            .complete_link("email", "alice@example.com", &code)
+            .unwrap();
+        assert_eq!(
+            outcome,
+            LinkOutcome::Linked {
+                user_id: user_id.clone()
+            }
+        );
+        assert_eq!(
+            reg.resolve("email", "alice@example.com").unwrap(),
+            Some(user_id)
+        );
+
+        // Codes are single-use.
+        assert_eq!(
+            reg.complete_link("slack", "U1", &code).unwrap(),
+            LinkOutcome::InvalidCode
+        );
+    }
+
+    #[test]
+    fn start_link_reuses_existing_user() {
+        let (_tmp, reg) = registry();
+        reg.link("alice", "telegram", "123").unwrap();
+        let (user_id, _) = reg.start_link("telegram", "123").unwrap();
+        assert_eq!(user_id, "alice");
+    }
+
+    #[test]
+    fn repeated_wrong_codes_lock_sender_out() {
+        let (_tmp, reg) = registry();
+        let (_, code) = reg.start_link("telegram", "123").unwrap();
+        let wrong = if code == "000000" { "000001" } else { "000000" };
+        for _ in 0..MAX_LINK_ATTEMPTS {
+            assert_eq!(
+                reg.complete_link("slack", "U1", wrong).unwrap(),
+                LinkOutcome::InvalidCode
+            );
+        }
+        assert_eq!(
+            reg.complete_link("slack", "U1", &code).unwrap(),
+            LinkOutcome::TooManyAttempts
+        );
+        assert_eq!(reg.resolve("slack", "U1").unwrap(), None);
+    }
+
+    #[test]
+    fn wrong_codes_from_many_senders_invalidate_pending_codes() {
+        let (_tmp, reg) = registry();
+        let (_, code) = reg.start_link("telegram", "123").unwrap();
+        let wrong = if code == "000000" { "000001" } else { "000000" };
+        for i in 0..MAX_TOTAL_LINK_FAILURES {
+            assert_eq!(
+                reg.complete_link("slack", &format!("U{i}"), wrong).unwrap(),
+                LinkOutcome::InvalidCode
+            );
+        }
+        // Each sender is still under its own cap, but the code is gone.
+        assert_eq!(
+            reg.complete_link("slack", "U0", &code).unwrap(),
+            LinkOutcome::InvalidCode
+        );
+        assert_eq!(reg.resolve("slack", "U0").unwrap(), None);
+
+        let (user_id, fresh) = reg.start_link("telegram", "123").unwrap();
+        assert_eq!(
+            reg.complete_link("slack", "U1", &fresh).unwrap(),
+            LinkOutcome::Linked { user_id }
+        );
+    }
+
+    #[test]
+    fn expand_allowlist_replaces_user_entries_per_channel() {
+        let (_tmp, reg) = registry();
+        reg.link("alice", "slack", "U1").unwrap();
+        reg.link("alice", "email", "alice@example.com").unwrap();
+
+        let entries = vec!["U9".to_string(), "user:alice".to_string()];
+        assert_eq!(reg.expand_allowlist("slack", &entries), vec!["U9", "U1"]);
+        assert_eq!(
+            reg.expand_allowlist("telegram", &["user:alice".to_string()]),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            reg.expand_allowlist("slack", &["user:nobody".to_string()]),
+            Vec::<String>::new()
+        );
+    }
+}
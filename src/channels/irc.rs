@@ -1,4 +1,5 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::markup::{render_markdown, MarkupFormat};
+use crate::channels::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -37,15 +38,6 @@ pub struct IrcChannel {
 
 type WriteHalf = tokio::io::WriteHalf<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>;
 
-/// Style instruction prepended to every IRC message before it reaches the LLM.
-/// IRC clients render plain text only — no markdown, no HTML, no XML.
-const IRC_STYLE_PREFIX: &str = "\
-[context: you are responding over IRC. \
-Plain text only. No markdown, no tables, no XML/HTML tags. \
-Never use triple backtick code fences. Use a single blank line to separate blocks instead. \
-Be terse and concise. \
-Use short lines. Avoid walls of text.]\n";
-
 /// Reserved bytes for the server-prepended sender prefix (`:nick!user@host `).
 const SENDER_PREFIX_RESERVE: usize = 64;
 
@@ -225,6 +217,46 @@ fn split_message(message: &str, max_bytes: usize) -> Vec<String> {
     chunks
 }
 
+/// Whether `text` addresses `nick`: a leading `nick:`/`nick,` or the nick as
+/// a standalone word (case-insensitive).
+fn addresses_nick(text: &str, nick: &str) -> bool {
+    if nick.is_empty() {
+        return false;
+    }
+    text.split(|c: char| c.is_whitespace() || matches!(c, ':' | ',' | '!' | '?' | '.'))
+        .any(|word| word.eq_ignore_ascii_case(nick))
+}
+
+/// Drop a leading `nick:` / `nick,` address so the agent sees the request.
+fn strip_nick_address<'a>(text: &'a str, nick: &str) -> &'a str {
+    let trimmed = text.trim_start();
+    match trimmed.get(..nick.len()) {
+        Some(head) if head.eq_ignore_ascii_case(nick) => {
+            let rest = &trimmed[nick.len()..];
+            match rest.strip_prefix([':', ',']) {
+                Some(rest) => rest.trim_start(),
+                None => text,
+            }
+        }
+        _ => text,
+    }
+}
+
+/// Room metadata for messages sent to a joined channel.
+fn channel_group_context(
+    channel: &str,
+    sender_nick: &str,
+    text: &str,
+    current_nick: &str,
+) -> GroupContext {
+    GroupContext {
+        room: channel.to_string(),
+        sender_name: sender_nick.to_string(),
+        mentioned: addresses_nick(text, current_nick),
+        replied_to_bot: false,
+    }
+}
+
 /// Configuration for constructing an `IrcChannel`.
 pub struct IrcChannelConfig {
     pub server: String,
@@ -304,7 +336,7 @@ impl IrcChannel {
 
 /// Certificate verifier that accepts any certificate (for `verify_tls=false`).
 #[derive(Debug)]
-struct NoVerify;
+pub(super) struct NoVerify;
 
 impl rustls::client::danger::ServerCertVerifier for NoVerify {
     fn verify_server_cert(
@@ -360,7 +392,8 @@ impl Channel for IrcChannel {
         // 512 - sender prefix (~64 bytes for :nick!user@host) - "PRIVMSG " - target - " :" - "\r\n"
         let overhead = SENDER_PREFIX_RESERVE + 10 + message.recipient.len() + 2;
         let max_payload = 512_usize.saturating_sub(overhead);
-        let chunks = split_message(&message.content, max_payload);
+        let content = render_markdown(&message.content, MarkupFormat::PlainText);
+        let chunks = split_message(&content, max_payload);
 
         for chunk in chunks {
             Self::send_raw(writer, &format!("PRIVMSG {} :{chunk}", message.recipient)).await?;
@@ -562,10 +595,12 @@ impl Channel for IrcChannel {
                     } else {
                         sender_nick.to_string()
                     };
-                    let content = if is_channel {
-                        format!("{IRC_STYLE_PREFIX}<{sender_nick}> {text}")
+                    let group = is_channel
+                        .then(|| channel_group_context(target, sender_nick, text, &current_nick));
+                    let content = if group.as_ref().is_some_and(|g| g.mentioned) {
+                        strip_nick_address(text, &current_nick).to_string()
                     } else {
-                        format!("{IRC_STYLE_PREFIX}{text}")
+                        text.to_string()
                     };
 
                     let seq = MSG_SEQ.fetch_add(1, Ordering::Relaxed);
@@ -580,6 +615,8 @@ impl Channel for IrcChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group,
+                        voice: false,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -805,6 +842,27 @@ mod tests {
         assert_eq!(chunks, vec![""]);
     }
 
+    // ── Group context ───────────────────────────────────────
+
+    #[test]
+    fn channel_group_context_detects_nick_address() {
+        let group = channel_group_context("#ops", "alice", "zc: status?", "zc");
+        assert_eq!(group.room, "#ops");
+        assert_eq!(group.sender_name, "alice");
+        assert!(group.mentioned);
+
+        assert!(channel_group_context("#ops", "alice", "ask ZC later", "zc").mentioned);
+        assert!(!channel_group_context("#ops", "alice", "zchelp", "zc").mentioned);
+    }
+
+    #[test]
+    fn strip_nick_address_removes_leading_address_only() {
+        assert_eq!(strip_nick_address("zc: status?", "zc"), "status?");
+        assert_eq!(strip_nick_address("ZC, hi", "zc"), "hi");
+        assert_eq!(strip_nick_address("zcbot hi", "zc"), "zcbot hi");
+        assert_eq!(strip_nick_address("hi zc", "zc"), "hi zc");
+    }
+
     // ── Allowlist ───────────────────────────────────────────
 
     #[test]
@@ -943,7 +1001,7 @@ mod tests {
 
     #[test]
     fn irc_config_serde_roundtrip() {
-        use crate::config::schema::IrcConfig;
+        use crate::config::schema::{GroupPolicyConfig, IrcConfig};
 
         let config = IrcConfig {
             server: "irc.example.com".into(),
@@ -956,6 +1014,7 @@ mod tests {
             nickserv_password: Some("secret".into()),
             sasl_password: None,
             verify_tls: Some(true),
+            group: GroupPolicyConfig::default(),
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -641,6 +641,8 @@ impl LarkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group: None,
+                        voice: false,
                     };
 
                     tracing::debug!("Lark WS: message in {}", lark_msg.chat_id);
@@ -837,6 +839,8 @@ impl LarkChannel {
             channel: "lark".to_string(),
             timestamp,
             thread_ts: None,
+            group: None,
+            voice: false,
         });
 
         messages
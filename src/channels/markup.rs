@@ -0,0 +1,723 @@
+//! Outbound message formatting shared by all channels.
+//!
+//! The agent answers in Markdown. Channels render it once through
+//! [`render_markdown`] for the markup their platform understands, and split
+//! long replies with [`split_markdown`], which breaks between blocks and
+//! re-fences code so every chunk renders on its own.
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// Markup dialect a channel can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupFormat {
+    /// Discord-style Markdown: no tables, escaped control characters.
+    Markdown,
+    /// Telegram `parse_mode: HTML` subset (`<b>`, `<i>`, `<s>`, `<code>`, `<pre>`, `<a>`, `<blockquote>`).
+    TelegramHtml,
+    /// Slack mrkdwn (`*bold*`, `_italic_`, `<url|text>`).
+    SlackMrkdwn,
+    /// Full HTML fragment, for Matrix and email.
+    Html,
+    /// No markup at all, for IRC and SMS-like transports.
+    PlainText,
+}
+
+const HORIZONTAL_RULE: &str = "──────────";
+const LINK_SCHEMES: [&str; 4] = ["http://", "https://", "mailto:", "tg://"];
+
+fn parser_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+/// Render agent Markdown for `format`. Raw HTML in the source is always
+/// shown as text, never passed through.
+pub fn render_markdown(markdown: &str, format: MarkupFormat) -> String {
+    let parser = Parser::new_ext(markdown, parser_options()).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    });
+
+    if format == MarkupFormat::Html {
+        let mut body = String::with_capacity(markdown.len() * 3 / 2);
+        pulldown_cmark::html::push_html(&mut body, parser);
+        return body;
+    }
+
+    let mut renderer = Renderer::new(format);
+    for event in parser {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+/// Split Markdown into chunks of at most `max_chars` characters.
+///
+/// Breaks fall between top-level blocks (paragraphs, list items, code
+/// blocks, tables) where possible. A block that is too long on its own is
+/// split at line, then word boundaries; an oversized fenced code block is
+/// cut between lines and each piece re-wrapped in the same fence. Apart
+/// from that re-fencing, concatenating the chunks yields the input.
+pub fn split_markdown(markdown: &str, max_chars: usize) -> Vec<String> {
+    if max_chars == 0 || markdown.chars().count() <= max_chars {
+        return vec![markdown.to_string()];
+    }
+
+    let mut boundaries = vec![0];
+    let mut depth = 0usize;
+    let mut top_level_list = false;
+    for (event, range) in Parser::new_ext(markdown, parser_options()).into_offset_iter() {
+        match event {
+            Event::Start(tag) => {
+                if depth == 0 {
+                    boundaries.push(range.start);
+                    top_level_list = matches!(tag, Tag::List(_));
+                } else if depth == 1 && top_level_list && matches!(tag, Tag::Item) {
+                    boundaries.push(range.start);
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth = depth.saturating_sub(1),
+            _ if depth == 0 => boundaries.push(range.start),
+            _ => {}
+        }
+    }
+    boundaries.push(markdown.len());
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for window in boundaries.windows(2) {
+        let segment = &markdown[window[0]..window[1]];
+        let segment_chars = segment.chars().count();
+        if current_chars + segment_chars <= max_chars {
+            current.push_str(segment);
+            current_chars += segment_chars;
+            continue;
+        }
+        if !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if segment_chars <= max_chars {
+            current = segment.to_string();
+        } else {
+            let mut pieces = split_block(segment, max_chars);
+            current = pieces.pop().unwrap_or_default();
+            chunks.extend(pieces);
+        }
+        current_chars = current.chars().count();
+    }
+    if !current.is_empty() || chunks.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split one oversized block, keeping fenced code renderable.
+fn split_block(block: &str, max_chars: usize) -> Vec<String> {
+    let Some((open, body, close)) = fenced_code_parts(block) else {
+        return split_text(block, max_chars);
+    };
+    let overhead = open.chars().count() + close.chars().count() + 3;
+    if overhead >= max_chars {
+        return split_text(block, max_chars);
+    }
+    split_text(body, max_chars - overhead)
+        .into_iter()
+        .map(|piece| format!("{open}\n{}\n{close}\n", piece.trim_end_matches('\n')))
+        .collect()
+}
+
+/// Opening fence line, body and closing fence of a fenced code block.
+fn fenced_code_parts(block: &str) -> Option<(&str, &str, &str)> {
+    let trimmed = block.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+    let close = &trimmed[..fence_len];
+    let (open, rest) = trimmed.split_once('\n')?;
+    let body = rest.trim_end();
+    let body = body
+        .strip_suffix(close)
+        .map_or(body, |b| b.trim_end_matches('\n'));
+    Some((open.trim_end(), body, close))
+}
+
+/// Split plain text at newlines in the second half of each window, then at
+/// spaces, then hard at a character boundary. Lossless.
+fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut remaining = text;
+    while !remaining.is_empty() {
+        let hard_split = remaining
+            .char_indices()
+            .nth(max_chars)
+            .map_or(remaining.len(), |(idx, _)| idx);
+
+        let chunk_end = if hard_split == remaining.len() {
+            hard_split
+        } else {
+            let search_area = &remaining[..hard_split];
+            match search_area.rfind('\n') {
+                Some(pos) if search_area[..pos].chars().count() >= max_chars / 2 => pos + 1,
+                _ => search_area.rfind(' ').map_or(hard_split, |space| space + 1),
+            }
+        };
+
+        chunks.push(remaining[..chunk_end].to_string());
+        remaining = &remaining[chunk_end..];
+    }
+    chunks
+}
+
+#[derive(Clone, Copy)]
+enum Style {
+    Strong,
+    Emphasis,
+    Strike,
+}
+
+/// Event-driven writer for the non-HTML formats.
+struct Renderer {
+    format: MarkupFormat,
+    out: String,
+    /// Outer buffers while a quote, code block or table cell is captured.
+    saved: Vec<String>,
+    /// Next number per open list; `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    /// A list marker was just written; the item's first block needs no gap.
+    item_fresh: bool,
+    /// Output offset and destination of each open link or image.
+    links: Vec<(usize, String)>,
+    /// Fence language while inside a code block.
+    code_language: Option<String>,
+    table: Option<Vec<Vec<String>>>,
+    table_row: Vec<String>,
+}
+
+impl Renderer {
+    fn new(format: MarkupFormat) -> Self {
+        Self {
+            format,
+            out: String::new(),
+            saved: Vec::new(),
+            lists: Vec::new(),
+            item_fresh: false,
+            links: Vec::new(),
+            code_language: None,
+            table: None,
+            table_row: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out.trim_end_matches('\n').to_string()
+    }
+
+    /// Tables are emitted as monospace text, so their cells carry no markup.
+    fn inline_format(&self) -> MarkupFormat {
+        if self.table.is_some() {
+            MarkupFormat::PlainText
+        } else {
+            self.format
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        self.item_fresh = false;
+        self.out.push_str(text);
+    }
+
+    fn begin_capture(&mut self) {
+        self.saved.push(std::mem::take(&mut self.out));
+    }
+
+    fn end_capture(&mut self) -> String {
+        let outer = self.saved.pop().unwrap_or_default();
+        std::mem::replace(&mut self.out, outer)
+    }
+
+    fn start_block(&mut self) {
+        if self.item_fresh {
+            self.item_fresh = false;
+            return;
+        }
+        if self.out.is_empty() {
+            return;
+        }
+        if self.lists.is_empty() {
+            let kept = self.out.trim_end_matches('\n').len();
+            self.out.truncate(kept);
+            self.out.push_str("\n\n");
+        } else {
+            if !self.out.ends_with('\n') {
+                self.out.push('\n');
+            }
+            self.out.push_str(&"  ".repeat(self.lists.len()));
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if self.code_language.is_some() {
+                    self.out.push_str(&text);
+                } else {
+                    let escaped = escape(&text, self.inline_format());
+                    self.write(&escaped);
+                }
+            }
+            Event::Code(code) => {
+                let rendered = inline_code(&code, self.inline_format());
+                self.write(&rendered);
+            }
+            Event::SoftBreak | Event::HardBreak => self.write("\n"),
+            Event::Rule => {
+                self.start_block();
+                self.write(HORIZONTAL_RULE);
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = match (self.format, checked) {
+                    (MarkupFormat::PlainText, true) => "[x] ",
+                    (MarkupFormat::PlainText, false) => "[ ] ",
+                    (_, true) => "☑ ",
+                    (_, false) => "☐ ",
+                };
+                self.write(marker);
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::HtmlBlock => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                let open = match self.format {
+                    MarkupFormat::Markdown if level <= HeadingLevel::H3 => {
+                        format!("{} ", "#".repeat(level as usize))
+                    }
+                    _ => style_markers(self.format, Style::Strong).0.to_string(),
+                };
+                self.write(&open);
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.begin_capture();
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || "+-#._".contains(*c))
+                        .collect(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_language = Some(language);
+                self.begin_capture();
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                }
+                self.item_fresh = false;
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                if !self.out.is_empty() && !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    _ if self.format == MarkupFormat::Markdown => "- ".to_string(),
+                    _ => "• ".to_string(),
+                };
+                self.out.push_str(&indent);
+                self.out.push_str(&marker);
+                self.item_fresh = true;
+            }
+            Tag::Table(_) => {
+                self.start_block();
+                self.table = Some(Vec::new());
+            }
+            Tag::TableHead | Tag::TableRow => self.table_row.clear(),
+            Tag::TableCell => self.begin_capture(),
+            Tag::Emphasis => self.open_style(Style::Emphasis),
+            Tag::Strong => self.open_style(Style::Strong),
+            Tag::Strikethrough => self.open_style(Style::Strike),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.item_fresh = false;
+                self.links.push((self.out.len(), dest_url.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(level) => {
+                let close = match self.format {
+                    MarkupFormat::Markdown if level <= HeadingLevel::H3 => "",
+                    _ => style_markers(self.format, Style::Strong).1,
+                };
+                self.write(close);
+            }
+            TagEnd::BlockQuote(_) => {
+                let quoted = self.end_capture();
+                let quoted = quote(quoted.trim_end_matches('\n'), self.format);
+                self.write(&quoted);
+            }
+            TagEnd::CodeBlock => {
+                let code = self.end_capture();
+                let language = self.code_language.take().unwrap_or_default();
+                let block = code_block(code.trim_end_matches('\n'), &language, self.format);
+                self.write(&block);
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+            }
+            TagEnd::TableCell => {
+                let cell = self.end_capture();
+                self.table_row.push(cell.trim().to_string());
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                let row = std::mem::take(&mut self.table_row);
+                if let Some(rows) = self.table.as_mut() {
+                    rows.push(row);
+                }
+            }
+            TagEnd::Table => {
+                let rows = self.table.take().unwrap_or_default();
+                let block = code_block(&monospace_table(&rows), "", self.format);
+                self.write(&block);
+            }
+            TagEnd::Emphasis => self.close_style(Style::Emphasis),
+            TagEnd::Strong => self.close_style(Style::Strong),
+            TagEnd::Strikethrough => self.close_style(Style::Strike),
+            TagEnd::Link | TagEnd::Image => {
+                if let Some((start, url)) = self.links.pop() {
+                    let text = self.out.split_off(start);
+                    let link = link(&text, &url, self.inline_format());
+                    self.write(&link);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn open_style(&mut self, style: Style) {
+        let open = style_markers(self.inline_format(), style).0;
+        self.write(open);
+    }
+
+    fn close_style(&mut self, style: Style) {
+        let close = style_markers(self.inline_format(), style).1;
+        self.write(close);
+    }
+}
+
+fn style_markers(format: MarkupFormat, style: Style) -> (&'static str, &'static str) {
+    match (format, style) {
+        (MarkupFormat::Markdown, Style::Strong) => ("**", "**"),
+        (MarkupFormat::Markdown, Style::Emphasis) | (MarkupFormat::SlackMrkdwn, Style::Strong) => {
+            ("*", "*")
+        }
+        (MarkupFormat::Markdown, Style::Strike) => ("~~", "~~"),
+        (MarkupFormat::TelegramHtml, Style::Strong) => ("<b>", "</b>"),
+        (MarkupFormat::TelegramHtml, Style::Emphasis) => ("<i>", "</i>"),
+        (MarkupFormat::TelegramHtml, Style::Strike) => ("<s>", "</s>"),
+        (MarkupFormat::SlackMrkdwn, Style::Emphasis) => ("_", "_"),
+        (MarkupFormat::SlackMrkdwn, Style::Strike) => ("~", "~"),
+        (MarkupFormat::Html | MarkupFormat::PlainText, _) => ("", ""),
+    }
+}
+
+fn escape(text: &str, format: MarkupFormat) -> String {
+    match format {
+        MarkupFormat::Markdown => escape_markdown(text),
+        MarkupFormat::TelegramHtml | MarkupFormat::Html => escape_html(text),
+        MarkupFormat::SlackMrkdwn => escape_slack(text),
+        MarkupFormat::PlainText => text.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Backslash-escape Markdown control characters, leaving bare URLs intact
+/// so the client still links them.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for word in text.split_inclusive(char::is_whitespace) {
+        if word.contains("://") {
+            escaped.push_str(word);
+            continue;
+        }
+        for c in word.chars() {
+            if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+fn inline_code(code: &str, format: MarkupFormat) -> String {
+    match format {
+        MarkupFormat::Markdown if code.contains('`') => format!("`` {code} ``"),
+        MarkupFormat::Markdown => format!("`{code}`"),
+        MarkupFormat::TelegramHtml | MarkupFormat::Html => {
+            format!("<code>{}</code>", escape_html(code))
+        }
+        MarkupFormat::SlackMrkdwn => format!("`{}`", escape_slack(code)),
+        MarkupFormat::PlainText => code.to_string(),
+    }
+}
+
+fn code_block(code: &str, language: &str, format: MarkupFormat) -> String {
+    match format {
+        MarkupFormat::Markdown => format!("```{language}\n{code}\n```"),
+        // Telegram rejects class attributes, so the language is dropped.
+        MarkupFormat::TelegramHtml | MarkupFormat::Html => {
+            format!("<pre><code>{}</code></pre>", escape_html(code))
+        }
+        MarkupFormat::SlackMrkdwn => format!("```\n{}\n```", escape_slack(code)),
+        MarkupFormat::PlainText => code.to_string(),
+    }
+}
+
+fn quote(text: &str, format: MarkupFormat) -> String {
+    match format {
+        MarkupFormat::TelegramHtml | MarkupFormat::Html => {
+            format!("<blockquote>{text}</blockquote>")
+        }
+        _ => text
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// `text` is already rendered; `url` is the raw destination.
+fn link(text: &str, url: &str, format: MarkupFormat) -> String {
+    let bare = text.is_empty() || text == url || text == escape(url, format);
+    match format {
+        MarkupFormat::Markdown if bare => url.to_string(),
+        MarkupFormat::Markdown => format!("[{text}]({url})"),
+        MarkupFormat::TelegramHtml | MarkupFormat::Html
+            if LINK_SCHEMES.iter().any(|scheme| url.starts_with(scheme)) =>
+        {
+            let label = if text.is_empty() {
+                escape_html(url)
+            } else {
+                text.to_string()
+            };
+            format!("<a href=\"{}\">{label}</a>", escape_html(url))
+        }
+        MarkupFormat::SlackMrkdwn if bare => format!("<{}>", escape_slack(url)),
+        MarkupFormat::SlackMrkdwn => format!("<{}|{text}>", escape_slack(url)),
+        _ if bare => escape(url, format),
+        _ => format!("{text} ({})", escape(url, format)),
+    }
+}
+
+/// Lay out table rows as aligned columns with a rule under the header.
+fn monospace_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map_or("", String::as_str);
+                format!("{cell:<width$}")
+            })
+            .collect();
+        lines.push(cells.join(" | ").trim_end().to_string());
+        if index == 0 {
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            lines.push(rule.join("-+-"));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+        "## Status\n\nAll **good** on `main`, see [docs](https://example.com/a_b).\n\n\
+- first\n- second\n  1. nested\n\n```rust\nlet x = 1 < 2;\n```\n\n\
+| Name | Count |\n|------|-------|\n| apples | 3 |\n\n> quoted *text*";
+
+    #[test]
+    fn renders_telegram_html() {
+        let rendered = render_markdown(SAMPLE, MarkupFormat::TelegramHtml);
+        assert_eq!(
+            rendered,
+            "<b>Status</b>\n\n\
+All <b>good</b> on <code>main</code>, see <a href=\"https://example.com/a_b\">docs</a>.\n\n\
+• first\n• second\n  1. nested\n\n\
+<pre><code>let x = 1 &lt; 2;</code></pre>\n\n\
+<pre><code>Name   | Count\n-------+------\napples | 3</code></pre>\n\n\
+<blockquote>quoted <i>text</i></blockquote>"
+        );
+    }
+
+    #[test]
+    fn renders_slack_mrkdwn() {
+        let rendered = render_markdown(SAMPLE, MarkupFormat::SlackMrkdwn);
+        assert_eq!(
+            rendered,
+            "*Status*\n\n\
+All *good* on `main`, see <https://example.com/a_b|docs>.\n\n\
+• first\n• second\n  1. nested\n\n\
+```\nlet x = 1 &lt; 2;\n```\n\n\
+```\nName   | Count\n-------+------\napples | 3\n```\n\n\
+> quoted _text_"
+        );
+    }
+
+    #[test]
+    fn renders_discord_markdown_with_tables_as_code() {
+        let rendered = render_markdown(SAMPLE, MarkupFormat::Markdown);
+        assert_eq!(
+            rendered,
+            "## Status\n\n\
+All **good** on `main`, see [docs](https://example.com/a_b).\n\n\
+- first\n- second\n  1. nested\n\n\
+```rust\nlet x = 1 < 2;\n```\n\n\
+```\nName   | Count\n-------+------\napples | 3\n```\n\n\
+> quoted *text*"
+        );
+        assert_eq!(
+            render_markdown("snake_case and https://x.io/a_b", MarkupFormat::Markdown),
+            "snake\\_case and https://x.io/a_b"
+        );
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let rendered = render_markdown(SAMPLE, MarkupFormat::PlainText);
+        assert_eq!(
+            rendered,
+            "Status\n\n\
+All good on main, see docs (https://example.com/a_b).\n\n\
+• first\n• second\n  1. nested\n\n\
+let x = 1 < 2;\n\n\
+Name   | Count\n-------+------\napples | 3\n\n\
+> quoted text"
+        );
+    }
+
+    #[test]
+    fn html_escapes_raw_html() {
+        let rendered = render_markdown("<b>hi</b> & **bye**", MarkupFormat::Html);
+        assert_eq!(
+            rendered,
+            "<p>&lt;b&gt;hi&lt;/b&gt; &amp; <strong>bye</strong></p>\n"
+        );
+        assert_eq!(
+            render_markdown("[x](javascript:alert(1))", MarkupFormat::TelegramHtml),
+            "x (javascript:alert(1))"
+        );
+    }
+
+    #[test]
+    fn split_breaks_between_blocks() {
+        let first = "a".repeat(60);
+        let second = "b".repeat(60);
+        let text = format!("{first}\n\n{second}");
+        let chunks = split_markdown(&text, 100);
+        assert_eq!(chunks, vec![format!("{first}\n\n"), second]);
+    }
+
+    #[test]
+    fn split_keeps_list_items_whole() {
+        let items = (0..20)
+            .map(|i| format!("- item number {i}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let chunks = split_markdown(&items, 60);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 60);
+            assert!(chunk.starts_with("- item"));
+            assert!(chunk.ends_with('\n'));
+        }
+        assert_eq!(chunks.concat(), items);
+    }
+
+    #[test]
+    fn split_refences_long_code_blocks() {
+        let body = (0..30)
+            .map(|i| format!("line {i:02}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let text = format!("Intro\n\n```python\n{body}```\n");
+        let chunks = split_markdown(&text, 80);
+        assert!(chunks.len() > 2);
+        assert_eq!(chunks[0], "Intro\n\n");
+        for chunk in &chunks[1..] {
+            assert!(chunk.chars().count() <= 80);
+            assert!(chunk.starts_with("```python\nline "));
+            assert!(chunk.ends_with("\n```\n"));
+        }
+    }
+
+    #[test]
+    fn split_hard_splits_unbroken_text() {
+        let text = "x".repeat(250);
+        let chunks = split_markdown(&text, 100);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.concat(), text);
+        assert_eq!(split_markdown("", 100), vec![""]);
+    }
+}
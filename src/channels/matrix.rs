@@ -1,4 +1,5 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::markup::{render_markdown, MarkupFormat};
+use crate::channels::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
 use async_trait::async_trait;
 use matrix_sdk::{
     authentication::matrix::MatrixSession,
@@ -217,6 +218,52 @@ impl MatrixChannel {
         !body.trim().is_empty()
     }
 
+    /// Markdown source as the plain body, rendered HTML as `formatted_body`.
+    fn message_content(markdown: &str) -> RoomMessageEventContent {
+        RoomMessageEventContent::text_html(markdown, render_markdown(markdown, MarkupFormat::Html))
+    }
+
+    /// Room metadata for rooms with more than two joined members; two-person
+    /// rooms are treated as direct chats.
+    fn group_context(
+        room_id: &str,
+        joined_members: u64,
+        sender: &str,
+        body: &str,
+        my_user_id: &str,
+        mentioned_in_content: bool,
+    ) -> Option<GroupContext> {
+        if joined_members <= 2 {
+            return None;
+        }
+        let localpart = my_user_id
+            .trim_start_matches('@')
+            .split(':')
+            .next()
+            .unwrap_or_default();
+        let mentioned = mentioned_in_content
+            || body.contains(my_user_id)
+            || (!localpart.is_empty()
+                && body
+                    .to_lowercase()
+                    .starts_with(&format!("{}:", localpart.to_lowercase())));
+        // Rich reply fallbacks quote the original author: "> <@bot:hs> ...".
+        let replied_to_bot = body.starts_with(&format!("> <{my_user_id}>"));
+        let sender_name = sender
+            .trim_start_matches('@')
+            .split(':')
+            .next()
+            .unwrap_or(sender)
+            .to_string();
+
+        Some(GroupContext {
+            room: room_id.to_string(),
+            sender_name,
+            mentioned,
+            replied_to_bot,
+        })
+    }
+
     fn cache_event_id(
         event_id: &str,
         recent_order: &mut std::collections::VecDeque<String>,
@@ -547,8 +594,7 @@ impl Channel for MatrixChannel {
             anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
         }
 
-        room.send(RoomMessageEventContent::text_markdown(&message.content))
-            .await?;
+        room.send(Self::message_content(&message.content)).await?;
 
         Ok(())
     }
@@ -625,6 +671,20 @@ impl Channel for MatrixChannel {
                     return;
                 }
 
+                let mentioned_in_content = event
+                    .content
+                    .mentions
+                    .as_ref()
+                    .is_some_and(|mentions| mentions.user_ids.contains(&my_user_id));
+                let group = MatrixChannel::group_context(
+                    room.room_id().as_str(),
+                    room.joined_members_count(),
+                    &sender,
+                    &body,
+                    my_user_id.as_str(),
+                    mentioned_in_content,
+                );
+
                 let event_id = event.event_id.to_string();
                 {
                     let mut guard = dedupe.lock().await;
@@ -645,6 +705,8 @@ impl Channel for MatrixChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: None,
+                    group,
+                    voice: false,
                 };
 
                 let _ = tx.send(msg).await;
@@ -699,6 +761,38 @@ mod tests {
         )
     }
 
+    #[test]
+    fn group_context_only_for_multi_member_rooms() {
+        assert!(
+            MatrixChannel::group_context("!r:hs", 2, "@alice:hs", "hi", "@bot:hs", false).is_none()
+        );
+
+        let group = MatrixChannel::group_context(
+            "!r:hs",
+            5,
+            "@alice:hs",
+            "bot: what's up?",
+            "@bot:hs",
+            false,
+        )
+        .unwrap();
+        assert_eq!(group.room, "!r:hs");
+        assert_eq!(group.sender_name, "alice");
+        assert!(group.mentioned);
+
+        let reply = MatrixChannel::group_context(
+            "!r:hs",
+            5,
+            "@alice:hs",
+            "> <@bot:hs> done\n\nthanks",
+            "@bot:hs",
+            false,
+        )
+        .unwrap();
+        assert!(reply.replied_to_bot);
+        assert!(!reply.mentioned);
+    }
+
     #[test]
     fn creates_with_correct_fields() {
         let ch = make_channel();
@@ -844,7 +938,7 @@ mod tests {
 
     #[test]
     fn send_content_uses_markdown_formatting() {
-        let content = RoomMessageEventContent::text_markdown("**hello**");
+        let content = MatrixChannel::message_content("**hello**");
         let value = serde_json::to_value(content).unwrap();
 
         assert_eq!(value["msgtype"], "m.text");
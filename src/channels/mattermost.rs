@@ -1,9 +1,30 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
 use anyhow::{bail, Result};
 use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
 
-/// Mattermost channel — polls channel posts via REST API v4.
+/// Mattermost's default maximum post length (characters).
+const MATTERMOST_MAX_POST_LENGTH: usize = 16383;
+
+/// Recent message keys remembered to drop duplicate deliveries.
+const SEEN_CAPACITY: usize = 512;
+
+/// Interval between REST polls while the WebSocket is unavailable.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Upper bound for the WebSocket reconnect backoff.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Mattermost channel — receives events over the `/api/v4/websocket` stream
+/// and falls back to polling channel posts via REST API v4 while the socket
+/// is unavailable.
 /// Mattermost is API-compatible with many Slack patterns but uses a dedicated v4 structure.
 pub struct MattermostChannel {
     base_url: String, // e.g., https://mm.example.com
@@ -15,8 +36,53 @@ pub struct MattermostChannel {
     thread_replies: bool,
     /// When true, only respond to messages that @-mention the bot.
     mention_only: bool,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
     /// Handle for the background typing-indicator loop (aborted on stop_typing).
     typing_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Outbound actions for the live WebSocket, if one is connected.
+    ws_actions: Arc<Mutex<Option<mpsc::UnboundedSender<serde_json::Value>>>>,
+    /// Direct-message channels seen on the socket; they are always in scope.
+    direct_channels: Mutex<HashSet<String>>,
+    /// Keys of recently delivered messages. A resumed socket replays
+    /// buffered events that REST polling may already have delivered.
+    seen: Mutex<(VecDeque<String>, HashSet<String>)>,
+    /// Ids of recent posts made by the bot; only reactions to these are forwarded.
+    own_posts: Mutex<(VecDeque<String>, HashSet<String>)>,
+}
+
+/// Insert `key` into a bounded FIFO set; false when it was already present.
+fn remember_bounded(set: &mut (VecDeque<String>, HashSet<String>), key: &str) -> bool {
+    let (order, keys) = set;
+    if !keys.insert(key.to_string()) {
+        return false;
+    }
+    order.push_back(key.to_string());
+    if order.len() > SEEN_CAPACITY {
+        if let Some(oldest) = order.pop_front() {
+            keys.remove(&oldest);
+        }
+    }
+    true
+}
+
+/// Why a WebSocket session ended without an error.
+enum SocketExit {
+    /// The message receiver was dropped; stop listening.
+    ReceiverClosed,
+    /// The server closed the socket or a sequence gap was detected.
+    Reconnect,
+}
+
+/// Connection state carried across WebSocket reconnects.
+struct SocketState {
+    /// Server-assigned id used to resume the connection.
+    connection_id: Option<String>,
+    /// Next expected server event sequence number.
+    next_seq: i64,
+    /// Newest post `create_at` seen, used by REST catch-up polls.
+    last_create_at: i64,
 }
 
 impl MattermostChannel {
@@ -37,10 +103,28 @@ impl MattermostChannel {
             allowed_users,
             thread_replies,
             mention_only,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
             typing_handle: Mutex::new(None),
+            ws_actions: Arc::new(Mutex::new(None)),
+            direct_channels: Mutex::new(HashSet::new()),
+            seen: Mutex::new((VecDeque::new(), HashSet::new())),
+            own_posts: Mutex::new((VecDeque::new(), HashSet::new())),
         }
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.mattermost")
     }
@@ -81,123 +165,472 @@ impl MattermostChannel {
             .to_string();
         (id, username)
     }
-}
 
-#[async_trait]
-impl Channel for MattermostChannel {
-    fn name(&self) -> &str {
-        "mattermost"
+    fn first_delivery(&self, key: &str) -> bool {
+        remember_bounded(&mut self.seen.lock(), key)
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Mattermost supports threading via 'root_id'.
-        // We pack 'channel_id:root_id' into recipient if it's a thread.
-        let (channel_id, root_id) = if let Some((c, r)) = message.recipient.split_once(':') {
-            (c, Some(r))
+    /// Posts are accepted from the configured channel (any channel when
+    /// unset) and from direct messages to the bot.
+    fn in_scope(&self, channel_id: &str) -> bool {
+        self.channel_id
+            .as_deref()
+            .is_none_or(|scoped| scoped == channel_id)
+            || self.direct_channels.lock().contains(channel_id)
+    }
+
+    /// `ws(s)://` URL for the event stream, resuming `connection_id` at the
+    /// next expected sequence number when known.
+    fn websocket_url(&self, state: &SocketState) -> String {
+        let base = if let Some(rest) = self.base_url.strip_prefix("https://") {
+            format!("wss://{rest}")
+        } else if let Some(rest) = self.base_url.strip_prefix("http://") {
+            format!("ws://{rest}")
         } else {
-            (message.recipient.as_str(), None)
+            self.base_url.clone()
         };
+        match state.connection_id {
+            Some(ref connection_id) => format!(
+                "{base}/api/v4/websocket?connection_id={}&sequence_number={}",
+                urlencoding::encode(connection_id),
+                state.next_seq
+            ),
+            None => format!("{base}/api/v4/websocket"),
+        }
+    }
 
-        let mut body_map = serde_json::json!({
-            "channel_id": channel_id,
-            "message": message.content
+    /// Turn a WebSocket event into a channel message.
+    ///
+    /// Handles `posted` (including direct messages), `post_edited` and
+    /// `reaction_added` on the bot's own posts. Other events, system posts
+    /// and the bot's own activity are dropped.
+    fn parse_ws_event(
+        &self,
+        event: &serde_json::Value,
+        bot_user_id: &str,
+        bot_username: &str,
+    ) -> Option<ChannelMessage> {
+        let kind = event.get("event").and_then(|e| e.as_str())?;
+        let data = event.get("data")?;
+
+        match kind {
+            "posted" | "post_edited" => {
+                let post: serde_json::Value =
+                    serde_json::from_str(data.get("post").and_then(|p| p.as_str())?).ok()?;
+                let channel_id = post.get("channel_id").and_then(|c| c.as_str())?;
+                if post
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .is_some_and(|t| t.starts_with("system_"))
+                {
+                    return None;
+                }
+                if post.get("user_id").and_then(|u| u.as_str()) == Some(bot_user_id) {
+                    if let Some(id) = post.get("id").and_then(|i| i.as_str()) {
+                        remember_bounded(&mut self.own_posts.lock(), id);
+                    }
+                    return None;
+                }
+                if data.get("channel_type").and_then(|t| t.as_str()) == Some("D") {
+                    self.direct_channels.lock().insert(channel_id.to_string());
+                }
+                if !self.in_scope(channel_id) {
+                    return None;
+                }
+
+                let mut msg =
+                    self.parse_mattermost_post(&post, bot_user_id, bot_username, 0, channel_id)?;
+                if kind == "post_edited" {
+                    let edit_at = post.get("edit_at").and_then(|e| e.as_i64()).unwrap_or(0);
+                    msg.id = format!("{}_edit_{edit_at}", msg.id);
+                    msg.content = format!("[edited] {}", msg.content);
+                }
+                self.first_delivery(&msg.id).then_some(msg)
+            }
+            "reaction_added" => {
+                // A reaction cannot carry an @-mention.
+                if self.mention_only {
+                    return None;
+                }
+                let reaction: serde_json::Value =
+                    serde_json::from_str(data.get("reaction").and_then(|r| r.as_str())?).ok()?;
+                let user_id = reaction.get("user_id").and_then(|u| u.as_str())?;
+                let post_id = reaction.get("post_id").and_then(|p| p.as_str())?;
+                let emoji = reaction.get("emoji_name").and_then(|e| e.as_str())?;
+                let channel_id = event
+                    .get("broadcast")
+                    .and_then(|b| b.get("channel_id"))
+                    .and_then(|c| c.as_str())
+                    .filter(|c| !c.is_empty())?;
+                // Reactions elsewhere in the channel are not meant for the bot.
+                if user_id == bot_user_id
+                    || !self.in_scope(channel_id)
+                    || !self.own_posts.lock().1.contains(post_id)
+                {
+                    return None;
+                }
+                if !self.is_user_allowed(user_id) {
+                    tracing::warn!(
+                        "Mattermost: ignoring reaction from unauthorized user: {user_id}"
+                    );
+                    return None;
+                }
+
+                let create_at = reaction
+                    .get("create_at")
+                    .and_then(|c| c.as_i64())
+                    .unwrap_or(0);
+                let id = format!("mattermost_{post_id}_reaction_{user_id}_{emoji}");
+                self.first_delivery(&id).then(|| ChannelMessage {
+                    id,
+                    sender: user_id.to_string(),
+                    reply_target: channel_id.to_string(),
+                    content: format!("[reaction] :{emoji}: on post {post_id}"),
+                    channel: "mattermost".to_string(),
+                    #[allow(clippy::cast_sign_loss)]
+                    timestamp: (create_at / 1000) as u64,
+                    thread_ts: None,
+                    group: None,
+                    voice: false,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Fetch posts newer than `last_create_at` from one channel.
+    async fn poll_once(
+        &self,
+        channel_id: &str,
+        bot_user_id: &str,
+        bot_username: &str,
+        last_create_at: &mut i64,
+    ) -> Result<Vec<ChannelMessage>> {
+        let data: serde_json::Value = self
+            .http_client()
+            .get(format!(
+                "{}/api/v4/channels/{}/posts",
+                self.base_url, channel_id
+            ))
+            .bearer_auth(&self.bot_token)
+            .query(&[("since", last_create_at.to_string())])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let mut messages = Vec::new();
+        if let Some(posts) = data.get("posts").and_then(|p| p.as_object()) {
+            // Process in chronological order
+            let mut post_list: Vec<_> = posts.values().collect();
+            post_list.sort_by_key(|p| p.get("create_at").and_then(|c| c.as_i64()).unwrap_or(0));
+
+            for post in post_list {
+                let msg = self.parse_mattermost_post(
+                    post,
+                    bot_user_id,
+                    bot_username,
+                    *last_create_at,
+                    channel_id,
+                );
+                let create_at = post
+                    .get("create_at")
+                    .and_then(|c| c.as_i64())
+                    .unwrap_or(*last_create_at);
+                *last_create_at = (*last_create_at).max(create_at);
+
+                if let Some(channel_msg) = msg.filter(|m| self.first_delivery(&m.id)) {
+                    messages.push(channel_msg);
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Poll the configured channel for `duration`. Returns `false` once the
+    /// receiver is gone.
+    async fn poll_for(
+        &self,
+        tx: &mpsc::Sender<ChannelMessage>,
+        channel_id: &str,
+        bot_user_id: &str,
+        bot_username: &str,
+        last_create_at: &mut i64,
+        duration: Duration,
+    ) -> bool {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let messages = match self
+                .poll_once(channel_id, bot_user_id, bot_username, last_create_at)
+                .await
+            {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::warn!("Mattermost poll error: {e}");
+                    continue;
+                }
+            };
+            for message in messages {
+                if tx.send(message).await.is_err() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Fetch posts the socket may have missed. Returns `false` once the
+    /// receiver is gone.
+    async fn catch_up(
+        &self,
+        tx: &mpsc::Sender<ChannelMessage>,
+        bot_user_id: &str,
+        bot_username: &str,
+        last_create_at: &mut i64,
+    ) -> bool {
+        let Some(channel_id) = self.channel_id.as_deref() else {
+            return true;
+        };
+        match self
+            .poll_once(channel_id, bot_user_id, bot_username, last_create_at)
+            .await
+        {
+            Ok(messages) => {
+                for message in messages {
+                    if tx.send(message).await.is_err() {
+                        return false;
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Mattermost catch-up poll failed: {e}"),
+        }
+        true
+    }
+
+    /// Run one WebSocket session: authenticate, forward events and relay
+    /// outbound actions (typing indicators) until the socket closes.
+    async fn listen_websocket(
+        &self,
+        tx: &mpsc::Sender<ChannelMessage>,
+        bot_user_id: &str,
+        bot_username: &str,
+        state: &mut SocketState,
+    ) -> Result<SocketExit> {
+        let (ws_stream, _) = tokio_tungstenite::connect_async(self.websocket_url(state)).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let mut action_seq: i64 = 1;
+        let auth = serde_json::json!({
+            "seq": action_seq,
+            "action": "authentication_challenge",
+            "data": { "token": self.bot_token },
         });
+        write.send(Message::Text(auth.to_string().into())).await?;
+
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+        *self.ws_actions.lock() = Some(action_tx);
+
+        let result = loop {
+            let frame = tokio::select! {
+                frame = read.next() => frame,
+                Some(mut action) = action_rx.recv() => {
+                    action_seq += 1;
+                    action["seq"] = serde_json::json!(action_seq);
+                    if let Err(e) = write.send(Message::Text(action.to_string().into())).await {
+                        break Err(e.into());
+                    }
+                    continue;
+                }
+            };
+            let text = match frame {
+                Some(Ok(Message::Text(t))) => t,
+                Some(Ok(Message::Ping(data))) => {
+                    if let Err(e) = write.send(Message::Pong(data)).await {
+                        break Err(e.into());
+                    }
+                    continue;
+                }
+                Some(Ok(Message::Close(_))) | None => break Ok(SocketExit::Reconnect),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => break Err(e.into()),
+            };
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(text.as_ref()) else {
+                continue;
+            };
 
-        if let Some(root) = root_id {
-            body_map.as_object_mut().unwrap().insert(
-                "root_id".to_string(),
-                serde_json::Value::String(root.to_string()),
-            );
+            // Replies to our own actions carry `seq_reply` instead of `seq`.
+            if let Some(reply_to) = event.get("seq_reply") {
+                if event.get("status").and_then(|s| s.as_str()) != Some("OK") {
+                    tracing::warn!("Mattermost: websocket action {reply_to} failed: {event}");
+                }
+                continue;
+            }
+
+            if event.get("event").and_then(|e| e.as_str()) == Some("hello") {
+                let connection_id = event
+                    .get("data")
+                    .and_then(|d| d.get("connection_id"))
+                    .and_then(|c| c.as_str())
+                    .unwrap_or("");
+                if state.connection_id.as_deref() != Some(connection_id) {
+                    // Fresh connection: sequence restarts and buffered
+                    // events from the previous one are gone.
+                    let resumed_failed = state.connection_id.is_some();
+                    state.connection_id = Some(connection_id.to_string());
+                    state.next_seq = 0;
+                    if resumed_failed
+                        && !self
+                            .catch_up(tx, bot_user_id, bot_username, &mut state.last_create_at)
+                            .await
+                    {
+                        break Ok(SocketExit::ReceiverClosed);
+                    }
+                }
+                tracing::info!("Mattermost: connected via WebSocket");
+            }
+
+            if let Some(seq) = event.get("seq").and_then(|s| s.as_i64()) {
+                if seq != state.next_seq {
+                    tracing::warn!(
+                        "Mattermost: websocket sequence gap (expected {}, got {seq}); reconnecting",
+                        state.next_seq
+                    );
+                    break Ok(SocketExit::Reconnect);
+                }
+                state.next_seq = seq + 1;
+            }
+
+            if let Some(message) = self.parse_ws_event(&event, bot_user_id, bot_username) {
+                state.last_create_at = state
+                    .last_create_at
+                    .max(i64::try_from(message.timestamp).unwrap_or(0) * 1000);
+                if tx.send(message).await.is_err() {
+                    break Ok(SocketExit::ReceiverClosed);
+                }
+            }
+        };
+
+        *self.ws_actions.lock() = None;
+        result
+    }
+
+    fn truncate_post(text: &str) -> &str {
+        match text.char_indices().nth(MATTERMOST_MAX_POST_LENGTH) {
+            Some((end, _)) => &text[..end],
+            None => text,
         }
+    }
 
+    async fn delete_post(&self, post_id: &str) -> Result<()> {
         let resp = self
             .http_client()
-            .post(format!("{}/api/v4/posts", self.base_url))
+            .delete(format!("{}/api/v4/posts/{post_id}", self.base_url))
             .bearer_auth(&self.bot_token)
-            .json(&body_map)
             .send()
             .await?;
-
         let status = resp.status();
         if !status.is_success() {
-            let body = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
-            bail!("Mattermost post failed ({status}): {body}");
+            let body = resp.text().await.unwrap_or_default();
+            bail!("Mattermost post delete failed ({status}): {body}");
         }
+        Ok(())
+    }
 
+    async fn patch_post(&self, post_id: &str, text: &str) -> Result<()> {
+        let resp = self
+            .http_client()
+            .put(format!("{}/api/v4/posts/{post_id}/patch", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .json(&serde_json::json!({ "message": text }))
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            bail!("Mattermost post patch failed ({status}): {body}");
+        }
         Ok(())
     }
+}
+
+/// Split a `channel_id[:root_id]` recipient.
+fn split_recipient(recipient: &str) -> (&str, Option<&str>) {
+    match recipient.split_once(':') {
+        Some((channel, root)) => (channel, Some(root)),
+        None => (recipient, None),
+    }
+}
+
+#[async_trait]
+impl Channel for MattermostChannel {
+    fn name(&self) -> &str {
+        "mattermost"
+    }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
-        let channel_id = self
-            .channel_id
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("Mattermost channel_id required for listening"))?;
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        self.create_post(&message.recipient, &message.content)
+            .await
+            .map(|_| ())
+    }
 
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
         let (bot_user_id, bot_username) = self.get_bot_identity().await;
         #[allow(clippy::cast_possible_truncation)]
-        let mut last_create_at = (std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis()) as i64;
+        let mut state = SocketState {
+            connection_id: None,
+            next_seq: 0,
+            last_create_at: (std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()) as i64,
+        };
+        let mut backoff = Duration::from_secs(1);
 
-        tracing::info!("Mattermost channel listening on {}...", channel_id);
+        tracing::info!(
+            "Mattermost channel listening on {}...",
+            self.channel_id.as_deref().unwrap_or("all channels")
+        );
 
         loop {
-            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
-
-            let resp = match self
-                .http_client()
-                .get(format!(
-                    "{}/api/v4/channels/{}/posts",
-                    self.base_url, channel_id
-                ))
-                .bearer_auth(&self.bot_token)
-                .query(&[("since", last_create_at.to_string())])
-                .send()
+            match self
+                .listen_websocket(&tx, &bot_user_id, &bot_username, &mut state)
                 .await
             {
-                Ok(r) => r,
-                Err(e) => {
-                    tracing::warn!("Mattermost poll error: {e}");
-                    continue;
+                Ok(SocketExit::ReceiverClosed) => return Ok(()),
+                Ok(SocketExit::Reconnect) => {
+                    backoff = Duration::from_secs(1);
+                    tokio::time::sleep(backoff).await;
                 }
-            };
-
-            let data: serde_json::Value = match resp.json().await {
-                Ok(d) => d,
                 Err(e) => {
-                    tracing::warn!("Mattermost parse error: {e}");
-                    continue;
-                }
-            };
-
-            if let Some(posts) = data.get("posts").and_then(|p| p.as_object()) {
-                // Process in chronological order
-                let mut post_list: Vec<_> = posts.values().collect();
-                post_list.sort_by_key(|p| p.get("create_at").and_then(|c| c.as_i64()).unwrap_or(0));
-
-                for post in post_list {
-                    let msg = self.parse_mattermost_post(
-                        post,
-                        &bot_user_id,
-                        &bot_username,
-                        last_create_at,
-                        &channel_id,
-                    );
-                    let create_at = post
-                        .get("create_at")
-                        .and_then(|c| c.as_i64())
-                        .unwrap_or(last_create_at);
-                    last_create_at = last_create_at.max(create_at);
-
-                    if let Some(channel_msg) = msg {
-                        if tx.send(channel_msg).await.is_err() {
+                    // Poll the configured channel until the next attempt so
+                    // nothing is missed while the socket is down.
+                    if let Some(channel_id) = self.channel_id.as_deref() {
+                        tracing::warn!(
+                            "Mattermost websocket unavailable ({e}); polling for {}s",
+                            backoff.as_secs()
+                        );
+                        if !self
+                            .poll_for(
+                                &tx,
+                                channel_id,
+                                &bot_user_id,
+                                &bot_username,
+                                &mut state.last_create_at,
+                                backoff,
+                            )
+                            .await
+                        {
                             return Ok(());
                         }
+                    } else {
+                        tracing::warn!(
+                            "Mattermost websocket unavailable ({e}); retrying in {}s",
+                            backoff.as_secs()
+                        );
+                        tokio::time::sleep(backoff).await;
                     }
+                    backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
                 }
             }
         }
@@ -220,37 +653,41 @@ impl Channel for MattermostChannel {
         let client = self.http_client();
         let token = self.bot_token.clone();
         let base_url = self.base_url.clone();
+        let ws_actions = Arc::clone(&self.ws_actions);
 
         // recipient is "channel_id" or "channel_id:root_id"
-        let (channel_id, parent_id) = match recipient.split_once(':') {
-            Some((channel, parent)) => (channel.to_string(), Some(parent.to_string())),
-            None => (recipient.to_string(), None),
-        };
+        let (channel_id, parent_id) = split_recipient(recipient);
+        let body = serde_json::json!({
+            "channel_id": channel_id,
+            "parent_id": parent_id.unwrap_or(""),
+        });
 
         let handle = tokio::spawn(async move {
             let url = format!("{base_url}/api/v4/users/me/typing");
             loop {
-                let mut body = serde_json::json!({ "channel_id": channel_id });
-                if let Some(ref pid) = parent_id {
-                    body.as_object_mut()
-                        .unwrap()
-                        .insert("parent_id".to_string(), serde_json::json!(pid));
-                }
-
-                if let Ok(r) = client
-                    .post(&url)
-                    .bearer_auth(&token)
-                    .json(&body)
-                    .send()
-                    .await
-                {
-                    if !r.status().is_success() {
-                        tracing::debug!(status = %r.status(), "Mattermost typing indicator failed");
+                // Prefer the live socket's `user_typing` action; fall back to REST.
+                let sent_over_socket = ws_actions.lock().as_ref().is_some_and(|actions| {
+                    actions
+                        .send(serde_json::json!({ "action": "user_typing", "data": body }))
+                        .is_ok()
+                });
+
+                if !sent_over_socket {
+                    if let Ok(r) = client
+                        .post(&url)
+                        .bearer_auth(&token)
+                        .json(&body)
+                        .send()
+                        .await
+                    {
+                        if !r.status().is_success() {
+                            tracing::debug!(status = %r.status(), "Mattermost typing indicator failed");
+                        }
                     }
                 }
 
                 // Mattermost typing events expire after ~6s; re-fire every 4s.
-                tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+                tokio::time::sleep(Duration::from_secs(4)).await;
             }
         });
 
@@ -267,9 +704,116 @@ impl Channel for MattermostChannel {
         }
         Ok(())
     }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let post_id = self.create_post(&message.recipient, initial_text).await?;
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+        Ok(post_id)
+    }
+
+    async fn update_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        // Rate-limit edits per recipient
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        match self.patch_post(message_id, Self::truncate_post(text)).await {
+            Ok(()) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(recipient.to_string(), Instant::now());
+            }
+            Err(e) => tracing::debug!("Mattermost draft update failed: {e}"),
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+
+        if text.chars().count() <= MATTERMOST_MAX_POST_LENGTH
+            && self.patch_post(message_id, text).await.is_ok()
+        {
+            return Ok(());
+        }
+
+        // Too long for one post or the edit failed: replace the draft.
+        if let Err(e) = self.delete_post(message_id).await {
+            tracing::debug!("Mattermost draft delete failed: {e}");
+        }
+        self.send(&SendMessage::new(text, recipient)).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        self.delete_post(message_id).await
+    }
 }
 
 impl MattermostChannel {
+    /// Create a post (threaded when the recipient carries a root id) and
+    /// return its id.
+    async fn create_post(&self, recipient: &str, text: &str) -> Result<Option<String>> {
+        // Mattermost supports threading via 'root_id'.
+        // We pack 'channel_id:root_id' into recipient if it's a thread.
+        let (channel_id, root_id) = split_recipient(recipient);
+
+        let mut body_map = serde_json::json!({
+            "channel_id": channel_id,
+            "message": text
+        });
+
+        if let Some(root) = root_id {
+            body_map.as_object_mut().unwrap().insert(
+                "root_id".to_string(),
+                serde_json::Value::String(root.to_string()),
+            );
+        }
+
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v4/posts", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .json(&body_map)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+            bail!("Mattermost post failed ({status}): {body}");
+        }
+
+        let created: serde_json::Value = resp.json().await.unwrap_or_default();
+        Ok(created
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(String::from))
+    }
+
     fn parse_mattermost_post(
         &self,
         post: &serde_json::Value,
@@ -322,6 +866,8 @@ impl MattermostChannel {
             #[allow(clippy::cast_sign_loss)]
             timestamp: (create_at / 1000) as u64,
             thread_ts: None,
+            group: None,
+            voice: false,
         })
     }
 }
@@ -915,4 +1461,178 @@ mod tests {
             normalize_mattermost_content("@mybot hello @mybotx world", "bot123", "mybot", &post);
         assert_eq!(result.as_deref(), Some("hello @mybotx world"));
     }
+
+    fn posted_event(kind: &str, post: serde_json::Value, channel_type: &str) -> serde_json::Value {
+        json!({
+            "event": kind,
+            "data": { "post": post.to_string(), "channel_type": channel_type },
+            "broadcast": { "channel_id": post["channel_id"] },
+            "seq": 3
+        })
+    }
+
+    fn scoped_channel() -> MattermostChannel {
+        MattermostChannel::new(
+            "url".into(),
+            "token".into(),
+            Some("chan1".into()),
+            vec!["*".into()],
+            false,
+            false,
+        )
+    }
+
+    #[test]
+    fn websocket_url_switches_scheme_and_resumes() {
+        let ch = MattermostChannel::new(
+            "https://mm.example.com/".into(),
+            "token".into(),
+            None,
+            vec![],
+            false,
+            false,
+        );
+        let mut state = SocketState {
+            connection_id: None,
+            next_seq: 0,
+            last_create_at: 0,
+        };
+        assert_eq!(
+            ch.websocket_url(&state),
+            "wss://mm.example.com/api/v4/websocket"
+        );
+
+        state.connection_id = Some("conn 1".into());
+        state.next_seq = 42;
+        assert_eq!(
+            ch.websocket_url(&state),
+            "wss://mm.example.com/api/v4/websocket?connection_id=conn%201&sequence_number=42"
+        );
+    }
+
+    #[test]
+    fn ws_posted_event_parses_and_deduplicates() {
+        let ch = scoped_channel();
+        let event = posted_event(
+            "posted",
+            json!({"id": "p1", "channel_id": "chan1", "user_id": "u1", "message": "hi", "create_at": 1_600_000_000_000_i64}),
+            "O",
+        );
+
+        let msg = ch.parse_ws_event(&event, "bot", "botname").unwrap();
+        assert_eq!(msg.id, "mattermost_p1");
+        assert_eq!(msg.content, "hi");
+        assert_eq!(msg.reply_target, "chan1");
+        assert!(ch.parse_ws_event(&event, "bot", "botname").is_none());
+    }
+
+    #[test]
+    fn ws_direct_messages_bypass_channel_scope() {
+        let ch = scoped_channel();
+        let other = posted_event(
+            "posted",
+            json!({"id": "p1", "channel_id": "chan2", "user_id": "u1", "message": "hi", "create_at": 1}),
+            "O",
+        );
+        assert!(ch.parse_ws_event(&other, "bot", "botname").is_none());
+
+        let dm = posted_event(
+            "posted",
+            json!({"id": "p2", "channel_id": "dm1", "user_id": "u1", "message": "hi", "create_at": 2}),
+            "D",
+        );
+        assert_eq!(
+            ch.parse_ws_event(&dm, "bot", "botname")
+                .unwrap()
+                .reply_target,
+            "dm1"
+        );
+
+        // Edits carry no channel type; the DM channel is remembered.
+        let edit = posted_event(
+            "post_edited",
+            json!({"id": "p2", "channel_id": "dm1", "user_id": "u1", "message": "hello", "create_at": 2, "edit_at": 5}),
+            "",
+        );
+        let msg = ch.parse_ws_event(&edit, "bot", "botname").unwrap();
+        assert_eq!(msg.id, "mattermost_p2_edit_5");
+        assert_eq!(msg.content, "[edited] hello");
+    }
+
+    #[test]
+    fn ws_ignores_system_posts_and_own_posts() {
+        let ch = scoped_channel();
+        for post in [
+            json!({"id": "p1", "channel_id": "chan1", "user_id": "u1", "message": "joined", "create_at": 1, "type": "system_join_channel"}),
+            json!({"id": "p2", "channel_id": "chan1", "user_id": "bot", "message": "reply", "create_at": 2}),
+        ] {
+            assert!(ch
+                .parse_ws_event(&posted_event("posted", post, "O"), "bot", "botname")
+                .is_none());
+        }
+        assert!(ch
+            .parse_ws_event(&json!({"event": "typing", "data": {}}), "bot", "botname")
+            .is_none());
+    }
+
+    #[test]
+    fn ws_reaction_added_becomes_message() {
+        let ch = scoped_channel();
+        let own_post = json!({
+            "event": "posted",
+            "data": { "post": json!({"id": "p1", "user_id": "bot", "channel_id": "chan1", "message": "Deployed", "create_at": 1}).to_string() },
+            "seq": 3
+        });
+        assert!(ch.parse_ws_event(&own_post, "bot", "botname").is_none());
+        let event = json!({
+            "event": "reaction_added",
+            "data": { "reaction": json!({"user_id": "u1", "post_id": "p1", "emoji_name": "thumbsup", "create_at": 1_600_000_000_000_i64}).to_string() },
+            "broadcast": { "channel_id": "chan1" },
+            "seq": 4
+        });
+
+        let msg = ch.parse_ws_event(&event, "bot", "botname").unwrap();
+        assert_eq!(msg.id, "mattermost_p1_reaction_u1_thumbsup");
+        assert_eq!(msg.content, "[reaction] :thumbsup: on post p1");
+        assert_eq!(msg.reply_target, "chan1");
+
+        let own = json!({
+            "event": "reaction_added",
+            "data": { "reaction": json!({"user_id": "bot", "post_id": "p1", "emoji_name": "eyes"}).to_string() },
+            "broadcast": { "channel_id": "chan1" }
+        });
+        assert!(ch.parse_ws_event(&own, "bot", "botname").is_none());
+        assert!(make_mention_only_channel()
+            .parse_ws_event(&event, "bot", "botname")
+            .is_none());
+    }
+
+    #[test]
+    fn ws_reaction_on_other_users_post_is_dropped() {
+        let ch = scoped_channel();
+        let event = json!({
+            "event": "reaction_added",
+            "data": { "reaction": json!({"user_id": "u1", "post_id": "p2", "emoji_name": "tada"}).to_string() },
+            "broadcast": { "channel_id": "chan1" }
+        });
+        assert!(ch.parse_ws_event(&event, "bot", "botname").is_none());
+    }
+
+    #[test]
+    fn truncate_post_respects_char_limit() {
+        let long = "é".repeat(MATTERMOST_MAX_POST_LENGTH + 10);
+        assert_eq!(
+            MattermostChannel::truncate_post(&long).chars().count(),
+            MATTERMOST_MAX_POST_LENGTH
+        );
+        assert_eq!(MattermostChannel::truncate_post("short"), "short");
+    }
+
+    #[test]
+    fn draft_updates_follow_stream_mode() {
+        let ch = make_channel(vec!["*".into()], false);
+        assert!(!ch.supports_draft_updates());
+        let ch = ch.with_streaming(StreamMode::Partial, 500);
+        assert!(ch.supports_draft_updates());
+    }
 }
@@ -19,14 +19,18 @@ pub mod cli;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
+pub mod group;
+pub mod identities;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
 pub mod linq;
+pub mod markup;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+pub mod mqtt;
 pub mod nextcloud_talk;
 pub mod nostr;
 pub mod qq;
@@ -35,17 +39,21 @@ pub mod slack;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
+pub mod tts;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_storage;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_web;
+pub mod xmpp;
+pub mod zulip;
 
 pub use clawdtalk::{ClawdTalkChannel, ClawdTalkConfig};
 pub use cli::CliChannel;
 pub use dingtalk::DingTalkChannel;
 pub use discord::DiscordChannel;
 pub use email_channel::EmailChannel;
+pub use identities::IdentityRegistry;
 pub use imessage::IMessageChannel;
 pub use irc::IrcChannel;
 #[cfg(feature = "channel-lark")]
@@ -54,16 +62,19 @@ pub use linq::LinqChannel;
 #[cfg(feature = "channel-matrix")]
 pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
+pub use mqtt::MqttChannel;
 pub use nextcloud_talk::NextcloudTalkChannel;
 pub use nostr::NostrChannel;
 pub use qq::QQChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
-pub use traits::{Channel, SendMessage};
+pub use traits::{Channel, GroupContext, SendMessage};
 pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
+pub use xmpp::XmppChannel;
+pub use zulip::ZulipChannel;
 
 use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop, scrub_credentials};
 use crate::config::Config;
@@ -151,6 +162,16 @@ enum ChannelRuntimeCommand {
     SetModel(String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IdentityCommand {
+    StartLink,
+    CompleteLink(String),
+    Unlink,
+    ShowPreferences,
+    SetPreference(String, String),
+    UnsetPreference(String),
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 struct ModelCacheState {
     entries: Vec<ModelCacheEntry>,
@@ -221,6 +242,13 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    reasoning_display: crate::config::ReasoningDisplay,
+    injection_guard: Option<Arc<crate::security::InjectionGuard>>,
+    cost_tracker: Option<Arc<crate::cost::CostTracker>>,
+    identities: Option<Arc<IdentityRegistry>>,
+    group_policies: Arc<HashMap<String, crate::config::GroupPolicyConfig>>,
+    room_rate_limiter: Arc<group::RoomRateLimiter>,
+    tts: Arc<crate::config::TtsConfig>,
 }
 
 #[derive(Clone)]
@@ -256,12 +284,50 @@ impl InFlightTaskCompletion {
     }
 }
 
-fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
-    format!("{}_{}_{}", msg.channel, msg.sender, msg.id)
+/// Conversation owner: the linked canonical user, else the channel sender.
+fn conversation_owner(msg: &traits::ChannelMessage, canonical_user: Option<&str>) -> String {
+    match canonical_user {
+        Some(user_id) => format!("user_{user_id}"),
+        None => format!("{}_{}", msg.channel, msg.sender),
+    }
+}
+
+fn conversation_memory_key(msg: &traits::ChannelMessage, canonical_user: Option<&str>) -> String {
+    match canonical_user {
+        Some(_) => format!(
+            "{}_{}_{}",
+            conversation_owner(msg, canonical_user),
+            msg.channel,
+            msg.id
+        ),
+        None => format!("{}_{}", conversation_owner(msg, None), msg.id),
+    }
+}
+
+fn conversation_history_key(msg: &traits::ChannelMessage, canonical_user: Option<&str>) -> String {
+    let owner = conversation_owner(msg, canonical_user);
+    // Each email thread and Zulip topic is its own conversation, even with
+    // the same sender.
+    match msg.thread_ts.as_deref() {
+        Some(thread) if msg.channel == "email" || msg.channel == "zulip" => {
+            format!("{owner}_{thread}")
+        }
+        _ => owner,
+    }
 }
 
-fn conversation_history_key(msg: &traits::ChannelMessage) -> String {
-    format!("{}_{}", msg.channel, msg.sender)
+fn resolve_canonical_user(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+) -> Option<String> {
+    let registry = ctx.identities.as_ref()?;
+    match registry.resolve(&msg.channel, &msg.sender) {
+        Ok(user_id) => user_id,
+        Err(err) => {
+            tracing::warn!("Failed to resolve identity for {}: {err}", msg.channel);
+            None
+        }
+    }
 }
 
 fn interruption_scope_key(msg: &traits::ChannelMessage) -> String {
@@ -395,11 +461,18 @@ fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
              - Use triple backticks for code blocks\n\
              - Use emoji naturally to add personality — but don't overdo it\n\
              - Be concise and direct. Skip filler phrases like 'Great question!' or 'Certainly!'\n\
-             - Structure longer answers with bold headers, not raw markdown ## headers\n\
+             - Markdown headers, lists and tables are converted for Telegram; keep tables narrow\n\
              - For media attachments use markers: [IMAGE:<path-or-url>], [DOCUMENT:<path-or-url>], [VIDEO:<path-or-url>], [AUDIO:<path-or-url>], or [VOICE:<path-or-url>]\n\
              - Keep normal text outside markers and never wrap markers in code fences.\n\
              - Use tool results silently: answer the latest user message directly, and do not narrate delayed/internal tool execution bookkeeping.",
         ),
+        "irc" => Some(
+            "When responding on IRC:\n\
+             - Plain text only. Markdown is flattened before sending, so formatting is lost\n\
+             - No XML/HTML tags. Use a single blank line to separate blocks\n\
+             - Be terse and concise\n\
+             - Use short lines. Avoid walls of text",
+        ),
         _ => None,
     }
 }
@@ -493,6 +566,40 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
     }
 }
 
+fn parse_identity_command(content: &str) -> Option<IdentityCommand> {
+    let trimmed = content.trim();
+    if !trimmed.starts_with('/') {
+        return None;
+    }
+
+    let mut parts = trimmed.split_whitespace();
+    let command_token = parts.next()?;
+    let base_command = command_token
+        .split('@')
+        .next()
+        .unwrap_or(command_token)
+        .to_ascii_lowercase();
+
+    match base_command.as_str() {
+        "/link" => match parts.next() {
+            Some(code) => Some(IdentityCommand::CompleteLink(code.to_string())),
+            None => Some(IdentityCommand::StartLink),
+        },
+        "/unlink" => Some(IdentityCommand::Unlink),
+        "/prefs" => match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            None => Some(IdentityCommand::ShowPreferences),
+            Some("set") => {
+                let key = parts.next()?.to_string();
+                let value = parts.collect::<Vec<_>>().join(" ");
+                Some(IdentityCommand::SetPreference(key, value))
+            }
+            Some("unset") => Some(IdentityCommand::UnsetPreference(parts.next()?.to_string())),
+            Some(_) => None,
+        },
+        _ => None,
+    }
+}
+
 fn resolve_provider_alias(name: &str) -> Option<String> {
     let candidate = name.trim();
     if candidate.is_empty() {
@@ -976,7 +1083,13 @@ async fn handle_runtime_command_if_needed(
         return true;
     };
 
-    let sender_key = conversation_history_key(msg);
+    let sender_key = match msg.group.as_ref() {
+        Some(room) => group::room_history_key(msg, room),
+        None => {
+            let canonical_user = resolve_canonical_user(ctx, msg);
+            conversation_history_key(msg, canonical_user.as_deref())
+        }
+    };
     let mut current = get_route_selection(ctx, &sender_key);
 
     let response = match command {
@@ -1041,6 +1154,137 @@ async fn handle_runtime_command_if_needed(
     true
 }
 
+fn build_identity_command_response(
+    registry: &IdentityRegistry,
+    msg: &traits::ChannelMessage,
+    command: IdentityCommand,
+) -> Result<String> {
+    let preferences_need_link =
+        "This account is not linked to a user yet. Send `/link` to create one.".to_string();
+
+    Ok(match command {
+        IdentityCommand::StartLink => {
+            let (user_id, code) = registry.start_link(&msg.channel, &msg.sender)?;
+            format!(
+                "🔗 Link code for user `{user_id}`: {code}\nSend `/link {code}` from your other account within {} minutes.",
+                identities::LINK_CODE_TTL.as_secs() / 60
+            )
+        }
+        IdentityCommand::CompleteLink(code) => {
+            match registry.complete_link(&msg.channel, &msg.sender, &code)? {
+                identities::LinkOutcome::Linked { user_id } => format!(
+                    "✅ Linked to user `{user_id}`. History, memory and preferences are now shared across your linked accounts."
+                ),
+                identities::LinkOutcome::InvalidCode => {
+                    "❌ Invalid or expired link code. Send `/link` from your other account to get a new one.".to_string()
+                }
+                identities::LinkOutcome::TooManyAttempts => {
+                    "⏳ Too many invalid link codes. Try again later.".to_string()
+                }
+            }
+        }
+        IdentityCommand::Unlink => {
+            if registry.unlink(&msg.channel, &msg.sender)? {
+                "Unlinked this account.".to_string()
+            } else {
+                "This account is not linked.".to_string()
+            }
+        }
+        IdentityCommand::ShowPreferences => match registry.resolve(&msg.channel, &msg.sender)? {
+            Some(user_id) => {
+                let preferences = registry.preferences(&user_id)?;
+                if preferences.is_empty() {
+                    format!(
+                        "No preferences set for user `{user_id}`.\nUse `/prefs set <key> <value>`."
+                    )
+                } else {
+                    let mut response = format!("Preferences for user `{user_id}`:\n");
+                    for (key, value) in &preferences {
+                        let _ = writeln!(response, "- {key}: {value}");
+                    }
+                    response
+                }
+            }
+            None => preferences_need_link,
+        },
+        IdentityCommand::SetPreference(key, value) => {
+            match registry.resolve(&msg.channel, &msg.sender)? {
+                Some(user_id) => match registry.set_preference(&user_id, &key, &value) {
+                    Ok(()) => format!("Preference `{key}` set."),
+                    Err(err) => format!("❌ {err}"),
+                },
+                None => preferences_need_link,
+            }
+        }
+        IdentityCommand::UnsetPreference(key) => {
+            match registry.resolve(&msg.channel, &msg.sender)? {
+                Some(user_id) => {
+                    if registry.remove_preference(&user_id, &key)? {
+                        format!("Preference `{key}` removed.")
+                    } else {
+                        format!("Preference `{key}` is not set.")
+                    }
+                }
+                None => preferences_need_link,
+            }
+        }
+    })
+}
+
+async fn handle_identity_command_if_needed(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    target_channel: Option<&Arc<dyn Channel>>,
+) -> bool {
+    let Some(registry) = ctx.identities.as_ref() else {
+        return false;
+    };
+    let Some(command) = parse_identity_command(&msg.content) else {
+        return false;
+    };
+
+    let Some(channel) = target_channel else {
+        return true;
+    };
+
+    // Link codes and preferences are private; keep them out of rooms.
+    let response = if msg.group.is_some() {
+        Ok("Identity commands only work in a direct message with me.".to_string())
+    } else {
+        build_identity_command_response(registry, msg, command)
+    };
+    let response = response.unwrap_or_else(|err| {
+        tracing::warn!("Identity command failed on {}: {err}", msg.channel);
+        "⚠️ Identity command failed. Please try again later.".to_string()
+    });
+
+    if let Err(err) = channel
+        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+        .await
+    {
+        tracing::warn!(
+            "Failed to send identity command response on {}: {err}",
+            channel.name()
+        );
+    }
+
+    true
+}
+
+fn build_user_preferences_prompt(
+    preferences: &std::collections::BTreeMap<String, String>,
+) -> String {
+    if preferences.is_empty() {
+        return String::new();
+    }
+    let mut prompt =
+        "## User Preferences\n\nThe user set these preferences. Follow them unless they conflict with your instructions:\n".to_string();
+    for (key, value) in preferences {
+        let _ = writeln!(prompt, "- {key}: {value}");
+    }
+    prompt
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
@@ -1472,6 +1716,36 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Follow a text reply with synthesized speech when the TTS settings, the
+/// channel override or the user's `voice_replies` preference ask for it.
+async fn send_voice_reply_if_wanted(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    channel: &dyn Channel,
+    voice_preference: Option<&str>,
+    reply: &str,
+) -> anyhow::Result<()> {
+    if !channel.supports_voice_replies()
+        || !tts::wants_voice_reply(&ctx.tts, &msg.channel, msg.voice, voice_preference)
+    {
+        return Ok(());
+    }
+    let Some(text) = tts::speech_text(reply, ctx.tts.max_chars) else {
+        tracing::debug!(
+            channel = %msg.channel,
+            "Skipping voice reply: nothing to speak or longer than tts.max_chars"
+        );
+        return Ok(());
+    };
+    let audio = tts::synthesize_speech(&text, &ctx.tts).await?;
+    channel
+        .send_voice_reply(
+            &SendMessage::new(text, &msg.reply_target).in_thread(msg.thread_ts.clone()),
+            &audio,
+        )
+        .await
+}
+
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
@@ -1520,11 +1794,25 @@ async fn process_channel_message(
     if let Err(err) = maybe_apply_runtime_config_update(ctx.as_ref()).await {
         tracing::warn!("Failed to apply runtime config update: {err}");
     }
+    if handle_identity_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
+        return;
+    }
     if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
         return;
     }
 
-    let history_key = conversation_history_key(&msg);
+    let canonical_user = resolve_canonical_user(ctx.as_ref(), &msg);
+    // Everyone in a room shares one history; each line names its speaker.
+    let (history_key, turn_content) = match msg.group.as_ref() {
+        Some(room) => (
+            group::room_history_key(&msg, room),
+            group::attributed_content(room, &msg.content),
+        ),
+        None => (
+            conversation_history_key(&msg, canonical_user.as_deref()),
+            msg.content.clone(),
+        ),
+    };
     let route = get_route_selection(ctx.as_ref(), &history_key);
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
@@ -1547,7 +1835,7 @@ async fn process_channel_message(
         }
     };
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-        let autosave_key = conversation_memory_key(&msg);
+        let autosave_key = conversation_memory_key(&msg, canonical_user.as_deref());
         let _ = ctx
             .memory
             .store(
@@ -1570,7 +1858,7 @@ async fn process_channel_message(
         .is_some_and(|turns| !turns.is_empty());
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
-    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&turn_content));
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -1589,12 +1877,31 @@ async fn process_channel_message(
             build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
-                last_turn.content = format!("{memory_context}{}", msg.content);
+                last_turn.content = format!("{memory_context}{turn_content}");
             }
         }
     }
 
-    let system_prompt = build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel);
+    let mut system_prompt = build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel);
+    let mut voice_preference = None;
+    if msg.group.is_some() {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(group::GROUP_PROMPT_NOTE);
+    } else if let (Some(registry), Some(user_id)) =
+        (ctx.identities.as_ref(), canonical_user.as_deref())
+    {
+        match registry.preferences(user_id) {
+            Ok(preferences) => {
+                voice_preference = preferences.get(tts::VOICE_REPLIES_PREFERENCE).cloned();
+                let preferences_prompt = build_user_preferences_prompt(&preferences);
+                if !preferences_prompt.is_empty() {
+                    system_prompt.push_str("\n\n");
+                    system_prompt.push_str(&preferences_prompt);
+                }
+            }
+            Err(err) => tracing::warn!("Failed to load preferences for {user_id}: {err}"),
+        }
+    }
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
     let use_streaming = target_channel
@@ -1720,6 +2027,9 @@ async fn process_channel_message(
                 } else {
                     ctx.non_cli_excluded_tools.as_ref()
                 },
+                ctx.reasoning_display,
+                ctx.injection_guard.as_deref(),
+                ctx.cost_tracker.as_deref(),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -1887,13 +2197,24 @@ async fn process_channel_message(
                     }
                 } else if let Err(e) = channel
                     .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
+                        &SendMessage::new(&delivered_response, &msg.reply_target)
                             .in_thread(msg.thread_ts.clone()),
                     )
                     .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
+                if let Err(e) = send_voice_reply_if_wanted(
+                    ctx.as_ref(),
+                    &msg,
+                    channel.as_ref(),
+                    voice_preference.as_deref(),
+                    &delivered_response,
+                )
+                .await
+                {
+                    tracing::warn!("Failed to send voice reply on {}: {e}", channel.name());
+                }
             }
         }
         LlmExecutionResult::Completed(Ok(Err(e))) => {
@@ -1987,7 +2308,7 @@ async fn process_channel_message(
                     .downcast_ref::<providers::ProviderCapabilityError>()
                     .is_some_and(|capability| capability.capability.eq_ignore_ascii_case("vision"));
                 let rolled_back = should_rollback_user_turn
-                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &msg.content);
+                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &turn_content);
 
                 if !rolled_back {
                     // Close the orphan user turn so subsequent messages don't
@@ -2074,6 +2395,84 @@ async fn process_channel_message(
     }
 }
 
+/// Group policies by channel name. Legacy `mention_only` flags map to
+/// `reply = "mention"`.
+fn collect_group_policies(config: &Config) -> HashMap<String, crate::config::GroupPolicyConfig> {
+    let legacy_mention = |policy: &crate::config::GroupPolicyConfig, mention_only: bool| {
+        let mut policy = policy.clone();
+        if mention_only && policy.reply == crate::config::GroupReplyMode::Always {
+            policy.reply = crate::config::GroupReplyMode::Mention;
+        }
+        policy
+    };
+
+    let channels = &config.channels_config;
+    let mut policies = HashMap::new();
+    if let Some(tg) = channels.telegram.as_ref() {
+        policies.insert(
+            "telegram".to_string(),
+            legacy_mention(&tg.group, tg.mention_only),
+        );
+    }
+    if let Some(dc) = channels.discord.as_ref() {
+        policies.insert(
+            "discord".to_string(),
+            legacy_mention(&dc.group, dc.mention_only),
+        );
+    }
+    if let Some(sl) = channels.slack.as_ref() {
+        policies.insert("slack".to_string(), sl.group.clone());
+    }
+    if let Some(mx) = channels.matrix.as_ref() {
+        policies.insert("matrix".to_string(), mx.group.clone());
+    }
+    if let Some(irc) = channels.irc.as_ref() {
+        policies.insert("irc".to_string(), irc.group.clone());
+    }
+    policies
+}
+
+/// Decide whether a room message gets a reply. Messages that do not are
+/// recorded in the room history so later replies see them.
+fn admit_group_message(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> bool {
+    let Some(room) = msg.group.as_ref() else {
+        return true;
+    };
+    let policy = ctx
+        .group_policies
+        .get(&msg.channel)
+        .cloned()
+        .unwrap_or_default();
+    let room_key = group::room_history_key(msg, room);
+
+    let admitted = if group::triggers_reply(&policy, room, &msg.content) {
+        let allowed = ctx.room_rate_limiter.try_acquire(
+            &room_key,
+            policy.max_replies_per_minute,
+            Instant::now(),
+        );
+        if !allowed {
+            tracing::info!(
+                channel = %msg.channel,
+                room = %room.room,
+                "Group reply rate limit reached; recording message without reply"
+            );
+        }
+        allowed
+    } else {
+        false
+    };
+
+    if !admitted {
+        append_sender_turn(
+            ctx,
+            &room_key,
+            ChatMessage::user(group::attributed_content(room, &msg.content)),
+        );
+    }
+    admitted
+}
+
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
     ctx: Arc<ChannelRuntimeContext>,
@@ -2088,6 +2487,10 @@ async fn run_message_dispatch_loop(
     let task_sequence = Arc::new(AtomicU64::new(1));
 
     while let Some(msg) = rx.recv().await {
+        if !admit_group_message(ctx.as_ref(), &msg) {
+            continue;
+        }
+
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
@@ -2621,6 +3024,73 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::BindTelegram { identity } => {
             bind_telegram_identity(config, &identity).await
         }
+        crate::ChannelCommands::LinkIdentity {
+            user,
+            channel,
+            sender,
+        } => {
+            let registry = IdentityRegistry::open(&config.workspace_dir)?;
+            let channel = channel.trim().to_ascii_lowercase();
+            registry.link(user.trim(), &channel, sender.trim())?;
+            println!(
+                "✅ Linked {channel} sender {} to user {}",
+                sender.trim(),
+                user.trim()
+            );
+            reload_daemon_after_identity_change();
+            Ok(())
+        }
+        crate::ChannelCommands::UnlinkIdentity { channel, sender } => {
+            let registry = IdentityRegistry::open(&config.workspace_dir)?;
+            let channel = channel.trim().to_ascii_lowercase();
+            if registry.unlink(&channel, sender.trim())? {
+                println!("✅ Unlinked {channel} sender {}", sender.trim());
+                reload_daemon_after_identity_change();
+            } else {
+                println!("ℹ️ {channel} sender {} is not linked", sender.trim());
+            }
+            Ok(())
+        }
+        crate::ChannelCommands::Identities => {
+            let registry = IdentityRegistry::open(&config.workspace_dir)?;
+            let users = registry.users()?;
+            if users.is_empty() {
+                println!("No linked identities.");
+                println!("Link one with: zeroclaw channel link-identity <user> <channel> <sender>");
+                return Ok(());
+            }
+            for user in users {
+                println!("👤 {}", user.id);
+                for identity in &user.identities {
+                    println!("   {}: {}", identity.channel, identity.sender);
+                }
+                for (key, value) in &user.preferences {
+                    println!("   pref {key} = {value}");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Allowlist `user:` entries are expanded at startup, so a running daemon
+/// needs a restart to pick up link changes.
+fn reload_daemon_after_identity_change() {
+    match maybe_restart_managed_daemon_service() {
+        Ok(true) => {
+            println!("🔄 Detected running managed daemon service; reloaded automatically.");
+        }
+        Ok(false) => {
+            println!(
+                "ℹ️ No managed daemon service detected. If `zeroclaw daemon`/`channel start` is already running, restart it to apply `user:` allowlist entries."
+            );
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠️ Identity link saved, but failed to reload daemon service automatically: {e}\n\
+                 Restart service manually with `zeroclaw service stop && zeroclaw service start`."
+            );
+        }
     }
 }
 
@@ -2671,38 +3141,52 @@ fn collect_configured_channels(
     if let Some(ref dc) = config.channels_config.discord {
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            channel: Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_transcription(config.transcription.clone()),
+            ),
         });
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            channel: Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_app_token(sl.app_token.clone())
+                .with_events_api(
+                    sl.signing_secret
+                        .as_deref()
+                        .is_some_and(|s| !s.trim().is_empty()),
+                ),
+            ),
         });
     }
 
     if let Some(ref mm) = config.channels_config.mattermost {
         channels.push(ConfiguredChannel {
             display_name: "Mattermost",
-            channel: Arc::new(MattermostChannel::new(
-                mm.url.clone(),
-                mm.bot_token.clone(),
-                mm.channel_id.clone(),
-                mm.allowed_users.clone(),
-                mm.thread_replies.unwrap_or(true),
-                mm.mention_only.unwrap_or(false),
-            )),
+            channel: Arc::new(
+                MattermostChannel::new(
+                    mm.url.clone(),
+                    mm.bot_token.clone(),
+                    mm.channel_id.clone(),
+                    mm.allowed_users.clone(),
+                    mm.thread_replies.unwrap_or(true),
+                    mm.mention_only.unwrap_or(false),
+                )
+                .with_streaming(mm.stream_mode, mm.draft_update_interval_ms),
+            ),
         });
     }
 
@@ -2740,14 +3224,17 @@ fn collect_configured_channels(
     if let Some(ref sig) = config.channels_config.signal {
         channels.push(ConfiguredChannel {
             display_name: "Signal",
-            channel: Arc::new(SignalChannel::new(
-                sig.http_url.clone(),
-                sig.account.clone(),
-                sig.group_id.clone(),
-                sig.allowed_from.clone(),
-                sig.ignore_attachments,
-                sig.ignore_stories,
-            )),
+            channel: Arc::new(
+                SignalChannel::new(
+                    sig.http_url.clone(),
+                    sig.account.clone(),
+                    sig.group_id.clone(),
+                    sig.allowed_from.clone(),
+                    sig.ignore_attachments,
+                    sig.ignore_stories,
+                )
+                .with_transcription(config.transcription.clone()),
+            ),
         });
     }
 
@@ -2827,7 +3314,10 @@ fn collect_configured_channels(
     if let Some(ref email_cfg) = config.channels_config.email {
         channels.push(ConfiguredChannel {
             display_name: "Email",
-            channel: Arc::new(EmailChannel::new(email_cfg.clone())),
+            channel: Arc::new(
+                EmailChannel::new(email_cfg.clone())
+                    .with_workspace_dir(config.workspace_dir.clone()),
+            ),
         });
     }
 
@@ -2849,6 +3339,50 @@ fn collect_configured_channels(
         });
     }
 
+    if let Some(ref xmpp) = config.channels_config.xmpp {
+        channels.push(ConfiguredChannel {
+            display_name: "XMPP",
+            channel: Arc::new(XmppChannel::new(xmpp::XmppChannelConfig {
+                jid: xmpp.jid.clone(),
+                password: xmpp.password.clone(),
+                server: xmpp.server.clone(),
+                port: xmpp.port,
+                direct_tls: xmpp.direct_tls,
+                resource: xmpp.resource.clone(),
+                rooms: xmpp.rooms.clone(),
+                nickname: xmpp.nickname.clone(),
+                allowed_users: xmpp.allowed_users.clone(),
+                verify_tls: xmpp.verify_tls.unwrap_or(true),
+                stream_mode: xmpp.stream_mode,
+                draft_update_interval_ms: xmpp.draft_update_interval_ms,
+            })),
+        });
+    }
+
+    if let Some(ref mq) = config.channels_config.mqtt {
+        channels.push(ConfiguredChannel {
+            display_name: "MQTT",
+            channel: Arc::new(MqttChannel::new(mq.clone())),
+        });
+    }
+
+    if let Some(ref zl) = config.channels_config.zulip {
+        channels.push(ConfiguredChannel {
+            display_name: "Zulip",
+            channel: Arc::new(
+                ZulipChannel::new(
+                    zl.site_url.clone(),
+                    zl.email.clone(),
+                    zl.api_key.clone(),
+                    zl.streams.clone(),
+                    zl.allowed_users.clone(),
+                )
+                .with_default_topic(zl.default_topic.clone())
+                .with_streaming(zl.stream_mode, zl.draft_update_interval_ms),
+            ),
+        });
+    }
+
     #[cfg(feature = "channel-lark")]
     if let Some(ref lk) = config.channels_config.lark {
         channels.push(ConfiguredChannel {
@@ -2962,7 +3496,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         auth_profile_override: None,
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
-        reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning: providers::reasoning::ReasoningSettings::from_runtime(&config.runtime),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -2999,10 +3533,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         Arc::from(observability::create_observer(&config.observability));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
+    let security = Arc::new(
+        SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+            .with_egress(&config.security.egress),
+    );
     let model = resolved_default_model(&config);
     let temperature = config.default_temperature;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
@@ -3086,6 +3620,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
         "pushover",
         "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
     ));
+    if config
+        .channels_config
+        .mqtt
+        .as_ref()
+        .is_some_and(|mqtt| !mqtt.publish_topics.is_empty())
+    {
+        tool_descs.push((
+            "mqtt_publish",
+            "Publish a message to an MQTT topic on the configured broker. Use when: controlling or notifying devices on the bus. Only configured publish_topics are allowed.",
+        ));
+    }
     if !config.agents.is_empty() {
         tool_descs.push((
             "delegate",
@@ -3131,14 +3676,27 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
+    let identities = match IdentityRegistry::open(&config.workspace_dir) {
+        Ok(registry) => Some(Arc::new(registry)),
+        Err(err) => {
+            tracing::warn!("Identity registry unavailable, identity linking disabled: {err}");
+            None
+        }
+    };
+    // `user:<id>` allowlist entries admit every identity linked to that user.
+    let mut channel_config = config.clone();
+    if let Some(registry) = identities.as_ref() {
+        registry.expand_channel_allowlists(&mut channel_config.channels_config);
+    }
+
     // Collect active channels from a shared builder to keep startup and doctor parity.
     let mut channels: Vec<Arc<dyn Channel>> =
-        collect_configured_channels(&config, "runtime startup")
+        collect_configured_channels(&channel_config, "runtime startup")
             .into_iter()
             .map(|configured| configured.channel)
             .collect();
 
-    if let Some(ref ns) = config.channels_config.nostr {
+    if let Some(ref ns) = channel_config.channels_config.nostr {
         channels.push(Arc::new(
             NostrChannel::new(&ns.private_key, ns.relays.clone(), &ns.allowed_pubkeys).await?,
         ));
@@ -3251,6 +3809,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        reasoning_display: config.channels_config.reasoning_display,
+        injection_guard: crate::security::InjectionGuard::from_config(&config.security.injection)
+            .map(Arc::new),
+        cost_tracker: crate::cost::CostTracker::from_config(&config).map(Arc::new),
+        identities,
+        group_policies: Arc::new(collect_group_policies(&config)),
+        room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+        tts: Arc::new(config.tts.clone()),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -3464,6 +4030,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3513,6 +4086,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3565,6 +4145,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4038,6 +4625,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4052,6 +4646,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4097,6 +4693,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4111,6 +4714,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4172,6 +4777,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4184,6 +4796,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4231,6 +4845,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4243,6 +4864,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4299,6 +4922,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4311,6 +4941,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4388,6 +5020,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4400,6 +5039,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4459,6 +5100,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4471,6 +5119,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4545,6 +5195,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4557,6 +5214,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4616,6 +5275,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4628,6 +5294,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4676,6 +5344,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -4688,6 +5363,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -4847,6 +5524,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4858,6 +5542,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
+            voice: false,
         })
         .await
         .unwrap();
@@ -4869,6 +5555,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
+            voice: false,
         })
         .await
         .unwrap();
@@ -4927,6 +5615,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4939,6 +5634,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             })
             .await
             .unwrap();
@@ -4951,6 +5648,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             })
             .await
             .unwrap();
@@ -5019,6 +5718,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5031,6 +5737,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             })
             .await
             .unwrap();
@@ -5043,6 +5751,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             })
             .await
             .unwrap();
@@ -5093,6 +5803,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -5105,6 +5822,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -5152,6 +5871,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -5164,6 +5890,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -5540,21 +6268,117 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
 
-        assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
+        assert_eq!(conversation_memory_key(&msg, None), "slack_U123_msg_abc123");
     }
 
     #[test]
-    fn conversation_memory_key_is_unique_per_message() {
-        let msg1 = traits::ChannelMessage {
-            id: "msg_1".into(),
-            sender: "U123".into(),
-            reply_target: "C456".into(),
-            content: "first".into(),
-            channel: "slack".into(),
+    fn conversation_history_key_separates_email_threads() {
+        let mut msg = traits::ChannelMessage {
+            id: "m1@example.com".into(),
+            sender: "alice@example.com".into(),
+            reply_target: "alice@example.com".into(),
+            content: "hello".into(),
+            channel: "email".into(),
             timestamp: 1,
-            thread_ts: None,
+            thread_ts: Some("root1@example.com".into()),
+            group: None,
+            voice: false,
+        };
+        assert_eq!(
+            conversation_history_key(&msg, None),
+            "email_alice@example.com_root1@example.com"
+        );
+
+        msg.channel = "zulip".into();
+        msg.thread_ts = Some("deploys".into());
+        assert_eq!(
+            conversation_history_key(&msg, None),
+            "zulip_alice@example.com_deploys"
+        );
+
+        msg.channel = "slack".into();
+        assert_eq!(
+            conversation_history_key(&msg, None),
+            "slack_alice@example.com"
+        );
+    }
+
+    #[test]
+    fn conversation_keys_use_canonical_user_when_linked() {
+        let mut msg = traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: "U123".into(),
+            reply_target: "C456".into(),
+            content: "hello".into(),
+            channel: "slack".into(),
+            timestamp: 1,
+            thread_ts: None,
+            group: None,
+            voice: false,
+        };
+        assert_eq!(conversation_history_key(&msg, Some("alice")), "user_alice");
+        assert_eq!(
+            conversation_memory_key(&msg, Some("alice")),
+            "user_alice_slack_msg_1"
+        );
+
+        msg.channel = "email".into();
+        msg.thread_ts = Some("root1@example.com".into());
+        assert_eq!(
+            conversation_history_key(&msg, Some("alice")),
+            "user_alice_root1@example.com"
+        );
+    }
+
+    #[test]
+    fn parse_identity_command_covers_link_and_prefs() {
+        assert_eq!(
+            parse_identity_command("/link"),
+            Some(IdentityCommand::StartLink)
+        );
+        assert_eq!(
+            parse_identity_command(" /link 123456 "),
+            Some(IdentityCommand::CompleteLink("123456".into()))
+        );
+        assert_eq!(
+            parse_identity_command("/unlink@bot"),
+            Some(IdentityCommand::Unlink)
+        );
+        assert_eq!(
+            parse_identity_command("/prefs"),
+            Some(IdentityCommand::ShowPreferences)
+        );
+        assert_eq!(
+            parse_identity_command("/prefs set tone short and friendly"),
+            Some(IdentityCommand::SetPreference(
+                "tone".into(),
+                "short and friendly".into()
+            ))
+        );
+        assert_eq!(
+            parse_identity_command("/prefs unset tone"),
+            Some(IdentityCommand::UnsetPreference("tone".into()))
+        );
+        assert_eq!(parse_identity_command("/prefs set"), None);
+        assert_eq!(parse_identity_command("link me"), None);
+    }
+
+    #[test]
+    fn conversation_memory_key_is_unique_per_message() {
+        let msg1 = traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: "U123".into(),
+            reply_target: "C456".into(),
+            content: "first".into(),
+            channel: "slack".into(),
+            timestamp: 1,
+            thread_ts: None,
+            group: None,
+            voice: false,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5564,11 +6388,13 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
 
         assert_ne!(
-            conversation_memory_key(&msg1),
-            conversation_memory_key(&msg2)
+            conversation_memory_key(&msg1, None),
+            conversation_memory_key(&msg2, None)
         );
     }
 
@@ -5585,6 +6411,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5594,10 +6422,12 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
 
         mem.store(
-            &conversation_memory_key(&msg1),
+            &conversation_memory_key(&msg1, None),
             &msg1.content,
             MemoryCategory::Conversation,
             None,
@@ -5605,7 +6435,7 @@ BTC is currently around $65,000 based on latest tool output."#
         .await
         .unwrap();
         mem.store(
-            &conversation_memory_key(&msg2),
+            &conversation_memory_key(&msg2, None),
             &msg2.content,
             MemoryCategory::Conversation,
             None,
@@ -5668,6 +6498,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -5680,6 +6517,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -5695,6 +6534,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -5718,6 +6559,408 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(calls[1][3].1.contains("follow up"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_shares_history_across_linked_identities() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let tmp = TempDir::new().unwrap();
+        let registry = IdentityRegistry::open(tmp.path()).unwrap();
+        registry
+            .link("alice", "test-channel", "alice-chat")
+            .unwrap();
+        registry
+            .link("alice", "test-channel", "alice-mail")
+            .unwrap();
+        registry
+            .set_preference("alice", "language", "German")
+            .unwrap();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: Some(Arc::new(registry)),
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
+        });
+
+        for (id, sender, content) in [
+            ("msg-a", "alice-chat", "hello"),
+            ("msg-b", "alice-mail", "follow up"),
+        ] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: content.to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    group: None,
+                    voice: false,
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].len(), 4);
+        assert!(calls[1][0].1.contains("- language: German"));
+        assert!(calls[1][1].1.contains("hello"));
+        assert!(calls[1][3].1.contains("follow up"));
+    }
+
+    fn group_test_context(
+        provider: Arc<dyn Provider>,
+        channel: Arc<dyn Channel>,
+        policy: crate::config::GroupPolicyConfig,
+    ) -> Arc<ChannelRuntimeContext> {
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+        let mut group_policies = HashMap::new();
+        group_policies.insert("test-channel".to_string(), policy);
+
+        Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider,
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(group_policies),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
+        })
+    }
+
+    fn room_message(
+        id: &str,
+        sender: &str,
+        content: &str,
+        mentioned: bool,
+    ) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: id.to_string(),
+            sender: sender.to_string(),
+            reply_target: "room-1".to_string(),
+            content: content.to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            group: Some(GroupContext {
+                room: "room-1".to_string(),
+                sender_name: sender.to_string(),
+                mentioned,
+                replied_to_bot: false,
+            }),
+            voice: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn group_messages_share_attributed_room_history() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let ctx = group_test_context(
+            provider_impl.clone(),
+            channel_impl.clone(),
+            crate::config::GroupPolicyConfig {
+                reply: crate::config::GroupReplyMode::Mention,
+                ..Default::default()
+            },
+        );
+
+        let chatter = room_message("m1", "bob", "lunch at noon?", false);
+        assert!(!admit_group_message(ctx.as_ref(), &chatter));
+
+        let question = room_message("m2", "alice", "what do you think?", true);
+        assert!(admit_group_message(ctx.as_ref(), &question));
+        process_channel_message(ctx.clone(), question, CancellationToken::new()).await;
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0][0].1.contains("## Group Chat"));
+        let user_turn = &calls[0][1].1;
+        assert!(user_turn.contains("[bob] lunch at noon?"));
+        assert!(user_turn.contains("[alice] what do you think?"));
+        assert_eq!(channel_impl.sent_messages.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn group_rate_limit_records_excess_messages_without_reply() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let ctx = group_test_context(
+            provider_impl,
+            channel_impl,
+            crate::config::GroupPolicyConfig {
+                max_replies_per_minute: 1,
+                ..Default::default()
+            },
+        );
+
+        assert!(admit_group_message(
+            ctx.as_ref(),
+            &room_message("m1", "alice", "first", true)
+        ));
+        assert!(!admit_group_message(
+            ctx.as_ref(),
+            &room_message("m2", "alice", "second", true)
+        ));
+
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let room = histories.get("test-channel_room_room-1").unwrap();
+        assert_eq!(room.len(), 1);
+        assert_eq!(room[0].content, "[alice] second");
+    }
+
+    #[derive(Default)]
+    struct VoiceRecordingChannel {
+        sent_messages: tokio::sync::Mutex<Vec<String>>,
+        voice_replies: tokio::sync::Mutex<Vec<(String, Vec<u8>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for VoiceRecordingChannel {
+        fn name(&self) -> &str {
+            "test-channel"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent_messages
+                .lock()
+                .await
+                .push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn supports_voice_replies(&self) -> bool {
+            true
+        }
+
+        async fn send_voice_reply(
+            &self,
+            message: &SendMessage,
+            audio: &tts::SpeechAudio,
+        ) -> anyhow::Result<()> {
+            self.voice_replies
+                .lock()
+                .await
+                .push((message.recipient.clone(), audio.bytes.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_follows_voice_input_with_voice_reply() {
+        let channel_impl = Arc::new(VoiceRecordingChannel::default());
+        let mut ctx = (*group_test_context(
+            Arc::new(HistoryCaptureProvider::default()),
+            channel_impl.clone(),
+            crate::config::GroupPolicyConfig::default(),
+        ))
+        .clone();
+        ctx.tts = Arc::new(crate::config::TtsConfig {
+            enabled: true,
+            provider: crate::config::TtsProvider::Command,
+            command: vec!["cat".to_string()],
+            ..Default::default()
+        });
+        let ctx = Arc::new(ctx);
+
+        let mut spoken = traits::ChannelMessage {
+            id: "v1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "alice".to_string(),
+            content: "[Voice] what's the status?".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            group: None,
+            voice: true,
+        };
+        process_channel_message(ctx.clone(), spoken.clone(), CancellationToken::new()).await;
+
+        spoken.id = "t1".to_string();
+        spoken.voice = false;
+        process_channel_message(ctx, spoken, CancellationToken::new()).await;
+
+        assert_eq!(channel_impl.sent_messages.lock().await.len(), 2);
+        let voice_replies = channel_impl.voice_replies.lock().await;
+        assert_eq!(voice_replies.len(), 1);
+        assert_eq!(voice_replies[0].0, "alice");
+        assert_eq!(voice_replies[0].1, b"response-1");
+    }
+
+    #[test]
+    fn collect_group_policies_maps_legacy_mention_only() {
+        let mut config = Config::default();
+        config.channels_config.telegram = Some(crate::config::TelegramConfig {
+            bot_token: "token".into(),
+            allowed_users: vec![],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: true,
+            group: crate::config::GroupPolicyConfig::default(),
+        });
+
+        let policies = collect_group_policies(&config);
+        assert_eq!(
+            policies["telegram"].reply,
+            crate::config::GroupReplyMode::Mention
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_handles_link_command_without_llm_call() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let tmp = TempDir::new().unwrap();
+        let registry = Arc::new(IdentityRegistry::open(tmp.path()).unwrap());
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: Some(registry.clone()),
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "/link".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                group: None,
+                voice: false,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("Link code for user"));
+        assert!(registry.resolve("test-channel", "alice").unwrap().is_some());
+        assert!(provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn process_channel_message_enriches_current_turn_without_persisting_context() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -5753,6 +6996,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -5765,6 +7015,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -5838,6 +7090,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -5850,6 +7109,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -6153,6 +7414,8 @@ This is an example JSON object for profile settings."#;
             allowed_users: vec![],
             thread_replies: Some(true),
             mention_only: Some(false),
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
         });
 
         let channels = collect_configured_channels(&config, "test");
@@ -6387,6 +7650,13 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6400,6 +7670,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -6453,6 +7725,13 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            reasoning_display: crate::config::ReasoningDisplay::Hide,
+            injection_guard: None,
+            cost_tracker: None,
+            identities: None,
+            group_policies: Arc::new(HashMap::new()),
+            room_rate_limiter: Arc::new(group::RoomRateLimiter::default()),
+            tts: Arc::new(crate::config::TtsConfig::default()),
         });
 
         process_channel_message(
@@ -6465,6 +7744,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
@@ -6480,6 +7761,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
+                voice: false,
             },
             CancellationToken::new(),
         )
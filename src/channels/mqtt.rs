@@ -0,0 +1,1114 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{MqttConfig, MqttProtocol};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, Mutex};
+
+// Use tokio_rustls's re-export of rustls types
+use tokio_rustls::rustls;
+
+/// Upper bound for a single inbound packet.
+const MAX_PACKET_BYTES: usize = 1024 * 1024;
+
+/// How long to wait for CONNACK/SUBACK before giving up.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
+
+const PACKET_CONNECT: u8 = 1;
+const PACKET_CONNACK: u8 = 2;
+const PACKET_PUBLISH: u8 = 3;
+const PACKET_PUBACK: u8 = 4;
+const PACKET_SUBSCRIBE: u8 = 8;
+const PACKET_SUBACK: u8 = 9;
+const PACKET_PINGREQ: u8 = 12;
+const PACKET_PINGRESP: u8 = 13;
+const PACKET_DISCONNECT: u8 = 14;
+
+const PROP_RESPONSE_TOPIC: u8 = 0x08;
+const PROP_CORRELATION_DATA: u8 = 0x09;
+
+// ── Codec ───────────────────────────────────────────────────
+
+/// An inbound control packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Packet {
+    ConnAck { code: u8 },
+    Publish(Publish),
+    PubAck,
+    SubAck { packet_id: u16, codes: Vec<u8> },
+    PingResp,
+    Disconnect { code: u8 },
+    Other(u8),
+}
+
+/// A PUBLISH packet, in either direction.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Publish {
+    topic: String,
+    payload: Vec<u8>,
+    qos: u8,
+    retain: bool,
+    packet_id: Option<u16>,
+    /// MQTT v5 response topic property.
+    response_topic: Option<String>,
+    /// MQTT v5 correlation data property.
+    correlation_data: Option<Vec<u8>>,
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_bytes(out: &mut Vec<u8>, data: &[u8]) {
+    // MQTT strings and binary data are capped at 65535 bytes.
+    let len = u16::try_from(data.len()).unwrap_or(u16::MAX);
+    put_u16(out, len);
+    out.extend_from_slice(&data[..usize::from(len)]);
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let mut byte = u8::try_from(value % 128).unwrap_or_default();
+        value /= 128;
+        if value > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Prefix a variable header + payload with its fixed header.
+fn frame(first_byte: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![first_byte];
+    put_varint(&mut out, body.len());
+    out.extend_from_slice(body);
+    out
+}
+
+/// Credentials and session options for CONNECT.
+struct ConnectOptions<'a> {
+    protocol: MqttProtocol,
+    client_id: &'a str,
+    username: Option<&'a str>,
+    password: Option<&'a str>,
+    keep_alive_secs: u16,
+}
+
+fn encode_connect(opts: &ConnectOptions<'_>) -> Vec<u8> {
+    let mut body = Vec::new();
+    put_bytes(&mut body, b"MQTT");
+    body.push(match opts.protocol {
+        MqttProtocol::V311 => 4,
+        MqttProtocol::V5 => 5,
+    });
+    // Clean session: replies are only meaningful while we are connected.
+    let mut flags = 0x02;
+    if opts.username.is_some() {
+        flags |= 0x80;
+    }
+    if opts.password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    put_u16(&mut body, opts.keep_alive_secs);
+    if opts.protocol == MqttProtocol::V5 {
+        put_varint(&mut body, 0);
+    }
+    put_bytes(&mut body, opts.client_id.as_bytes());
+    if let Some(username) = opts.username {
+        put_bytes(&mut body, username.as_bytes());
+    }
+    if let Some(password) = opts.password {
+        put_bytes(&mut body, password.as_bytes());
+    }
+    frame(PACKET_CONNECT << 4, &body)
+}
+
+fn encode_subscribe(protocol: MqttProtocol, packet_id: u16, topics: &[String], qos: u8) -> Vec<u8> {
+    let mut body = Vec::new();
+    put_u16(&mut body, packet_id);
+    if protocol == MqttProtocol::V5 {
+        put_varint(&mut body, 0);
+    }
+    for topic in topics {
+        put_bytes(&mut body, topic.as_bytes());
+        body.push(qos);
+    }
+    frame((PACKET_SUBSCRIBE << 4) | 0x02, &body)
+}
+
+fn encode_publish(protocol: MqttProtocol, publish: &Publish) -> Vec<u8> {
+    let mut body = Vec::new();
+    put_bytes(&mut body, publish.topic.as_bytes());
+    if publish.qos > 0 {
+        put_u16(&mut body, publish.packet_id.unwrap_or(1));
+    }
+    if protocol == MqttProtocol::V5 {
+        let mut props = Vec::new();
+        if let Some(ref topic) = publish.response_topic {
+            props.push(PROP_RESPONSE_TOPIC);
+            put_bytes(&mut props, topic.as_bytes());
+        }
+        if let Some(ref data) = publish.correlation_data {
+            props.push(PROP_CORRELATION_DATA);
+            put_bytes(&mut props, data);
+        }
+        put_varint(&mut body, props.len());
+        body.extend_from_slice(&props);
+    }
+    body.extend_from_slice(&publish.payload);
+    let first = (PACKET_PUBLISH << 4) | (publish.qos << 1) | u8::from(publish.retain);
+    frame(first, &body)
+}
+
+fn encode_puback(packet_id: u16) -> Vec<u8> {
+    frame(PACKET_PUBACK << 4, &packet_id.to_be_bytes())
+}
+
+fn encode_pingreq() -> Vec<u8> {
+    frame(PACKET_PINGREQ << 4, &[])
+}
+
+fn encode_disconnect() -> Vec<u8> {
+    frame(PACKET_DISCONNECT << 4, &[])
+}
+
+/// Cursor over a packet body.
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.remaining() < n {
+            bail!("MQTT packet truncated");
+        }
+        let out = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn varint(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        for shift in 0..4 {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7F) << (7 * shift);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        bail!("MQTT malformed variable byte integer")
+    }
+
+    fn binary(&mut self) -> Result<&'a [u8]> {
+        let len = usize::from(self.u16()?);
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        Ok(std::str::from_utf8(self.binary()?)
+            .context("MQTT string is not UTF-8")?
+            .to_string())
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let out = &self.data[self.pos..];
+        self.pos = self.data.len();
+        out
+    }
+
+    /// Read a v5 property block, keeping response topic and correlation
+    /// data and skipping everything else.
+    fn properties(&mut self, publish: &mut Publish) -> Result<()> {
+        let len = self.varint()?;
+        let mut props = Decoder::new(self.take(len)?);
+        while props.remaining() > 0 {
+            match props.u8()? {
+                PROP_RESPONSE_TOPIC => publish.response_topic = Some(props.string()?),
+                PROP_CORRELATION_DATA => {
+                    publish.correlation_data = Some(props.binary()?.to_vec());
+                }
+                0x01 | 0x17 | 0x19 | 0x24 | 0x25 | 0x28 | 0x29 | 0x2A => {
+                    props.u8()?;
+                }
+                0x13 | 0x21 | 0x22 | 0x23 => {
+                    props.u16()?;
+                }
+                0x02 | 0x11 | 0x18 | 0x27 => {
+                    props.take(4)?;
+                }
+                0x0B => {
+                    props.varint()?;
+                }
+                0x03 | 0x12 | 0x15 | 0x1A | 0x1C | 0x1F | 0x16 => {
+                    props.binary()?;
+                }
+                0x26 => {
+                    props.binary()?;
+                    props.binary()?;
+                }
+                other => bail!("MQTT unknown property 0x{other:02x}"),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn decode_packet(protocol: MqttProtocol, first_byte: u8, body: &[u8]) -> Result<Packet> {
+    let mut d = Decoder::new(body);
+    let v5 = protocol == MqttProtocol::V5;
+    Ok(match first_byte >> 4 {
+        PACKET_CONNACK => {
+            d.u8()?;
+            Packet::ConnAck { code: d.u8()? }
+        }
+        PACKET_PUBLISH => {
+            let qos = (first_byte >> 1) & 0x03;
+            if qos > 2 {
+                bail!("MQTT invalid QoS in PUBLISH");
+            }
+            let mut publish = Publish {
+                topic: d.string()?,
+                qos,
+                retain: first_byte & 0x01 != 0,
+                ..Publish::default()
+            };
+            if qos > 0 {
+                publish.packet_id = Some(d.u16()?);
+            }
+            if v5 {
+                d.properties(&mut publish)?;
+            }
+            publish.payload = d.rest().to_vec();
+            Packet::Publish(publish)
+        }
+        PACKET_PUBACK => Packet::PubAck,
+        PACKET_SUBACK => {
+            let packet_id = d.u16()?;
+            if v5 {
+                let len = d.varint()?;
+                d.take(len)?;
+            }
+            Packet::SubAck {
+                packet_id,
+                codes: d.rest().to_vec(),
+            }
+        }
+        PACKET_PINGRESP => Packet::PingResp,
+        PACKET_DISCONNECT => Packet::Disconnect {
+            code: if d.remaining() > 0 { d.u8()? } else { 0 },
+        },
+        other => Packet::Other(other),
+    })
+}
+
+async fn read_packet<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    protocol: MqttProtocol,
+) -> Result<Packet> {
+    let first_byte = reader.read_u8().await?;
+    let mut len = 0usize;
+    for shift in 0..4 {
+        let byte = reader.read_u8().await?;
+        len |= usize::from(byte & 0x7F) << (7 * shift);
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if shift == 3 {
+            bail!("MQTT malformed remaining length");
+        }
+    }
+    if len > MAX_PACKET_BYTES {
+        bail!("MQTT packet exceeds {MAX_PACKET_BYTES} bytes");
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await?;
+    decode_packet(protocol, first_byte, &body)
+}
+
+/// Read packets on a dedicated task and forward them over a channel.
+///
+/// `read_packet` is not cancel-safe: dropping it mid-packet loses the bytes
+/// already consumed, so it must never race other branches in `select!`.
+/// The task ends after the first read error or once the receiver is dropped.
+fn spawn_reader(
+    mut reader: MqttReader,
+    protocol: MqttProtocol,
+) -> (tokio::task::JoinHandle<()>, mpsc::Receiver<Result<Packet>>) {
+    let (tx, rx) = mpsc::channel(16);
+    let handle = tokio::spawn(async move {
+        loop {
+            let packet = read_packet(&mut reader, protocol).await;
+            let failed = packet.is_err();
+            if tx.send(packet).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    (handle, rx)
+}
+
+/// Does `topic` match the subscription `filter` (`+` and `#` wildcards)?
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut levels = topic.split('/');
+    for part in filter.split('/') {
+        if part == "#" {
+            return true;
+        }
+        match levels.next() {
+            Some(level) if part == "+" || part == level => {}
+            _ => return false,
+        }
+    }
+    levels.next().is_none()
+}
+
+fn connack_reason(protocol: MqttProtocol, code: u8) -> &'static str {
+    match (protocol, code) {
+        (MqttProtocol::V311, 1) => "unacceptable protocol version",
+        (MqttProtocol::V311, 2) => "client identifier rejected",
+        (MqttProtocol::V311, 3) | (MqttProtocol::V5, 0x88) => "server unavailable",
+        (MqttProtocol::V311, 4) | (MqttProtocol::V5, 0x86) => "bad username or password",
+        (MqttProtocol::V311, 5) | (MqttProtocol::V5, 0x87) => "not authorized",
+        (MqttProtocol::V5, 0x84) => "unsupported protocol version",
+        (MqttProtocol::V5, 0x85) => "client identifier not valid",
+        _ => "connection refused",
+    }
+}
+
+// ── Connection ──────────────────────────────────────────────
+
+type MqttReader = Box<dyn AsyncRead + Send + Unpin>;
+type MqttWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+fn tls_config(cfg: &MqttConfig) -> Result<rustls::ClientConfig> {
+    let builder = if cfg.verify_tls.unwrap_or(true) {
+        let mut roots: rustls::RootCertStore =
+            webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
+        if let Some(ref ca) = cfg.ca_cert_path {
+            for cert in crate::gateway::tls::load_certs(&crate::gateway::tls::resolve_path(ca))? {
+                roots.add(cert)?;
+            }
+        }
+        rustls::ClientConfig::builder().with_root_certificates(roots)
+    } else {
+        rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(super::irc::NoVerify))
+    };
+
+    match (&cfg.client_cert_path, &cfg.client_key_path) {
+        (Some(cert), Some(key)) => {
+            let certs = crate::gateway::tls::load_certs(&crate::gateway::tls::resolve_path(cert))?;
+            let key = crate::gateway::tls::load_key(&crate::gateway::tls::resolve_path(key))?;
+            Ok(builder.with_client_auth_cert(certs, key)?)
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => bail!("MQTT client_cert_path and client_key_path must be set together"),
+    }
+}
+
+fn default_port(cfg: &MqttConfig) -> u16 {
+    cfg.port.unwrap_or(if cfg.tls { 8883 } else { 1883 })
+}
+
+/// Open the transport and complete CONNECT/CONNACK.
+async fn connect(cfg: &MqttConfig, client_id: &str) -> Result<(MqttReader, MqttWriter)> {
+    let tcp = tokio::net::TcpStream::connect((cfg.host.as_str(), default_port(cfg))).await?;
+    let (mut reader, mut writer): (MqttReader, MqttWriter) = if cfg.tls {
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(tls_config(cfg)?));
+        let name = rustls::pki_types::ServerName::try_from(cfg.host.clone())?;
+        let tls = connector.connect(name, tcp).await?;
+        let (r, w) = tokio::io::split(tls);
+        (Box::new(r), Box::new(w))
+    } else {
+        let (r, w) = tcp.into_split();
+        (Box::new(r), Box::new(w))
+    };
+
+    let connect = encode_connect(&ConnectOptions {
+        protocol: cfg.protocol,
+        client_id,
+        username: cfg.username.as_deref(),
+        password: cfg.password.as_deref(),
+        keep_alive_secs: cfg.keep_alive_secs,
+    });
+    writer.write_all(&connect).await?;
+    writer.flush().await?;
+
+    match tokio::time::timeout(HANDSHAKE_TIMEOUT, read_packet(&mut reader, cfg.protocol))
+        .await
+        .context("MQTT timed out waiting for CONNACK")??
+    {
+        Packet::ConnAck { code: 0 } => Ok((reader, writer)),
+        Packet::ConnAck { code } => bail!(
+            "MQTT connection refused: {} (code {code})",
+            connack_reason(cfg.protocol, code)
+        ),
+        other => bail!("MQTT expected CONNACK, got {other:?}"),
+    }
+}
+
+/// Publish one message on a short-lived connection. Used by the
+/// `mqtt_publish` tool, which runs independently of the channel listener.
+pub async fn publish_once(
+    cfg: &MqttConfig,
+    topic: &str,
+    payload: &[u8],
+    retain: bool,
+) -> Result<()> {
+    let client_id = format!(
+        "{}-pub-{}",
+        client_id_base(cfg),
+        &uuid::Uuid::new_v4().to_string()[..8]
+    );
+    let (mut reader, mut writer) = connect(cfg, &client_id).await?;
+    let qos = cfg.qos.min(1);
+    let publish = Publish {
+        topic: topic.to_string(),
+        payload: payload.to_vec(),
+        qos,
+        retain,
+        packet_id: (qos > 0).then_some(1),
+        ..Publish::default()
+    };
+    writer
+        .write_all(&encode_publish(cfg.protocol, &publish))
+        .await?;
+    writer.flush().await?;
+    if qos > 0 {
+        loop {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, read_packet(&mut reader, cfg.protocol))
+                .await
+                .context("MQTT timed out waiting for PUBACK")??
+            {
+                Packet::PubAck => break,
+                Packet::Disconnect { code } => bail!("MQTT broker disconnected (code {code})"),
+                _ => {}
+            }
+        }
+    }
+    writer.write_all(&encode_disconnect()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+fn client_id_base(cfg: &MqttConfig) -> String {
+    cfg.client_id
+        .clone()
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| "zeroclaw".into())
+}
+
+// ── Channel ─────────────────────────────────────────────────
+
+/// MQTT channel — subscribes to topics for inbound prompts and publishes
+/// replies to a response topic.
+///
+/// With MQTT v5 the publisher's response-topic and correlation-data
+/// properties are honoured; the correlation data travels as `thread_ts`.
+pub struct MqttChannel {
+    config: MqttConfig,
+    client_id: String,
+    next_packet_id: AtomicU16,
+    /// Write half of the live connection, if any.
+    writer: Arc<Mutex<Option<MqttWriter>>>,
+}
+
+impl MqttChannel {
+    pub fn new(config: MqttConfig) -> Self {
+        let client_id = client_id_base(&config);
+        Self {
+            config,
+            client_id,
+            next_packet_id: AtomicU16::new(1),
+            writer: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn packet_id(&self) -> u16 {
+        // Packet id 0 is reserved.
+        loop {
+            let id = self.next_packet_id.fetch_add(1, Ordering::Relaxed);
+            if id != 0 {
+                return id;
+            }
+        }
+    }
+
+    fn response_topic_for(&self, topic: &str) -> String {
+        match self.config.response_topic.as_deref() {
+            Some(template) if !template.trim().is_empty() => template.replace("{topic}", topic),
+            _ => format!("{topic}/response"),
+        }
+    }
+
+    fn parse_publish(&self, publish: &Publish) -> Option<ChannelMessage> {
+        if !self
+            .config
+            .topics
+            .iter()
+            .any(|filter| topic_matches(filter, &publish.topic))
+        {
+            return None;
+        }
+        // Retained messages are stale state, not prompts.
+        if publish.retain {
+            return None;
+        }
+        let Ok(text) = std::str::from_utf8(&publish.payload) else {
+            tracing::warn!("MQTT: ignoring non-UTF-8 payload on {}", publish.topic);
+            return None;
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+
+        let reply_target = publish
+            .response_topic
+            .clone()
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| self.response_topic_for(&publish.topic));
+
+        Some(ChannelMessage {
+            id: format!("mqtt_{}", uuid::Uuid::new_v4()),
+            sender: publish.topic.clone(),
+            reply_target,
+            content: text.to_string(),
+            channel: "mqtt".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: publish
+                .correlation_data
+                .as_ref()
+                .map(|data| base64::engine::general_purpose::STANDARD.encode(data)),
+            group: None,
+            voice: false,
+        })
+    }
+
+    async fn write_packet(&self, packet: &[u8]) -> Result<()> {
+        let mut guard = self.writer.lock().await;
+        let writer = guard
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("MQTT not connected"))?;
+        writer.write_all(packet).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    async fn subscribe(&self, reader: &mut MqttReader, writer: &mut MqttWriter) -> Result<()> {
+        let packet_id = self.packet_id();
+        let qos = self.config.qos.min(1);
+        writer
+            .write_all(&encode_subscribe(
+                self.config.protocol,
+                packet_id,
+                &self.config.topics,
+                qos,
+            ))
+            .await?;
+        writer.flush().await?;
+        loop {
+            let packet =
+                tokio::time::timeout(HANDSHAKE_TIMEOUT, read_packet(reader, self.config.protocol))
+                    .await
+                    .context("MQTT timed out waiting for SUBACK")??;
+            if let Packet::SubAck {
+                packet_id: id,
+                codes,
+            } = packet
+            {
+                if id != packet_id {
+                    continue;
+                }
+                for (topic, code) in self.config.topics.iter().zip(&codes) {
+                    if *code >= 0x80 {
+                        bail!("MQTT subscription to {topic} rejected (code 0x{code:02x})");
+                    }
+                }
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for MqttChannel {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let qos = self.config.qos.min(1);
+        let publish = Publish {
+            topic: message.recipient.clone(),
+            payload: message.content.as_bytes().to_vec(),
+            qos,
+            retain: false,
+            packet_id: (qos > 0).then(|| self.packet_id()),
+            response_topic: None,
+            correlation_data: message
+                .thread_ts
+                .as_ref()
+                .and_then(|t| base64::engine::general_purpose::STANDARD.decode(t).ok()),
+        };
+        self.write_packet(&encode_publish(self.config.protocol, &publish))
+            .await
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        if self.config.topics.is_empty() {
+            bail!("MQTT channel has no topics to subscribe to");
+        }
+        tracing::info!(
+            "MQTT channel connecting to {}:{} as {}...",
+            self.config.host,
+            default_port(&self.config),
+            self.client_id
+        );
+
+        let (mut reader, mut writer) = connect(&self.config, &self.client_id).await?;
+        self.subscribe(&mut reader, &mut writer).await?;
+        tracing::info!(
+            "MQTT channel subscribed to {}",
+            self.config.topics.join(", ")
+        );
+        *self.writer.lock().await = Some(writer);
+        let (reader_task, mut packets) = spawn_reader(reader, self.config.protocol);
+
+        let interval = Duration::from_secs(u64::from(self.config.keep_alive_secs.max(5)));
+        let mut keepalive = tokio::time::interval(interval);
+        keepalive.tick().await;
+        let mut awaiting_pong = false;
+
+        let result = loop {
+            tokio::select! {
+                packet = packets.recv() => {
+                    let Some(packet) = packet else {
+                        break Err(anyhow::anyhow!("MQTT reader stopped"));
+                    };
+                    match packet {
+                        Ok(Packet::Publish(publish)) => {
+                            if let (1, Some(id)) = (publish.qos, publish.packet_id) {
+                                if let Err(e) = self.write_packet(&encode_puback(id)).await {
+                                    break Err(e);
+                                }
+                            }
+                            if let Some(msg) = self.parse_publish(&publish) {
+                                if tx.send(msg).await.is_err() {
+                                    break Ok(());
+                                }
+                            }
+                        }
+                        Ok(Packet::PingResp) => awaiting_pong = false,
+                        Ok(Packet::Disconnect { code }) => {
+                            break Err(anyhow::anyhow!("MQTT broker disconnected (code 0x{code:02x})"));
+                        }
+                        Ok(_) => {}
+                        Err(e) => break Err(e),
+                    }
+                }
+                _ = keepalive.tick() => {
+                    if awaiting_pong {
+                        break Err(anyhow::anyhow!("MQTT keepalive timed out"));
+                    }
+                    if let Err(e) = self.write_packet(&encode_pingreq()).await {
+                        break Err(e);
+                    }
+                    awaiting_pong = true;
+                }
+            }
+        };
+
+        reader_task.abort();
+        if let Some(mut writer) = self.writer.lock().await.take() {
+            let _ = writer.write_all(&encode_disconnect()).await;
+        }
+        result
+    }
+
+    async fn health_check(&self) -> bool {
+        let client_id = format!("{}-health", self.client_id);
+        match connect(&self.config, &client_id).await {
+            Ok((_, mut writer)) => {
+                let _ = writer.write_all(&encode_disconnect()).await;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_config(protocol: MqttProtocol) -> MqttConfig {
+        MqttConfig {
+            host: "127.0.0.1".into(),
+            port: None,
+            tls: false,
+            verify_tls: None,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            client_id: Some("bot".into()),
+            username: None,
+            password: None,
+            protocol,
+            topics: vec!["home/+/ask".into()],
+            response_topic: None,
+            qos: 1,
+            keep_alive_secs: 30,
+            publish_topics: vec![],
+        }
+    }
+
+    async fn roundtrip(protocol: MqttProtocol, bytes: &[u8]) -> Packet {
+        let mut reader = bytes;
+        read_packet(&mut reader, protocol).await.unwrap()
+    }
+
+    // ── Codec ───────────────────────────────────────────────
+
+    #[test]
+    fn varint_encoding_matches_spec() {
+        for (value, expected) in [
+            (0usize, vec![0x00]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xFF, 0x7F]),
+            (2_097_152, vec![0x80, 0x80, 0x80, 0x01]),
+        ] {
+            let mut out = Vec::new();
+            put_varint(&mut out, value);
+            assert_eq!(out, expected, "{value}");
+            assert_eq!(Decoder::new(&out).varint().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn connect_encodes_credentials_and_version() {
+        let packet = encode_connect(&ConnectOptions {
+            protocol: MqttProtocol::V311,
+            client_id: "bot",
+            username: Some("u"),
+            password: Some("p"),
+            keep_alive_secs: 30,
+        });
+        assert_eq!(
+            packet,
+            [
+                0x10, 21, 0, 4, b'M', b'Q', b'T', b'T', 4, 0xC2, 0, 30, 0, 3, b'b', b'o', b't', 0,
+                1, b'u', 0, 1, b'p'
+            ]
+        );
+
+        let v5 = encode_connect(&ConnectOptions {
+            protocol: MqttProtocol::V5,
+            client_id: "bot",
+            username: None,
+            password: None,
+            keep_alive_secs: 60,
+        });
+        // Protocol level 5, clean start only, empty property block.
+        assert_eq!(&v5[8..13], &[5, 0x02, 0, 60, 0]);
+    }
+
+    #[test]
+    fn subscribe_sets_reserved_flags() {
+        let packet = encode_subscribe(MqttProtocol::V311, 7, &["a/#".into()], 1);
+        assert_eq!(packet, [0x82, 8, 0, 7, 0, 3, b'a', b'/', b'#', 1]);
+    }
+
+    #[tokio::test]
+    async fn publish_roundtrips_v311() {
+        let publish = Publish {
+            topic: "home/kitchen/ask".into(),
+            payload: b"lights?".to_vec(),
+            qos: 1,
+            retain: false,
+            packet_id: Some(42),
+            ..Publish::default()
+        };
+        let bytes = encode_publish(MqttProtocol::V311, &publish);
+        assert_eq!(
+            roundtrip(MqttProtocol::V311, &bytes).await,
+            Packet::Publish(publish)
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_roundtrips_v5_properties() {
+        let publish = Publish {
+            topic: "t".into(),
+            payload: b"x".to_vec(),
+            qos: 0,
+            retain: true,
+            packet_id: None,
+            response_topic: Some("replies/1".into()),
+            correlation_data: Some(vec![0, 1, 2]),
+        };
+        let bytes = encode_publish(MqttProtocol::V5, &publish);
+        assert_eq!(
+            roundtrip(MqttProtocol::V5, &bytes).await,
+            Packet::Publish(publish)
+        );
+    }
+
+    #[tokio::test]
+    async fn v5_publish_skips_unknown_properties() {
+        // Payload format (byte), message expiry (u32), user property (pair).
+        let props = [0x01, 1, 0x02, 0, 0, 0, 60, 0x26, 0, 1, b'k', 0, 1, b'v'];
+        let mut body = Vec::new();
+        put_bytes(&mut body, b"t");
+        put_varint(&mut body, props.len());
+        body.extend_from_slice(&props);
+        body.extend_from_slice(b"hi");
+        let packet = roundtrip(MqttProtocol::V5, &frame(0x30, &body)).await;
+        let Packet::Publish(publish) = packet else {
+            panic!("expected publish");
+        };
+        assert_eq!(publish.payload, b"hi");
+        assert!(publish.response_topic.is_none());
+    }
+
+    #[tokio::test]
+    async fn decodes_control_packets() {
+        assert_eq!(
+            roundtrip(MqttProtocol::V311, &[0x20, 2, 0, 5]).await,
+            Packet::ConnAck { code: 5 }
+        );
+        assert_eq!(
+            roundtrip(MqttProtocol::V5, &[0x90, 4, 0, 9, 0, 0x80]).await,
+            Packet::SubAck {
+                packet_id: 9,
+                codes: vec![0x80]
+            }
+        );
+        assert_eq!(
+            roundtrip(MqttProtocol::V311, &[0xD0, 0]).await,
+            Packet::PingResp
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_packets() {
+        let mut bytes = vec![0x30];
+        put_varint(&mut bytes, MAX_PACKET_BYTES + 1);
+        let mut reader = bytes.as_slice();
+        assert!(read_packet(&mut reader, MqttProtocol::V311).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn reader_task_keeps_packets_whole_across_ticks() {
+        let (mut broker, client) = tokio::io::duplex(64);
+        let (_task, mut packets) = spawn_reader(Box::new(client), MqttProtocol::V311);
+        let bytes = encode_publish(
+            MqttProtocol::V311,
+            &Publish {
+                topic: "home/kitchen/ask".into(),
+                payload: b"hello".to_vec(),
+                ..Publish::default()
+            },
+        );
+        let (head, tail) = bytes.split_at(4);
+        broker.write_all(head).await.unwrap();
+
+        // Ticks firing mid-packet must not drop the bytes already read.
+        let mut tick = tokio::time::interval(Duration::from_millis(5));
+        for _ in 0..3 {
+            tokio::select! {
+                packet = packets.recv() => panic!("unexpected packet: {packet:?}"),
+                _ = tick.tick() => {}
+            }
+        }
+        broker.write_all(tail).await.unwrap();
+        let Some(Ok(Packet::Publish(publish))) = packets.recv().await else {
+            panic!("expected publish");
+        };
+        assert_eq!(publish.payload, b"hello");
+
+        drop(broker);
+        assert!(matches!(packets.recv().await, Some(Err(_))));
+        assert!(packets.recv().await.is_none());
+    }
+
+    #[test]
+    fn topic_filters_match_wildcards() {
+        assert!(topic_matches("home/+/ask", "home/kitchen/ask"));
+        assert!(!topic_matches("home/+/ask", "home/kitchen/ask/x"));
+        assert!(topic_matches("home/#", "home"));
+        assert!(topic_matches("home/#", "home/a/b"));
+        assert!(topic_matches("#", "anything/at/all"));
+        assert!(!topic_matches("home/lamp", "home/lamp/set"));
+        assert!(topic_matches("home/lamp", "home/lamp"));
+    }
+
+    #[test]
+    fn connack_reasons_depend_on_protocol() {
+        assert_eq!(
+            connack_reason(MqttProtocol::V311, 4),
+            "bad username or password"
+        );
+        assert_eq!(connack_reason(MqttProtocol::V5, 0x87), "not authorized");
+    }
+
+    // ── Channel ─────────────────────────────────────────────
+
+    #[test]
+    fn default_port_depends_on_tls() {
+        let mut cfg = make_config(MqttProtocol::V311);
+        assert_eq!(default_port(&cfg), 1883);
+        cfg.tls = true;
+        assert_eq!(default_port(&cfg), 8883);
+        cfg.port = Some(9000);
+        assert_eq!(default_port(&cfg), 9000);
+    }
+
+    #[test]
+    fn parse_publish_defaults_reply_topic() {
+        let ch = MqttChannel::new(make_config(MqttProtocol::V311));
+        let msg = ch
+            .parse_publish(&Publish {
+                topic: "home/kitchen/ask".into(),
+                payload: b" turn on the lights ".to_vec(),
+                ..Publish::default()
+            })
+            .unwrap();
+        assert_eq!(msg.sender, "home/kitchen/ask");
+        assert_eq!(msg.reply_target, "home/kitchen/ask/response");
+        assert_eq!(msg.content, "turn on the lights");
+        assert_eq!(msg.channel, "mqtt");
+        assert!(msg.thread_ts.is_none());
+    }
+
+    #[test]
+    fn parse_publish_uses_template_and_v5_properties() {
+        let mut cfg = make_config(MqttProtocol::V5);
+        cfg.response_topic = Some("bot/{topic}".into());
+        let ch = MqttChannel::new(cfg);
+
+        let templated = ch
+            .parse_publish(&Publish {
+                topic: "home/hall/ask".into(),
+                payload: b"hi".to_vec(),
+                ..Publish::default()
+            })
+            .unwrap();
+        assert_eq!(templated.reply_target, "bot/home/hall/ask");
+
+        let v5 = ch
+            .parse_publish(&Publish {
+                topic: "home/hall/ask".into(),
+                payload: b"hi".to_vec(),
+                response_topic: Some("client/42/reply".into()),
+                correlation_data: Some(b"req-1".to_vec()),
+                ..Publish::default()
+            })
+            .unwrap();
+        assert_eq!(v5.reply_target, "client/42/reply");
+        assert_eq!(v5.thread_ts.as_deref(), Some("cmVxLTE="));
+    }
+
+    #[test]
+    fn parse_publish_skips_retained_binary_and_unsubscribed() {
+        let ch = MqttChannel::new(make_config(MqttProtocol::V311));
+        for publish in [
+            Publish {
+                topic: "home/hall/ask".into(),
+                payload: b"old".to_vec(),
+                retain: true,
+                ..Publish::default()
+            },
+            Publish {
+                topic: "home/hall/ask".into(),
+                payload: vec![0xFF, 0xFE],
+                ..Publish::default()
+            },
+            Publish {
+                topic: "office/ask".into(),
+                payload: b"hi".to_vec(),
+                ..Publish::default()
+            },
+        ] {
+            assert!(ch.parse_publish(&publish).is_none(), "{publish:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn send_publishes_with_correlation_data() {
+        let ch = MqttChannel::new(make_config(MqttProtocol::V5));
+        let (client, mut broker) = tokio::io::duplex(4096);
+        *ch.writer.lock().await = Some(Box::new(client));
+
+        ch.send(&SendMessage::new("done", "client/42/reply").in_thread(Some("cmVxLTE=".into())))
+            .await
+            .unwrap();
+        ch.writer.lock().await.take();
+
+        let Packet::Publish(publish) = read_packet(&mut broker, MqttProtocol::V5).await.unwrap()
+        else {
+            panic!("expected publish");
+        };
+        assert_eq!(publish.topic, "client/42/reply");
+        assert_eq!(publish.payload, b"done");
+        assert_eq!(publish.qos, 1);
+        assert_eq!(publish.correlation_data.as_deref(), Some(&b"req-1"[..]));
+    }
+
+    #[tokio::test]
+    async fn send_without_connection_fails() {
+        let ch = MqttChannel::new(make_config(MqttProtocol::V311));
+        assert!(ch
+            .send(&SendMessage::new("hi", "home/kitchen/ask/response"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn subscribe_reports_rejected_topics() {
+        let ch = MqttChannel::new(make_config(MqttProtocol::V311));
+        let (client, mut broker) = tokio::io::duplex(4096);
+        let (read, write) = tokio::io::split(client);
+        let mut reader: MqttReader = Box::new(read);
+        let mut writer: MqttWriter = Box::new(write);
+
+        // Packet ids start at 1.
+        broker.write_all(&[0x90, 3, 0, 1, 0x80]).await.unwrap();
+        let err = ch.subscribe(&mut reader, &mut writer).await.unwrap_err();
+        assert!(err.to_string().contains("home/+/ask"));
+
+        broker.write_all(&[0x90, 3, 0, 2, 0x01]).await.unwrap();
+        ch.subscribe(&mut reader, &mut writer).await.unwrap();
+    }
+}
@@ -168,6 +168,8 @@ impl NextcloudTalkChannel {
             channel: "nextcloud_talk".to_string(),
             timestamp,
             thread_ts: None,
+            group: None,
+            voice: false,
         });
 
         messages
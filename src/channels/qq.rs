@@ -392,6 +392,8 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                group: None,
+                                voice: false,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -431,6 +433,8 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                group: None,
+                                voice: false,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
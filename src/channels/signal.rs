@@ -1,5 +1,7 @@
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::tts::SpeechAudio;
 use async_trait::async_trait;
+use base64::Engine;
 use futures_util::StreamExt;
 use reqwest::Client;
 use serde::Deserialize;
@@ -28,6 +30,7 @@ pub struct SignalChannel {
     allowed_from: Vec<String>,
     ignore_attachments: bool,
     ignore_stories: bool,
+    transcription: Option<crate::config::TranscriptionConfig>,
 }
 
 // ── signal-cli SSE event JSON shapes ────────────────────────────
@@ -87,9 +90,18 @@ impl SignalChannel {
             allowed_from,
             ignore_attachments,
             ignore_stories,
+            transcription: None,
         }
     }
 
+    /// Configure transcription of incoming voice notes.
+    pub fn with_transcription(mut self, config: crate::config::TranscriptionConfig) -> Self {
+        if config.enabled {
+            self.transcription = Some(config);
+        }
+        self
+    }
+
     fn http_client(&self) -> Client {
         let builder = Client::builder().connect_timeout(Duration::from_secs(10));
         let builder = crate::config::apply_runtime_proxy_to_builder(builder, "channel.signal");
@@ -215,8 +227,12 @@ impl SignalChannel {
         Ok(parsed.get("result").cloned())
     }
 
-    /// Process a single SSE envelope, returning a ChannelMessage if valid.
-    fn process_envelope(&self, envelope: &Envelope) -> Option<ChannelMessage> {
+    /// Apply the story, attachment, allowlist and group filters, returning
+    /// the data message and its sender when the envelope should be handled.
+    fn accepted_data_message<'a>(
+        &self,
+        envelope: &'a Envelope,
+    ) -> Option<(&'a DataMessage, String)> {
         // Skip story messages when configured
         if self.ignore_stories && envelope.story_message.is_some() {
             return None;
@@ -232,7 +248,6 @@ impl SignalChannel {
             }
         }
 
-        let text = data_msg.message.as_deref().filter(|t| !t.is_empty())?;
         let sender = Self::sender(envelope)?;
 
         if !self.is_sender_allowed(&sender) {
@@ -243,6 +258,100 @@ impl SignalChannel {
             return None;
         }
 
+        Some((data_msg, sender))
+    }
+
+    /// Process a single SSE envelope, returning a ChannelMessage if valid.
+    fn process_envelope(&self, envelope: &Envelope) -> Option<ChannelMessage> {
+        let (data_msg, sender) = self.accepted_data_message(envelope)?;
+        let text = data_msg.message.as_deref().filter(|t| !t.is_empty())?;
+        Some(self.build_message(envelope, data_msg, sender, text.to_string(), false))
+    }
+
+    /// Transcribe an attachment-only voice note into a message.
+    async fn process_voice_envelope(&self, envelope: &Envelope) -> Option<ChannelMessage> {
+        let config = self.transcription.as_ref()?;
+        let (data_msg, sender) = self.accepted_data_message(envelope)?;
+        if data_msg.message.as_deref().is_some_and(|t| !t.is_empty()) {
+            return None;
+        }
+        let attachment = voice_note_attachment(data_msg)?;
+        let id = attachment.get("id")?.as_str()?;
+
+        let target = self.reply_target(data_msg, &sender);
+        let audio = match self.fetch_attachment(id, &target).await {
+            Ok(audio) => audio,
+            Err(e) => {
+                tracing::warn!("Signal voice note download failed: {e}");
+                return None;
+            }
+        };
+
+        let file_name = voice_note_file_name(attachment);
+        let text = match crate::channels::transcription::transcribe_audio(audio, &file_name, config)
+            .await
+        {
+            Ok(text) if !text.trim().is_empty() => text,
+            Ok(_) => {
+                tracing::info!("Signal voice transcription returned empty text, skipping");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Signal voice transcription failed: {e}");
+                return None;
+            }
+        };
+
+        Some(self.build_message(envelope, data_msg, sender, format!("[Voice] {text}"), true))
+    }
+
+    /// Fetch attachment bytes from signal-cli (`getAttachment` returns base64).
+    async fn fetch_attachment(&self, id: &str, target: &str) -> anyhow::Result<Vec<u8>> {
+        let mut params = self.target_params(target);
+        params["id"] = serde_json::Value::String(id.to_string());
+
+        let result = self
+            .rpc_request("getAttachment", params)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("getAttachment returned no result"))?;
+        let data = result
+            .get("data")
+            .and_then(serde_json::Value::as_str)
+            .or_else(|| result.as_str())
+            .ok_or_else(|| anyhow::anyhow!("getAttachment result has no data"))?;
+        Ok(base64::engine::general_purpose::STANDARD.decode(data)?)
+    }
+
+    /// JSON-RPC `recipient`/`groupId` + `account` params for a reply target.
+    fn target_params(&self, recipient: &str) -> serde_json::Value {
+        match Self::parse_recipient_target(recipient) {
+            RecipientTarget::Direct(number) => serde_json::json!({
+                "recipient": [number],
+                "account": &self.account,
+            }),
+            RecipientTarget::Group(group_id) => serde_json::json!({
+                "groupId": group_id,
+                "account": &self.account,
+            }),
+        }
+    }
+
+    /// Text message, or else a transcribed voice note.
+    async fn next_message(&self, envelope: &Envelope) -> Option<ChannelMessage> {
+        match self.process_envelope(envelope) {
+            Some(msg) => Some(msg),
+            None => self.process_voice_envelope(envelope).await,
+        }
+    }
+
+    fn build_message(
+        &self,
+        envelope: &Envelope,
+        data_msg: &DataMessage,
+        sender: String,
+        content: String,
+        voice: bool,
+    ) -> ChannelMessage {
         let target = self.reply_target(data_msg, &sender);
 
         let timestamp = data_msg
@@ -258,16 +367,51 @@ impl SignalChannel {
                 .unwrap_or(u64::MAX)
             });
 
-        Some(ChannelMessage {
+        ChannelMessage {
             id: format!("sig_{timestamp}"),
-            sender: sender.clone(),
+            sender,
             reply_target: target,
-            content: text.to_string(),
+            content,
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
-        })
+            group: None,
+            voice,
+        }
+    }
+}
+
+/// First audio attachment of a message (Signal sends voice notes as
+/// attachment-only messages).
+fn voice_note_attachment(data_msg: &DataMessage) -> Option<&serde_json::Value> {
+    data_msg.attachments.as_ref()?.iter().find(|att| {
+        att.get("contentType")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|ct| ct.starts_with("audio/"))
+    })
+}
+
+/// File name with an extension the transcription API accepts. Signal voice
+/// notes are AAC in an MP4 container and usually arrive without a name.
+fn voice_note_file_name(attachment: &serde_json::Value) -> String {
+    if let Some(name) = attachment
+        .get("filename")
+        .and_then(serde_json::Value::as_str)
+        .filter(|name| name.contains('.'))
+    {
+        return name.to_string();
     }
+    let extension = match attachment
+        .get("contentType")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("")
+    {
+        "audio/ogg" => "ogg",
+        "audio/mpeg" => "mp3",
+        "audio/wav" | "audio/x-wav" => "wav",
+        _ => "m4a",
+    };
+    format!("voice-note.{extension}")
 }
 
 #[async_trait]
@@ -277,18 +421,31 @@ impl Channel for SignalChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let params = match Self::parse_recipient_target(&message.recipient) {
-            RecipientTarget::Direct(number) => serde_json::json!({
-                "recipient": [number],
-                "message": &message.content,
-                "account": &self.account,
-            }),
-            RecipientTarget::Group(group_id) => serde_json::json!({
-                "groupId": group_id,
-                "message": &message.content,
-                "account": &self.account,
-            }),
-        };
+        let mut params = self.target_params(&message.recipient);
+        params["message"] = serde_json::Value::String(message.content.clone());
+
+        self.rpc_request("send", params).await?;
+        Ok(())
+    }
+
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    async fn send_voice_reply(
+        &self,
+        message: &SendMessage,
+        audio: &SpeechAudio,
+    ) -> anyhow::Result<()> {
+        // signal-cli accepts inline attachments as data URIs.
+        let attachment = format!(
+            "data:{};filename={};base64,{}",
+            audio.format.mime_type(),
+            audio.file_name(),
+            base64::engine::general_purpose::STANDARD.encode(&audio.bytes)
+        );
+        let mut params = self.target_params(&message.recipient);
+        params["attachments"] = serde_json::json!([attachment]);
 
         self.rpc_request("send", params).await?;
         Ok(())
@@ -369,7 +526,7 @@ impl Channel for SignalChannel {
                             match serde_json::from_str::<SseEnvelope>(&current_data) {
                                 Ok(sse) => {
                                     if let Some(ref envelope) = sse.envelope {
-                                        if let Some(msg) = self.process_envelope(envelope) {
+                                        if let Some(msg) = self.next_message(envelope).await {
                                             if tx.send(msg).await.is_err() {
                                                 return Ok(());
                                             }
@@ -396,7 +553,7 @@ impl Channel for SignalChannel {
                 match serde_json::from_str::<SseEnvelope>(&current_data) {
                     Ok(sse) => {
                         if let Some(ref envelope) = sse.envelope {
-                            if let Some(msg) = self.process_envelope(envelope) {
+                            if let Some(msg) = self.next_message(envelope).await {
                                 let _ = tx.send(msg).await;
                             }
                         }
@@ -427,16 +584,7 @@ impl Channel for SignalChannel {
     }
 
     async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
-        let params = match Self::parse_recipient_target(recipient) {
-            RecipientTarget::Direct(number) => serde_json::json!({
-                "recipient": [number],
-                "account": &self.account,
-            }),
-            RecipientTarget::Group(group_id) => serde_json::json!({
-                "groupId": group_id,
-                "account": &self.account,
-            }),
-        };
+        let params = self.target_params(recipient);
         self.rpc_request("sendTyping", params).await?;
         Ok(())
     }
@@ -489,6 +637,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn voice_note_attachment_picks_audio_and_names_it() {
+        let data_msg = DataMessage {
+            message: None,
+            timestamp: Some(1_700_000_000_000),
+            group_info: None,
+            attachments: Some(vec![
+                serde_json::json!({ "id": "img", "contentType": "image/png" }),
+                serde_json::json!({ "id": "vn", "contentType": "audio/aac", "filename": null }),
+            ]),
+        };
+        let attachment = voice_note_attachment(&data_msg).unwrap();
+        assert_eq!(attachment["id"], "vn");
+        assert_eq!(voice_note_file_name(attachment), "voice-note.m4a");
+        assert_eq!(
+            voice_note_file_name(&serde_json::json!({ "contentType": "audio/ogg" })),
+            "voice-note.ogg"
+        );
+        assert_eq!(
+            voice_note_file_name(&serde_json::json!({ "filename": "memo.mp3" })),
+            "memo.mp3"
+        );
+    }
+
+    #[test]
+    fn target_params_cover_direct_and_group() {
+        let ch = make_channel();
+        let direct = ch.target_params("+1111111111");
+        assert_eq!(direct["recipient"][0], "+1111111111");
+        assert_eq!(direct["account"], "+1234567890");
+        let group = ch.target_params("group:abc");
+        assert_eq!(group["groupId"], "abc");
+        assert!(group.get("recipient").is_none());
+    }
+
     #[test]
     fn creates_with_correct_fields() {
         let ch = make_channel();
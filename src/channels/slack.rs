@@ -1,13 +1,35 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::markup::{render_markdown, split_markdown, MarkupFormat};
+use super::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
+use tokio_tungstenite::tungstenite::Message;
 
-/// Slack channel — polls conversations.history via Web API
+/// Max age of a signed Events API request (Slack recommends 5 minutes).
+const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+/// Recent message keys remembered to drop duplicate deliveries.
+const SEEN_CAPACITY: usize = 512;
+
+/// Longest `text` Slack displays in full; longer messages are split.
+const SLACK_MAX_MESSAGE_LENGTH: usize = 4000;
+
+/// Slack channel. Receives events over Socket Mode when an app token is
+/// configured, via the gateway's Events API route when a signing secret is
+/// configured, and otherwise by polling `conversations.history`.
 pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    app_token: Option<String>,
+    events_api: bool,
+    /// Keys of recently delivered messages. `message` and `app_mention`
+    /// fire for the same post, and Slack retries unacknowledged events.
+    seen: Mutex<(VecDeque<String>, HashSet<String>)>,
 }
 
 impl SlackChannel {
@@ -16,13 +38,76 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
+            app_token: None,
+            events_api: false,
+            seen: Mutex::new((VecDeque::new(), HashSet::new())),
         }
     }
 
+    /// Receive events over Socket Mode with this app-level token (`xapp-...`).
+    pub fn with_app_token(mut self, app_token: Option<String>) -> Self {
+        self.app_token = app_token
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        self
+    }
+
+    /// Receive events through the gateway's `/slack/events` route instead of polling.
+    pub fn with_events_api(mut self, enabled: bool) -> Self {
+        self.events_api = enabled;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
 
+    async fn post_message(
+        &self,
+        channel: &str,
+        thread_ts: Option<&str>,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": text
+        });
+
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/chat.postMessage")
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+        }
+
+        // Slack returns 200 for most app-level errors; check JSON "ok" field
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack chat.postMessage failed: {err}");
+        }
+
+        Ok(())
+    }
+
     /// Check if a Slack user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -158,56 +243,377 @@ impl SlackChannel {
         channels.dedup();
         Ok(channels)
     }
-}
 
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
+    /// Record a delivery; false if the key was already seen recently.
+    fn first_delivery(&self, key: &str) -> bool {
+        let mut guard = self.seen.lock();
+        let (order, keys) = &mut *guard;
+        if !keys.insert(key.to_string()) {
+            return false;
+        }
+        order.push_back(key.to_string());
+        if order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = order.pop_front() {
+                keys.remove(&oldest);
+            }
+        }
+        true
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let mut body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
+    fn in_scope(&self, channel_id: &str) -> bool {
+        self.configured_channel_id()
+            .is_none_or(|scoped| scoped == channel_id)
+    }
 
-        if let Some(ref ts) = message.thread_ts {
-            body["thread_ts"] = serde_json::json!(ts);
+    /// Remove `<@BOT>` mentions so the agent sees only the request.
+    fn strip_bot_mention(text: &str, bot_user_id: &str) -> String {
+        if bot_user_id.is_empty() {
+            return text.trim().to_string();
         }
+        text.replace(&format!("<@{bot_user_id}>"), "")
+            .trim()
+            .to_string()
+    }
 
-        let resp = self
-            .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
+    /// Room metadata for channel and multi-person DM messages; `None` for
+    /// 1:1 DMs (`D…` channel IDs).
+    fn group_context(
+        msg: &serde_json::Value,
+        channel_id: &str,
+        channel_type: Option<&str>,
+        raw_text: &str,
+        bot_user_id: &str,
+    ) -> Option<GroupContext> {
+        if channel_type == Some("im") || channel_id.starts_with('D') {
+            return None;
+        }
+        let has_bot = !bot_user_id.is_empty();
+        let mentioned = has_bot && raw_text.contains(&format!("<@{bot_user_id}>"));
+        let replied_to_bot =
+            has_bot && msg.get("parent_user_id").and_then(|u| u.as_str()) == Some(bot_user_id);
+        let profile = msg.get("user_profile");
+        let sender_name = ["display_name", "real_name"]
+            .iter()
+            .find_map(|field| {
+                profile
+                    .and_then(|p| p.get(*field))
+                    .and_then(|n| n.as_str())
+                    .filter(|n| !n.is_empty())
+            })
+            .or_else(|| msg.get("user").and_then(|u| u.as_str()))
+            .unwrap_or("unknown")
+            .to_string();
+
+        Some(GroupContext {
+            room: channel_id.to_string(),
+            sender_name,
+            mentioned,
+            replied_to_bot,
+        })
+    }
 
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+    /// Turn an Events API `event_callback` payload into a channel message.
+    ///
+    /// Handles `message` (including thread replies, DMs and edits) and
+    /// `app_mention`. Bot posts, other subtypes and duplicates are dropped.
+    pub fn parse_event_callback(&self, payload: &serde_json::Value) -> Option<ChannelMessage> {
+        if payload.get("type").and_then(|t| t.as_str()) != Some("event_callback") {
+            return None;
+        }
+        let event = payload.get("event")?;
+        let bot_user_id = payload
+            .get("authorizations")
+            .and_then(|a| a.get(0))
+            .and_then(|a| a.get("user_id"))
+            .and_then(|u| u.as_str())
+            .unwrap_or("");
 
-        if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+        let event_type = event.get("type").and_then(|t| t.as_str())?;
+        if event_type != "message" && event_type != "app_mention" {
+            return None;
+        }
+        let channel_id = event.get("channel").and_then(|c| c.as_str())?;
+
+        // Edits carry the new message under `message`.
+        let (msg, edited) = match event.get("subtype").and_then(|s| s.as_str()) {
+            None | Some("thread_broadcast" | "file_share") => (event, false),
+            Some("message_changed") => (event.get("message")?, true),
+            Some(_) => return None,
+        };
+        if msg.get("bot_id").is_some() {
+            return None;
+        }
+        let user = msg.get("user").and_then(|u| u.as_str())?;
+        if user == bot_user_id {
+            return None;
+        }
+        if !self.in_scope(channel_id) {
+            return None;
+        }
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+            return None;
         }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
+        let ts = msg.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+        let raw_text = msg.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        let text = Self::strip_bot_mention(raw_text, bot_user_id);
+        if text.is_empty() {
+            return None;
+        }
+        let mut group = Self::group_context(
+            msg,
+            channel_id,
+            event.get("channel_type").and_then(|t| t.as_str()),
+            raw_text,
+            bot_user_id,
+        );
+        if let Some(group) = group.as_mut() {
+            group.mentioned |= event_type == "app_mention";
+        }
+
+        let id = if edited {
+            let edit_ts = event.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+            format!("slack_{channel_id}_{ts}_edit_{edit_ts}")
+        } else {
+            format!("slack_{channel_id}_{ts}")
+        };
+        if !self.first_delivery(&id) {
+            return None;
+        }
+
+        Some(ChannelMessage {
+            id,
+            sender: user.to_string(),
+            reply_target: channel_id.to_string(),
+            content: if edited {
+                format!("[edited] {text}")
+            } else {
+                text
+            },
+            channel: "slack".to_string(),
+            timestamp: unix_now(),
+            thread_ts: Self::inbound_thread_ts(msg, ts),
+            group,
+            voice: false,
+        })
+    }
+
+    /// Turn an interactive `block_actions` payload (button click or menu
+    /// selection) into a channel message whose content is the chosen value.
+    pub fn parse_interaction(&self, payload: &serde_json::Value) -> Option<ChannelMessage> {
+        if payload.get("type").and_then(|t| t.as_str()) != Some("block_actions") {
+            return None;
+        }
+        let user = payload
+            .get("user")
+            .and_then(|u| u.get("id"))
+            .and_then(|u| u.as_str())?;
+        let channel_id = payload
+            .get("channel")
+            .and_then(|c| c.get("id"))
+            .or_else(|| payload.get("container").and_then(|c| c.get("channel_id")))
+            .and_then(|c| c.as_str())?;
+        if !self.in_scope(channel_id) {
+            return None;
+        }
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring interaction from unauthorized user: {user}");
+            return None;
+        }
+
+        let action = payload.get("actions").and_then(|a| a.get(0))?;
+        let value = action
+            .get("value")
+            .or_else(|| action.get("selected_option").and_then(|o| o.get("value")))
+            .or_else(|| action.get("text").and_then(|t| t.get("text")))
+            .or_else(|| action.get("action_id"))
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())?;
+        let action_ts = action
+            .get("action_ts")
+            .and_then(|t| t.as_str())
+            .unwrap_or("");
+
+        let id = format!("slack_{channel_id}_action_{action_ts}");
+        if !self.first_delivery(&id) {
+            return None;
+        }
+
+        let thread_ts = payload.get("message").and_then(|m| {
+            let ts = m.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+            Self::inbound_thread_ts(m, ts)
+        });
+        // A button click is always addressed to the bot.
+        let group =
+            Self::group_context(payload, channel_id, None, "", "").map(|group| GroupContext {
+                mentioned: true,
+                ..group
+            });
+
+        Some(ChannelMessage {
+            id,
+            sender: user.to_string(),
+            reply_target: channel_id.to_string(),
+            content: value.to_string(),
+            channel: "slack".to_string(),
+            timestamp: unix_now(),
+            thread_ts,
+            group,
+            voice: false,
+        })
+    }
+
+    /// Ask Slack for a Socket Mode WebSocket URL.
+    async fn open_socket_url(&self, app_token: &str) -> anyhow::Result<String> {
+        let resp: serde_json::Value = self
+            .http_client()
+            .post("https://slack.com/api/apps.connections.open")
+            .bearer_auth(app_token)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if resp.get("ok") != Some(&serde_json::Value::Bool(true)) {
+            let err = resp
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack apps.connections.open failed: {err}");
         }
+        resp.get("url")
+            .and_then(|u| u.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Slack apps.connections.open returned no url"))
+    }
+
+    /// Socket Mode: acknowledge every envelope, forward events and button
+    /// clicks. Returns when Slack asks for a reconnect; the channel
+    /// supervisor then opens a fresh connection.
+    async fn listen_socket_mode(
+        &self,
+        app_token: &str,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let url = self.open_socket_url(app_token).await?;
+        let (ws_stream, _) = tokio_tungstenite::connect_async(&url).await?;
+        let (mut write, mut read) = ws_stream.split();
+        tracing::info!("Slack: connected via Socket Mode");
+
+        while let Some(frame) = read.next().await {
+            let text = match frame? {
+                Message::Text(t) => t,
+                Message::Ping(data) => {
+                    write.send(Message::Pong(data)).await?;
+                    continue;
+                }
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(envelope) = serde_json::from_str::<serde_json::Value>(text.as_ref()) else {
+                continue;
+            };
 
+            // Slack redelivers envelopes that are not acknowledged promptly.
+            if let Some(envelope_id) = envelope.get("envelope_id").and_then(|e| e.as_str()) {
+                let ack = serde_json::json!({ "envelope_id": envelope_id });
+                write.send(Message::Text(ack.to_string().into())).await?;
+            }
+
+            let payload = envelope.get("payload").unwrap_or(&serde_json::Value::Null);
+            let message = match envelope.get("type").and_then(|t| t.as_str()) {
+                Some("events_api") => self.parse_event_callback(payload),
+                Some("interactive") => self.parse_interaction(payload),
+                Some("disconnect") => {
+                    let reason = envelope
+                        .get("reason")
+                        .and_then(|r| r.as_str())
+                        .unwrap_or("unknown");
+                    tracing::info!("Slack: Socket Mode disconnect requested ({reason})");
+                    break;
+                }
+                _ => None,
+            };
+            if let Some(message) = message {
+                if tx.send(message).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Verify a Slack request signature.
+///
+/// `X-Slack-Signature` is `v0=` + hex(HMAC-SHA256(secret, `v0:{timestamp}:{body}`)),
+/// and `X-Slack-Request-Timestamp` must be within five minutes of `now`.
+pub fn verify_slack_signature(
+    secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+) -> bool {
+    let Ok(ts) = timestamp.trim().parse::<i64>() else {
+        return false;
+    };
+    if (now - ts).abs() > SIGNATURE_TOLERANCE_SECS {
+        return false;
+    }
+    let Some(Ok(provided)) = signature.trim().strip_prefix("v0=").map(hex::decode) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{ts}:").as_bytes());
+    mac.update(body);
+    mac.verify_slice(&provided).is_ok()
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        for chunk in split_markdown(&message.content, SLACK_MAX_MESSAGE_LENGTH) {
+            self.post_message(
+                &message.recipient,
+                message.thread_ts.as_deref(),
+                &render_markdown(&chunk, MarkupFormat::SlackMrkdwn),
+            )
+            .await?;
+        }
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if let Some(ref app_token) = self.app_token {
+            return self.listen_socket_mode(app_token, tx).await;
+        }
+        if self.events_api {
+            // Events arrive through the gateway's /slack/events route.
+            tracing::info!(
+                "Slack channel active (Events API mode). \
+                Point your Slack app's Request URL to the gateway's /slack/events endpoint."
+            );
+            loop {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }
+        }
+
         let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
         let scoped_channel = self.configured_channel_id();
         let mut discovered_channels: Vec<String> = Vec::new();
@@ -336,11 +742,10 @@ impl Channel for SlackChannel {
                             reply_target: channel_id.clone(),
                             content: text.to_string(),
                             channel: "slack".to_string(),
-                            timestamp: std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs(),
+                            timestamp: unix_now(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            group: Self::group_context(msg, &channel_id, None, text, &bot_user_id),
+                            voice: false,
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -525,6 +930,128 @@ mod tests {
         assert_eq!(thread_ts.as_deref(), Some("123.001"));
     }
 
+    fn event_callback(event: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "type": "event_callback",
+            "authorizations": [{"user_id": "UBOT"}],
+            "event": event
+        })
+    }
+
+    fn sign(secret: &str, ts: i64, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("v0:{ts}:").as_bytes());
+        mac.update(body);
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn verify_slack_signature_checks_hmac_and_age() {
+        let body = br#"{"type":"event_callback"}"#;
+        let now = 1_700_000_000;
+        let sig = sign("secret", now, body);
+        let ts = now.to_string();
+        assert!(verify_slack_signature("secret", &ts, body, &sig, now + 10));
+        assert!(!verify_slack_signature("other", &ts, body, &sig, now));
+        assert!(!verify_slack_signature("secret", &ts, b"{}", &sig, now));
+        assert!(!verify_slack_signature(
+            "secret",
+            &ts,
+            body,
+            &sig,
+            now + 301
+        ));
+        assert!(!verify_slack_signature("secret", &ts, body, "", now));
+    }
+
+    #[test]
+    fn with_app_token_ignores_blank_values() {
+        let ch =
+            SlackChannel::new("xoxb-fake".into(), None, vec![]).with_app_token(Some("  ".into()));
+        assert!(ch.app_token.is_none());
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![])
+            .with_app_token(Some("xapp-1".into()));
+        assert_eq!(ch.app_token.as_deref(), Some("xapp-1"));
+    }
+
+    #[test]
+    fn parse_event_callback_handles_messages_and_mentions_once() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let reply = event_callback(serde_json::json!({
+            "type": "message",
+            "channel": "C1",
+            "user": "U1",
+            "text": "<@UBOT> status?",
+            "ts": "100.2",
+            "thread_ts": "100.1"
+        }));
+        let msg = ch.parse_event_callback(&reply).unwrap();
+        assert_eq!(msg.id, "slack_C1_100.2");
+        assert_eq!(msg.content, "status?");
+        assert_eq!(msg.reply_target, "C1");
+        assert_eq!(msg.thread_ts.as_deref(), Some("100.1"));
+        let group = msg.group.expect("channel messages carry room context");
+        assert_eq!(group.room, "C1");
+        assert_eq!(group.sender_name, "U1");
+        assert!(group.mentioned);
+
+        // The matching app_mention for the same post is a duplicate.
+        let mut mention = reply.clone();
+        mention["event"]["type"] = "app_mention".into();
+        assert!(ch.parse_event_callback(&mention).is_none());
+    }
+
+    #[test]
+    fn parse_event_callback_handles_edits_and_skips_bots() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["U1".into()]);
+        let edit = event_callback(serde_json::json!({
+            "type": "message",
+            "subtype": "message_changed",
+            "channel": "D1",
+            "ts": "200.5",
+            "message": {"user": "U1", "text": "fixed typo", "ts": "200.1"}
+        }));
+        let msg = ch.parse_event_callback(&edit).unwrap();
+        assert_eq!(msg.content, "[edited] fixed typo");
+        assert_eq!(msg.id, "slack_D1_200.1_edit_200.5");
+        assert!(msg.group.is_none(), "1:1 DMs are not rooms");
+
+        for event in [
+            serde_json::json!({"type": "message", "channel": "C1", "user": "U1", "bot_id": "B1", "text": "hi", "ts": "1.0"}),
+            serde_json::json!({"type": "message", "channel": "C1", "user": "UBOT", "text": "hi", "ts": "2.0"}),
+            serde_json::json!({"type": "message", "subtype": "channel_join", "channel": "C1", "user": "U1", "text": "joined", "ts": "3.0"}),
+            serde_json::json!({"type": "message", "channel": "C1", "user": "U2", "text": "hi", "ts": "4.0"}),
+            serde_json::json!({"type": "reaction_added", "user": "U1"}),
+        ] {
+            assert!(ch.parse_event_callback(&event_callback(event)).is_none());
+        }
+    }
+
+    #[test]
+    fn parse_event_callback_respects_channel_scope() {
+        let ch = SlackChannel::new("xoxb-fake".into(), Some("C1".into()), vec!["*".into()]);
+        let other = event_callback(serde_json::json!({
+            "type": "message", "channel": "C2", "user": "U1", "text": "hi", "ts": "1.0"
+        }));
+        assert!(ch.parse_event_callback(&other).is_none());
+    }
+
+    #[test]
+    fn parse_interaction_uses_button_value_and_message_thread() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": {"id": "U1"},
+            "channel": {"id": "C1"},
+            "message": {"ts": "300.1"},
+            "actions": [{"action_id": "approve_btn", "value": "approve", "action_ts": "300.9"}]
+        });
+        let msg = ch.parse_interaction(&payload).unwrap();
+        assert_eq!(msg.content, "approve");
+        assert_eq!(msg.thread_ts.as_deref(), Some("300.1"));
+        assert!(ch.parse_interaction(&payload).is_none());
+    }
+
     #[test]
     fn inbound_thread_ts_none_when_ts_missing() {
         let msg = serde_json::json!({});
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::markup::{render_markdown, split_markdown, MarkupFormat};
+use super::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
+use super::tts::SpeechAudio;
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -36,59 +38,16 @@ enum IncomingAttachmentKind {
 const TELEGRAM_BIND_COMMAND: &str = "/bind";
 
 /// Split a message into chunks that respect Telegram's 4096 character limit.
-/// Tries to split at word boundaries when possible, and handles continuation.
-/// The effective per-chunk limit is reduced to leave room for continuation markers.
+/// Chunks break between Markdown blocks where possible, and the per-chunk
+/// limit is reduced to leave room for continuation markers.
 fn split_message_for_telegram(message: &str) -> Vec<String> {
     if message.chars().count() <= TELEGRAM_MAX_MESSAGE_LENGTH {
         return vec![message.to_string()];
     }
-
-    let mut chunks = Vec::new();
-    let mut remaining = message;
-    let chunk_limit = TELEGRAM_MAX_MESSAGE_LENGTH - TELEGRAM_CONTINUATION_OVERHEAD;
-
-    while !remaining.is_empty() {
-        // If the remainder fits within the full limit, take it all (last chunk
-        // or single chunk — continuation overhead is at most 14 chars).
-        if remaining.chars().count() <= TELEGRAM_MAX_MESSAGE_LENGTH {
-            chunks.push(remaining.to_string());
-            break;
-        }
-
-        // Find the byte offset for the Nth character boundary.
-        let hard_split = remaining
-            .char_indices()
-            .nth(chunk_limit)
-            .map_or(remaining.len(), |(idx, _)| idx);
-
-        let chunk_end = if hard_split == remaining.len() {
-            hard_split
-        } else {
-            // Try to find a good break point (newline, then space)
-            let search_area = &remaining[..hard_split];
-
-            // Prefer splitting at newline
-            if let Some(pos) = search_area.rfind('\n') {
-                // Don't split if the newline is too close to the start
-                if search_area[..pos].chars().count() >= chunk_limit / 2 {
-                    pos + 1
-                } else {
-                    // Try space as fallback
-                    search_area.rfind(' ').unwrap_or(hard_split) + 1
-                }
-            } else if let Some(pos) = search_area.rfind(' ') {
-                pos + 1
-            } else {
-                // Hard split at character boundary
-                hard_split
-            }
-        };
-
-        chunks.push(remaining[..chunk_end].to_string());
-        remaining = &remaining[chunk_end..];
-    }
-
-    chunks
+    split_markdown(
+        message,
+        TELEGRAM_MAX_MESSAGE_LENGTH - TELEGRAM_CONTINUATION_OVERHEAD,
+    )
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -530,6 +489,42 @@ impl TelegramChannel {
             .unwrap_or(false)
     }
 
+    /// Room metadata for group and supergroup messages; `None` in private chats.
+    fn group_context(
+        &self,
+        message: &serde_json::Value,
+        text: &str,
+        reply_target: &str,
+    ) -> Option<GroupContext> {
+        if !Self::is_group_message(message) {
+            return None;
+        }
+        let bot_username = self.bot_username.lock().clone();
+        let mentioned = bot_username
+            .as_deref()
+            .is_some_and(|bot| Self::contains_bot_mention(text, bot));
+        let replied_to_bot = message
+            .get("reply_to_message")
+            .and_then(|reply| reply.get("from"))
+            .and_then(|from| from.get("username"))
+            .and_then(serde_json::Value::as_str)
+            .zip(bot_username.as_deref())
+            .is_some_and(|(author, bot)| author.eq_ignore_ascii_case(bot));
+        let sender_name = message
+            .get("from")
+            .and_then(|from| from.get("username").or_else(|| from.get("first_name")))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+
+        Some(GroupContext {
+            room: reply_target.to_string(),
+            sender_name,
+            mentioned,
+            replied_to_bot,
+        })
+    }
+
     fn is_user_allowed(&self, username: &str) -> bool {
         let identity = Self::normalize_identity(username);
         self.allowed_users
@@ -920,6 +915,12 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             content = format!("{quote}\n\n{content}");
         }
 
+        let group = self.group_context(
+            message,
+            attachment.caption.as_deref().unwrap_or_default(),
+            &reply_target,
+        );
+
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
             sender: sender_identity,
@@ -931,6 +932,8 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
+            voice: false,
         })
     }
 
@@ -1036,6 +1039,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         } else {
             format!("[Voice] {text}")
         };
+        let group = self.group_context(message, &text, &reply_target);
 
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
@@ -1048,6 +1052,8 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
+            voice: true,
         })
     }
 
@@ -1192,6 +1198,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         } else {
             content
         };
+        let group = self.group_context(message, text, &reply_target);
 
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
@@ -1204,6 +1211,8 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
+            voice: false,
         })
     }
 
@@ -1253,166 +1262,6 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Ok(format!("data:image/jpeg;base64,{}", b64))
     }
 
-    /// Convert Markdown to Telegram HTML format.
-    /// Telegram HTML supports: <b>, <i>, <u>, <s>, <code>, <pre>, <a href="...">
-    /// This mirrors OpenClaw's markdownToTelegramHtml approach.
-    fn markdown_to_telegram_html(text: &str) -> String {
-        let lines: Vec<&str> = text.split('\n').collect();
-        let mut result_lines: Vec<String> = Vec::new();
-
-        for line in &lines {
-            let trimmed_line = line.trim_start();
-            if trimmed_line.starts_with("```") {
-                // Preserve fence lines so the second-pass block parser can consume them
-                // without interference from inline backtick handling.
-                result_lines.push(trimmed_line.to_string());
-                continue;
-            }
-
-            let mut line_out = String::new();
-
-            // Handle code blocks (``` ... ```) - handled at text level below
-            // Handle headers: ## Title → <b>Title</b>
-            let stripped = line.trim_start_matches('#');
-            let header_level = line.len() - stripped.len();
-            if header_level > 0 && line.starts_with('#') && stripped.starts_with(' ') {
-                let title = Self::escape_html(stripped.trim());
-                result_lines.push(format!("<b>{title}</b>"));
-                continue;
-            }
-
-            // Inline formatting
-            let mut i = 0;
-            let bytes = line.as_bytes();
-            let len = bytes.len();
-            while i < len {
-                // Bold: **text** or __text__
-                if i + 1 < len && bytes[i] == b'*' && bytes[i + 1] == b'*' {
-                    if let Some(end) = line[i + 2..].find("**") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<b>{inner}</b>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                if i + 1 < len && bytes[i] == b'_' && bytes[i + 1] == b'_' {
-                    if let Some(end) = line[i + 2..].find("__") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<b>{inner}</b>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                // Italic: *text* or _text_ (single)
-                if bytes[i] == b'*' && (i == 0 || bytes[i - 1] != b'*') {
-                    if let Some(end) = line[i + 1..].find('*') {
-                        if end > 0 {
-                            let inner = Self::escape_html(&line[i + 1..i + 1 + end]);
-                            line_out.push_str(&format!("<i>{inner}</i>"));
-                            i += 2 + end;
-                            continue;
-                        }
-                    }
-                }
-                // Inline code: `code`
-                if bytes[i] == b'`' && (i == 0 || bytes[i - 1] != b'`') {
-                    if let Some(end) = line[i + 1..].find('`') {
-                        let inner = Self::escape_html(&line[i + 1..i + 1 + end]);
-                        line_out.push_str(&format!("<code>{inner}</code>"));
-                        i += 2 + end;
-                        continue;
-                    }
-                }
-                // Markdown link: [text](url)
-                if bytes[i] == b'[' {
-                    if let Some(bracket_end) = line[i + 1..].find(']') {
-                        let text_part = &line[i + 1..i + 1 + bracket_end];
-                        let after_bracket = i + 1 + bracket_end + 1; // position after ']'
-                        if after_bracket < len && bytes[after_bracket] == b'(' {
-                            if let Some(paren_end) = line[after_bracket + 1..].find(')') {
-                                let url = &line[after_bracket + 1..after_bracket + 1 + paren_end];
-                                if url.starts_with("http://") || url.starts_with("https://") {
-                                    let text_html = Self::escape_html(text_part);
-                                    let url_html = Self::escape_html(url);
-                                    line_out.push_str(&format!(
-                                        "<a href=\"{url_html}\">{text_html}</a>"
-                                    ));
-                                    i = after_bracket + 1 + paren_end + 1;
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                }
-                // Strikethrough: ~~text~~
-                if i + 1 < len && bytes[i] == b'~' && bytes[i + 1] == b'~' {
-                    if let Some(end) = line[i + 2..].find("~~") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<s>{inner}</s>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                // Default: escape HTML entities
-                let ch = line[i..].chars().next().unwrap();
-                match ch {
-                    '<' => line_out.push_str("&lt;"),
-                    '>' => line_out.push_str("&gt;"),
-                    '&' => line_out.push_str("&amp;"),
-                    '"' => line_out.push_str("&quot;"),
-                    '\'' => line_out.push_str("&#39;"),
-                    _ => line_out.push(ch),
-                }
-                i += ch.len_utf8();
-            }
-            result_lines.push(line_out);
-        }
-
-        // Second pass: handle ``` code blocks across lines
-        let joined = result_lines.join("\n");
-        let mut final_out = String::with_capacity(joined.len());
-        let mut in_code_block = false;
-        let mut code_buf = String::new();
-
-        for line in joined.split('\n') {
-            let trimmed = line.trim();
-            if trimmed.starts_with("```") {
-                if !in_code_block {
-                    in_code_block = true;
-                    code_buf.clear();
-                } else {
-                    in_code_block = false;
-                    let escaped = code_buf.trim_end_matches('\n');
-                    // Telegram HTML parse mode supports <pre> and <code>, but not class attributes.
-                    final_out.push_str(&format!("<pre><code>{escaped}</code></pre>\n"));
-                    code_buf.clear();
-                }
-            } else if in_code_block {
-                code_buf.push_str(line);
-                code_buf.push('\n');
-            } else {
-                final_out.push_str(line);
-                final_out.push('\n');
-            }
-        }
-        if in_code_block && !code_buf.is_empty() {
-            final_out.push_str(&format!(
-                "<pre><code>{}</code></pre>\n",
-                code_buf.trim_end()
-            ));
-        }
-
-        final_out.trim_end_matches('\n').to_string()
-    }
-
-    fn escape_html(s: &str) -> String {
-        s.replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('"', "&quot;")
-            .replace('\'', "&#39;")
-    }
-
     async fn send_text_chunks(
         &self,
         message: &str,
@@ -1434,36 +1283,36 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 chunk.to_string()
             };
 
-            let mut markdown_body = serde_json::json!({
+            let mut html_body = serde_json::json!({
                 "chat_id": chat_id,
-                "text": Self::markdown_to_telegram_html(&text),
+                "text": render_markdown(&text, MarkupFormat::TelegramHtml),
                 "parse_mode": "HTML"
             });
 
             // Add message_thread_id for forum topic support
             if let Some(tid) = thread_id {
-                markdown_body["message_thread_id"] = serde_json::Value::String(tid.to_string());
+                html_body["message_thread_id"] = serde_json::Value::String(tid.to_string());
             }
 
-            let markdown_resp = self
+            let html_resp = self
                 .http_client()
                 .post(self.api_url("sendMessage"))
-                .json(&markdown_body)
+                .json(&html_body)
                 .send()
                 .await?;
 
-            if markdown_resp.status().is_success() {
+            if html_resp.status().is_success() {
                 if index < chunks.len() - 1 {
                     tokio::time::sleep(Duration::from_millis(100)).await;
                 }
                 continue;
             }
 
-            let markdown_status = markdown_resp.status();
-            let markdown_err = markdown_resp.text().await.unwrap_or_default();
+            let html_status = html_resp.status();
+            let html_err = html_resp.text().await.unwrap_or_default();
             tracing::warn!(
-                status = ?markdown_status,
-                "Telegram sendMessage with Markdown failed; retrying without parse_mode"
+                status = ?html_status,
+                "Telegram sendMessage with HTML failed; retrying without parse_mode"
             );
 
             let mut plain_body = serde_json::json!({
@@ -1486,9 +1335,9 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 let plain_status = plain_resp.status();
                 let plain_err = plain_resp.text().await.unwrap_or_default();
                 anyhow::bail!(
-                    "Telegram sendMessage failed (markdown {}: {}; plain {}: {})",
-                    markdown_status,
-                    markdown_err,
+                    "Telegram sendMessage failed (html {}: {}; plain {}: {})",
+                    html_status,
+                    html_err,
                     plain_status,
                     plain_err
                 );
@@ -2200,7 +2049,7 @@ impl Channel for TelegramChannel {
         let body = serde_json::json!({
             "chat_id": chat_id,
             "message_id": msg_id,
-            "text": Self::markdown_to_telegram_html(text),
+            "text": render_markdown(text, MarkupFormat::TelegramHtml),
             "parse_mode": "HTML",
         });
 
@@ -2215,7 +2064,7 @@ impl Channel for TelegramChannel {
             return Ok(());
         }
 
-        // Markdown failed — retry without parse_mode
+        // HTML failed — retry without parse_mode
         let plain_body = serde_json::json!({
             "chat_id": chat_id,
             "message_id": msg_id,
@@ -2307,18 +2156,16 @@ impl Channel for TelegramChannel {
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let mut offset: i64 = 0;
 
-        if self.mention_only {
-            let _ = self.get_bot_username().await;
-        }
+        // The bot username drives mention detection for `mention_only` and
+        // for group reply policies.
+        let _ = self.get_bot_username().await;
 
         tracing::info!("Telegram channel listening for messages...");
 
         loop {
-            if self.mention_only {
-                let missing_username = self.bot_username.lock().is_none();
-                if missing_username {
-                    let _ = self.get_bot_username().await;
-                }
+            let missing_username = self.bot_username.lock().is_none();
+            if missing_username {
+                let _ = self.get_bot_username().await;
             }
 
             let url = self.api_url("getUpdates");
@@ -2467,6 +2314,49 @@ Ensure only one `zeroclaw` process is using this bot token."
         }
         Ok(())
     }
+
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    async fn send_voice_reply(
+        &self,
+        message: &SendMessage,
+        audio: &SpeechAudio,
+    ) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = Self::parse_reply_target(&message.recipient);
+        // sendVoice only renders Ogg/Opus as a voice note; anything else goes out as audio.
+        let (method, field) = if audio.is_voice_note() {
+            ("sendVoice", "voice")
+        } else {
+            ("sendAudio", "audio")
+        };
+
+        let part = Part::bytes(audio.bytes.clone())
+            .file_name(audio.file_name())
+            .mime_str(audio.format.mime_type())?;
+        let mut form = Form::new()
+            .text("chat_id", chat_id.clone())
+            .part(field, part);
+        if let Some(tid) = thread_id {
+            form = form.text("message_thread_id", tid);
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url(method))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Telegram {method} failed: {err}");
+        }
+
+        tracing::info!("Telegram voice reply sent to {chat_id}");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2594,8 +2484,9 @@ mod tests {
 
     #[test]
     fn telegram_markdown_to_html_escapes_quotes_in_link_href() {
-        let rendered = TelegramChannel::markdown_to_telegram_html(
+        let rendered = render_markdown(
             "[click](https://example.com?q=\"x\"&a='b')",
+            MarkupFormat::TelegramHtml,
         );
         assert_eq!(
             rendered,
@@ -2605,7 +2496,7 @@ mod tests {
 
     #[test]
     fn telegram_markdown_to_html_escapes_quotes_in_plain_text() {
-        let rendered = TelegramChannel::markdown_to_telegram_html("say \"hi\" & <tag> 'ok'");
+        let rendered = render_markdown("say \"hi\" & <tag> 'ok'", MarkupFormat::TelegramHtml);
         assert_eq!(
             rendered,
             "say &quot;hi&quot; &amp; &lt;tag&gt; &#39;ok&#39;"
@@ -2614,8 +2505,9 @@ mod tests {
 
     #[test]
     fn telegram_markdown_to_html_code_block_drops_language_attribute() {
-        let rendered = TelegramChannel::markdown_to_telegram_html(
+        let rendered = render_markdown(
             "```rust\" onclick=\"alert(1)\nlet x = 1;\n```",
+            MarkupFormat::TelegramHtml,
         );
         assert_eq!(rendered, "<pre><code>let x = 1;</code></pre>");
         assert!(!rendered.contains("language-"));
@@ -3432,6 +3324,47 @@ mod tests {
         assert!(ch.parse_update_message(&update).is_none());
     }
 
+    #[test]
+    fn parse_update_message_tags_group_messages_with_room_context() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        {
+            let mut cache = ch.bot_username.lock();
+            *cache = Some("mybot".to_string());
+        }
+
+        let update = serde_json::json!({
+            "update_id": 11,
+            "message": {
+                "message_id": 45,
+                "text": "sounds good",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": -100_200_300, "type": "supergroup" },
+                "reply_to_message": {
+                    "message_id": 40,
+                    "text": "Deploy finished",
+                    "from": { "id": 1, "username": "MyBot", "is_bot": true }
+                }
+            }
+        });
+        let msg = ch.parse_update_message(&update).expect("group message");
+        let group = msg.group.expect("group context");
+        assert_eq!(group.room, "-100200300");
+        assert_eq!(group.sender_name, "alice");
+        assert!(!group.mentioned);
+        assert!(group.replied_to_bot);
+
+        let private = serde_json::json!({
+            "update_id": 12,
+            "message": {
+                "message_id": 46,
+                "text": "hi @mybot",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 555, "type": "private" }
+            }
+        });
+        assert!(ch.parse_update_message(&private).unwrap().group.is_none());
+    }
+
     #[test]
     fn parse_update_message_mention_only_group_strips_mention_and_drops_empty() {
         let ch = TelegramChannel::new("token".into(), vec!["*".into()], true);
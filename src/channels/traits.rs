@@ -12,6 +12,24 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Set when the message comes from a multi-party room rather than a
+    /// direct conversation with the bot.
+    pub group: Option<GroupContext>,
+    /// The user spoke this message: `content` is a transcribed voice note.
+    pub voice: bool,
+}
+
+/// Room metadata for messages received in group chats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupContext {
+    /// Stable room identifier (chat, channel or room ID) within the channel
+    pub room: String,
+    /// Sender display name used to attribute lines in the shared room history
+    pub sender_name: String,
+    /// The message @-mentions (or otherwise addresses) the bot
+    pub mentioned: bool,
+    /// The message replies to one of the bot's own messages
+    pub replied_to_bot: bool,
 }
 
 /// Message to send through a channel
@@ -142,6 +160,22 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Whether this channel can deliver synthesized speech via `send_voice_reply`.
+    fn supports_voice_replies(&self) -> bool {
+        false
+    }
+
+    /// Send synthesized speech to `message.recipient`, as a native voice note
+    /// when the platform accepts the audio format. `message.content` carries
+    /// the spoken text for captions or fallbacks.
+    async fn send_voice_reply(
+        &self,
+        _message: &SendMessage,
+        _audio: &super::tts::SpeechAudio,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support voice replies", self.name())
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +206,8 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                group: None,
+                voice: false,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -188,6 +224,8 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
 
         let cloned = message.clone();
@@ -244,6 +282,18 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn default_voice_reply_is_unsupported() {
+        let channel = DummyChannel;
+        let audio = super::super::tts::SpeechAudio::new(b"OggS".to_vec());
+
+        assert!(!channel.supports_voice_replies());
+        assert!(channel
+            .send_voice_reply(&SendMessage::new("hi", "bob"), &audio)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
@@ -0,0 +1,408 @@
+//! Text-to-speech for voice-note replies.
+//!
+//! The counterpart of [`super::transcription`]: when a user talks to the
+//! agent by voice, channels that support it answer with synthesized speech
+//! from an OpenAI-compatible `/audio/speech` endpoint or a local command
+//! such as Piper.
+
+use anyhow::{bail, Context, Result};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use super::markup::{render_markdown, MarkupFormat};
+use crate::config::{TtsConfig, TtsProvider, VoiceReplyMode};
+
+/// Identity preference (`/prefs set voice_replies always`) that overrides
+/// the configured reply mode for one user.
+pub const VOICE_REPLIES_PREFERENCE: &str = "voice_replies";
+
+/// Placeholder in `tts.command` arguments for the output file path.
+const OUTPUT_PLACEHOLDER: &str = "{output}";
+
+/// Maximum run time of the `command` backend.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Opus timestamps count 48 kHz samples regardless of the input rate.
+const OPUS_GRANULE_RATE: f64 = 48_000.0;
+
+/// Audio container, detected from the leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Ogg,
+    Mp3,
+    Wav,
+    Unknown,
+}
+
+impl AudioFormat {
+    fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"OggS") {
+            Self::Ogg
+        } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE") {
+            Self::Wav
+        } else if bytes.starts_with(b"ID3")
+            || (bytes.len() > 1 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0)
+        {
+            Self::Mp3
+        } else {
+            Self::Unknown
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Ogg => "audio/ogg",
+            Self::Mp3 => "audio/mpeg",
+            Self::Wav => "audio/wav",
+            Self::Unknown => "application/octet-stream",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ogg => "ogg",
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+            Self::Unknown => "bin",
+        }
+    }
+}
+
+/// Synthesized speech ready to upload.
+#[derive(Debug, Clone)]
+pub struct SpeechAudio {
+    pub bytes: Vec<u8>,
+    pub format: AudioFormat,
+}
+
+impl SpeechAudio {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let format = AudioFormat::detect(&bytes);
+        Self { bytes, format }
+    }
+
+    /// Ogg/Opus is what Telegram, WhatsApp and Discord play as a voice note;
+    /// other formats are sent as a regular audio file.
+    pub fn is_voice_note(&self) -> bool {
+        self.format == AudioFormat::Ogg && find(&self.bytes, b"OpusHead").is_some()
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("reply.{}", self.format.extension())
+    }
+
+    /// Playback length from the last Ogg page's granule position or the
+    /// WAV header. `None` for formats that need full decoding.
+    pub fn duration_secs(&self) -> Option<f64> {
+        match self.format {
+            AudioFormat::Ogg => ogg_opus_duration(&self.bytes),
+            AudioFormat::Wav => wav_duration(&self.bytes),
+            AudioFormat::Mp3 | AudioFormat::Unknown => None,
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn ogg_opus_duration(bytes: &[u8]) -> Option<f64> {
+    let head = find(bytes, b"OpusHead")?;
+    let pre_skip = u16::from_le_bytes(bytes.get(head + 10..head + 12)?.try_into().ok()?);
+    let last_page = bytes.windows(4).rposition(|window| window == b"OggS")?;
+    let granule = u64::from_le_bytes(bytes.get(last_page + 6..last_page + 14)?.try_into().ok()?);
+    #[allow(clippy::cast_precision_loss)]
+    let samples = granule.saturating_sub(u64::from(pre_skip)) as f64;
+    Some(samples / OPUS_GRANULE_RATE)
+}
+
+fn wav_duration(bytes: &[u8]) -> Option<f64> {
+    let byte_rate = u32::from_le_bytes(bytes.get(28..32)?.try_into().ok()?);
+    let data = find(bytes, b"data")?;
+    let data_len = u32::from_le_bytes(bytes.get(data + 4..data + 8)?.try_into().ok()?);
+    (byte_rate > 0).then(|| f64::from(data_len) / f64::from(byte_rate))
+}
+
+fn parse_voice_reply_mode(value: &str) -> Option<VoiceReplyMode> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" | "never" => Some(VoiceReplyMode::Off),
+        "voice" => Some(VoiceReplyMode::Voice),
+        "always" | "on" => Some(VoiceReplyMode::Always),
+        _ => None,
+    }
+}
+
+/// Effective mode: the user's preference, then the channel override, then
+/// the global `tts.reply`.
+pub fn voice_reply_mode(
+    config: &TtsConfig,
+    channel: &str,
+    user_preference: Option<&str>,
+) -> VoiceReplyMode {
+    user_preference
+        .and_then(parse_voice_reply_mode)
+        .or_else(|| config.channels.get(channel).copied())
+        .unwrap_or(config.reply)
+}
+
+/// Whether a reply on `channel` should also be spoken.
+pub fn wants_voice_reply(
+    config: &TtsConfig,
+    channel: &str,
+    inbound_voice: bool,
+    user_preference: Option<&str>,
+) -> bool {
+    config.enabled
+        && match voice_reply_mode(config, channel, user_preference) {
+            VoiceReplyMode::Off => false,
+            VoiceReplyMode::Voice => inbound_voice,
+            VoiceReplyMode::Always => true,
+        }
+}
+
+/// Text to speak for a Markdown reply: code blocks dropped, markup removed.
+/// `None` when nothing is left or the result exceeds `max_chars`.
+pub fn speech_text(reply: &str, max_chars: usize) -> Option<String> {
+    let mut prose = String::with_capacity(reply.len());
+    let mut in_code = false;
+    for line in reply.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code {
+            prose.push_str(line);
+            prose.push('\n');
+        }
+    }
+    let text = render_markdown(&prose, MarkupFormat::PlainText);
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > max_chars {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+/// Synthesize `text` with the configured backend.
+pub async fn synthesize_speech(text: &str, config: &TtsConfig) -> Result<SpeechAudio> {
+    let bytes = match config.provider {
+        TtsProvider::OpenAi => synthesize_openai(text, config).await?,
+        TtsProvider::Command => synthesize_command(text, config).await?,
+    };
+    if bytes.is_empty() {
+        bail!("Speech synthesis returned no audio");
+    }
+    Ok(SpeechAudio::new(bytes))
+}
+
+async fn synthesize_openai(text: &str, config: &TtsConfig) -> Result<Vec<u8>> {
+    let api_key = config
+        .api_key
+        .clone()
+        .filter(|key| !key.trim().is_empty())
+        .or_else(|| std::env::var("OPENAI_API_KEY").ok());
+
+    let client = crate::config::build_runtime_proxy_client("tts.openai");
+    let mut request = client.post(&config.api_url).json(&serde_json::json!({
+        "model": config.model,
+        "input": text,
+        "voice": config.voice,
+        "response_format": "opus",
+    }));
+    // Self-hosted compatible servers often run without a key.
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+
+    let resp = request
+        .send()
+        .await
+        .context("Failed to send speech request")?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body: serde_json::Value = resp.json().await.unwrap_or_default();
+        let error_msg = body["error"]["message"].as_str().unwrap_or("unknown error");
+        bail!("Speech API error ({}): {}", status, error_msg);
+    }
+
+    Ok(resp
+        .bytes()
+        .await
+        .context("Failed to read speech audio")?
+        .to_vec())
+}
+
+async fn synthesize_command(text: &str, config: &TtsConfig) -> Result<Vec<u8>> {
+    let Some((program, args)) = config.command.split_first() else {
+        bail!("tts.command is empty — set it to the speech program and its arguments");
+    };
+
+    let output_path =
+        std::env::temp_dir().join(format!("zeroclaw-tts-{}.audio", uuid::Uuid::new_v4()));
+    let output_arg = output_path.to_string_lossy();
+    let writes_file = args.iter().any(|arg| arg.contains(OUTPUT_PLACEHOLDER));
+
+    let mut child = tokio::process::Command::new(program)
+        .args(
+            args.iter()
+                .map(|arg| arg.replace(OUTPUT_PLACEHOLDER, &output_arg)),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start TTS command '{program}'"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may exit first; its status decides the outcome.
+        if let Err(e) = stdin.write_all(text.as_bytes()).await {
+            tracing::debug!("TTS command closed stdin early: {e}");
+        }
+    }
+
+    let result = tokio::time::timeout(COMMAND_TIMEOUT, child.wait_with_output()).await;
+    let audio = match result {
+        Err(_) => Err(anyhow::anyhow!(
+            "TTS command timed out after {}s",
+            COMMAND_TIMEOUT.as_secs()
+        )),
+        Ok(Err(e)) => Err(anyhow::Error::new(e).context("TTS command failed to run")),
+        Ok(Ok(output)) if !output.status.success() => Err(anyhow::anyhow!(
+            "TTS command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Ok(Ok(_)) if writes_file => tokio::fs::read(&output_path)
+            .await
+            .context("TTS command did not write the {output} file"),
+        Ok(Ok(output)) => Ok(output.stdout),
+    };
+    let _ = tokio::fs::remove_file(&output_path).await;
+    audio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ogg_opus(granule: u64, pre_skip: u16) -> Vec<u8> {
+        let mut bytes = b"OggS".to_vec();
+        bytes.extend_from_slice(&[0; 24]);
+        bytes.extend_from_slice(b"OpusHead\x01\x01");
+        bytes.extend_from_slice(&pre_skip.to_le_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(b"OggS\x00\x04");
+        bytes.extend_from_slice(&granule.to_le_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        bytes
+    }
+
+    #[test]
+    fn detects_formats_and_ogg_duration() {
+        let audio = SpeechAudio::new(ogg_opus(48_000 * 3 + 312, 312));
+        assert_eq!(audio.format, AudioFormat::Ogg);
+        assert!(audio.is_voice_note());
+        assert_eq!(audio.file_name(), "reply.ogg");
+        assert_eq!(audio.duration_secs(), Some(3.0));
+
+        assert_eq!(
+            SpeechAudio::new(b"ID3\x04rest".to_vec()).format,
+            AudioFormat::Mp3
+        );
+        assert_eq!(
+            SpeechAudio::new(b"hello".to_vec()).format,
+            AudioFormat::Unknown
+        );
+    }
+
+    #[test]
+    fn reads_wav_duration_from_header() {
+        let mut wav = b"RIFF\x00\x00\x00\x00WAVEfmt ".to_vec();
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 1, 0]);
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&64_000u32.to_le_bytes());
+        let audio = SpeechAudio::new(wav);
+        assert_eq!(audio.format, AudioFormat::Wav);
+        assert!(!audio.is_voice_note());
+        assert_eq!(audio.duration_secs(), Some(2.0));
+    }
+
+    #[test]
+    fn reply_mode_prefers_user_then_channel_then_default() {
+        let mut config = TtsConfig {
+            enabled: true,
+            ..TtsConfig::default()
+        };
+        config
+            .channels
+            .insert("discord".into(), VoiceReplyMode::Off);
+
+        assert!(wants_voice_reply(&config, "telegram", true, None));
+        assert!(!wants_voice_reply(&config, "telegram", false, None));
+        assert!(!wants_voice_reply(&config, "discord", true, None));
+        assert!(wants_voice_reply(&config, "discord", false, Some("always")));
+        assert!(!wants_voice_reply(&config, "telegram", true, Some("off")));
+        assert!(wants_voice_reply(&config, "telegram", true, Some("bogus")));
+
+        config.enabled = false;
+        assert!(!wants_voice_reply(
+            &config,
+            "telegram",
+            true,
+            Some("always")
+        ));
+    }
+
+    #[test]
+    fn speech_text_drops_code_and_markup() {
+        let reply = "**Valve 3** is open.\n\n```sh\nsystemctl status pump\n```\n\nSee [the log](https://x.io).";
+        assert_eq!(
+            speech_text(reply, 200).as_deref(),
+            Some("Valve 3 is open.\n\nSee the log (https://x.io).")
+        );
+        assert!(speech_text("```\nonly code\n```", 200).is_none());
+        assert!(speech_text("too long", 3).is_none());
+    }
+
+    #[tokio::test]
+    async fn command_backend_reads_output_file_or_stdout() {
+        let config = TtsConfig {
+            provider: TtsProvider::Command,
+            command: vec![
+                "sh".into(),
+                "-c".into(),
+                "cat > \"$0\"".into(),
+                "{output}".into(),
+            ],
+            ..TtsConfig::default()
+        };
+        let audio = synthesize_speech("ID3 spoken", &config).await.unwrap();
+        assert_eq!(audio.bytes, b"ID3 spoken");
+        assert_eq!(audio.format, AudioFormat::Mp3);
+
+        let config = TtsConfig {
+            provider: TtsProvider::Command,
+            command: vec!["cat".into()],
+            ..TtsConfig::default()
+        };
+        let audio = synthesize_speech("RIFF", &config).await.unwrap();
+        assert_eq!(audio.bytes, b"RIFF");
+
+        let config = TtsConfig {
+            provider: TtsProvider::Command,
+            command: vec!["sh".into(), "-c".into(), "echo boom >&2; exit 3".into()],
+            ..TtsConfig::default()
+        };
+        let err = synthesize_speech("x", &config).await.unwrap_err();
+        assert!(err.to_string().contains("boom"), "got: {err}");
+    }
+}
@@ -1,4 +1,5 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::tts::{AudioFormat, SpeechAudio};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -62,6 +63,169 @@ impl WhatsAppChannel {
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
         let mut messages = Vec::new();
 
+        for (msg, normalized_from) in self.authorized_messages(payload) {
+            // Extract text content (voice notes go through `transcribe_voice_notes`)
+            let content = if let Some(text_obj) = msg.get("text") {
+                text_obj
+                    .get("body")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("")
+                    .to_string()
+            } else {
+                // Could be image, audio, etc. — skip for now
+                tracing::debug!("WhatsApp: skipping non-text message from {normalized_from}");
+                continue;
+            };
+
+            if content.is_empty() {
+                continue;
+            }
+
+            messages.push(Self::build_message(msg, normalized_from, content, false));
+        }
+
+        messages
+    }
+
+    /// Download and transcribe the voice notes in a webhook payload.
+    /// Failures are logged and the note skipped.
+    pub async fn transcribe_voice_notes(
+        &self,
+        payload: &serde_json::Value,
+        config: &crate::config::TranscriptionConfig,
+    ) -> Vec<ChannelMessage> {
+        let mut messages = Vec::new();
+
+        for (msg, normalized_from) in self.authorized_messages(payload) {
+            let Some(audio) = msg.get("audio") else {
+                continue;
+            };
+            let Some(media_id) = audio.get("id").and_then(|i| i.as_str()) else {
+                continue;
+            };
+            let extension = match audio
+                .get("mime_type")
+                .and_then(|m| m.as_str())
+                .unwrap_or("audio/ogg")
+                .split(';')
+                .next()
+                .unwrap_or_default()
+            {
+                "audio/mpeg" => "mp3",
+                "audio/mp4" | "audio/aac" => "m4a",
+                _ => "ogg",
+            };
+
+            let data = match self.download_media(media_id).await {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("WhatsApp: voice note download failed: {e}");
+                    continue;
+                }
+            };
+            let file_name = format!("voice-note.{extension}");
+            let text = match super::transcription::transcribe_audio(data, &file_name, config).await
+            {
+                Ok(text) if !text.trim().is_empty() => text,
+                Ok(_) => {
+                    tracing::info!("WhatsApp: voice transcription returned empty text, skipping");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("WhatsApp: voice transcription failed: {e}");
+                    continue;
+                }
+            };
+
+            messages.push(Self::build_message(
+                msg,
+                normalized_from,
+                format!("[Voice] {text}"),
+                true,
+            ));
+        }
+
+        messages
+    }
+
+    /// Fetch media bytes: resolve the media ID to a short-lived URL, then download it.
+    async fn download_media(&self, media_id: &str) -> anyhow::Result<Vec<u8>> {
+        let url = format!("https://graph.facebook.com/v18.0/{media_id}");
+        ensure_https(&url)?;
+
+        let meta: serde_json::Value = self
+            .http_client()
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let media_url = meta
+            .get("url")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media {media_id} has no url"))?;
+        ensure_https(media_url)?;
+
+        let bytes = self
+            .http_client()
+            .get(media_url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Upload audio to the media endpoint and return its media ID.
+    async fn upload_media(&self, audio: &SpeechAudio) -> anyhow::Result<String> {
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/media",
+            self.endpoint_id
+        );
+        ensure_https(&url)?;
+
+        let part = reqwest::multipart::Part::bytes(audio.bytes.clone())
+            .file_name(audio.file_name())
+            .mime_str(audio.format.mime_type())?;
+        let form = reqwest::multipart::Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", audio.format.mime_type())
+            .part("file", part);
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp media upload failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp API error: {status}");
+        }
+
+        let body: serde_json::Value = resp.json().await?;
+        body.get("id")
+            .and_then(|i| i.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media upload returned no id"))
+    }
+
+    /// Walk the webhook structure and yield each message from an allowed
+    /// number, with the sender normalized to E.164.
+    fn authorized_messages<'a>(
+        &self,
+        payload: &'a serde_json::Value,
+    ) -> Vec<(&'a serde_json::Value, String)> {
+        let mut messages = Vec::new();
+
         // WhatsApp Cloud API webhook structure:
         // { "object": "whatsapp_business_account", "entry": [...] }
         let Some(entries) = payload.get("entry").and_then(|e| e.as_array()) else {
@@ -105,50 +269,44 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
-                    let content = if let Some(text_obj) = msg.get("text") {
-                        text_obj
-                            .get("body")
-                            .and_then(|b| b.as_str())
-                            .unwrap_or("")
-                            .to_string()
-                    } else {
-                        // Could be image, audio, etc. — skip for now
-                        tracing::debug!("WhatsApp: skipping non-text message from {from}");
-                        continue;
-                    };
-
-                    if content.is_empty() {
-                        continue;
-                    }
-
-                    // Get timestamp
-                    let timestamp = msg
-                        .get("timestamp")
-                        .and_then(|t| t.as_str())
-                        .and_then(|t| t.parse::<u64>().ok())
-                        .unwrap_or_else(|| {
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs()
-                        });
-
-                    messages.push(ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
-                        reply_target: normalized_from.clone(),
-                        sender: normalized_from,
-                        content,
-                        channel: "whatsapp".to_string(),
-                        timestamp,
-                        thread_ts: None,
-                    });
+                    messages.push((msg, normalized_from));
                 }
             }
         }
 
         messages
     }
+
+    fn build_message(
+        msg: &serde_json::Value,
+        normalized_from: String,
+        content: String,
+        voice: bool,
+    ) -> ChannelMessage {
+        // Get timestamp
+        let timestamp = msg
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse::<u64>().ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            });
+
+        ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            reply_target: normalized_from.clone(),
+            sender: normalized_from,
+            content,
+            channel: "whatsapp".to_string(),
+            timestamp,
+            thread_ts: None,
+            group: None,
+            voice,
+        }
+    }
 }
 
 #[async_trait]
@@ -202,6 +360,57 @@ impl Channel for WhatsAppChannel {
         Ok(())
     }
 
+    fn supports_voice_replies(&self) -> bool {
+        true
+    }
+
+    async fn send_voice_reply(
+        &self,
+        message: &SendMessage,
+        audio: &SpeechAudio,
+    ) -> anyhow::Result<()> {
+        // Ogg/Opus plays as a voice note; MP3 as an audio file. WAV is rejected by the API.
+        if !matches!(audio.format, AudioFormat::Ogg | AudioFormat::Mp3) {
+            anyhow::bail!("WhatsApp voice replies need Ogg/Opus or MP3 audio");
+        }
+        let media_id = self.upload_media(audio).await?;
+
+        let url = format!(
+            "https://graph.facebook.com/v18.0/{}/messages",
+            self.endpoint_id
+        );
+        let to = message
+            .recipient
+            .strip_prefix('+')
+            .unwrap_or(&message.recipient);
+        let body = serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": to,
+            "type": "audio",
+            "audio": { "id": media_id }
+        });
+
+        ensure_https(&url)?;
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp voice reply failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp API error: {status}");
+        }
+
+        Ok(())
+    }
+
     async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         // WhatsApp uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /whatsapp endpoint.
@@ -756,6 +965,41 @@ mod tests {
         assert!(msgs.is_empty());
     }
 
+    #[test]
+    fn whatsapp_authorized_messages_include_voice_notes_from_allowed_numbers() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "messages": [
+                            {
+                                "from": "1234567890",
+                                "timestamp": "1699999999",
+                                "type": "audio",
+                                "audio": { "id": "audio123", "mime_type": "audio/ogg; codecs=opus", "voice": true }
+                            },
+                            {
+                                "from": "5550000000",
+                                "type": "audio",
+                                "audio": { "id": "audio456" }
+                            }
+                        ]
+                    }
+                }]
+            }]
+        });
+        let msgs = ch.authorized_messages(&payload);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].1, "+1234567890");
+
+        let msg =
+            WhatsAppChannel::build_message(msgs[0].0, msgs[0].1.clone(), "[Voice] hi".into(), true);
+        assert!(msg.voice);
+        assert_eq!(msg.reply_target, "+1234567890");
+        assert_eq!(msg.timestamp, 1_699_999_999);
+    }
+
     #[test]
     fn whatsapp_parse_video_message_skipped() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
@@ -287,6 +287,8 @@ impl Channel for WhatsAppWebChannel {
                                         content: trimmed.to_string(),
                                         timestamp: chrono::Utc::now().timestamp() as u64,
                                         thread_ts: None,
+                                        group: None,
+                                        voice: false,
                                     })
                                     .await
                                 {
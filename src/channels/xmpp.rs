@@ -0,0 +1,1545 @@
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
+use async_trait::async_trait;
+use base64::Engine;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, Mutex};
+
+// Use tokio_rustls's re-export of rustls types
+use tokio_rustls::rustls;
+
+/// Interval between XEP-0199 pings to the server.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// If nothing arrives for this long (pings included), the connection is dead.
+const READ_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Upper bound for a single buffered stanza.
+const MAX_STANZA_BYTES: usize = 1024 * 1024;
+
+/// Maximum element nesting accepted from the server.
+const MAX_DEPTH: usize = 32;
+
+const NS_TLS: &str = "urn:ietf:params:xml:ns:xmpp-tls";
+const NS_SASL: &str = "urn:ietf:params:xml:ns:xmpp-sasl";
+const NS_BIND: &str = "urn:ietf:params:xml:ns:xmpp-bind";
+const NS_SESSION: &str = "urn:ietf:params:xml:ns:xmpp-session";
+const NS_STANZAS: &str = "urn:ietf:params:xml:ns:xmpp-stanzas";
+const NS_MUC: &str = "http://jabber.org/protocol/muc";
+const NS_CHATSTATES: &str = "http://jabber.org/protocol/chatstates";
+const NS_CORRECT: &str = "urn:xmpp:message-correct:0";
+const NS_RETRACT: &str = "urn:xmpp:message-retract:1";
+const NS_FALLBACK: &str = "urn:xmpp:fallback:0";
+const NS_HINTS: &str = "urn:xmpp:hints";
+const NS_DELAY: &str = "urn:xmpp:delay";
+const NS_PING: &str = "urn:xmpp:ping";
+
+// ── Minimal XML model ────────────────────────────────────────
+
+/// A parsed XML element. Names are local (namespace prefixes stripped);
+/// `xmlns` is kept as an ordinary attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn with_attr(mut self, name: &str, value: &str) -> Self {
+        self.attrs.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn with_child(mut self, child: Element) -> Self {
+        self.children.push(Node::Element(child));
+        self
+    }
+
+    fn with_text(mut self, text: &str) -> Self {
+        self.children.push(Node::Text(text.to_string()));
+        self
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(e) => Some(e),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// First child with this local name, in any namespace.
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|e| e.name == name)
+    }
+
+    /// First child with this local name and an explicit `xmlns`.
+    fn child_ns(&self, name: &str, ns: &str) -> Option<&Element> {
+        self.elements()
+            .find(|e| e.name == name && e.attr("xmlns") == Some(ns))
+    }
+
+    fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(t) => Some(t.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect()
+    }
+
+    fn to_xml(&self) -> String {
+        let mut out = String::new();
+        self.write_xml(&mut out);
+        out
+    }
+
+    fn write_xml(&self, out: &mut String) {
+        out.push('<');
+        out.push_str(&self.name);
+        for (k, v) in &self.attrs {
+            out.push(' ');
+            out.push_str(k);
+            out.push_str("='");
+            out.push_str(&escape_xml(v));
+            out.push('\'');
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+        out.push('>');
+        for node in &self.children {
+            match node {
+                Node::Element(e) => e.write_xml(out),
+                Node::Text(t) => out.push_str(&escape_xml(t)),
+            }
+        }
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after.find(';') else {
+            out.push_str(after);
+            return out;
+        };
+        let entity = &after[1..semi];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(ch) => out.push(ch),
+            None => out.push_str(&after[..=semi]),
+        }
+        rest = &after[semi + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+/// One top-level item of an XMPP stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StreamItem {
+    /// `<stream:stream ...>` header (attributes only).
+    Open(Element),
+    /// `</stream:stream>`.
+    Close,
+    /// A complete top-level element (stanza or nonza).
+    Stanza(Element),
+}
+
+/// Parsed start tag: element, whether it was self-closing, and the offset
+/// just past `>`.
+type StartTag = (Element, bool, usize);
+
+/// Parse a start tag at `pos` (which must point at `<`).
+/// `Ok(None)` means the input ends before the tag does.
+fn parse_start_tag(input: &str, pos: usize) -> anyhow::Result<Option<StartTag>> {
+    let bytes = input.as_bytes();
+    let mut i = pos + 1;
+    let name_start = i;
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'/' && bytes[i] != b'>'
+    {
+        i += 1;
+    }
+    if i >= bytes.len() {
+        return Ok(None);
+    }
+    let name = &input[name_start..i];
+    if name.is_empty() {
+        anyhow::bail!("XMPP: empty element name");
+    }
+    let mut element = Element::new(local_name(name));
+
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i) {
+            None => return Ok(None),
+            Some(b'>') => return Ok(Some((element, false, i + 1))),
+            Some(b'/') => {
+                return match bytes.get(i + 1) {
+                    None => Ok(None),
+                    Some(b'>') => Ok(Some((element, true, i + 2))),
+                    Some(_) => anyhow::bail!("XMPP: malformed tag <{name}>"),
+                };
+            }
+            Some(_) => {}
+        }
+
+        let Some(eq) = input[i..].find('=') else {
+            return Ok(None);
+        };
+        let attr_name = input[i..i + eq].trim();
+        if attr_name.is_empty() || attr_name.contains(['<', '>', '/']) {
+            anyhow::bail!("XMPP: malformed attribute in <{name}>");
+        }
+        i += eq + 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let quote = match bytes.get(i) {
+            None => return Ok(None),
+            Some(q @ (b'\'' | b'"')) => *q as char,
+            Some(_) => anyhow::bail!("XMPP: unquoted attribute in <{name}>"),
+        };
+        let Some(close) = input[i + 1..].find(quote) else {
+            return Ok(None);
+        };
+        let value = unescape_xml(&input[i + 1..i + 1 + close]);
+        element.attrs.push((attr_name.to_string(), value));
+        i += close + 2;
+    }
+}
+
+/// Parse a full element (start tag, content, end tag) at `pos`.
+fn parse_element(
+    input: &str,
+    pos: usize,
+    depth: usize,
+) -> anyhow::Result<Option<(Element, usize)>> {
+    if depth > MAX_DEPTH {
+        anyhow::bail!("XMPP: element nesting too deep");
+    }
+    let Some((mut element, self_closing, mut i)) = parse_start_tag(input, pos)? else {
+        return Ok(None);
+    };
+    if self_closing {
+        return Ok(Some((element, i)));
+    }
+
+    loop {
+        let rest = &input[i..];
+        if rest.is_empty() {
+            return Ok(None);
+        }
+        if rest.starts_with("</") {
+            let Some(end) = rest.find('>') else {
+                return Ok(None);
+            };
+            if local_name(rest[2..end].trim()) != element.name {
+                anyhow::bail!("XMPP: mismatched end tag for <{}>", element.name);
+            }
+            return Ok(Some((element, i + end + 1)));
+        }
+        if rest.starts_with("<!--") {
+            let Some(end) = rest.find("-->") else {
+                return Ok(None);
+            };
+            i += end + 3;
+            continue;
+        }
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let Some(end) = cdata.find("]]>") else {
+                return Ok(None);
+            };
+            element.children.push(Node::Text(cdata[..end].to_string()));
+            i += 9 + end + 3;
+            continue;
+        }
+        if rest.starts_with('<') {
+            let Some((child, next)) = parse_element(input, i, depth + 1)? else {
+                return Ok(None);
+            };
+            element.children.push(Node::Element(child));
+            i = next;
+            continue;
+        }
+        let Some(lt) = rest.find('<') else {
+            return Ok(None);
+        };
+        element.children.push(Node::Text(unescape_xml(&rest[..lt])));
+        i += lt;
+    }
+}
+
+/// Parse the next stream item from `input`, returning it with the number of
+/// bytes consumed. `Ok(None)` means more data is needed.
+fn parse_item(input: &str) -> anyhow::Result<Option<(StreamItem, usize)>> {
+    let mut i = 0;
+    loop {
+        let rest = &input[i..];
+        let trimmed = rest.trim_start();
+        i += rest.len() - trimmed.len();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        if trimmed.starts_with("<?") {
+            let Some(end) = trimmed.find("?>") else {
+                return Ok(None);
+            };
+            i += end + 2;
+            continue;
+        }
+        if trimmed.starts_with("<!--") {
+            let Some(end) = trimmed.find("-->") else {
+                return Ok(None);
+            };
+            i += end + 3;
+            continue;
+        }
+        if trimmed.starts_with("</") {
+            let Some(end) = trimmed.find('>') else {
+                return Ok(None);
+            };
+            if local_name(trimmed[2..end].trim()) == "stream" {
+                return Ok(Some((StreamItem::Close, i + end + 1)));
+            }
+            anyhow::bail!("XMPP: unexpected end tag at stream level");
+        }
+        if trimmed.starts_with('<') {
+            // The stream header is a start tag that stays open.
+            let Some((element, self_closing, end)) = parse_start_tag(input, i)? else {
+                return Ok(None);
+            };
+            if element.name == "stream" && !self_closing {
+                return Ok(Some((StreamItem::Open(element), end)));
+            }
+            return Ok(parse_element(input, i, 0)?.map(|(e, end)| (StreamItem::Stanza(e), end)));
+        }
+        // Stray character data between stanzas is ignored.
+        match trimmed.find('<') {
+            Some(lt) => i += lt,
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Buffers bytes from the server and yields complete stream items.
+struct StanzaReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> StanzaReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
+    }
+
+    async fn next_item(&mut self) -> anyhow::Result<StreamItem> {
+        loop {
+            // Whitespace keepalives would otherwise accumulate forever.
+            let blank = self
+                .buf
+                .iter()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count();
+            self.buf.drain(..blank);
+
+            let text = match std::str::from_utf8(&self.buf) {
+                Ok(text) => text,
+                Err(e) => std::str::from_utf8(&self.buf[..e.valid_up_to()]).unwrap_or(""),
+            };
+            if let Some((item, used)) = parse_item(text)? {
+                self.buf.drain(..used);
+                return Ok(item);
+            }
+            if self.buf.len() > MAX_STANZA_BYTES {
+                anyhow::bail!("XMPP stanza exceeds {MAX_STANZA_BYTES} bytes");
+            }
+
+            let mut chunk = [0u8; 4096];
+            let n = self.inner.read(&mut chunk).await?;
+            if n == 0 {
+                anyhow::bail!("XMPP connection closed by server");
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    /// Read the next top-level element, failing on stream open/close.
+    async fn next_stanza(&mut self) -> anyhow::Result<Element> {
+        match self.next_item().await? {
+            StreamItem::Stanza(element) => Ok(element),
+            StreamItem::Close => anyhow::bail!("XMPP stream closed by server"),
+            StreamItem::Open(_) => anyhow::bail!("XMPP: unexpected stream header"),
+        }
+    }
+}
+
+// ── JIDs ─────────────────────────────────────────────────────
+
+/// `user@domain/resource` → `user@domain`.
+fn bare_jid(jid: &str) -> &str {
+    jid.split_once('/').map_or(jid, |(bare, _)| bare)
+}
+
+fn jid_resource(jid: &str) -> Option<&str> {
+    jid.split_once('/').map(|(_, resource)| resource)
+}
+
+fn jid_domain(jid: &str) -> &str {
+    let bare = bare_jid(jid);
+    bare.split_once('@').map_or(bare, |(_, domain)| domain)
+}
+
+fn jid_localpart(jid: &str) -> &str {
+    bare_jid(jid).split_once('@').map_or("", |(local, _)| local)
+}
+
+// ── Stream negotiation ──────────────────────────────────────
+
+fn stream_header(domain: &str) -> String {
+    format!(
+        "<?xml version='1.0'?><stream:stream to='{}' version='1.0' xml:lang='en' \
+         xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams'>",
+        escape_xml(domain)
+    )
+}
+
+async fn write_str<W: AsyncWrite + Unpin + ?Sized>(
+    writer: &mut W,
+    data: &str,
+) -> anyhow::Result<()> {
+    writer.write_all(data.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Open (or restart) the stream and return the server's `<features/>`.
+async fn open_stream<R, W>(
+    reader: &mut StanzaReader<R>,
+    writer: &mut W,
+    domain: &str,
+) -> anyhow::Result<Element>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    write_str(writer, &stream_header(domain)).await?;
+    match reader.next_item().await? {
+        StreamItem::Open(_) => {}
+        other => anyhow::bail!("XMPP: expected stream header, got {other:?}"),
+    }
+    let features = reader.next_stanza().await?;
+    if features.name != "features" {
+        anyhow::bail!("XMPP: expected stream features, got <{}>", features.name);
+    }
+    Ok(features)
+}
+
+/// Negotiate STARTTLS on a plaintext stream. The caller upgrades the
+/// connection to TLS afterwards.
+async fn negotiate_starttls<S>(stream: &mut S, domain: &str) -> anyhow::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (read, mut write) = tokio::io::split(stream);
+    let mut reader = StanzaReader::new(read);
+    let features = open_stream(&mut reader, &mut write, domain).await?;
+    if features.child_ns("starttls", NS_TLS).is_none() {
+        anyhow::bail!("XMPP server does not offer STARTTLS");
+    }
+    write_str(
+        &mut write,
+        &Element::new("starttls").with_attr("xmlns", NS_TLS).to_xml(),
+    )
+    .await?;
+    let reply = reader.next_stanza().await?;
+    if reply.name != "proceed" {
+        anyhow::bail!("XMPP server refused STARTTLS");
+    }
+    Ok(())
+}
+
+/// SASL PLAIN authentication, stream restart and resource binding.
+/// Returns the full JID assigned by the server.
+async fn authenticate<R, W>(
+    reader: &mut StanzaReader<R>,
+    writer: &mut W,
+    jid: &str,
+    password: &str,
+    resource: &str,
+) -> anyhow::Result<String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let domain = jid_domain(jid);
+    let features = open_stream(reader, writer, domain).await?;
+    let mechanisms: Vec<String> = features
+        .child_ns("mechanisms", NS_SASL)
+        .map(|m| m.elements().map(Element::text).collect())
+        .unwrap_or_default();
+    if !mechanisms.iter().any(|m| m == "PLAIN") {
+        anyhow::bail!(
+            "XMPP server does not offer SASL PLAIN (offered: {})",
+            mechanisms.join(", ")
+        );
+    }
+
+    let credentials = base64::engine::general_purpose::STANDARD
+        .encode(format!("\0{}\0{password}", jid_localpart(jid)));
+    let auth = Element::new("auth")
+        .with_attr("xmlns", NS_SASL)
+        .with_attr("mechanism", "PLAIN")
+        .with_text(&credentials);
+    write_str(writer, &auth.to_xml()).await?;
+    let reply = reader.next_stanza().await?;
+    match reply.name.as_str() {
+        "success" => {}
+        "failure" => {
+            let condition = reply
+                .elements()
+                .next()
+                .map_or("unknown", |c| c.name.as_str());
+            anyhow::bail!("XMPP authentication failed: {condition}");
+        }
+        other => anyhow::bail!("XMPP: unexpected SASL reply <{other}>"),
+    }
+
+    let features = open_stream(reader, writer, domain).await?;
+    if features.child_ns("bind", NS_BIND).is_none() {
+        anyhow::bail!("XMPP server does not offer resource binding");
+    }
+    let bind = Element::new("iq")
+        .with_attr("type", "set")
+        .with_attr("id", "bind_1")
+        .with_child(
+            Element::new("bind")
+                .with_attr("xmlns", NS_BIND)
+                .with_child(Element::new("resource").with_text(resource)),
+        );
+    write_str(writer, &bind.to_xml()).await?;
+    let result = await_iq_result(reader, "bind_1").await?;
+    let bound = result
+        .child("bind")
+        .and_then(|b| b.child("jid"))
+        .map(Element::text)
+        .unwrap_or_else(|| format!("{}/{resource}", bare_jid(jid)));
+
+    // RFC 3921 session establishment, still required by some older servers.
+    if features
+        .child_ns("session", NS_SESSION)
+        .is_some_and(|s| s.child("optional").is_none())
+    {
+        let session = Element::new("iq")
+            .with_attr("type", "set")
+            .with_attr("id", "session_1")
+            .with_child(Element::new("session").with_attr("xmlns", NS_SESSION));
+        write_str(writer, &session.to_xml()).await?;
+        await_iq_result(reader, "session_1").await?;
+    }
+
+    Ok(bound)
+}
+
+/// Wait for the `<iq type='result'>` answering `id`.
+async fn await_iq_result<R: AsyncRead + Unpin>(
+    reader: &mut StanzaReader<R>,
+    id: &str,
+) -> anyhow::Result<Element> {
+    loop {
+        let stanza = reader.next_stanza().await?;
+        if stanza.name != "iq" || stanza.attr("id") != Some(id) {
+            continue;
+        }
+        if stanza.attr("type") == Some("result") {
+            return Ok(stanza);
+        }
+        let condition = stanza
+            .child("error")
+            .and_then(|e| e.elements().next())
+            .map_or("unknown", |c| c.name.as_str())
+            .to_string();
+        anyhow::bail!("XMPP request {id} failed: {condition}");
+    }
+}
+
+// ── Channel ─────────────────────────────────────────────────
+
+type XmppWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Configuration for constructing an `XmppChannel`.
+pub struct XmppChannelConfig {
+    pub jid: String,
+    pub password: String,
+    pub server: Option<String>,
+    pub port: Option<u16>,
+    pub direct_tls: bool,
+    pub resource: Option<String>,
+    pub rooms: Vec<String>,
+    pub nickname: Option<String>,
+    pub allowed_users: Vec<String>,
+    pub verify_tls: bool,
+    pub stream_mode: StreamMode,
+    pub draft_update_interval_ms: u64,
+}
+
+/// XMPP client-to-server channel.
+///
+/// Connects over STARTTLS (or direct TLS), authenticates with SASL PLAIN,
+/// and handles 1:1 chats and MUC rooms. Typing uses XEP-0085 chat states;
+/// draft updates use XEP-0308 message correction.
+pub struct XmppChannel {
+    jid: String,
+    password: String,
+    server: String,
+    port: u16,
+    direct_tls: bool,
+    resource: String,
+    rooms: Vec<String>,
+    nickname: String,
+    allowed_users: Vec<String>,
+    verify_tls: bool,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: parking_lot::Mutex<HashMap<String, Instant>>,
+    /// Shared write half of the stream for sending stanzas.
+    writer: Arc<Mutex<Option<XmppWriter>>>,
+}
+
+impl XmppChannel {
+    pub fn new(cfg: XmppChannelConfig) -> Self {
+        let server = cfg
+            .server
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| jid_domain(&cfg.jid).to_string());
+        let port = cfg.port.unwrap_or(if cfg.direct_tls { 5223 } else { 5222 });
+        let nickname = cfg
+            .nickname
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| jid_localpart(&cfg.jid).to_string());
+        Self {
+            jid: bare_jid(&cfg.jid).to_string(),
+            password: cfg.password,
+            server,
+            port,
+            direct_tls: cfg.direct_tls,
+            resource: cfg.resource.unwrap_or_else(|| "zeroclaw".into()),
+            rooms: cfg
+                .rooms
+                .iter()
+                .map(|r| bare_jid(r.trim()).to_string())
+                .collect(),
+            nickname,
+            allowed_users: cfg.allowed_users,
+            verify_tls: cfg.verify_tls,
+            stream_mode: cfg.stream_mode,
+            draft_update_interval_ms: cfg.draft_update_interval_ms,
+            last_draft_edit: parking_lot::Mutex::new(HashMap::new()),
+            writer: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Allowlist entries match the sender's bare JID, a MUC occupant JID
+    /// (`room@service/nick`), or a whole room by its bare JID.
+    fn is_user_allowed(&self, sender: &str, conversation: &str) -> bool {
+        self.allowed_users.iter().any(|u| {
+            u == "*" || u.eq_ignore_ascii_case(sender) || u.eq_ignore_ascii_case(conversation)
+        })
+    }
+
+    fn is_room(&self, jid: &str) -> bool {
+        self.rooms.iter().any(|r| r.eq_ignore_ascii_case(jid))
+    }
+
+    fn message_type(&self, recipient: &str) -> &'static str {
+        if self.is_room(recipient) {
+            "groupchat"
+        } else {
+            "chat"
+        }
+    }
+
+    fn new_stanza_id() -> String {
+        format!("zc-{}", uuid::Uuid::new_v4())
+    }
+
+    /// Create a TLS connection to the server, negotiating STARTTLS first
+    /// unless direct TLS is configured.
+    async fn connect(
+        &self,
+    ) -> anyhow::Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+        let domain = jid_domain(&self.jid).to_string();
+        let mut tcp = tokio::net::TcpStream::connect((self.server.as_str(), self.port)).await?;
+        if !self.direct_tls {
+            negotiate_starttls(&mut tcp, &domain).await?;
+        }
+
+        let tls_config = if self.verify_tls {
+            let root_store: rustls::RootCertStore =
+                webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
+            rustls::ClientConfig::builder()
+                .with_root_certificates(root_store)
+                .with_no_client_auth()
+        } else {
+            rustls::ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(super::irc::NoVerify))
+                .with_no_client_auth()
+        };
+
+        // The certificate must match the XMPP domain, not the connect host.
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(tls_config));
+        let name = rustls::pki_types::ServerName::try_from(domain)?;
+        Ok(connector.connect(name, tcp).await?)
+    }
+
+    async fn write_stanza(&self, stanza: &Element) -> anyhow::Result<()> {
+        let mut guard = self.writer.lock().await;
+        let writer = guard
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("XMPP not connected"))?;
+        write_str(writer, &stanza.to_xml()).await
+    }
+
+    fn message_stanza(&self, recipient: &str) -> Element {
+        Element::new("message")
+            .with_attr("to", recipient)
+            .with_attr("type", self.message_type(recipient))
+            .with_attr("id", &Self::new_stanza_id())
+    }
+
+    fn chat_state(&self, recipient: &str, state: &str) -> Element {
+        self.message_stanza(recipient)
+            .with_child(Element::new(state).with_attr("xmlns", NS_CHATSTATES))
+            .with_child(Element::new("no-store").with_attr("xmlns", NS_HINTS))
+    }
+
+    /// A XEP-0308 correction of the message with id `original_id`.
+    fn correction(&self, recipient: &str, original_id: &str, text: &str) -> Element {
+        self.message_stanza(recipient)
+            .with_child(Element::new("body").with_text(text))
+            .with_child(
+                Element::new("replace")
+                    .with_attr("id", original_id)
+                    .with_attr("xmlns", NS_CORRECT),
+            )
+    }
+
+    fn join_presence(&self, room: &str) -> Element {
+        Element::new("presence")
+            .with_attr("to", &format!("{room}/{}", self.nickname))
+            .with_child(
+                Element::new("x")
+                    .with_attr("xmlns", NS_MUC)
+                    .with_child(Element::new("history").with_attr("maxstanzas", "0")),
+            )
+    }
+
+    /// Turn an inbound `<message/>` into a channel message.
+    ///
+    /// Handles 1:1 chats, MUC group chat and MUC private messages. Errors,
+    /// our own room echoes, room history and body-less stanzas (chat
+    /// states) are dropped.
+    fn parse_message(&self, stanza: &Element) -> Option<ChannelMessage> {
+        let from = stanza.attr("from")?;
+        let kind = stanza.attr("type").unwrap_or("normal");
+        if kind == "error" {
+            tracing::warn!("XMPP: error message from {from}");
+            return None;
+        }
+        let body = stanza.child("body").map(Element::text)?;
+        let body = body.trim();
+        if body.is_empty() {
+            return None;
+        }
+
+        let conversation = bare_jid(from);
+        let in_room = self.is_room(conversation);
+        let (sender, reply_target) = match kind {
+            "groupchat" => {
+                let nick = jid_resource(from).unwrap_or("");
+                // Room subject/system messages have no nick; our own
+                // messages are echoed back by the room.
+                if !in_room || nick.is_empty() || nick == self.nickname {
+                    return None;
+                }
+                if stanza.child_ns("delay", NS_DELAY).is_some() {
+                    return None;
+                }
+                (from.to_string(), conversation.to_string())
+            }
+            // MUC private messages must be answered at the occupant JID.
+            "chat" | "normal" if in_room => (from.to_string(), from.to_string()),
+            "chat" | "normal" => (conversation.to_string(), conversation.to_string()),
+            _ => return None,
+        };
+
+        if !self.is_user_allowed(&sender, conversation) {
+            tracing::warn!("XMPP: ignoring message from unauthorized user: {sender}");
+            return None;
+        }
+
+        let stanza_id = stanza
+            .attr("id")
+            .map_or_else(Self::new_stanza_id, str::to_string);
+        let edited = stanza.child_ns("replace", NS_CORRECT).is_some();
+
+        Some(ChannelMessage {
+            id: format!("xmpp_{conversation}_{stanza_id}"),
+            sender,
+            reply_target,
+            content: if edited {
+                format!("[edited] {body}")
+            } else {
+                body.to_string()
+            },
+            channel: "xmpp".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: stanza.child("thread").map(Element::text),
+            group: None,
+            voice: false,
+        })
+    }
+
+    /// Reply to server and peer IQs: answer pings, refuse everything else
+    /// as RFC 6120 requires.
+    fn iq_response(stanza: &Element) -> Option<Element> {
+        let kind = stanza.attr("type")?;
+        if kind != "get" && kind != "set" {
+            return None;
+        }
+        let mut reply = Element::new("iq").with_attr("id", stanza.attr("id").unwrap_or(""));
+        if let Some(from) = stanza.attr("from") {
+            reply = reply.with_attr("to", from);
+        }
+        if kind == "get" && stanza.child_ns("ping", NS_PING).is_some() {
+            return Some(reply.with_attr("type", "result"));
+        }
+        Some(
+            reply.with_attr("type", "error").with_child(
+                Element::new("error")
+                    .with_attr("type", "cancel")
+                    .with_child(Element::new("service-unavailable").with_attr("xmlns", NS_STANZAS)),
+            ),
+        )
+    }
+
+    /// Handle one inbound stanza. Returns `false` once the receiver is gone.
+    async fn handle_stanza(
+        &self,
+        stanza: &Element,
+        tx: &mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<bool> {
+        match stanza.name.as_str() {
+            "message" => {
+                if let Some(msg) = self.parse_message(stanza) {
+                    return Ok(tx.send(msg).await.is_ok());
+                }
+            }
+            "iq" => {
+                if let Some(reply) = Self::iq_response(stanza) {
+                    self.write_stanza(&reply).await?;
+                }
+            }
+            "presence" if stanza.attr("type") == Some("error") => {
+                tracing::warn!(
+                    "XMPP: presence error from {} (room join failed?)",
+                    stanza.attr("from").unwrap_or("server")
+                );
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl Channel for XmppChannel {
+    fn name(&self) -> &str {
+        "xmpp"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let mut stanza = self
+            .message_stanza(&message.recipient)
+            .with_child(Element::new("body").with_text(&message.content))
+            .with_child(Element::new("active").with_attr("xmlns", NS_CHATSTATES));
+        if let Some(ref thread) = message.thread_ts {
+            stanza = stanza.with_child(Element::new("thread").with_text(thread));
+        }
+        self.write_stanza(&stanza).await
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tracing::info!(
+            "XMPP channel connecting to {}:{} as {}...",
+            self.server,
+            self.port,
+            self.jid
+        );
+
+        let tls = self.connect().await?;
+        let (read, mut write) = tokio::io::split(tls);
+        let mut reader = StanzaReader::new(read);
+        let bound = authenticate(
+            &mut reader,
+            &mut write,
+            &self.jid,
+            &self.password,
+            &self.resource,
+        )
+        .await?;
+        tracing::info!("XMPP connected as {bound}");
+
+        write_str(&mut write, &Element::new("presence").to_xml()).await?;
+        for room in &self.rooms {
+            write_str(&mut write, &self.join_presence(room).to_xml()).await?;
+            tracing::info!("XMPP joining {room} as {}", self.nickname);
+        }
+
+        {
+            let mut guard = self.writer.lock().await;
+            *guard = Some(Box::new(write));
+        }
+
+        let domain = jid_domain(&self.jid).to_string();
+        let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+        keepalive.tick().await;
+        let mut last_read = Instant::now();
+
+        let result = loop {
+            tokio::select! {
+                item = reader.next_item() => {
+                    last_read = Instant::now();
+                    match item {
+                        Ok(StreamItem::Stanza(stanza)) => match self.handle_stanza(&stanza, &tx).await {
+                            Ok(true) => {}
+                            Ok(false) => break Ok(()),
+                            Err(e) => break Err(e),
+                        },
+                        Ok(StreamItem::Open(_)) => {}
+                        Ok(StreamItem::Close) => {
+                            break Err(anyhow::anyhow!("XMPP stream closed by server"));
+                        }
+                        Err(e) => break Err(e),
+                    }
+                }
+                _ = keepalive.tick() => {
+                    if last_read.elapsed() > READ_TIMEOUT {
+                        break Err(anyhow::anyhow!(
+                            "XMPP read timed out (no data for {READ_TIMEOUT:?})"
+                        ));
+                    }
+                    let ping = Element::new("iq")
+                        .with_attr("type", "get")
+                        .with_attr("to", &domain)
+                        .with_attr("id", &Self::new_stanza_id())
+                        .with_child(Element::new("ping").with_attr("xmlns", NS_PING));
+                    if let Err(e) = self.write_stanza(&ping).await {
+                        break Err(e);
+                    }
+                }
+            }
+        };
+
+        self.writer.lock().await.take();
+        result
+    }
+
+    async fn health_check(&self) -> bool {
+        // Lightweight connectivity check: STARTTLS/TLS handshake, then close
+        match self.connect().await {
+            Ok(mut tls) => {
+                let _ = write_str(&mut tls, "</stream:stream>").await;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.write_stanza(&self.chat_state(recipient, "composing"))
+            .await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.write_stanza(&self.chat_state(recipient, "paused"))
+            .await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+        let text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let stanza = self
+            .message_stanza(&message.recipient)
+            .with_child(Element::new("body").with_text(text));
+        let id = stanza.attr("id").unwrap_or_default().to_string();
+        self.write_stanza(&stanza).await?;
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+        Ok(Some(id))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit corrections per recipient
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        self.write_stanza(&self.correction(recipient, message_id, text))
+            .await?;
+        self.last_draft_edit
+            .lock()
+            .insert(recipient.to_string(), Instant::now());
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let stanza = self
+            .correction(recipient, message_id, text)
+            .with_child(Element::new("active").with_attr("xmlns", NS_CHATSTATES));
+        self.write_stanza(&stanza).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        // XEP-0424 retraction, with a fallback body for older clients.
+        let stanza = self
+            .message_stanza(recipient)
+            .with_child(
+                Element::new("retract")
+                    .with_attr("id", message_id)
+                    .with_attr("xmlns", NS_RETRACT),
+            )
+            .with_child(
+                Element::new("fallback")
+                    .with_attr("xmlns", NS_FALLBACK)
+                    .with_attr("for", NS_RETRACT),
+            )
+            .with_child(Element::new("body").with_text("(message retracted)"))
+            .with_child(Element::new("store").with_attr("xmlns", NS_HINTS));
+        self.write_stanza(&stanza).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_channel() -> XmppChannel {
+        XmppChannel::new(XmppChannelConfig {
+            jid: "bot@example.org".into(),
+            password: "secret".into(),
+            server: None,
+            port: None,
+            direct_tls: false,
+            resource: None,
+            rooms: vec!["dev@conference.example.org".into()],
+            nickname: None,
+            allowed_users: vec![
+                "alice@example.org".into(),
+                "dev@conference.example.org".into(),
+            ],
+            verify_tls: true,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+        })
+    }
+
+    fn stanza(xml: &str) -> Element {
+        match parse_item(xml).unwrap().unwrap().0 {
+            StreamItem::Stanza(e) => e,
+            other => panic!("expected stanza, got {other:?}"),
+        }
+    }
+
+    // ── XML ─────────────────────────────────────────────────
+
+    #[test]
+    fn parse_item_reads_stanza_with_entities() {
+        let input =
+            "<message from='a@b/c' type=\"chat\"><body>1 &lt; 2 &amp;&#x20;&#65;</body></message><iq/>";
+        let (item, used) = parse_item(input).unwrap().unwrap();
+        let StreamItem::Stanza(msg) = item else {
+            panic!("expected stanza");
+        };
+        assert_eq!(msg.name, "message");
+        assert_eq!(msg.attr("from"), Some("a@b/c"));
+        assert_eq!(msg.attr("type"), Some("chat"));
+        assert_eq!(msg.child("body").unwrap().text(), "1 < 2 & A");
+        assert_eq!(&input[used..], "<iq/>");
+    }
+
+    #[test]
+    fn parse_item_handles_stream_header_and_close() {
+        let input = "<?xml version='1.0'?><stream:stream from='example.org' id='s1' \
+                     xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams' version='1.0'>";
+        let (item, used) = parse_item(input).unwrap().unwrap();
+        let StreamItem::Open(header) = item else {
+            panic!("expected stream header");
+        };
+        assert_eq!(header.attr("id"), Some("s1"));
+        assert_eq!(used, input.len());
+
+        let (item, _) = parse_item("  </stream:stream>").unwrap().unwrap();
+        assert_eq!(item, StreamItem::Close);
+    }
+
+    #[test]
+    fn parse_item_strips_namespace_prefixes() {
+        let features = stanza(
+            "<stream:features><bind xmlns='urn:ietf:params:xml:ns:xmpp-bind'/></stream:features>",
+        );
+        assert_eq!(features.name, "features");
+        assert!(features.child_ns("bind", NS_BIND).is_some());
+    }
+
+    #[test]
+    fn parse_item_waits_for_incomplete_input() {
+        assert!(parse_item("<message><body>hel").unwrap().is_none());
+        assert!(parse_item("<message to='a").unwrap().is_none());
+        assert!(parse_item("   ").unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_item_rejects_mismatched_tags() {
+        assert!(parse_item("<message><body>hi</message>").is_err());
+    }
+
+    #[test]
+    fn parse_item_rejects_deep_nesting() {
+        let input = "<a>".repeat(MAX_DEPTH + 2) + &"</a>".repeat(MAX_DEPTH + 2);
+        assert!(parse_item(&input).is_err());
+    }
+
+    #[test]
+    fn to_xml_escapes_text_and_attributes() {
+        let xml = Element::new("message")
+            .with_attr("to", "a'b@example.org")
+            .with_child(Element::new("body").with_text("<b>&</b>"))
+            .to_xml();
+        assert_eq!(
+            xml,
+            "<message to='a&apos;b@example.org'><body>&lt;b&gt;&amp;&lt;/b&gt;</body></message>"
+        );
+        assert_eq!(stanza(&xml).child("body").unwrap().text(), "<b>&</b>");
+    }
+
+    #[test]
+    fn jid_helpers_split_parts() {
+        assert_eq!(bare_jid("alice@example.org/phone"), "alice@example.org");
+        assert_eq!(jid_resource("room@muc.example.org/nick/x"), Some("nick/x"));
+        assert_eq!(jid_domain("alice@example.org/phone"), "example.org");
+        assert_eq!(jid_localpart("alice@example.org"), "alice");
+        assert_eq!(jid_localpart("example.org"), "");
+    }
+
+    // ── Config defaults ─────────────────────────────────────
+
+    #[test]
+    fn defaults_derive_from_jid() {
+        let ch = make_channel();
+        assert_eq!(ch.server, "example.org");
+        assert_eq!(ch.port, 5222);
+        assert_eq!(ch.nickname, "bot");
+        assert_eq!(ch.resource, "zeroclaw");
+    }
+
+    #[test]
+    fn direct_tls_defaults_to_port_5223() {
+        let ch = XmppChannel::new(XmppChannelConfig {
+            jid: "bot@example.org".into(),
+            password: String::new(),
+            server: Some("xmpp.example.org".into()),
+            port: None,
+            direct_tls: true,
+            resource: None,
+            rooms: vec![],
+            nickname: Some("claw".into()),
+            allowed_users: vec![],
+            verify_tls: true,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+        });
+        assert_eq!(ch.server, "xmpp.example.org");
+        assert_eq!(ch.port, 5223);
+        assert_eq!(ch.nickname, "claw");
+    }
+
+    // ── Inbound messages ────────────────────────────────────
+
+    #[test]
+    fn parse_direct_message_uses_bare_jid() {
+        let ch = make_channel();
+        let msg = ch
+            .parse_message(&stanza(
+                "<message from='Alice@example.org/phone' type='chat' id='m1'>\
+                 <body> hello </body><thread>t1</thread></message>",
+            ))
+            .unwrap();
+        assert_eq!(msg.sender, "Alice@example.org");
+        assert_eq!(msg.reply_target, "Alice@example.org");
+        assert_eq!(msg.content, "hello");
+        assert_eq!(msg.channel, "xmpp");
+        assert_eq!(msg.id, "xmpp_Alice@example.org_m1");
+        assert_eq!(msg.thread_ts.as_deref(), Some("t1"));
+    }
+
+    #[test]
+    fn parse_direct_message_rejects_unknown_sender() {
+        let ch = make_channel();
+        assert!(ch
+            .parse_message(&stanza(
+                "<message from='mallory@example.org/x' type='chat'><body>hi</body></message>",
+            ))
+            .is_none());
+    }
+
+    #[test]
+    fn parse_groupchat_replies_to_room() {
+        let ch = make_channel();
+        let msg = ch
+            .parse_message(&stanza(
+                "<message from='dev@conference.example.org/carol' type='groupchat' id='g1'>\
+                 <body>ping</body></message>",
+            ))
+            .unwrap();
+        assert_eq!(msg.sender, "dev@conference.example.org/carol");
+        assert_eq!(msg.reply_target, "dev@conference.example.org");
+    }
+
+    #[test]
+    fn parse_groupchat_skips_own_echo_history_and_subject() {
+        let ch = make_channel();
+        for xml in [
+            "<message from='dev@conference.example.org/bot' type='groupchat'><body>echo</body></message>",
+            "<message from='dev@conference.example.org/carol' type='groupchat'><body>old</body>\
+             <delay xmlns='urn:xmpp:delay' stamp='2024-01-01T00:00:00Z'/></message>",
+            "<message from='dev@conference.example.org' type='groupchat'><body>topic</body></message>",
+            "<message from='other@conference.example.org/carol' type='groupchat'><body>x</body></message>",
+        ] {
+            assert!(ch.parse_message(&stanza(xml)).is_none(), "{xml}");
+        }
+    }
+
+    #[test]
+    fn parse_muc_private_message_replies_to_occupant() {
+        let ch = make_channel();
+        let msg = ch
+            .parse_message(&stanza(
+                "<message from='dev@conference.example.org/carol' type='chat'><body>psst</body></message>",
+            ))
+            .unwrap();
+        assert_eq!(msg.reply_target, "dev@conference.example.org/carol");
+    }
+
+    #[test]
+    fn parse_correction_marks_edited() {
+        let ch = make_channel();
+        let msg = ch
+            .parse_message(&stanza(
+                "<message from='alice@example.org/phone' type='chat' id='m2'><body>fixed</body>\
+                 <replace id='m1' xmlns='urn:xmpp:message-correct:0'/></message>",
+            ))
+            .unwrap();
+        assert_eq!(msg.content, "[edited] fixed");
+    }
+
+    #[test]
+    fn parse_skips_chat_states_and_errors() {
+        let ch = make_channel();
+        assert!(ch
+            .parse_message(&stanza(
+                "<message from='alice@example.org/phone' type='chat'>\
+                 <composing xmlns='http://jabber.org/protocol/chatstates'/></message>",
+            ))
+            .is_none());
+        assert!(ch
+            .parse_message(&stanza(
+                "<message from='alice@example.org' type='error'><body>x</body></message>",
+            ))
+            .is_none());
+    }
+
+    #[test]
+    fn wildcard_allows_everyone() {
+        let mut ch = make_channel();
+        ch.allowed_users = vec!["*".into()];
+        assert!(ch.is_user_allowed("anyone@example.net", "anyone@example.net"));
+    }
+
+    #[test]
+    fn empty_allowlist_denies_everyone() {
+        let mut ch = make_channel();
+        ch.allowed_users.clear();
+        assert!(!ch.is_user_allowed("alice@example.org", "alice@example.org"));
+    }
+
+    // ── IQ handling ─────────────────────────────────────────
+
+    #[test]
+    fn iq_ping_gets_result() {
+        let reply = XmppChannel::iq_response(&stanza(
+            "<iq from='example.org' type='get' id='p1'><ping xmlns='urn:xmpp:ping'/></iq>",
+        ))
+        .unwrap();
+        assert_eq!(reply.attr("type"), Some("result"));
+        assert_eq!(reply.attr("id"), Some("p1"));
+        assert_eq!(reply.attr("to"), Some("example.org"));
+    }
+
+    #[test]
+    fn unknown_iq_gets_service_unavailable() {
+        let reply = XmppChannel::iq_response(&stanza(
+            "<iq type='get' id='v1'><query xmlns='jabber:iq:version'/></iq>",
+        ))
+        .unwrap();
+        assert_eq!(reply.attr("type"), Some("error"));
+        assert!(reply
+            .child("error")
+            .unwrap()
+            .child_ns("service-unavailable", NS_STANZAS)
+            .is_some());
+        assert!(XmppChannel::iq_response(&stanza("<iq type='result' id='x'/>")).is_none());
+    }
+
+    // ── Outbound stanzas ────────────────────────────────────
+
+    #[test]
+    fn outbound_type_depends_on_recipient() {
+        let ch = make_channel();
+        assert_eq!(ch.message_type("dev@conference.example.org"), "groupchat");
+        assert_eq!(ch.message_type("dev@conference.example.org/carol"), "chat");
+        assert_eq!(ch.message_type("alice@example.org"), "chat");
+    }
+
+    #[test]
+    fn correction_references_original_id() {
+        let ch = make_channel();
+        let xml = ch.correction("alice@example.org", "zc-1", "done").to_xml();
+        let parsed = stanza(&xml);
+        assert_eq!(parsed.child("body").unwrap().text(), "done");
+        assert_eq!(
+            parsed.child_ns("replace", NS_CORRECT).unwrap().attr("id"),
+            Some("zc-1")
+        );
+    }
+
+    #[test]
+    fn join_presence_requests_no_history() {
+        let ch = make_channel();
+        let presence = ch.join_presence("dev@conference.example.org");
+        assert_eq!(presence.attr("to"), Some("dev@conference.example.org/bot"));
+        let history = presence
+            .child_ns("x", NS_MUC)
+            .unwrap()
+            .child("history")
+            .unwrap();
+        assert_eq!(history.attr("maxstanzas"), Some("0"));
+    }
+
+    #[tokio::test]
+    async fn send_writes_message_stanza() {
+        let ch = make_channel();
+        let (client, mut server) = tokio::io::duplex(4096);
+        *ch.writer.lock().await = Some(Box::new(client));
+
+        ch.send(&SendMessage::new("hi & bye", "dev@conference.example.org"))
+            .await
+            .unwrap();
+        ch.writer.lock().await.take();
+
+        let mut out = String::new();
+        server.read_to_string(&mut out).await.unwrap();
+        let msg = stanza(&out);
+        assert_eq!(msg.attr("type"), Some("groupchat"));
+        assert_eq!(msg.child("body").unwrap().text(), "hi & bye");
+        assert!(msg.child_ns("active", NS_CHATSTATES).is_some());
+    }
+
+    #[tokio::test]
+    async fn send_without_connection_fails() {
+        let ch = make_channel();
+        assert!(ch
+            .send(&SendMessage::new("hi", "alice@example.org"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn send_draft_is_noop_when_streaming_off() {
+        let ch = make_channel();
+        assert!(!ch.supports_draft_updates());
+        let id = ch
+            .send_draft(&SendMessage::new("x", "alice@example.org"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+    }
+
+    // ── Stream negotiation ──────────────────────────────────
+
+    #[tokio::test]
+    async fn authenticate_runs_sasl_and_bind() {
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let header = "<stream:stream from='example.org' id='s' version='1.0' \
+                      xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams'>";
+        let script = format!(
+            "{header}<stream:features><mechanisms xmlns='{NS_SASL}'>\
+             <mechanism>SCRAM-SHA-1</mechanism><mechanism>PLAIN</mechanism></mechanisms>\
+             </stream:features><success xmlns='{NS_SASL}'/>\
+             {header}<stream:features><bind xmlns='{NS_BIND}'/>\
+             <session xmlns='{NS_SESSION}'><optional/></session></stream:features>\
+             <iq type='result' id='bind_1'><bind xmlns='{NS_BIND}'>\
+             <jid>bot@example.org/zeroclaw</jid></bind></iq>"
+        );
+        server.write_all(script.as_bytes()).await.unwrap();
+
+        let (read, mut write) = tokio::io::split(client);
+        let mut reader = StanzaReader::new(read);
+        let bound = authenticate(&mut reader, &mut write, "bot@example.org", "pw", "zeroclaw")
+            .await
+            .unwrap();
+        assert_eq!(bound, "bot@example.org/zeroclaw");
+        drop(reader);
+        drop(write);
+
+        let mut sent = String::new();
+        server.read_to_string(&mut sent).await.unwrap();
+        let expected = base64::engine::general_purpose::STANDARD.encode("\0bot\0pw");
+        assert!(sent.contains(&format!("mechanism='PLAIN'>{expected}</auth>")));
+        assert!(sent.contains("<resource>zeroclaw</resource>"));
+        assert!(!sent.contains("session_1"));
+    }
+
+    #[tokio::test]
+    async fn authenticate_reports_sasl_failure() {
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let script = format!(
+            "<stream:stream version='1.0'><stream:features><mechanisms xmlns='{NS_SASL}'>\
+             <mechanism>PLAIN</mechanism></mechanisms></stream:features>\
+             <failure xmlns='{NS_SASL}'><not-authorized/></failure>"
+        );
+        server.write_all(script.as_bytes()).await.unwrap();
+
+        let (read, mut write) = tokio::io::split(client);
+        let mut reader = StanzaReader::new(read);
+        let err = authenticate(&mut reader, &mut write, "bot@example.org", "bad", "r")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not-authorized"));
+    }
+
+    #[tokio::test]
+    async fn stanza_reader_handles_split_chunks() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut reader = StanzaReader::new(client);
+        tokio::spawn(async move {
+            for part in [" <message><bo", "dy>caf\u{e9}", "</body></message>"] {
+                server.write_all(part.as_bytes()).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+        let msg = reader.next_stanza().await.unwrap();
+        assert_eq!(msg.child("body").unwrap().text(), "café");
+    }
+}
@@ -0,0 +1,762 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Zulip's default maximum message length (characters).
+const ZULIP_MAX_MESSAGE_LENGTH: usize = 10000;
+
+/// Zulip answers idle long-polls with a heartbeat roughly every minute.
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Recipient prefix for stream messages (`stream:<name>`).
+const STREAM_PREFIX: &str = "stream:";
+
+/// Zulip channel — receives messages through the real-time event queue
+/// (`/register` + `/events` long-poll) and sends via REST API v1.
+///
+/// Stream messages use `stream:<name>` as the reply target and the topic as
+/// `thread_ts`; private messages use the comma-separated emails of the other
+/// participants.
+pub struct ZulipChannel {
+    site_url: String, // e.g., https://chat.zulip.org
+    email: String,
+    api_key: String,
+    /// Streams to listen on; empty means every subscribed stream.
+    streams: Vec<String>,
+    allowed_users: Vec<String>,
+    /// Topic used when sending to a stream without a thread.
+    default_topic: String,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
+    /// Topic of each open draft, so an oversized final reply stays in it.
+    draft_topics: Mutex<HashMap<String, String>>,
+    /// User ids by email, learned from inbound messages (typing needs ids).
+    user_ids: Mutex<HashMap<String, i64>>,
+}
+
+/// Event queue registration returned by `/register`.
+struct EventQueue {
+    queue_id: String,
+    last_event_id: i64,
+}
+
+impl ZulipChannel {
+    pub fn new(
+        site_url: String,
+        email: String,
+        api_key: String,
+        streams: Vec<String>,
+        allowed_users: Vec<String>,
+    ) -> Self {
+        let site_url = site_url.trim_end_matches('/').to_string();
+        Self {
+            site_url,
+            email,
+            api_key,
+            streams,
+            allowed_users,
+            default_topic: "general chat".into(),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
+            draft_topics: Mutex::new(HashMap::new()),
+            user_ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the topic used for stream messages sent outside a thread.
+    pub fn with_default_topic(mut self, topic: Option<String>) -> Self {
+        if let Some(topic) = topic.filter(|t| !t.trim().is_empty()) {
+            self.default_topic = topic;
+        }
+        self
+    }
+
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("channel.zulip")
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/api/v1/{path}", self.site_url)
+    }
+
+    /// Check a sender against the allowlist by email (case-insensitive) or
+    /// numeric user id. Empty list means deny everyone. "*" means allow everyone.
+    fn is_user_allowed(&self, email: &str, user_id: i64) -> bool {
+        let id = user_id.to_string();
+        self.allowed_users
+            .iter()
+            .any(|u| u == "*" || u.eq_ignore_ascii_case(email) || *u == id)
+    }
+
+    fn in_scope(&self, stream: &str) -> bool {
+        self.streams.is_empty() || self.streams.iter().any(|s| s.eq_ignore_ascii_case(stream))
+    }
+
+    /// Turn a REST response into JSON, surfacing Zulip's `msg` on errors.
+    async fn check_response(resp: reqwest::Response, action: &str) -> Result<Value> {
+        let status = resp.status();
+        let body: Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() || body.get("result").and_then(Value::as_str) == Some("error") {
+            let msg = body.get("msg").and_then(Value::as_str).unwrap_or("");
+            let code = body.get("code").and_then(Value::as_str).unwrap_or("");
+            bail!("Zulip {action} failed ({status} {code}): {msg}");
+        }
+        Ok(body)
+    }
+
+    /// Register an event queue for new messages.
+    async fn register(&self) -> Result<EventQueue> {
+        let resp = self
+            .http_client()
+            .post(self.api_url("register"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&[
+                ("event_types", r#"["message"]"#),
+                ("apply_markdown", "false"),
+                ("all_public_streams", "false"),
+            ])
+            .send()
+            .await?;
+        let body = Self::check_response(resp, "register").await?;
+        let Some(queue_id) = body.get("queue_id").and_then(Value::as_str) else {
+            bail!("Zulip register returned no queue_id");
+        };
+        Ok(EventQueue {
+            queue_id: queue_id.to_string(),
+            last_event_id: body
+                .get("last_event_id")
+                .and_then(Value::as_i64)
+                .unwrap_or(-1),
+        })
+    }
+
+    /// Long-poll the event queue. Returns `Ok(None)` when the queue has
+    /// expired and must be registered again.
+    async fn get_events(&self, queue: &EventQueue) -> Result<Option<Vec<Value>>> {
+        let resp = self
+            .http_client()
+            .get(self.api_url("events"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .query(&[
+                ("queue_id", queue.queue_id.clone()),
+                ("last_event_id", queue.last_event_id.to_string()),
+            ])
+            .timeout(LONG_POLL_TIMEOUT)
+            .send()
+            .await?;
+        match Self::check_response(resp, "events").await {
+            Ok(body) => Ok(Some(
+                body.get("events")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default(),
+            )),
+            Err(e) if e.to_string().contains("BAD_EVENT_QUEUE_ID") => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse_zulip_message(&self, message: &Value) -> Option<ChannelMessage> {
+        let id = message.get("id").and_then(Value::as_i64)?;
+        let sender_email = message.get("sender_email").and_then(Value::as_str)?;
+        let sender_id = message
+            .get("sender_id")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        if sender_email.eq_ignore_ascii_case(&self.email) {
+            return None;
+        }
+
+        let content = message
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .trim();
+        if content.is_empty() {
+            return None;
+        }
+
+        let (reply_target, thread_ts) = match message.get("type").and_then(Value::as_str)? {
+            "stream" => {
+                let stream = message.get("display_recipient").and_then(Value::as_str)?;
+                if !self.in_scope(stream) {
+                    return None;
+                }
+                let topic = message
+                    .get("subject")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                (format!("{STREAM_PREFIX}{stream}"), Some(topic.to_string()))
+            }
+            "private" => {
+                // Reply to everyone in the conversation except ourselves.
+                let others: Vec<&str> = message
+                    .get("display_recipient")
+                    .and_then(Value::as_array)?
+                    .iter()
+                    .filter_map(|r| r.get("email").and_then(Value::as_str))
+                    .filter(|e| !e.eq_ignore_ascii_case(&self.email))
+                    .collect();
+                if others.is_empty() {
+                    return None;
+                }
+                (others.join(","), None)
+            }
+            _ => return None,
+        };
+
+        if !self.is_user_allowed(sender_email, sender_id) {
+            tracing::warn!("Zulip: ignoring message from unauthorized user: {sender_email}");
+            return None;
+        }
+
+        if sender_id != 0 {
+            self.user_ids
+                .lock()
+                .insert(sender_email.to_ascii_lowercase(), sender_id);
+        }
+
+        Some(ChannelMessage {
+            id: format!("zulip_{id}"),
+            sender: sender_email.to_string(),
+            reply_target,
+            content: content.to_string(),
+            channel: "zulip".to_string(),
+            timestamp: message
+                .get("timestamp")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            thread_ts,
+            group: None,
+            voice: false,
+        })
+    }
+
+    /// Form fields addressing `recipient` (stream + topic, or private).
+    fn message_target(&self, recipient: &str, topic: Option<&str>) -> Vec<(&'static str, String)> {
+        match recipient.strip_prefix(STREAM_PREFIX) {
+            Some(stream) => vec![
+                ("type", "stream".into()),
+                ("to", stream.to_string()),
+                (
+                    "topic",
+                    topic
+                        .filter(|t| !t.is_empty())
+                        .unwrap_or(&self.default_topic)
+                        .to_string(),
+                ),
+            ],
+            None => {
+                let emails: Vec<&str> = recipient.split(',').map(str::trim).collect();
+                vec![
+                    ("type", "private".into()),
+                    ("to", serde_json::to_string(&emails).unwrap_or_default()),
+                ]
+            }
+        }
+    }
+
+    /// Send one message and return its id.
+    async fn post_message(
+        &self,
+        recipient: &str,
+        topic: Option<&str>,
+        text: &str,
+    ) -> Result<Option<String>> {
+        let mut form = self.message_target(recipient, topic);
+        form.push(("content", text.to_string()));
+        let resp = self
+            .http_client()
+            .post(self.api_url("messages"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&form)
+            .send()
+            .await?;
+        let body = Self::check_response(resp, "send").await?;
+        Ok(body
+            .get("id")
+            .and_then(Value::as_i64)
+            .map(|id| id.to_string()))
+    }
+
+    async fn edit_message(&self, message_id: &str, text: &str) -> Result<()> {
+        let resp = self
+            .http_client()
+            .patch(self.api_url(&format!("messages/{}", raw_message_id(message_id))))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&[("content", text)])
+            .send()
+            .await?;
+        Self::check_response(resp, "edit").await.map(|_| ())
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<()> {
+        let resp = self
+            .http_client()
+            .delete(self.api_url(&format!("messages/{}", raw_message_id(message_id))))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .send()
+            .await?;
+        Self::check_response(resp, "delete").await.map(|_| ())
+    }
+
+    async fn set_typing(&self, recipient: &str, op: &str) -> Result<()> {
+        // Stream typing needs a stream id and topic; only direct messages
+        // get an indicator.
+        if recipient.starts_with(STREAM_PREFIX) {
+            return Ok(());
+        }
+        let ids: Vec<i64> = {
+            let known = self.user_ids.lock();
+            recipient
+                .split(',')
+                .filter_map(|e| known.get(&e.trim().to_ascii_lowercase()).copied())
+                .collect()
+        };
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let resp = self
+            .http_client()
+            .post(self.api_url("typing"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&[
+                ("op", op.to_string()),
+                ("type", "direct".to_string()),
+                ("to", serde_json::to_string(&ids).unwrap_or_default()),
+            ])
+            .send()
+            .await?;
+        Self::check_response(resp, "typing").await.map(|_| ())
+    }
+
+    async fn react(&self, message_id: &str, emoji: &str, add: bool) -> Result<()> {
+        let Some(name) = zulip_emoji_name(emoji) else {
+            bail!("Zulip: no emoji name known for {emoji:?}");
+        };
+        let url = self.api_url(&format!(
+            "messages/{}/reactions",
+            raw_message_id(message_id)
+        ));
+        let client = self.http_client();
+        let request = if add {
+            client.post(url)
+        } else {
+            client.delete(url)
+        };
+        let resp = request
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&[("emoji_name", name.as_str())])
+            .send()
+            .await?;
+        Self::check_response(resp, "reaction").await.map(|_| ())
+    }
+}
+
+/// `zulip_123` → `123`.
+fn raw_message_id(message_id: &str) -> &str {
+    message_id.strip_prefix("zulip_").unwrap_or(message_id)
+}
+
+/// Split text into chunks that fit Zulip's message limit, preferring
+/// line breaks.
+fn split_message(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > ZULIP_MAX_MESSAGE_LENGTH {
+        let limit = rest
+            .char_indices()
+            .nth(ZULIP_MAX_MESSAGE_LENGTH)
+            .map_or(rest.len(), |(i, _)| i);
+        let cut = rest[..limit]
+            .rfind('\n')
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        chunks.push(&rest[..cut]);
+        rest = rest[cut..].trim_start_matches('\n');
+    }
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Map a Unicode emoji to Zulip's emoji name. Names (optionally wrapped in
+/// colons) are passed through.
+fn zulip_emoji_name(emoji: &str) -> Option<String> {
+    let trimmed = emoji.trim().trim_matches(':');
+    if !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'))
+    {
+        return Some(trimmed.to_string());
+    }
+    let name = match trimmed.trim_end_matches('\u{FE0F}') {
+        "\u{1F440}" => "eyes",
+        "\u{2705}" => "check",
+        "\u{26A0}" => "warning",
+        "\u{274C}" => "cross_mark",
+        "\u{1F44D}" => "+1",
+        "\u{1F44E}" => "-1",
+        "\u{1F389}" => "tada",
+        "\u{1F914}" => "thinking",
+        "\u{2764}" => "heart",
+        "\u{1F525}" => "fire",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+#[async_trait]
+impl Channel for ZulipChannel {
+    fn name(&self) -> &str {
+        "zulip"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        for chunk in split_message(&message.content) {
+            self.post_message(&message.recipient, message.thread_ts.as_deref(), chunk)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let mut queue = self.register().await?;
+        tracing::info!(
+            "Zulip channel listening on {}...",
+            if self.streams.is_empty() {
+                "all subscribed streams".to_string()
+            } else {
+                self.streams.join(", ")
+            }
+        );
+
+        loop {
+            let Some(events) = self.get_events(&queue).await? else {
+                tracing::info!("Zulip event queue expired; registering a new one");
+                queue = self.register().await?;
+                continue;
+            };
+
+            for event in &events {
+                if let Some(id) = event.get("id").and_then(Value::as_i64) {
+                    queue.last_event_id = queue.last_event_id.max(id);
+                }
+                if event.get("type").and_then(Value::as_str) != Some("message") {
+                    continue;
+                }
+                let Some(msg) = event
+                    .get("message")
+                    .and_then(|m| self.parse_zulip_message(m))
+                else {
+                    continue;
+                };
+                if tx.send(msg).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        self.http_client()
+            .get(self.api_url("users/me"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn start_typing(&self, recipient: &str) -> Result<()> {
+        self.set_typing(recipient, "start").await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> Result<()> {
+        self.set_typing(recipient, "stop").await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            split_message(&message.content)[0]
+        };
+        let id = self
+            .post_message(
+                &message.recipient,
+                message.thread_ts.as_deref(),
+                initial_text,
+            )
+            .await?;
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+        if let (Some(id), Some(topic)) = (id.as_ref(), message.thread_ts.as_ref()) {
+            self.draft_topics.lock().insert(id.clone(), topic.clone());
+        }
+        Ok(id)
+    }
+
+    async fn update_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        // Rate-limit edits per recipient
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        match self.edit_message(message_id, split_message(text)[0]).await {
+            Ok(()) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(recipient.to_string(), Instant::now());
+            }
+            Err(e) => tracing::debug!("Zulip draft update failed: {e}"),
+        }
+        Ok(())
+    }
+
+    async fn finalize_draft(&self, recipient: &str, message_id: &str, text: &str) -> Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        let topic = self.draft_topics.lock().remove(raw_message_id(message_id));
+
+        let chunks = split_message(text);
+        if chunks.len() == 1 && self.edit_message(message_id, text).await.is_ok() {
+            return Ok(());
+        }
+
+        // Too long for one message or the edit failed: replace the draft.
+        if let Err(e) = self.delete_message(message_id).await {
+            tracing::debug!("Zulip draft delete failed: {e}");
+        }
+        for chunk in chunks {
+            self.post_message(recipient, topic.as_deref(), chunk)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        self.draft_topics.lock().remove(raw_message_id(message_id));
+        self.delete_message(message_id).await
+    }
+
+    async fn add_reaction(&self, _channel_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        self.react(message_id, emoji, true).await
+    }
+
+    async fn remove_reaction(
+        &self,
+        _channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> Result<()> {
+        self.react(message_id, emoji, false).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn make_channel(streams: Vec<String>, allowed: Vec<String>) -> ZulipChannel {
+        ZulipChannel::new(
+            "https://zulip.example.com/".into(),
+            "bot@zulip.example.com".into(),
+            "key".into(),
+            streams,
+            allowed,
+        )
+    }
+
+    fn stream_message(stream: &str, topic: &str, sender: &str) -> Value {
+        json!({
+            "id": 42,
+            "type": "stream",
+            "display_recipient": stream,
+            "subject": topic,
+            "sender_email": sender,
+            "sender_id": 7,
+            "content": " hello ",
+            "timestamp": 1_700_000_000
+        })
+    }
+
+    #[test]
+    fn zulip_url_trimming() {
+        let ch = make_channel(vec![], vec![]);
+        assert_eq!(ch.site_url, "https://zulip.example.com");
+        assert_eq!(
+            ch.api_url("events"),
+            "https://zulip.example.com/api/v1/events"
+        );
+    }
+
+    #[test]
+    fn zulip_allowlist_matches_email_or_id() {
+        let ch = make_channel(vec![], vec!["Alice@Example.com".into(), "9".into()]);
+        assert!(ch.is_user_allowed("alice@example.com", 1));
+        assert!(ch.is_user_allowed("bob@example.com", 9));
+        assert!(!ch.is_user_allowed("bob@example.com", 2));
+        assert!(make_channel(vec![], vec!["*".into()]).is_user_allowed("x@y", 0));
+        assert!(!make_channel(vec![], vec![]).is_user_allowed("x@y", 0));
+    }
+
+    #[test]
+    fn zulip_parse_stream_message_maps_topic_to_thread() {
+        let ch = make_channel(vec![], vec!["*".into()]);
+        let msg = ch
+            .parse_zulip_message(&stream_message("general", "deploys", "alice@example.com"))
+            .unwrap();
+        assert_eq!(msg.id, "zulip_42");
+        assert_eq!(msg.sender, "alice@example.com");
+        assert_eq!(msg.reply_target, "stream:general");
+        assert_eq!(msg.thread_ts.as_deref(), Some("deploys"));
+        assert_eq!(msg.content, "hello");
+        assert_eq!(msg.channel, "zulip");
+        assert_eq!(msg.timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn zulip_parse_skips_streams_out_of_scope() {
+        let ch = make_channel(vec!["ops".into()], vec!["*".into()]);
+        assert!(ch
+            .parse_zulip_message(&stream_message("general", "t", "alice@example.com"))
+            .is_none());
+        assert!(ch
+            .parse_zulip_message(&stream_message("Ops", "t", "alice@example.com"))
+            .is_some());
+    }
+
+    #[test]
+    fn zulip_parse_skips_own_and_unauthorized_messages() {
+        let ch = make_channel(vec![], vec!["alice@example.com".into()]);
+        assert!(ch
+            .parse_zulip_message(&stream_message("general", "t", "bot@zulip.example.com"))
+            .is_none());
+        assert!(ch
+            .parse_zulip_message(&stream_message("general", "t", "mallory@example.com"))
+            .is_none());
+    }
+
+    #[test]
+    fn zulip_parse_private_message_replies_to_other_participants() {
+        let ch = make_channel(vec![], vec!["*".into()]);
+        let msg = ch
+            .parse_zulip_message(&json!({
+                "id": 5,
+                "type": "private",
+                "display_recipient": [
+                    {"email": "alice@example.com", "id": 7},
+                    {"email": "bot@zulip.example.com", "id": 1},
+                    {"email": "carol@example.com", "id": 8}
+                ],
+                "sender_email": "alice@example.com",
+                "sender_id": 7,
+                "content": "hi"
+            }))
+            .unwrap();
+        assert_eq!(msg.reply_target, "alice@example.com,carol@example.com");
+        assert!(msg.thread_ts.is_none());
+        assert_eq!(ch.user_ids.lock().get("alice@example.com"), Some(&7));
+    }
+
+    #[test]
+    fn zulip_message_target_for_stream_and_private() {
+        let ch = make_channel(vec![], vec![]).with_default_topic(Some("bot".into()));
+        assert_eq!(
+            ch.message_target("stream:general", Some("deploys")),
+            vec![
+                ("type", "stream".to_string()),
+                ("to", "general".to_string()),
+                ("topic", "deploys".to_string()),
+            ]
+        );
+        assert_eq!(ch.message_target("stream:general", None)[2].1, "bot");
+        assert_eq!(
+            ch.message_target("a@x.com,b@x.com", None),
+            vec![
+                ("type", "private".to_string()),
+                ("to", r#"["a@x.com","b@x.com"]"#.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn zulip_split_message_prefers_line_breaks() {
+        assert_eq!(split_message("short"), vec!["short"]);
+        let line = "x".repeat(6000);
+        let text = format!("{line}\n{line}");
+        let chunks = split_message(&text);
+        assert_eq!(chunks, vec![line.as_str(), line.as_str()]);
+
+        let long = "y".repeat(ZULIP_MAX_MESSAGE_LENGTH + 5);
+        let chunks = split_message(&long);
+        assert_eq!(chunks[0].len(), ZULIP_MAX_MESSAGE_LENGTH);
+        assert_eq!(chunks[1].len(), 5);
+    }
+
+    #[test]
+    fn zulip_emoji_names() {
+        assert_eq!(zulip_emoji_name("\u{1F440}").as_deref(), Some("eyes"));
+        assert_eq!(zulip_emoji_name("\u{2705}").as_deref(), Some("check"));
+        assert_eq!(
+            zulip_emoji_name("\u{26A0}\u{FE0F}").as_deref(),
+            Some("warning")
+        );
+        assert_eq!(zulip_emoji_name(":rocket:").as_deref(), Some("rocket"));
+        assert!(zulip_emoji_name("\u{1F9A9}").is_none());
+    }
+
+    #[test]
+    fn zulip_raw_message_id_strips_prefix() {
+        assert_eq!(raw_message_id("zulip_42"), "42");
+        assert_eq!(raw_message_id("42"), "42");
+    }
+
+    #[tokio::test]
+    async fn zulip_send_draft_is_noop_when_streaming_off() {
+        let ch = make_channel(vec![], vec![]);
+        assert!(!ch.supports_draft_updates());
+        assert!(ch
+            .send_draft(&SendMessage::new("x", "stream:general"))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
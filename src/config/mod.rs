@@ -7,15 +7,19 @@ pub use schema::{
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
     AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
     BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
-    GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig,
-    ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig,
-    PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope, QueryClassificationConfig,
+    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EgressConfig,
+    EmbeddingRouteConfig, GatewayConfig, GatewayJobsConfig, GatewayScope, GatewayTlsConfig,
+    GatewayTokenConfig, GroupPolicyConfig, GroupReplyMode, HardwareConfig, HardwareTransport,
+    HeartbeatConfig, HookVerification, HooksConfig, HttpRequestConfig, IMessageConfig,
+    IdentityConfig, InboundHookConfig, InjectionAction, InjectionGuardConfig, LarkConfig,
+    MatrixConfig, MemoryConfig, ModelReasoningConfig, ModelRouteConfig, MqttConfig, MqttProtocol,
+    MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, PeripheralBoardConfig,
+    PeripheralsConfig, ProxyConfig, ProxyScope, QueryClassificationConfig, ReasoningDisplay,
     ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
     SchedulerConfig, SecretsConfig, SecurityConfig, SkillsConfig, SkillsPromptInjectionMode,
     SlackConfig, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
-    TelegramConfig, TranscriptionConfig, TunnelConfig, WebSearchConfig, WebhookConfig,
+    TelegramConfig, TranscriptionConfig, TtsConfig, TtsProvider, TunnelConfig, VoiceReplyMode,
+    WebSearchConfig, WebhookConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: &Option<T>) -> (&'static str, bool) {
@@ -44,6 +48,7 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            group: GroupPolicyConfig::default(),
         };
 
         let discord = DiscordConfig {
@@ -52,6 +57,7 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            group: GroupPolicyConfig::default(),
         };
 
         let lark = LarkConfig {
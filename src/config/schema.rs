@@ -40,6 +40,8 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
+    "tts.openai",
+    "gateway.jobs",
 ];
 
 const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
@@ -49,6 +51,8 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
     "memory.*",
     "tunnel.*",
     "transcription.*",
+    "tts.*",
+    "gateway.*",
 ];
 
 static RUNTIME_PROXY_CONFIG: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
@@ -87,6 +91,10 @@ pub struct Config {
     #[serde(default)]
     pub autonomy: AutonomyConfig,
 
+    /// Sandboxing, per-command resource limits and audit logging (`[security]`).
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     /// Runtime adapter configuration (`[runtime]`). Controls native vs Docker execution.
     #[serde(default)]
     pub runtime: RuntimeConfig,
@@ -202,6 +210,10 @@ pub struct Config {
     /// Voice transcription configuration (Whisper API via Groq).
     #[serde(default)]
     pub transcription: TranscriptionConfig,
+
+    /// Text-to-speech voice replies (`[tts]`).
+    #[serde(default)]
+    pub tts: TtsConfig,
 }
 
 // ── Delegate Agents ──────────────────────────────────────────────
@@ -360,6 +372,102 @@ impl Default for TranscriptionConfig {
     }
 }
 
+// ── Text-to-speech ───────────────────────────────────────────────
+
+fn default_tts_api_url() -> String {
+    "https://api.openai.com/v1/audio/speech".into()
+}
+
+fn default_tts_model() -> String {
+    "gpt-4o-mini-tts".into()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".into()
+}
+
+fn default_tts_max_chars() -> usize {
+    1500
+}
+
+/// Speech synthesis backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TtsProvider {
+    /// OpenAI-compatible `/audio/speech` endpoint.
+    #[default]
+    OpenAi,
+    /// Local command (e.g. Piper) that reads text on stdin and writes audio.
+    Command,
+}
+
+/// When channels answer with a voice note in addition to text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VoiceReplyMode {
+    /// Never send voice notes.
+    Off,
+    /// Reply by voice when the user's message was a voice note.
+    #[default]
+    Voice,
+    /// Reply by voice to every message.
+    Always,
+}
+
+/// Spoken replies for voice-capable channels (`[tts]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TtsConfig {
+    /// Enable voice-note replies on channels that support them.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Synthesis backend: `"openai"` (default) or `"command"`.
+    #[serde(default)]
+    pub provider: TtsProvider,
+    /// Speech endpoint for the `openai` backend.
+    #[serde(default = "default_tts_api_url")]
+    pub api_url: String,
+    /// API key for the `openai` backend. Falls back to `OPENAI_API_KEY`.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Speech model for the `openai` backend.
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    /// Voice name for the `openai` backend.
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    /// Program and arguments for the `command` backend. The reply text is
+    /// written to stdin; `{output}` in an argument is replaced with the file
+    /// to write, otherwise audio is read from stdout.
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Default voice reply mode: `"off"`, `"voice"` (default) or `"always"`.
+    #[serde(default)]
+    pub reply: VoiceReplyMode,
+    /// Per-channel overrides of `reply`, keyed by channel name.
+    #[serde(default)]
+    pub channels: HashMap<String, VoiceReplyMode>,
+    /// Replies longer than this many characters are sent as text only.
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: TtsProvider::default(),
+            api_url: default_tts_api_url(),
+            api_key: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            command: Vec::new(),
+            reply: VoiceReplyMode::default(),
+            channels: HashMap::new(),
+            max_chars: default_tts_max_chars(),
+        }
+    }
+}
+
 /// Agent orchestration configuration (`[agent]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
@@ -549,6 +657,11 @@ pub struct CostConfig {
     #[serde(default)]
     pub allow_override: bool,
 
+    /// Discount applied to requests served through provider batch APIs,
+    /// as a percentage of the regular price (default: 50)
+    #[serde(default = "default_batch_discount_percent")]
+    pub batch_discount_percent: u8,
+
     /// Per-model pricing (USD per 1M tokens)
     #[serde(default)]
     pub prices: std::collections::HashMap<String, ModelPricing>,
@@ -564,6 +677,28 @@ pub struct ModelPricing {
     /// Output price per 1M tokens
     #[serde(default)]
     pub output: f64,
+
+    /// Price per 1M prompt tokens read from the provider's prompt cache.
+    /// Falls back to `input` when unset.
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+
+    /// Price per 1M prompt tokens written to the provider's prompt cache.
+    /// Falls back to `input` when unset.
+    #[serde(default)]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Effective cache-read price (USD per 1M tokens).
+    pub fn cache_read_price(&self) -> f64 {
+        self.cache_read.unwrap_or(self.input)
+    }
+
+    /// Effective cache-write price (USD per 1M tokens).
+    pub fn cache_write_price(&self) -> f64 {
+        self.cache_write.unwrap_or(self.input)
+    }
 }
 
 fn default_daily_limit() -> f64 {
@@ -578,6 +713,10 @@ fn default_warn_percent() -> u8 {
     80
 }
 
+fn default_batch_discount_percent() -> u8 {
+    50
+}
+
 impl Default for CostConfig {
     fn default() -> Self {
         Self {
@@ -586,6 +725,7 @@ impl Default for CostConfig {
             monthly_limit_usd: default_monthly_limit(),
             warn_at_percent: default_warn_percent(),
             allow_override: false,
+            batch_discount_percent: default_batch_discount_percent(),
             prices: get_default_pricing(),
         }
     }
@@ -601,6 +741,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 3.0,
             output: 15.0,
+            cache_read: Some(0.30),
+            cache_write: Some(3.75),
         },
     );
     prices.insert(
@@ -608,6 +750,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 15.0,
             output: 75.0,
+            cache_read: Some(1.50),
+            cache_write: Some(18.75),
         },
     );
     prices.insert(
@@ -615,6 +759,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 3.0,
             output: 15.0,
+            cache_read: Some(0.30),
+            cache_write: Some(3.75),
         },
     );
     prices.insert(
@@ -622,6 +768,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.25,
             output: 1.25,
+            cache_read: Some(0.03),
+            cache_write: Some(0.30),
         },
     );
 
@@ -631,6 +779,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 5.0,
             output: 15.0,
+            cache_read: Some(2.50),
+            cache_write: None,
         },
     );
     prices.insert(
@@ -638,6 +788,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.15,
             output: 0.60,
+            cache_read: Some(0.075),
+            cache_write: None,
         },
     );
     prices.insert(
@@ -645,6 +797,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 15.0,
             output: 60.0,
+            cache_read: Some(7.50),
+            cache_write: None,
         },
     );
 
@@ -654,6 +808,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.10,
             output: 0.40,
+            cache_read: Some(0.025),
+            cache_write: None,
         },
     );
     prices.insert(
@@ -661,6 +817,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 1.25,
             output: 5.0,
+            cache_read: None,
+            cache_write: None,
         },
     );
 
@@ -768,6 +926,239 @@ pub struct GatewayConfig {
     /// Maximum distinct idempotency keys retained in memory.
     #[serde(default = "default_gateway_idempotency_max_keys")]
     pub idempotency_max_keys: usize,
+
+    /// Named, scoped API tokens (managed with `zeroclaw gateway tokens`).
+    #[serde(default)]
+    pub tokens: Vec<GatewayTokenConfig>,
+
+    /// Asynchronous `/webhook` jobs (`[gateway.jobs]`).
+    #[serde(default)]
+    pub jobs: GatewayJobsConfig,
+
+    /// Inbound event endpoints served at `/hooks/{name}` (`[gateway.hooks.<name>]`).
+    #[serde(default)]
+    pub hooks: HashMap<String, InboundHookConfig>,
+
+    /// Built-in TLS termination (`[gateway.tls]`).
+    #[serde(default)]
+    pub tls: GatewayTlsConfig,
+}
+
+/// How an inbound hook authenticates its sender.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookVerification {
+    /// Gateway bearer token with the `chat` scope
+    #[default]
+    Bearer,
+    /// `secret_header` must equal `secret`
+    SharedSecret,
+    /// GitHub `X-Hub-Signature-256` (HMAC-SHA256 of the body)
+    Github,
+    /// GitLab `X-Gitlab-Token`
+    Gitlab,
+    /// Stripe-style `Stripe-Signature: t=<ts>,v1=<hmac of "ts.body">`
+    Stripe,
+}
+
+/// Inbound event hook (`[gateway.hooks.<name>]`).
+///
+/// Verifies the sender, renders the JSON payload into a prompt with
+/// `template` and runs it as an async job; the answer can be sent to a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InboundHookConfig {
+    /// Accept events on this hook (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Signature scheme (default: bearer)
+    #[serde(default)]
+    pub verify: HookVerification,
+    /// Signing key or shared secret (stored encrypted when secrets.encrypt = true)
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Header compared with `secret` for `shared_secret` (default: X-Webhook-Secret)
+    #[serde(default = "default_hook_secret_header")]
+    pub secret_header: String,
+    /// Event names to accept, e.g. `issues.opened` or `issues`; empty = all
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Prompt template with `{{event}}`, `{{hook}}`, `{{payload}}`,
+    /// `{{payload.<path>}}` and `{{header.<name>}}` placeholders
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Channel that receives the agent's answer (telegram, discord, slack, mattermost)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel` (chat id, channel id, ...)
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+fn default_hook_secret_header() -> String {
+    "X-Webhook-Secret".into()
+}
+
+/// Asynchronous webhook jobs (`[gateway.jobs]`).
+///
+/// `POST /webhook` with `"async": true` queues the message and returns a job
+/// id; `GET /api/jobs/{id}` reports progress and the final answer.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayJobsConfig {
+    /// Maximum jobs running at once (default: 2)
+    #[serde(default = "default_gateway_jobs_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Hours to keep finished jobs before pruning (default: 168)
+    #[serde(default = "default_gateway_jobs_retention_hours")]
+    pub retention_hours: u64,
+    /// HMAC-SHA256 key for signing completion callbacks. Required to accept
+    /// `callback_url` (stored encrypted when secrets.encrypt = true).
+    #[serde(default)]
+    pub callback_secret: Option<String>,
+    /// Timeout per callback attempt in seconds (default: 10)
+    #[serde(default = "default_gateway_jobs_callback_timeout_secs")]
+    pub callback_timeout_secs: u64,
+    /// Delivery attempts per callback before giving up (default: 3)
+    #[serde(default = "default_gateway_jobs_callback_max_attempts")]
+    pub callback_max_attempts: u32,
+}
+
+fn default_gateway_jobs_max_concurrent() -> usize {
+    2
+}
+
+fn default_gateway_jobs_retention_hours() -> u64 {
+    168
+}
+
+fn default_gateway_jobs_callback_timeout_secs() -> u64 {
+    10
+}
+
+fn default_gateway_jobs_callback_max_attempts() -> u32 {
+    3
+}
+
+impl Default for GatewayJobsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_gateway_jobs_max_concurrent(),
+            retention_hours: default_gateway_jobs_retention_hours(),
+            callback_secret: None,
+            callback_timeout_secs: default_gateway_jobs_callback_timeout_secs(),
+            callback_max_attempts: default_gateway_jobs_callback_max_attempts(),
+        }
+    }
+}
+
+/// Built-in TLS termination for the gateway (`[gateway.tls]`).
+///
+/// Serves HTTPS directly, without a tunnel or reverse proxy. Certificate
+/// files are re-read when they change, so renewals need no restart.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayTlsConfig {
+    /// Serve HTTPS instead of plain HTTP (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// PEM certificate chain, leaf first
+    #[serde(default)]
+    pub cert_path: String,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1)
+    #[serde(default)]
+    pub key_path: String,
+    /// PEM CA bundle for client certificates. When set, every client must
+    /// present a certificate signed by one of these CAs (mutual TLS).
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+    /// Accept a verified client certificate in place of a pairing token
+    /// (default: false; requires `client_ca_path`)
+    #[serde(default)]
+    pub client_cert_auth: bool,
+    /// Seconds between checks for changed certificate files (default: 30, 0 = never)
+    #[serde(default = "default_gateway_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_gateway_tls_reload_interval_secs() -> u64 {
+    30
+}
+
+impl Default for GatewayTlsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cert_path: String::new(),
+            key_path: String::new(),
+            client_ca_path: None,
+            client_cert_auth: false,
+            reload_interval_secs: default_gateway_tls_reload_interval_secs(),
+        }
+    }
+}
+
+/// Access scope granted to a gateway API token.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GatewayScope {
+    /// Send messages: `/webhook` and `/ws/chat`
+    Chat,
+    /// Read-only dashboard: `GET /api/*` and `/api/events`
+    Read,
+    /// Everything, including config, cron and memory changes
+    Admin,
+}
+
+impl GatewayScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::Read => "read",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::fmt::Display for GatewayScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for GatewayScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "chat" => Ok(Self::Chat),
+            "read" => Ok(Self::Read),
+            "admin" => Ok(Self::Admin),
+            other => Err(format!(
+                "unknown gateway scope '{other}' (expected chat, read or admin)"
+            )),
+        }
+    }
+}
+
+/// Named gateway API token (`[[gateway.tokens]]`).
+///
+/// Only the SHA-256 hash of the bearer token is stored; the plaintext is
+/// printed once by `zeroclaw gateway tokens create`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayTokenConfig {
+    /// Unique token name (e.g. "monitoring")
+    pub name: String,
+    /// SHA-256 hash of the bearer token (hex)
+    pub token_hash: String,
+    /// Granted scopes
+    pub scopes: Vec<GatewayScope>,
+    /// RFC 3339 expiry; unset = never expires
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Max requests per minute for this token across all endpoints (0 = unlimited)
+    #[serde(default)]
+    pub rate_limit_per_minute: u32,
+    /// RFC 3339 creation time
+    #[serde(default)]
+    pub created_at: Option<String>,
 }
 
 fn default_gateway_port() -> u16 {
@@ -816,6 +1207,10 @@ impl Default for GatewayConfig {
             rate_limit_max_keys: default_gateway_rate_limit_max_keys(),
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
+            tokens: Vec::new(),
+            jobs: GatewayJobsConfig::default(),
+            hooks: HashMap::new(),
+            tls: GatewayTlsConfig::default(),
         }
     }
 }
@@ -1983,6 +2378,37 @@ pub struct RuntimeConfig {
     /// - `Some(false)`: disable reasoning/thinking when supported
     #[serde(default)]
     pub reasoning_enabled: Option<bool>,
+
+    /// Default reasoning effort (`low` | `medium` | `high`) for providers with
+    /// effort controls (OpenAI, OpenRouter).
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+
+    /// Default thinking token budget for providers with budget controls
+    /// (Anthropic extended thinking, Gemini thinking).
+    #[serde(default)]
+    pub reasoning_budget_tokens: Option<u32>,
+
+    /// Per-model overrides keyed by model name
+    /// (`[runtime.reasoning_models."claude-sonnet-4"]`).
+    #[serde(default)]
+    pub reasoning_models: HashMap<String, ModelReasoningConfig>,
+}
+
+/// Per-model reasoning override (`[runtime.reasoning_models."<model>"]`).
+///
+/// Unset fields fall back to the global `[runtime]` reasoning settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ModelReasoningConfig {
+    /// Enable or disable reasoning for this model.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Reasoning effort (`low` | `medium` | `high`).
+    #[serde(default)]
+    pub effort: Option<String>,
+    /// Thinking token budget.
+    #[serde(default)]
+    pub budget_tokens: Option<u32>,
 }
 
 /// Docker runtime configuration (`[runtime.docker]` section).
@@ -2057,6 +2483,9 @@ impl Default for RuntimeConfig {
             kind: default_runtime_kind(),
             docker: DockerRuntimeConfig::default(),
             reasoning_enabled: None,
+            reasoning_effort: None,
+            reasoning_budget_tokens: None,
+            reasoning_models: HashMap::new(),
         }
     }
 }
@@ -2445,6 +2874,12 @@ pub struct ChannelsConfig {
     pub email: Option<crate::channels::email_channel::EmailConfig>,
     /// IRC channel configuration.
     pub irc: Option<IrcConfig>,
+    /// XMPP channel configuration.
+    pub xmpp: Option<XmppConfig>,
+    /// Zulip channel configuration.
+    pub zulip: Option<ZulipConfig>,
+    /// MQTT channel configuration.
+    pub mqtt: Option<MqttConfig>,
     /// Lark/Feishu channel configuration.
     pub lark: Option<LarkConfig>,
     /// DingTalk channel configuration.
@@ -2461,6 +2896,10 @@ pub struct ChannelsConfig {
     /// Default: 300s for on-device LLMs (Ollama) which are slower than cloud APIs.
     #[serde(default = "default_channel_message_timeout_secs")]
     pub message_timeout_secs: u64,
+    /// How model reasoning ("thinking") is shown in channel replies.
+    /// Default: `hide`.
+    #[serde(default)]
+    pub reasoning_display: ReasoningDisplay,
 }
 
 impl ChannelsConfig {
@@ -2516,6 +2955,18 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(&self.irc)),
                 self.irc.is_some()
             ),
+            (
+                Box::new(ConfigWrapper::new(&self.xmpp)),
+                self.xmpp.is_some(),
+            ),
+            (
+                Box::new(ConfigWrapper::new(&self.zulip)),
+                self.zulip.is_some(),
+            ),
+            (
+                Box::new(ConfigWrapper::new(&self.mqtt)),
+                self.mqtt.is_some(),
+            ),
             (
                 Box::new(ConfigWrapper::new(&self.lark)),
                 self.lark.is_some(),
@@ -2570,16 +3021,33 @@ impl Default for ChannelsConfig {
             nextcloud_talk: None,
             email: None,
             irc: None,
+            xmpp: None,
+            zulip: None,
+            mqtt: None,
             lark: None,
             dingtalk: None,
             qq: None,
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
+            reasoning_display: ReasoningDisplay::default(),
         }
     }
 }
 
+/// How model reasoning is rendered in channel replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningDisplay {
+    /// Never show reasoning (default).
+    #[default]
+    Hide,
+    /// Prefix the reply with a short excerpt of the reasoning.
+    Summary,
+    /// Prefix the reply with the full reasoning.
+    Show,
+}
+
 /// Streaming mode for channels that support progressive message updates.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -2595,6 +3063,36 @@ fn default_draft_update_interval_ms() -> u64 {
     1000
 }
 
+/// When the agent replies to messages in group chats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupReplyMode {
+    /// Reply to every permitted message (default, matches direct chats).
+    #[default]
+    Always,
+    /// Reply only when the bot is @-mentioned or a message replies to it.
+    Mention,
+    /// Reply when mentioned, replied to, or a configured keyword appears.
+    Keywords,
+}
+
+/// Group-chat policy (`[channels_config.<channel>.group]`).
+///
+/// Messages in rooms that do not trigger a reply are still recorded in the
+/// room's shared history so later replies see the whole conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+pub struct GroupPolicyConfig {
+    /// When to reply in rooms: `"always"`, `"mention"` or `"keywords"`.
+    #[serde(default)]
+    pub reply: GroupReplyMode,
+    /// Case-insensitive trigger words for `reply = "keywords"`.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Maximum agent replies per room per minute. `0` = unlimited.
+    #[serde(default)]
+    pub max_replies_per_minute: u32,
+}
+
 /// Telegram bot channel configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TelegramConfig {
@@ -2613,9 +3111,13 @@ pub struct TelegramConfig {
     #[serde(default)]
     pub interrupt_on_new_message: bool,
     /// When true, only respond to messages that @-mention the bot in groups.
-    /// Direct messages are always processed.
+    /// Direct messages are always processed. Unmentioned messages are dropped
+    /// before they reach the room history; prefer `group.reply = "mention"`.
     #[serde(default)]
     pub mention_only: bool,
+    /// Group-chat reply policy, shared room history and rate limit.
+    #[serde(default)]
+    pub group: GroupPolicyConfig,
 }
 
 impl ChannelConfig for TelegramConfig {
@@ -2642,9 +3144,13 @@ pub struct DiscordConfig {
     #[serde(default)]
     pub listen_to_bots: bool,
     /// When true, only respond to messages that @-mention the bot.
-    /// Other messages in the guild are silently ignored.
+    /// Other messages in the guild are silently ignored and never reach the
+    /// room history; prefer `group.reply = "mention"`.
     #[serde(default)]
     pub mention_only: bool,
+    /// Group-chat reply policy for guild channels.
+    #[serde(default)]
+    pub group: GroupPolicyConfig,
 }
 
 impl ChannelConfig for DiscordConfig {
@@ -2661,14 +3167,22 @@ impl ChannelConfig for DiscordConfig {
 pub struct SlackConfig {
     /// Slack bot OAuth token (xoxb-...).
     pub bot_token: String,
-    /// Slack app-level token for Socket Mode (xapp-...).
+    /// Slack app-level token for Socket Mode (xapp-...). When set, events
+    /// arrive over a WebSocket instead of `conversations.history` polling.
     pub app_token: Option<String>,
+    /// Signing secret for the Events API. When set, the gateway accepts
+    /// events and button clicks at `POST /slack/events`.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
     /// Optional channel ID to restrict the bot to a single channel.
     /// Omit (or set `"*"`) to listen across all accessible channels.
     pub channel_id: Option<String>,
     /// Allowed Slack user IDs. Empty = deny all.
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Group-chat reply policy for channels and multi-person DMs.
+    #[serde(default)]
+    pub group: GroupPolicyConfig,
 }
 
 impl ChannelConfig for SlackConfig {
@@ -2700,6 +3214,12 @@ pub struct MattermostConfig {
     /// Other messages in the channel are silently ignored.
     #[serde(default)]
     pub mention_only: Option<bool>,
+    /// Streaming mode for progressive response delivery via post edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft post edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
 }
 
 impl ChannelConfig for MattermostConfig {
@@ -2711,6 +3231,41 @@ impl ChannelConfig for MattermostConfig {
     }
 }
 
+/// Zulip channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ZulipConfig {
+    /// Zulip server URL (e.g. `https://chat.zulip.org`).
+    pub site_url: String,
+    /// Bot account email.
+    pub email: String,
+    /// Bot API key.
+    pub api_key: String,
+    /// Streams to listen on. Empty means every stream the bot is subscribed to.
+    #[serde(default)]
+    pub streams: Vec<String>,
+    /// Allowed sender emails or numeric user ids, or "*" for all.
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Topic used when sending to a stream outside an existing topic.
+    #[serde(default)]
+    pub default_topic: Option<String>,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+}
+
+impl ChannelConfig for ZulipConfig {
+    fn name() -> &'static str {
+        "Zulip"
+    }
+    fn desc() -> &'static str {
+        "streams, topics and private messages"
+    }
+}
+
 /// Webhook channel configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WebhookConfig {
@@ -2762,6 +3317,9 @@ pub struct MatrixConfig {
     pub room_id: String,
     /// Allowed Matrix user IDs. Empty = deny all.
     pub allowed_users: Vec<String>,
+    /// Group-chat reply policy for rooms with more than two members.
+    #[serde(default)]
+    pub group: GroupPolicyConfig,
 }
 
 impl ChannelConfig for MatrixConfig {
@@ -2959,6 +3517,9 @@ pub struct IrcConfig {
     pub sasl_password: Option<String>,
     /// Verify TLS certificate (default: true)
     pub verify_tls: Option<bool>,
+    /// Group-chat reply policy for joined channels
+    #[serde(default)]
+    pub group: GroupPolicyConfig,
 }
 
 impl ChannelConfig for IrcConfig {
@@ -2974,6 +3535,122 @@ fn default_irc_port() -> u16 {
     6697
 }
 
+/// XMPP (Jabber) channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct XmppConfig {
+    /// Bot account JID (e.g. `bot@example.org`)
+    pub jid: String,
+    /// Account password (SASL PLAIN)
+    pub password: String,
+    /// Server hostname (defaults to the JID domain)
+    pub server: Option<String>,
+    /// Server port (default: 5222, or 5223 with `direct_tls`)
+    pub port: Option<u16>,
+    /// Connect with direct TLS (XEP-0368) instead of STARTTLS
+    #[serde(default)]
+    pub direct_tls: bool,
+    /// Resource to bind (default: "zeroclaw")
+    pub resource: Option<String>,
+    /// MUC rooms to join on connect (bare room JIDs)
+    #[serde(default)]
+    pub rooms: Vec<String>,
+    /// Nickname used in MUC rooms (defaults to the JID localpart)
+    pub nickname: Option<String>,
+    /// Allowed bare JIDs, room occupant JIDs or room JIDs, or "*" for all
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Verify TLS certificate (default: true)
+    pub verify_tls: Option<bool>,
+    /// Streaming mode for progressive replies via message correction (XEP-0308).
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft corrections.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+}
+
+impl ChannelConfig for XmppConfig {
+    fn name() -> &'static str {
+        "XMPP"
+    }
+    fn desc() -> &'static str {
+        "XMPP/Jabber with MUC rooms"
+    }
+}
+
+/// MQTT protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+pub enum MqttProtocol {
+    /// MQTT 3.1.1 (default).
+    #[default]
+    #[serde(rename = "3.1.1")]
+    V311,
+    /// MQTT 5, adds response-topic and correlation-data properties.
+    #[serde(rename = "5")]
+    V5,
+}
+
+/// MQTT channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttConfig {
+    /// Broker hostname
+    pub host: String,
+    /// Broker port (default: 1883, or 8883 with TLS)
+    pub port: Option<u16>,
+    /// Connect over TLS
+    #[serde(default)]
+    pub tls: bool,
+    /// Verify the broker's TLS certificate (default: true)
+    pub verify_tls: Option<bool>,
+    /// Extra CA certificate (PEM) trusted for the broker
+    pub ca_cert_path: Option<String>,
+    /// Client certificate (PEM) for mutual TLS
+    pub client_cert_path: Option<String>,
+    /// Client private key (PEM) for mutual TLS
+    pub client_key_path: Option<String>,
+    /// Client identifier (default: "zeroclaw")
+    pub client_id: Option<String>,
+    /// Username for broker authentication
+    pub username: Option<String>,
+    /// Password for broker authentication
+    pub password: Option<String>,
+    /// Protocol version: "3.1.1" (default) or "5"
+    #[serde(default)]
+    pub protocol: MqttProtocol,
+    /// Topic filters to subscribe to for inbound prompts (`+`/`#` allowed)
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Reply topic; `{topic}` expands to the inbound topic.
+    /// Default: `{topic}/response`. An MQTT v5 response topic takes precedence.
+    pub response_topic: Option<String>,
+    /// QoS for subscriptions and replies: 0 or 1 (default: 1)
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Keepalive interval in seconds (default: 30)
+    #[serde(default = "default_mqtt_keep_alive_secs")]
+    pub keep_alive_secs: u16,
+    /// Topic filters the `mqtt_publish` tool may publish to. Empty disables the tool.
+    #[serde(default)]
+    pub publish_topics: Vec<String>,
+}
+
+impl ChannelConfig for MqttConfig {
+    fn name() -> &'static str {
+        "MQTT"
+    }
+    fn desc() -> &'static str {
+        "MQTT broker topics"
+    }
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+fn default_mqtt_keep_alive_secs() -> u16 {
+    30
+}
+
 /// How ZeroClaw receives events from Feishu / Lark.
 ///
 /// - `websocket` (default) — persistent WSS long-connection; no public URL required.
@@ -3040,6 +3717,14 @@ pub struct SecurityConfig {
     /// Audit logging configuration
     #[serde(default)]
     pub audit: AuditConfig,
+
+    /// Network egress policy for tool traffic and shell commands
+    #[serde(default)]
+    pub egress: EgressConfig,
+
+    /// Prompt-injection guard for untrusted tool output and inbound messages
+    #[serde(default)]
+    pub injection: InjectionGuardConfig,
 }
 
 /// Sandbox configuration for OS-level isolation
@@ -3090,15 +3775,15 @@ pub enum SandboxBackend {
 /// Resource limits for command execution
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ResourceLimitsConfig {
-    /// Maximum memory in MB per command
+    /// Maximum memory in MB per command tree (0 = unlimited)
     #[serde(default = "default_max_memory_mb")]
     pub max_memory_mb: u32,
 
-    /// Maximum CPU time in seconds per command
+    /// Maximum CPU time in seconds per command (0 = unlimited)
     #[serde(default = "default_max_cpu_time_seconds")]
     pub max_cpu_time_seconds: u64,
 
-    /// Maximum number of subprocesses
+    /// Maximum number of tasks (processes and threads) per command tree (0 = unlimited)
     #[serde(default = "default_max_subprocesses")]
     pub max_subprocesses: u32,
 
@@ -3108,15 +3793,15 @@ pub struct ResourceLimitsConfig {
 }
 
 fn default_max_memory_mb() -> u32 {
-    512
+    2048
 }
 
 fn default_max_cpu_time_seconds() -> u64 {
-    60
+    0
 }
 
 fn default_max_subprocesses() -> u32 {
-    10
+    4096
 }
 
 fn default_memory_monitoring_enabled() -> bool {
@@ -3177,6 +3862,187 @@ impl Default for AuditConfig {
     }
 }
 
+/// Network egress policy for agent-initiated traffic (`[security.egress]`).
+///
+/// Applies to every HTTP client built for tools and, best effort, to shell
+/// commands. Cloud metadata endpoints are always blocked.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EgressConfig {
+    /// Hosts tools may reach (`example.com` also matches subdomains,
+    /// `*.example.com` only subdomains). Empty = any host not otherwise denied.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// Hosts tools may never reach (same pattern syntax as `allowed_hosts`)
+    #[serde(default)]
+    pub denied_hosts: Vec<String>,
+
+    /// Address ranges exempt from `block_private_networks` (e.g. `"10.20.0.0/16"`)
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
+
+    /// Address ranges tools may never reach
+    #[serde(default)]
+    pub denied_cidrs: Vec<String>,
+
+    /// Block loopback, private, link-local and other non-global addresses,
+    /// including names that resolve to them
+    #[serde(default = "default_true")]
+    pub block_private_networks: bool,
+
+    /// Give shell commands network access. `false` runs native shell commands
+    /// in an empty network namespace (Linux only; commands fail elsewhere).
+    #[serde(default = "default_true")]
+    pub shell_network: bool,
+}
+
+impl Default for EgressConfig {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            allowed_cidrs: Vec::new(),
+            denied_cidrs: Vec::new(),
+            block_private_networks: true,
+            shell_network: true,
+        }
+    }
+}
+
+impl EgressConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (field, values) in [
+            ("allowed_cidrs", &self.allowed_cidrs),
+            ("denied_cidrs", &self.denied_cidrs),
+        ] {
+            for (i, value) in values.iter().enumerate() {
+                if let Err(e) = value.parse::<crate::security::egress::Cidr>() {
+                    anyhow::bail!("security.egress.{field}[{i}]: {e}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the injection guard does with content it flags
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InjectionAction {
+    /// Keep the content and prepend a warning
+    Warn,
+    /// Fence the content in an `<untrusted_content>` block with a do-not-follow notice (default)
+    #[default]
+    Wrap,
+    /// Withhold the content from the model entirely
+    Quarantine,
+}
+
+/// Prompt-injection guard (`[security.injection]`).
+///
+/// Output from untrusted tools and messages from untrusted channels are tagged
+/// as data, scanned for instruction-like content, and — when flagged — wrapped
+/// or quarantined. High-risk tool calls later in the same turn then need
+/// approval.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InjectionGuardConfig {
+    /// Enable the guard
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Action for flagged content
+    #[serde(default)]
+    pub action: InjectionAction,
+
+    /// Heuristic score at or above which content is flagged
+    #[serde(default = "default_injection_threshold")]
+    pub threshold: u32,
+
+    /// Tools whose output is untrusted external content
+    #[serde(default = "default_injection_untrusted_tools")]
+    pub untrusted_tools: Vec<String>,
+
+    /// Channels whose inbound messages are untrusted (e.g. email from anyone)
+    #[serde(default = "default_injection_untrusted_channels")]
+    pub untrusted_channels: Vec<String>,
+
+    /// Extra regex patterns that flag content on their own
+    #[serde(default)]
+    pub extra_patterns: Vec<String>,
+
+    /// Require approval for high-risk tools after a detection in the same turn.
+    /// Only the CLI can prompt; other channels deny those calls.
+    #[serde(default = "default_true")]
+    pub require_approval: bool,
+
+    /// Tools gated by `require_approval`
+    #[serde(default = "default_injection_high_risk_tools")]
+    pub high_risk_tools: Vec<String>,
+
+    /// Optional model (on the active provider) asked to classify untrusted
+    /// content the heuristics did not flag. Adds one model call per result.
+    #[serde(default)]
+    pub classifier_model: Option<String>,
+}
+
+fn default_injection_threshold() -> u32 {
+    3
+}
+
+fn default_injection_untrusted_tools() -> Vec<String> {
+    [
+        "http_request",
+        "browser",
+        "web_search_tool",
+        "pdf_read",
+        "composio",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_injection_untrusted_channels() -> Vec<String> {
+    vec!["email".into(), "hook".into()]
+}
+
+fn default_injection_high_risk_tools() -> Vec<String> {
+    [
+        "shell",
+        "file_write",
+        "file_edit",
+        "git_operations",
+        "http_request",
+        "browser",
+        "composio",
+        "cron_add",
+        "cron_update",
+        "schedule",
+        "delegate",
+        "pushover",
+        "mqtt_publish",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for InjectionGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: InjectionAction::default(),
+            threshold: default_injection_threshold(),
+            untrusted_tools: default_injection_untrusted_tools(),
+            untrusted_channels: default_injection_untrusted_channels(),
+            extra_patterns: Vec::new(),
+            require_approval: true,
+            high_risk_tools: default_injection_high_risk_tools(),
+            classifier_model: None,
+        }
+    }
+}
+
 /// DingTalk configuration for Stream Mode messaging
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DingTalkConfig {
@@ -3268,6 +4134,7 @@ impl Default for Config {
             default_temperature: 0.7,
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
+            security: SecurityConfig::default(),
             runtime: RuntimeConfig::default(),
             reliability: ReliabilityConfig::default(),
             scheduler: SchedulerConfig::default(),
@@ -3297,6 +4164,7 @@ impl Default for Config {
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
         }
     }
 }
@@ -3690,6 +4558,18 @@ impl Config {
                 "config.web_search.brave_api_key",
             )?;
 
+            decrypt_optional_secret(&store, &mut config.tts.api_key, "config.tts.api_key")?;
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.gateway.jobs.callback_secret,
+                "config.gateway.jobs.callback_secret",
+            )?;
+
+            for hook in config.gateway.hooks.values_mut() {
+                decrypt_optional_secret(&store, &mut hook.secret, "config.gateway.hooks.*.secret")?;
+            }
+
             decrypt_optional_secret(
                 &store,
                 &mut config.storage.provider.config.db_url,
@@ -3826,6 +4706,99 @@ impl Config {
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
+        // Gateway tokens
+        let mut token_names = std::collections::HashSet::new();
+        for (i, token) in self.gateway.tokens.iter().enumerate() {
+            let name = token.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("gateway.tokens[{i}].name must not be empty");
+            }
+            if !token_names.insert(name) {
+                anyhow::bail!("gateway.tokens[{i}].name '{name}' is used more than once");
+            }
+            if token.token_hash.len() != 64
+                || !token.token_hash.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                anyhow::bail!(
+                    "gateway.tokens[{i}].token_hash must be a 64-character SHA-256 hex digest"
+                );
+            }
+            if token.scopes.is_empty() {
+                anyhow::bail!("gateway.tokens[{i}].scopes must not be empty");
+            }
+            if let Some(expires_at) = token.expires_at.as_deref() {
+                if chrono::DateTime::parse_from_rfc3339(expires_at).is_err() {
+                    anyhow::bail!("gateway.tokens[{i}].expires_at must be an RFC 3339 timestamp");
+                }
+            }
+        }
+        if self.gateway.jobs.max_concurrent == 0 {
+            anyhow::bail!("gateway.jobs.max_concurrent must be greater than 0");
+        }
+        if self.gateway.jobs.callback_max_attempts == 0 {
+            anyhow::bail!("gateway.jobs.callback_max_attempts must be greater than 0");
+        }
+        let tls = &self.gateway.tls;
+        if tls.enabled && (tls.cert_path.trim().is_empty() || tls.key_path.trim().is_empty()) {
+            anyhow::bail!(
+                "gateway.tls.cert_path and gateway.tls.key_path are required when TLS is enabled"
+            );
+        }
+        if tls.client_cert_auth
+            && tls
+                .client_ca_path
+                .as_deref()
+                .is_none_or(|p| p.trim().is_empty())
+        {
+            anyhow::bail!("gateway.tls.client_cert_auth requires gateway.tls.client_ca_path");
+        }
+        for (name, hook) in &self.gateway.hooks {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "gateway.hooks.{name}: name may only contain letters, digits, '-' and '_'"
+                );
+            }
+            if hook.verify != HookVerification::Bearer
+                && hook.secret.as_deref().is_none_or(|s| s.trim().is_empty())
+            {
+                anyhow::bail!("gateway.hooks.{name}.secret is required for this verify scheme");
+            }
+            if hook.verify == HookVerification::SharedSecret && hook.secret_header.trim().is_empty()
+            {
+                anyhow::bail!("gateway.hooks.{name}.secret_header must not be empty");
+            }
+            if hook.channel.is_some() != hook.to.is_some() {
+                anyhow::bail!("gateway.hooks.{name}: channel and to must be set together");
+            }
+            if let Some(channel) = hook.channel.as_deref() {
+                if !matches!(
+                    channel.to_ascii_lowercase().as_str(),
+                    "telegram" | "discord" | "slack" | "mattermost"
+                ) {
+                    anyhow::bail!(
+                        "gateway.hooks.{name}.channel must be one of telegram, discord, slack, mattermost"
+                    );
+                }
+            }
+        }
+
+        // Egress policy
+        self.security.egress.validate()?;
+
+        // Injection guard
+        for (i, pattern) in self.security.injection.extra_patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                anyhow::bail!("security.injection.extra_patterns[{i}] is not a valid regex: {e}");
+            }
+        }
+        if self.security.injection.threshold == 0 {
+            anyhow::bail!("security.injection.threshold must be greater than 0");
+        }
+
         Ok(())
     }
 
@@ -4138,6 +5111,22 @@ impl Config {
             "config.web_search.brave_api_key",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.tts.api_key,
+            "config.tts.api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.gateway.jobs.callback_secret,
+            "config.gateway.jobs.callback_secret",
+        )?;
+
+        for hook in config_to_save.gateway.hooks.values_mut() {
+            encrypt_optional_secret(&store, &mut hook.secret, "config.gateway.hooks.*.secret")?;
+        }
+
         encrypt_optional_secret(
             &store,
             &mut config_to_save.storage.provider.config.db_url,
@@ -4466,6 +5455,13 @@ default_temperature = 0.7
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
             },
+            security: SecurityConfig {
+                resources: ResourceLimitsConfig {
+                    max_memory_mb: 2048,
+                    ..ResourceLimitsConfig::default()
+                },
+                ..SecurityConfig::default()
+            },
             runtime: RuntimeConfig {
                 kind: "docker".into(),
                 ..RuntimeConfig::default()
@@ -4490,6 +5486,7 @@ default_temperature = 0.7
                     draft_update_interval_ms: default_draft_update_interval_ms(),
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    group: GroupPolicyConfig::default(),
                 }),
                 discord: None,
                 slack: None,
@@ -4503,12 +5500,16 @@ default_temperature = 0.7
                 nextcloud_talk: None,
                 email: None,
                 irc: None,
+                xmpp: None,
+                zulip: None,
+                mqtt: None,
                 lark: None,
                 dingtalk: None,
                 qq: None,
                 nostr: None,
                 clawdtalk: None,
                 message_timeout_secs: 300,
+                reasoning_display: ReasoningDisplay::Hide,
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -4529,6 +5530,7 @@ default_temperature = 0.7
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -4542,6 +5544,7 @@ default_temperature = 0.7
         assert_eq!(parsed.observability.runtime_trace_mode, "none");
         assert_eq!(parsed.autonomy.level, AutonomyLevel::Full);
         assert!(!parsed.autonomy.workspace_only);
+        assert_eq!(parsed.security.resources.max_memory_mb, 2048);
         assert_eq!(parsed.runtime.kind, "docker");
         assert!(parsed.heartbeat.enabled);
         assert_eq!(parsed.heartbeat.interval_minutes, 15);
@@ -4673,6 +5676,7 @@ tool_dispatcher = "xml"
             default_temperature: 0.9,
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
+            security: SecurityConfig::default(),
             runtime: RuntimeConfig::default(),
             reliability: ReliabilityConfig::default(),
             scheduler: SchedulerConfig::default(),
@@ -4702,6 +5706,7 @@ tool_dispatcher = "xml"
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
         };
 
         config.save().await.unwrap();
@@ -4849,6 +5854,7 @@ tool_dispatcher = "xml"
             draft_update_interval_ms: 500,
             interrupt_on_new_message: true,
             mention_only: false,
+            group: GroupPolicyConfig::default(),
         };
         let json = serde_json::to_string(&tc).unwrap();
         let parsed: TelegramConfig = serde_json::from_str(&json).unwrap();
@@ -4876,6 +5882,7 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            group: GroupPolicyConfig::default(),
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -4891,6 +5898,7 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            group: GroupPolicyConfig::default(),
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -4939,6 +5947,7 @@ tool_dispatcher = "xml"
             device_id: Some("DEVICE123".into()),
             room_id: "!room123:matrix.org".into(),
             allowed_users: vec!["@user:matrix.org".into()],
+            group: GroupPolicyConfig::default(),
         };
         let json = serde_json::to_string(&mc).unwrap();
         let parsed: MatrixConfig = serde_json::from_str(&json).unwrap();
@@ -4959,6 +5968,7 @@ tool_dispatcher = "xml"
             device_id: None,
             room_id: "!abc:synapse.local".into(),
             allowed_users: vec!["@admin:synapse.local".into(), "*".into()],
+            group: GroupPolicyConfig::default(),
         };
         let toml_str = toml::to_string(&mc).unwrap();
         let parsed: MatrixConfig = toml::from_str(&toml_str).unwrap();
@@ -5048,6 +6058,7 @@ allowed_users = ["@ops:matrix.org"]
                 device_id: None,
                 room_id: "!r:m".into(),
                 allowed_users: vec!["@u:m".into()],
+                group: GroupPolicyConfig::default(),
             }),
             signal: None,
             whatsapp: None,
@@ -5055,12 +6066,16 @@ allowed_users = ["@ops:matrix.org"]
             nextcloud_talk: None,
             email: None,
             irc: None,
+            xmpp: None,
+            zulip: None,
+            mqtt: None,
             lark: None,
             dingtalk: None,
             qq: None,
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            reasoning_display: ReasoningDisplay::Hide,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5128,6 +6143,8 @@ channel_id = "C123"
         let parsed: SlackConfig = toml::from_str(toml_str).unwrap();
         assert!(parsed.allowed_users.is_empty());
         assert_eq!(parsed.channel_id.as_deref(), Some("C123"));
+        assert!(parsed.app_token.is_none());
+        assert!(parsed.signing_secret.is_none());
     }
 
     #[test]
@@ -5267,12 +6284,16 @@ channel_id = "C123"
             nextcloud_talk: None,
             email: None,
             irc: None,
+            xmpp: None,
+            zulip: None,
+            mqtt: None,
             lark: None,
             dingtalk: None,
             qq: None,
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            reasoning_display: ReasoningDisplay::Hide,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5357,6 +6378,25 @@ channel_id = "C123"
             rate_limit_max_keys: 2048,
             idempotency_ttl_secs: 600,
             idempotency_max_keys: 4096,
+            tokens: vec![GatewayTokenConfig {
+                name: "monitoring".into(),
+                token_hash: "a".repeat(64),
+                scopes: vec![GatewayScope::Read],
+                expires_at: Some("2030-01-01T00:00:00Z".into()),
+                rate_limit_per_minute: 30,
+                created_at: None,
+            }],
+            jobs: GatewayJobsConfig {
+                max_concurrent: 4,
+                ..GatewayJobsConfig::default()
+            },
+            hooks: HashMap::new(),
+            tls: GatewayTlsConfig {
+                enabled: true,
+                cert_path: "/etc/zeroclaw/gateway.crt".into(),
+                key_path: "/etc/zeroclaw/gateway.key".into(),
+                ..GatewayTlsConfig::default()
+            },
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -5368,7 +6408,13 @@ channel_id = "C123"
         assert!(parsed.trust_forwarded_headers);
         assert_eq!(parsed.rate_limit_max_keys, 2048);
         assert_eq!(parsed.idempotency_ttl_secs, 600);
+        assert_eq!(parsed.tokens[0].scopes, vec![GatewayScope::Read]);
+        assert_eq!(parsed.tokens[0].rate_limit_per_minute, 30);
+        assert_eq!(parsed.jobs.max_concurrent, 4);
         assert_eq!(parsed.idempotency_max_keys, 4096);
+        assert!(parsed.tls.enabled);
+        assert_eq!(parsed.tls.key_path, "/etc/zeroclaw/gateway.key");
+        assert_eq!(parsed.tls.reload_interval_secs, 30);
     }
 
     #[test]
@@ -6719,4 +7765,33 @@ default_model = "legacy-model"
         assert!(!parsed.transcription.enabled);
         assert_eq!(parsed.transcription.max_duration_secs, 120);
     }
+
+    #[test]
+    async fn tts_config_parses_provider_and_channel_overrides() {
+        let toml_str = r#"
+            default_temperature = 0.7
+
+            [tts]
+            enabled = true
+            provider = "command"
+            command = ["piper", "--output_file", "{output}"]
+            reply = "always"
+            channels = { discord = "off" }
+        "#;
+        let parsed: Config = toml::from_str(toml_str).unwrap();
+        assert!(parsed.tts.enabled);
+        assert_eq!(parsed.tts.provider, TtsProvider::Command);
+        assert_eq!(parsed.tts.reply, VoiceReplyMode::Always);
+        assert_eq!(
+            parsed.tts.channels.get("discord"),
+            Some(&VoiceReplyMode::Off)
+        );
+        assert_eq!(parsed.tts.voice, "alloy");
+        assert_eq!(parsed.tts.max_chars, 1500);
+
+        let defaults: Config = toml::from_str("default_temperature = 0.7").unwrap();
+        assert!(!defaults.tts.enabled);
+        assert_eq!(defaults.tts.provider, TtsProvider::OpenAi);
+        assert_eq!(defaults.tts.reply, VoiceReplyMode::Voice);
+    }
 }
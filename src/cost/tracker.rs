@@ -34,6 +34,23 @@ impl CostTracker {
         })
     }
 
+    /// Open the tracker for `config` when cost tracking is enabled.
+    ///
+    /// Returns `None` when tracking is disabled or the storage cannot be
+    /// opened; the latter is logged rather than failing the caller.
+    pub fn from_config(config: &crate::config::Config) -> Option<Self> {
+        if !config.cost.enabled {
+            return None;
+        }
+        match Self::new(config.cost.clone(), &config.workspace_dir) {
+            Ok(tracker) => Some(tracker),
+            Err(e) => {
+                tracing::warn!("Failed to initialize cost tracker: {e}");
+                None
+            }
+        }
+    }
+
     /// Get the session ID.
     pub fn session_id(&self) -> &str {
         &self.session_id
@@ -133,6 +150,66 @@ impl CostTracker {
         Ok(())
     }
 
+    /// Key into the configured prices for `model` served by `provider`.
+    ///
+    /// Bare model names win; otherwise the `provider/model` form is used.
+    pub fn pricing_key(&self, provider: &str, model: &str) -> String {
+        if self.config.prices.contains_key(model) {
+            model.to_string()
+        } else {
+            format!("{provider}/{model}")
+        }
+    }
+
+    /// Price raw provider-reported usage with the configured model pricing.
+    ///
+    /// Cached prompt tokens are billed at the model's cache read/write rates
+    /// instead of the regular input rate. Unknown models are priced at zero.
+    pub fn price_provider_usage(
+        &self,
+        model: &str,
+        usage: &crate::providers::traits::TokenUsage,
+    ) -> TokenUsage {
+        let pricing = self.config.prices.get(model);
+        let input_price = pricing.map_or(0.0, |p| p.input);
+        let output_price = pricing.map_or(0.0, |p| p.output);
+
+        TokenUsage::new(
+            model,
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+            input_price,
+            output_price,
+        )
+        .with_cache_tokens(
+            usage.cache_read_tokens.unwrap_or(0),
+            usage.cache_write_tokens.unwrap_or(0),
+            input_price,
+            pricing.map_or(0.0, |p| p.cache_read_price()),
+            pricing.map_or(0.0, |p| p.cache_write_price()),
+        )
+    }
+
+    /// Price usage for a request served through a provider batch API,
+    /// applying the configured `batch_discount_percent`.
+    pub fn price_batch_usage(
+        &self,
+        model: &str,
+        usage: &crate::providers::traits::TokenUsage,
+    ) -> TokenUsage {
+        self.price_provider_usage(model, usage)
+            .with_discount(self.config.batch_discount_percent)
+    }
+
+    /// Price and record raw provider-reported usage.
+    pub fn record_provider_usage(
+        &self,
+        model: &str,
+        usage: &crate::providers::traits::TokenUsage,
+    ) -> Result<()> {
+        self.record_usage(self.price_provider_usage(model, usage))
+    }
+
     /// Get the current cost summary.
     pub fn get_summary(&self) -> Result<CostSummary> {
         let (daily_cost, monthly_cost) = {
@@ -447,6 +524,86 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn provider_usage_prices_cached_tokens_at_cache_rates() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let raw = crate::providers::traits::TokenUsage {
+            input_tokens: Some(1_000_000),
+            output_tokens: Some(0),
+            cache_read_tokens: Some(900_000),
+            cache_write_tokens: None,
+        };
+        let priced = tracker.price_provider_usage("anthropic/claude-sonnet-4-20250514", &raw);
+
+        // 100k uncached at 3.0 + 900k cached at 0.30 = 0.30 + 0.27
+        assert!((priced.cost_usd - 0.57).abs() < 1e-9);
+        assert_eq!(priced.cache_read_tokens, 900_000);
+
+        tracker
+            .record_provider_usage("anthropic/claude-sonnet-4-20250514", &raw)
+            .unwrap();
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert!((summary.session_cost_usd - 0.57).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pricing_key_prefers_bare_model_then_qualified_name() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = enabled_config();
+        config.prices.insert(
+            "custom-model".into(),
+            crate::config::schema::ModelPricing {
+                input: 1.0,
+                output: 1.0,
+                cache_read: None,
+                cache_write: None,
+            },
+        );
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+
+        assert_eq!(tracker.pricing_key("local", "custom-model"), "custom-model");
+        assert_eq!(
+            tracker.pricing_key("anthropic", "claude-sonnet-4-20250514"),
+            "anthropic/claude-sonnet-4-20250514"
+        );
+    }
+
+    #[test]
+    fn provider_usage_for_unknown_model_is_free() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let raw = crate::providers::traits::TokenUsage {
+            input_tokens: Some(500),
+            output_tokens: Some(500),
+            cache_read_tokens: Some(100),
+            cache_write_tokens: Some(100),
+        };
+        let priced = tracker.price_provider_usage("unknown/model", &raw);
+        assert!(priced.cost_usd.abs() < f64::EPSILON);
+        assert_eq!(priced.total_tokens, 1000);
+    }
+
+    #[test]
+    fn batch_usage_applies_configured_discount() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let raw = crate::providers::traits::TokenUsage {
+            input_tokens: Some(1_000_000),
+            output_tokens: Some(1_000_000),
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+        };
+        let regular = tracker.price_provider_usage("anthropic/claude-sonnet-4-20250514", &raw);
+        let batch = tracker.price_batch_usage("anthropic/claude-sonnet-4-20250514", &raw);
+        assert!(regular.cost_usd > 0.0);
+        assert!((batch.cost_usd - regular.cost_usd * 0.5).abs() < 1e-9);
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
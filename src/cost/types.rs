@@ -9,6 +9,12 @@ pub struct TokenUsage {
     pub input_tokens: u64,
     /// Output/completion tokens
     pub output_tokens: u64,
+    /// Portion of `input_tokens` served from the provider's prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Portion of `input_tokens` written to the provider's prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: u64,
     /// Total tokens
     pub total_tokens: u64,
     /// Calculated cost in USD
@@ -48,12 +54,52 @@ impl TokenUsage {
             model,
             input_tokens,
             output_tokens,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
             total_tokens,
             cost_usd,
             timestamp: chrono::Utc::now(),
         }
     }
 
+    /// Re-price the cached portion of `input_tokens` at cache read/write rates.
+    ///
+    /// `new()` prices every input token at the regular input rate; this moves
+    /// cache reads and writes onto their own rates. Cached counts are clamped
+    /// so they never exceed `input_tokens`.
+    pub fn with_cache_tokens(
+        mut self,
+        cache_read_tokens: u64,
+        cache_write_tokens: u64,
+        input_price_per_million: f64,
+        cache_read_price_per_million: f64,
+        cache_write_price_per_million: f64,
+    ) -> Self {
+        let input_price = Self::sanitize_price(input_price_per_million);
+        let read_price = Self::sanitize_price(cache_read_price_per_million);
+        let write_price = Self::sanitize_price(cache_write_price_per_million);
+
+        let cache_read_tokens = cache_read_tokens.min(self.input_tokens);
+        let cache_write_tokens =
+            cache_write_tokens.min(self.input_tokens.saturating_sub(cache_read_tokens));
+
+        let read_delta = (cache_read_tokens as f64 / 1_000_000.0) * (read_price - input_price);
+        let write_delta = (cache_write_tokens as f64 / 1_000_000.0) * (write_price - input_price);
+
+        self.cache_read_tokens = cache_read_tokens;
+        self.cache_write_tokens = cache_write_tokens;
+        self.cost_usd = (self.cost_usd + read_delta + write_delta).max(0.0);
+        self
+    }
+
+    /// Apply a percentage discount (e.g. batch API pricing) to the cost.
+    /// Percentages above 100 are clamped to a free request.
+    pub fn with_discount(mut self, discount_percent: u8) -> Self {
+        let factor = 1.0 - f64::from(discount_percent.min(100)) / 100.0;
+        self.cost_usd *= factor;
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
@@ -181,6 +227,52 @@ mod tests {
         assert_eq!(usage.total_tokens, 2000);
     }
 
+    #[test]
+    fn token_usage_cache_reads_are_discounted() {
+        // 10k input (8k cached), priced 3.0 input / 0.3 cache read.
+        let usage = TokenUsage::new("test/model", 10_000, 0, 3.0, 15.0)
+            .with_cache_tokens(8_000, 0, 3.0, 0.3, 3.75);
+
+        // Expected: (2000/1M)*3 + (8000/1M)*0.3 = 0.006 + 0.0024 = 0.0084
+        assert!((usage.cost_usd - 0.0084).abs() < 1e-9);
+        assert_eq!(usage.cache_read_tokens, 8_000);
+        assert_eq!(usage.input_tokens, 10_000);
+    }
+
+    #[test]
+    fn token_usage_cache_writes_carry_premium() {
+        let usage = TokenUsage::new("test/model", 4_000, 0, 3.0, 15.0)
+            .with_cache_tokens(0, 4_000, 3.0, 0.3, 3.75);
+
+        // Expected: (4000/1M)*3.75 = 0.015
+        assert!((usage.cost_usd - 0.015).abs() < 1e-9);
+        assert_eq!(usage.cache_write_tokens, 4_000);
+    }
+
+    #[test]
+    fn token_usage_cache_counts_are_clamped_to_input() {
+        let usage = TokenUsage::new("test/model", 100, 0, 1.0, 1.0)
+            .with_cache_tokens(80, 80, 1.0, 0.0, 1.0);
+        assert_eq!(usage.cache_read_tokens, 80);
+        assert_eq!(usage.cache_write_tokens, 20);
+        assert!(usage.cost_usd >= 0.0);
+    }
+
+    #[test]
+    fn token_usage_deserializes_records_without_cache_fields() {
+        let json = r#"{
+            "model": "legacy/model",
+            "input_tokens": 10,
+            "output_tokens": 5,
+            "total_tokens": 15,
+            "cost_usd": 0.0,
+            "timestamp": "2025-01-01T00:00:00Z"
+        }"#;
+        let usage: TokenUsage = serde_json::from_str(json).unwrap();
+        assert_eq!(usage.cache_read_tokens, 0);
+        assert_eq!(usage.cache_write_tokens, 0);
+    }
+
     #[test]
     fn cost_record_creation() {
         let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
@@ -0,0 +1,607 @@
+//! Provider batch API client for cron agent jobs in batch execution mode.
+//!
+//! Supports Anthropic Message Batches (`/v1/messages/batches`) and the OpenAI
+//! Batch API (`/v1/files` + `/v1/batches`). Each cron job is submitted as a
+//! single-request batch keyed by the job id; the scheduler polls the batch on
+//! later ticks and collects the result once the provider reports completion.
+
+use crate::config::Config;
+use crate::providers::anthropic::{AnthropicProvider, AnthropicUsage};
+use crate::providers::traits::TokenUsage;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Anthropic requires `max_tokens` on every request.
+const BATCH_MAX_TOKENS: u32 = 8192;
+const OPENAI_COMPLETION_WINDOW: &str = "24h";
+
+/// Both batch APIs expire unfinished batches after 24 hours; the scheduler
+/// gives up on a batch an hour after that.
+pub const BATCH_EXPIRY_HOURS: i64 = 25;
+/// Consecutive failed polls after which a batch is abandoned.
+pub const MAX_POLL_FAILURES: u32 = 5;
+
+/// Batch API flavour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchApi {
+    Anthropic,
+    OpenAi,
+}
+
+impl BatchApi {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Anthropic => "anthropic",
+            Self::OpenAi => "openai",
+        }
+    }
+
+    pub fn from_provider(provider: &str) -> Result<Self> {
+        match provider.trim().to_ascii_lowercase().as_str() {
+            "anthropic" => Ok(Self::Anthropic),
+            "openai" => Ok(Self::OpenAi),
+            other => anyhow::bail!(
+                "batch execution requires provider 'anthropic' or 'openai' (got '{other}')"
+            ),
+        }
+    }
+}
+
+/// A single prompt submitted as a batch.
+#[derive(Debug, Clone)]
+pub struct BatchRequest<'a> {
+    pub custom_id: &'a str,
+    pub model: &'a str,
+    pub prompt: &'a str,
+    pub temperature: f64,
+}
+
+/// Completed batch result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOutput {
+    pub text: String,
+    pub usage: TokenUsage,
+}
+
+/// Outcome of polling a submitted batch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchPoll {
+    Pending,
+    Succeeded(BatchOutput),
+    Failed(String),
+}
+
+pub struct BatchClient {
+    api: BatchApi,
+    base_url: String,
+    credential: String,
+}
+
+impl BatchClient {
+    pub fn new(api: BatchApi, base_url: Option<&str>, credential: &str) -> Self {
+        let default_base = match api {
+            BatchApi::Anthropic => ANTHROPIC_BASE_URL,
+            BatchApi::OpenAi => OPENAI_BASE_URL,
+        };
+        Self {
+            api,
+            base_url: base_url
+                .map(str::trim)
+                .filter(|u| !u.is_empty())
+                .unwrap_or(default_base)
+                .trim_end_matches('/')
+                .to_string(),
+            credential: credential.to_string(),
+        }
+    }
+
+    /// Build a client for `provider`. The configured `api_key` and `api_url`
+    /// belong to the default provider, so they are only used when `provider`
+    /// is that provider; otherwise the provider's own environment credential
+    /// and public endpoint are used.
+    pub fn from_config(config: &Config, provider: &str) -> Result<Self> {
+        let api = BatchApi::from_provider(provider)?;
+        let is_default = config
+            .default_provider
+            .as_deref()
+            .and_then(|name| BatchApi::from_provider(name).ok())
+            == Some(api);
+        let (api_key, api_url) = if is_default {
+            (config.api_key.as_deref(), config.api_url.as_deref())
+        } else {
+            (None, None)
+        };
+        let credential = crate::providers::resolve_provider_credential(api.as_str(), api_key)
+            .with_context(|| format!("{} API key not set for batch execution", api.as_str()))?;
+        Ok(Self::new(api, api_url, &credential))
+    }
+
+    pub fn api(&self) -> BatchApi {
+        self.api
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            &format!("provider.{}", self.api.as_str()),
+            120,
+            10,
+        )
+    }
+
+    fn authed(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.api {
+            BatchApi::Anthropic => {
+                let request = request.header("anthropic-version", ANTHROPIC_VERSION);
+                if AnthropicProvider::is_setup_token(&self.credential) {
+                    request
+                        .header("Authorization", format!("Bearer {}", self.credential))
+                        .header("anthropic-beta", "oauth-2025-04-20")
+                } else {
+                    request.header("x-api-key", &self.credential)
+                }
+            }
+            BatchApi::OpenAi => request.bearer_auth(&self.credential),
+        }
+    }
+
+    async fn send_json(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = self.authed(request).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{} batch API error ({status}): {}",
+                self.api.as_str(),
+                crate::providers::sanitize_api_error(&body)
+            );
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn send_text(&self, request: reqwest::RequestBuilder) -> Result<String> {
+        let response = self.authed(request).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{} batch API error ({status}): {}",
+                self.api.as_str(),
+                crate::providers::sanitize_api_error(&body)
+            );
+        }
+        Ok(response.text().await?)
+    }
+
+    /// Submit `request` as a new batch and return the provider batch id.
+    pub async fn submit(&self, request: &BatchRequest<'_>) -> Result<String> {
+        let body = match self.api {
+            BatchApi::Anthropic => {
+                let payload = json!({
+                    "requests": [{
+                        "custom_id": request.custom_id,
+                        "params": {
+                            "model": request.model,
+                            "max_tokens": BATCH_MAX_TOKENS,
+                            "temperature": request.temperature,
+                            "messages": [{"role": "user", "content": request.prompt}],
+                        },
+                    }],
+                });
+                let http = self.http_client();
+                self.send_json(
+                    http.post(format!("{}/v1/messages/batches", self.base_url))
+                        .json(&payload),
+                )
+                .await?
+            }
+            BatchApi::OpenAi => {
+                let line = json!({
+                    "custom_id": request.custom_id,
+                    "method": "POST",
+                    "url": "/v1/chat/completions",
+                    "body": {
+                        "model": request.model,
+                        "temperature": request.temperature,
+                        "messages": [{"role": "user", "content": request.prompt}],
+                    },
+                });
+                let part = reqwest::multipart::Part::bytes(format!("{line}\n").into_bytes())
+                    .file_name("batch.jsonl")
+                    .mime_str("application/jsonl")?;
+                let form = reqwest::multipart::Form::new()
+                    .text("purpose", "batch")
+                    .part("file", part);
+                let http = self.http_client();
+                let file = self
+                    .send_json(
+                        http.post(format!("{}/files", self.base_url))
+                            .multipart(form),
+                    )
+                    .await?;
+                let file_id = file
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .context("OpenAI file upload response missing id")?;
+                self.send_json(
+                    http.post(format!("{}/batches", self.base_url))
+                        .json(&json!({
+                            "input_file_id": file_id,
+                            "endpoint": "/v1/chat/completions",
+                            "completion_window": OPENAI_COMPLETION_WINDOW,
+                        })),
+                )
+                .await?
+            }
+        };
+
+        body.get("id")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("batch submission response missing id")
+    }
+
+    /// Check a submitted batch and fetch the result for `custom_id` once it
+    /// has finished.
+    pub async fn poll(&self, batch_id: &str, custom_id: &str) -> Result<BatchPoll> {
+        match self.api {
+            BatchApi::Anthropic => self.poll_anthropic(batch_id, custom_id).await,
+            BatchApi::OpenAi => self.poll_openai(batch_id, custom_id).await,
+        }
+    }
+
+    async fn poll_anthropic(&self, batch_id: &str, custom_id: &str) -> Result<BatchPoll> {
+        let http = self.http_client();
+        let batch = self
+            .send_json(http.get(format!("{}/v1/messages/batches/{batch_id}", self.base_url)))
+            .await?;
+        if batch.get("processing_status").and_then(Value::as_str) != Some("ended") {
+            return Ok(BatchPoll::Pending);
+        }
+        let results_url = batch
+            .get("results_url")
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{}/v1/messages/batches/{batch_id}/results", self.base_url),
+                str::to_string,
+            );
+        let results = self.send_text(http.get(results_url)).await?;
+        let Some(entry) = find_result_line(&results, custom_id) else {
+            return Ok(BatchPoll::Failed(format!(
+                "batch {batch_id} ended without a result for {custom_id}"
+            )));
+        };
+        Ok(parse_anthropic_result(&entry))
+    }
+
+    async fn poll_openai(&self, batch_id: &str, custom_id: &str) -> Result<BatchPoll> {
+        let http = self.http_client();
+        let batch = self
+            .send_json(http.get(format!("{}/batches/{batch_id}", self.base_url)))
+            .await?;
+        let status = batch
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match status {
+            "completed" => {}
+            "failed" | "expired" | "cancelled" | "cancelling" => {
+                return Ok(BatchPoll::Failed(format!("batch {batch_id} {status}")));
+            }
+            _ => return Ok(BatchPoll::Pending),
+        }
+
+        let file_id = batch
+            .get("output_file_id")
+            .and_then(Value::as_str)
+            .or_else(|| batch.get("error_file_id").and_then(Value::as_str));
+        let Some(file_id) = file_id else {
+            return Ok(BatchPoll::Failed(format!(
+                "batch {batch_id} completed without an output file"
+            )));
+        };
+        let results = self
+            .send_text(http.get(format!("{}/files/{file_id}/content", self.base_url)))
+            .await?;
+        let Some(entry) = find_result_line(&results, custom_id) else {
+            return Ok(BatchPoll::Failed(format!(
+                "batch {batch_id} completed without a result for {custom_id}"
+            )));
+        };
+        Ok(parse_openai_result(&entry))
+    }
+}
+
+fn find_result_line(jsonl: &str, custom_id: &str) -> Option<Value> {
+    jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .find(|entry| entry.get("custom_id").and_then(Value::as_str) == Some(custom_id))
+}
+
+fn parse_anthropic_result(entry: &Value) -> BatchPoll {
+    let result = &entry["result"];
+    match result.get("type").and_then(Value::as_str) {
+        Some("succeeded") => {
+            let message = &result["message"];
+            let text = message["content"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|block| block.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let usage = serde_json::from_value::<AnthropicUsage>(message["usage"].clone())
+                .map(AnthropicUsage::into_token_usage)
+                .unwrap_or_default();
+            BatchPoll::Succeeded(BatchOutput { text, usage })
+        }
+        Some("errored") => BatchPoll::Failed(format!(
+            "batch request errored: {}",
+            result["error"]["error"]["message"]
+                .as_str()
+                .or_else(|| result["error"]["message"].as_str())
+                .unwrap_or("unknown error")
+        )),
+        Some(other) => BatchPoll::Failed(format!("batch request {other}")),
+        None => BatchPoll::Failed("batch result missing type".to_string()),
+    }
+}
+
+fn parse_openai_result(entry: &Value) -> BatchPoll {
+    if let Some(message) = entry["error"]["message"].as_str() {
+        return BatchPoll::Failed(format!("batch request errored: {message}"));
+    }
+    let response = &entry["response"];
+    let status_code = response["status_code"].as_u64().unwrap_or(0);
+    let body = &response["body"];
+    if status_code != 200 {
+        return BatchPoll::Failed(format!(
+            "batch request failed ({status_code}): {}",
+            body["error"]["message"].as_str().unwrap_or("unknown error")
+        ));
+    }
+    let usage = &body["usage"];
+    BatchPoll::Succeeded(BatchOutput {
+        text: body["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        usage: TokenUsage {
+            input_tokens: usage["prompt_tokens"].as_u64(),
+            output_tokens: usage["completion_tokens"].as_u64(),
+            cache_read_tokens: usage["prompt_tokens_details"]["cached_tokens"].as_u64(),
+            cache_write_tokens: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn request() -> BatchRequest<'static> {
+        BatchRequest {
+            custom_id: "job-1",
+            model: "test-model",
+            prompt: "Summarize",
+            temperature: 0.7,
+        }
+    }
+
+    #[test]
+    fn batch_api_rejects_unsupported_provider() {
+        assert_eq!(
+            BatchApi::from_provider("Anthropic").unwrap(),
+            BatchApi::Anthropic
+        );
+        let err = BatchApi::from_provider("openrouter").unwrap_err();
+        assert!(err.to_string().contains("batch execution requires"));
+    }
+
+    #[test]
+    fn from_config_keeps_default_provider_credentials_to_that_provider() {
+        let config = Config {
+            default_provider: Some("anthropic".into()),
+            api_key: Some("anthropic-key".into()),
+            api_url: Some("https://proxy.example.com".into()),
+            ..Config::default()
+        };
+
+        let client = BatchClient::from_config(&config, "anthropic").unwrap();
+        assert_eq!(client.credential, "anthropic-key");
+        assert_eq!(client.base_url, "https://proxy.example.com");
+
+        // Without OPENAI_API_KEY this errors; with it, the env key is used.
+        if let Ok(client) = BatchClient::from_config(&config, "openai") {
+            assert_ne!(client.credential, "anthropic-key");
+            assert_eq!(client.base_url, OPENAI_BASE_URL);
+        }
+    }
+
+    #[tokio::test]
+    async fn anthropic_submit_and_collect_result() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages/batches"))
+            .and(header("x-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msgbatch_1",
+                "processing_status": "in_progress"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/messages/batches/msgbatch_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msgbatch_1",
+                "processing_status": "ended",
+                "results_url": format!("{}/results/msgbatch_1", server.uri())
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/results/msgbatch_1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    json!({
+                        "custom_id": "job-1",
+                        "result": {
+                            "type": "succeeded",
+                            "message": {
+                                "content": [{"type": "text", "text": "All done"}],
+                                "usage": {"input_tokens": 120, "output_tokens": 30}
+                            }
+                        }
+                    })
+                    .to_string()
+                        + "\n",
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = BatchClient::new(BatchApi::Anthropic, Some(&server.uri()), "test-key");
+        let batch_id = client.submit(&request()).await.unwrap();
+        assert_eq!(batch_id, "msgbatch_1");
+
+        let BatchPoll::Succeeded(output) = client.poll(&batch_id, "job-1").await.unwrap() else {
+            panic!("expected a succeeded batch");
+        };
+        assert_eq!(output.text, "All done");
+        assert_eq!(output.usage.input_tokens, Some(120));
+        assert_eq!(output.usage.output_tokens, Some(30));
+    }
+
+    #[test]
+    fn anthropic_result_folds_cache_tokens_into_input() {
+        let entry = json!({
+            "custom_id": "job-1",
+            "result": {
+                "type": "succeeded",
+                "message": {
+                    "content": [{"type": "text", "text": "ok"}],
+                    "usage": {
+                        "input_tokens": 20,
+                        "output_tokens": 10,
+                        "cache_read_input_tokens": 900,
+                        "cache_creation_input_tokens": 80
+                    }
+                }
+            }
+        });
+
+        let BatchPoll::Succeeded(output) = parse_anthropic_result(&entry) else {
+            panic!("expected a succeeded batch");
+        };
+        assert_eq!(output.usage.input_tokens, Some(1000));
+        assert_eq!(output.usage.output_tokens, Some(10));
+        assert_eq!(output.usage.cache_read_tokens, Some(900));
+        assert_eq!(output.usage.cache_write_tokens, Some(80));
+    }
+
+    #[tokio::test]
+    async fn anthropic_poll_reports_pending_until_ended() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/messages/batches/msgbatch_2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msgbatch_2",
+                "processing_status": "in_progress"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = BatchClient::new(BatchApi::Anthropic, Some(&server.uri()), "test-key");
+        assert_eq!(
+            client.poll("msgbatch_2", "job-1").await.unwrap(),
+            BatchPoll::Pending
+        );
+    }
+
+    #[tokio::test]
+    async fn openai_submit_and_collect_result() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/files"))
+            .and(header("authorization", "Bearer test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "file-in"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/batches"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"id": "batch_1", "status": "validating"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/batches/batch_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "batch_1",
+                "status": "completed",
+                "output_file_id": "file-out"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/file-out/content"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    json!({
+                        "custom_id": "job-1",
+                        "response": {
+                            "status_code": 200,
+                            "body": {
+                                "choices": [{"message": {"content": "Summary"}}],
+                                "usage": {"prompt_tokens": 50, "completion_tokens": 10}
+                            }
+                        },
+                        "error": null
+                    })
+                    .to_string(),
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = BatchClient::new(BatchApi::OpenAi, Some(&server.uri()), "test-key");
+        let batch_id = client.submit(&request()).await.unwrap();
+        assert_eq!(batch_id, "batch_1");
+
+        let BatchPoll::Succeeded(output) = client.poll(&batch_id, "job-1").await.unwrap() else {
+            panic!("expected a succeeded batch");
+        };
+        assert_eq!(output.text, "Summary");
+        assert_eq!(output.usage.input_tokens, Some(50));
+    }
+
+    #[test]
+    fn openai_expired_batch_and_errored_line_are_failures() {
+        let entry = json!({
+            "custom_id": "job-1",
+            "response": null,
+            "error": {"message": "rate limited"}
+        });
+        assert_eq!(
+            parse_openai_result(&entry),
+            BatchPoll::Failed("batch request errored: rate limited".into())
+        );
+
+        let entry = json!({
+            "custom_id": "job-1",
+            "result": {"type": "expired"}
+        });
+        assert_eq!(
+            parse_anthropic_result(&entry),
+            BatchPoll::Failed("batch request expired".into())
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::config::Config;
 use crate::security::SecurityPolicy;
 use anyhow::{bail, Result};
 
+mod batch;
 mod schedule;
 mod store;
 mod types;
@@ -14,10 +15,14 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_pending_batch_jobs,
+    list_runs, record_last_run, record_run, record_run_with_cost, remove_job, reschedule_after_run,
+    set_pending_batch, update_job,
+};
+pub use types::{
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, ExecutionMode, JobType, PendingBatch, Schedule,
+    SessionTarget,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
@@ -51,6 +56,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 if let Some(prompt) = &job.prompt {
                     println!("    prompt: {prompt}");
                 }
+                if let Some(pending) = &job.pending_batch {
+                    println!(
+                        "    batch: {} {} (submitted {})",
+                        pending.provider,
+                        pending.batch_id,
+                        pending.submitted_at.to_rfc3339()
+                    );
+                }
             }
             Ok(())
         }
@@ -129,10 +142,12 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             };
 
             if let Some(ref cmd) = command {
-                let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+                let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+                    .with_egress(&config.security.egress);
                 if !security.is_command_allowed(cmd) {
                     bail!("Command blocked by security policy: {cmd}");
                 }
+                security.egress.check_command(cmd)?;
             }
 
             let patch = CronJobPatch {
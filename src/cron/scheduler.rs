@@ -2,11 +2,15 @@ use crate::channels::{
     Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
 use crate::config::Config;
+use crate::cron::batch::{
+    BatchClient, BatchPoll, BatchRequest, BATCH_EXPIRY_HOURS, MAX_POLL_FAILURES,
+};
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    due_jobs, list_pending_batch_jobs, next_run_for_schedule, record_last_run,
+    record_run_with_cost, remove_job, reschedule_after_run, set_pending_batch, update_job, CronJob,
+    CronJobPatch, DeliveryConfig, ExecutionMode, JobType, PendingBatch, Schedule, SessionTarget,
 };
-use crate::security::SecurityPolicy;
+use crate::security::{egress, CommandLimits, SecurityPolicy};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{stream, StreamExt};
@@ -23,10 +27,10 @@ pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
     let mut interval = time::interval(Duration::from_secs(poll_secs));
     interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
+    let security = Arc::new(
+        SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+            .with_egress(&config.security.egress),
+    );
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
@@ -45,11 +49,13 @@ pub async fn run(config: Config) -> Result<()> {
         };
 
         process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        poll_pending_batches(&config).await;
     }
 }
 
 pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
-    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+        .with_egress(&config.security.egress);
     execute_job_with_retry(config, &security, job).await
 }
 
@@ -98,18 +104,21 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
-                let config = config.clone();
-                let security = Arc::clone(security);
-                let component = component.to_owned();
-                async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
-                }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+    let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let component = component.to_owned();
+        async move {
+            Box::pin(execute_and_persist_job(
+                &config,
+                security.as_ref(),
+                &job,
+                &component,
+            ))
+            .await
+        }
+    }))
+    .buffer_unordered(max_concurrent);
 
     while let Some((job_id, success)) = in_flight.next().await {
         if !success {
@@ -128,41 +137,223 @@ async fn execute_and_persist_job(
     warn_if_high_frequency_agent_job(job);
 
     let started_at = Utc::now();
+    if is_batch_agent_job(job) {
+        match submit_batch_job(config, security, job).await {
+            Ok(pending) => {
+                tracing::info!(
+                    "Cron job '{}' submitted as {} batch {}",
+                    job.id,
+                    pending.provider,
+                    pending.batch_id
+                );
+                return (job.id.clone(), true);
+            }
+            Err(e) => {
+                let output = format!("batch submission failed: {e}");
+                let success =
+                    persist_job_result(config, job, false, &output, started_at, Utc::now(), None)
+                        .await;
+                return (job.id.clone(), success);
+            }
+        }
+    }
+
     let (success, output) = execute_job_with_retry(config, security, job).await;
     let finished_at = Utc::now();
-    let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
+    let success =
+        persist_job_result(config, job, success, &output, started_at, finished_at, None).await;
 
     (job.id.clone(), success)
 }
 
-async fn run_agent_job(
-    config: &Config,
-    security: &SecurityPolicy,
-    job: &CronJob,
-) -> (bool, String) {
+fn is_batch_agent_job(job: &CronJob) -> bool {
+    matches!(job.job_type, JobType::Agent) && job.execution_mode == ExecutionMode::Batch
+}
+
+fn agent_job_policy_violation(security: &SecurityPolicy) -> Option<String> {
     if !security.can_act() {
-        return (
-            false,
-            "blocked by security policy: autonomy is read-only".to_string(),
-        );
+        return Some("blocked by security policy: autonomy is read-only".to_string());
     }
 
     if security.is_rate_limited() {
-        return (
-            false,
-            "blocked by security policy: rate limit exceeded".to_string(),
-        );
+        return Some("blocked by security policy: rate limit exceeded".to_string());
     }
 
     if !security.record_action() {
-        return (
-            false,
-            "blocked by security policy: action budget exhausted".to_string(),
-        );
+        return Some("blocked by security policy: action budget exhausted".to_string());
     }
+
+    None
+}
+
+fn agent_job_prompt(job: &CronJob) -> String {
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
-    let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    format!("[cron:{} {name}] {prompt}", job.id)
+}
+
+/// Submit a batch-mode agent job and mark it as waiting on the batch.
+async fn submit_batch_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> Result<PendingBatch> {
+    if let Some(reason) = agent_job_policy_violation(security) {
+        anyhow::bail!(reason);
+    }
+
+    let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+    let client = BatchClient::from_config(config, provider)?;
+    let model = job
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .ok_or_else(|| anyhow::anyhow!("no model configured for batch job"))?;
+    let prompt = agent_job_prompt(job);
+
+    let batch_id = client
+        .submit(&BatchRequest {
+            custom_id: &job.id,
+            model: &model,
+            prompt: &prompt,
+            temperature: config.default_temperature,
+        })
+        .await?;
+
+    let pending = PendingBatch {
+        provider: client.api().as_str().to_string(),
+        batch_id,
+        model,
+        submitted_at: Utc::now(),
+        poll_failures: 0,
+    };
+    set_pending_batch(config, &job.id, Some(&pending))?;
+    Ok(pending)
+}
+
+/// Poll every job waiting on a batch and persist results for finished ones.
+async fn poll_pending_batches(config: &Config) {
+    let jobs = match list_pending_batch_jobs(config) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Scheduler pending batch query failed: {e}");
+            return;
+        }
+    };
+
+    for job in jobs {
+        if let Err(e) = poll_batch_job(config, &job).await {
+            tracing::warn!("Polling batch for cron job '{}' failed: {e}", job.id);
+        }
+    }
+}
+
+async fn poll_batch_job(config: &Config, job: &CronJob) -> Result<()> {
+    let Some(pending) = job.pending_batch.as_ref() else {
+        return Ok(());
+    };
+    let polled = match BatchClient::from_config(config, &pending.provider) {
+        Ok(client) => client.poll(&pending.batch_id, &job.id).await,
+        Err(e) => Err(e),
+    };
+    let expired = Utc::now() - pending.submitted_at > chrono::Duration::hours(BATCH_EXPIRY_HOURS);
+
+    let (success, output, cost_usd) = match polled {
+        Ok(BatchPoll::Pending) if !expired => {
+            if pending.poll_failures > 0 {
+                let reset = PendingBatch {
+                    poll_failures: 0,
+                    ..pending.clone()
+                };
+                set_pending_batch(config, &job.id, Some(&reset))?;
+            }
+            return Ok(());
+        }
+        Ok(BatchPoll::Pending) => (
+            false,
+            format!(
+                "agent job failed: batch {} unfinished after {BATCH_EXPIRY_HOURS}h",
+                pending.batch_id
+            ),
+            None,
+        ),
+        Err(e) => {
+            let poll_failures = pending.poll_failures + 1;
+            if poll_failures < MAX_POLL_FAILURES && !expired {
+                let retry = PendingBatch {
+                    poll_failures,
+                    ..pending.clone()
+                };
+                set_pending_batch(config, &job.id, Some(&retry))?;
+                return Err(e);
+            }
+            (
+                false,
+                format!(
+                    "agent job failed: polling batch {} failed {poll_failures} times: {e}",
+                    pending.batch_id
+                ),
+                None,
+            )
+        }
+        Ok(BatchPoll::Succeeded(result)) => {
+            let cost_usd = record_batch_cost(config, pending, &result.usage);
+            let output = if result.text.trim().is_empty() {
+                "agent job executed".to_string()
+            } else {
+                result.text
+            };
+            (true, output, cost_usd)
+        }
+        Ok(BatchPoll::Failed(reason)) => (false, format!("agent job failed: {reason}"), None),
+    };
+
+    set_pending_batch(config, &job.id, None)?;
+    persist_job_result(
+        config,
+        job,
+        success,
+        &output,
+        pending.submitted_at,
+        Utc::now(),
+        cost_usd,
+    )
+    .await;
+    Ok(())
+}
+
+/// Price a finished batch request at the discounted batch rate and record it
+/// with the cost tracker when cost tracking is enabled.
+fn record_batch_cost(
+    config: &Config,
+    pending: &PendingBatch,
+    usage: &crate::providers::traits::TokenUsage,
+) -> Option<f64> {
+    let tracker = match crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+        Ok(tracker) => tracker,
+        Err(e) => {
+            tracing::warn!("Failed to open cost tracker for batch result: {e}");
+            return None;
+        }
+    };
+    let pricing_key = tracker.pricing_key(&pending.provider, &pending.model);
+    let priced = tracker.price_batch_usage(&pricing_key, usage);
+    let cost_usd = priced.cost_usd;
+    if let Err(e) = tracker.record_usage(priced) {
+        tracing::warn!("Failed to record batch cost: {e}");
+    }
+    Some(cost_usd)
+}
+
+async fn run_agent_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> (bool, String) {
+    if let Some(reason) = agent_job_policy_violation(security) {
+        return (false, reason);
+    }
+    let prefixed_prompt = agent_job_prompt(job);
     let model_override = job.model.clone();
 
     let run_result = match job.session_target {
@@ -200,6 +391,7 @@ async fn persist_job_result(
     output: &str,
     started_at: DateTime<Utc>,
     finished_at: DateTime<Utc>,
+    cost_usd: Option<f64>,
 ) -> bool {
     let duration_ms = (finished_at - started_at).num_milliseconds();
 
@@ -212,7 +404,7 @@ async fn persist_job_result(
         }
     }
 
-    let _ = record_run(
+    let _ = record_run_with_cost(
         config,
         &job.id,
         started_at,
@@ -220,6 +412,7 @@ async fn persist_job_result(
         if success { "ok" } else { "error" },
         Some(output),
         duration_ms,
+        cost_usd,
     );
 
     if is_one_shot_auto_delete(job) {
@@ -296,6 +489,17 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
 
+    send_to_channel(config, channel, target, output).await
+}
+
+/// Send `output` to `target` on a configured outbound channel
+/// (telegram, discord, slack or mattermost).
+pub(crate) async fn send_to_channel(
+    config: &Config,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Result<()> {
     match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
@@ -469,6 +673,10 @@ async fn run_job_command_with_timeout(
         );
     }
 
+    if let Err(e) = security.egress.check_command(&job.command) {
+        return (false, format!("blocked by security policy: {e}"));
+    }
+
     if !security.record_action() {
         return (
             false,
@@ -476,16 +684,24 @@ async fn run_job_command_with_timeout(
         );
     }
 
-    let child = match Command::new("sh")
-        .arg("-lc")
+    let mut cmd = Command::new("sh");
+    cmd.arg("-lc")
         .arg(&job.command)
         .current_dir(&config.workspace_dir)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .kill_on_drop(true)
-        .spawn()
-    {
+        .kill_on_drop(true);
+    let limits = match CommandLimits::apply(&mut cmd, &config.security.resources) {
+        Ok(limits) => limits,
+        Err(e) => return (false, format!("resource limit error: {e}")),
+    };
+    if !security.egress.shell_network() {
+        if let Err(e) = egress::isolate_network(&mut cmd) {
+            return (false, format!("network isolation error: {e}"));
+        }
+    }
+    let child = match cmd.spawn() {
         Ok(child) => child,
         Err(e) => return (false, format!("spawn error: {e}")),
     };
@@ -494,8 +710,15 @@ async fn run_job_command_with_timeout(
         Ok(Ok(output)) => {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let stderr = String::from_utf8_lossy(&output.stderr);
+            let limit_note = limits
+                .violation(output.status)
+                .map(|violation| {
+                    tracing::warn!("Cron job '{}': {violation}", job.id);
+                    format!("\nlimit: {violation}")
+                })
+                .unwrap_or_default();
             let combined = format!(
-                "status={}\nstdout:\n{}\nstderr:\n{}",
+                "status={}\nstdout:\n{}\nstderr:\n{}{limit_note}",
                 output.status,
                 stdout.trim(),
                 stderr.trim()
@@ -545,6 +768,8 @@ mod tests {
             job_type: JobType::Shell,
             session_target: SessionTarget::Isolated,
             model: None,
+            execution_mode: ExecutionMode::Direct,
+            pending_batch: None,
             enabled: true,
             delivery: DeliveryConfig::default(),
             delete_after_run: false,
@@ -573,6 +798,35 @@ mod tests {
         assert!(output.contains("status=exit status: 0"));
     }
 
+    #[tokio::test]
+    async fn run_job_command_blocks_metadata_endpoint() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.allowed_commands.push("curl".into());
+        let job = test_job("curl -s http://169.254.169.254/latest/meta-data/");
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+            .with_egress(&config.security.egress);
+
+        let (success, output) = run_job_command(&config, &security, &job).await;
+        assert!(!success);
+        assert!(output.contains("cloud metadata endpoint"), "{output}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_job_command_applies_resource_limits() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.allowed_commands = vec!["ulimit".into()];
+        config.security.resources.max_cpu_time_seconds = 9;
+        let job = test_job("ulimit -t");
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_job_command(&config, &security, &job).await;
+        assert!(success, "{output}");
+        assert!(output.contains("stdout:\n9\n"));
+    }
+
     #[tokio::test]
     async fn run_job_command_failure() {
         let tmp = TempDir::new().unwrap();
@@ -787,7 +1041,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", started, finished, None).await;
         assert!(success);
 
         let runs = cron::list_runs(&config, &job.id, 10).unwrap();
@@ -808,6 +1062,7 @@ mod tests {
             "Hello",
             SessionTarget::Isolated,
             None,
+            ExecutionMode::Direct,
             None,
             true,
         )
@@ -815,7 +1070,7 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(&config, &job, true, "ok", started, finished, None).await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -833,6 +1088,7 @@ mod tests {
             "Hello",
             SessionTarget::Isolated,
             None,
+            ExecutionMode::Direct,
             None,
             true,
         )
@@ -840,13 +1096,256 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success =
+            persist_job_result(&config, &job, false, "boom", started, finished, None).await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
         assert_eq!(updated.last_status.as_deref(), Some("error"));
     }
 
+    #[tokio::test]
+    async fn batch_agent_job_submits_then_records_discounted_run() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages/batches"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msgbatch_cron",
+                "processing_status": "in_progress"
+            })))
+            .mount(&server)
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("anthropic".into());
+        config.default_model = Some("claude-batch-test".into());
+        config.api_key = Some("test-key".into());
+        config.api_url = Some(server.uri());
+        config.cost.enabled = true;
+        config.cost.prices.insert(
+            "anthropic/claude-batch-test".into(),
+            crate::config::schema::ModelPricing {
+                input: 10.0,
+                output: 10.0,
+                cache_read: None,
+                cache_write: None,
+            },
+        );
+        let job = cron::add_agent_job(
+            &config,
+            Some("nightly".into()),
+            crate::cron::Schedule::Every { every_ms: 60_000 },
+            "Summarize",
+            SessionTarget::Isolated,
+            None,
+            ExecutionMode::Batch,
+            None,
+            false,
+        )
+        .unwrap();
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (_, submitted) = Box::pin(execute_and_persist_job(
+            &config,
+            &security,
+            &job,
+            "batch-test",
+        ))
+        .await;
+        assert!(submitted);
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        let pending = stored
+            .pending_batch
+            .clone()
+            .expect("batch should be pending");
+        assert_eq!(pending.batch_id, "msgbatch_cron");
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+        let far_future = Utc::now() + ChronoDuration::days(365);
+        assert!(cron::due_jobs(&config, far_future).unwrap().is_empty());
+
+        Mock::given(method("GET"))
+            .and(path("/v1/messages/batches/msgbatch_cron"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msgbatch_cron",
+                "processing_status": "ended",
+                "results_url": format!("{}/results/msgbatch_cron", server.uri())
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/results/msgbatch_cron"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    serde_json::json!({
+                        "custom_id": job.id,
+                        "result": {
+                            "type": "succeeded",
+                            "message": {
+                                "content": [{"type": "text", "text": "nightly summary"}],
+                                "usage": {"input_tokens": 100_000, "output_tokens": 100_000}
+                            }
+                        }
+                    })
+                    .to_string(),
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        poll_pending_batches(&config).await;
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "ok");
+        assert_eq!(runs[0].output.as_deref(), Some("nightly summary"));
+        // 200k tokens at $10/M = $2.00, halved by the batch discount.
+        let cost = runs[0].cost_usd.expect("batch run should record cost");
+        assert!((cost - 1.0).abs() < 1e-9);
+
+        let updated = cron::get_job(&config, &job.id).unwrap();
+        assert!(updated.pending_batch.is_none());
+        assert_eq!(updated.last_status.as_deref(), Some("ok"));
+    }
+
+    fn pending_batch_job(config: &Config, submitted_at: DateTime<Utc>) -> CronJob {
+        let job = cron::add_agent_job(
+            config,
+            None,
+            crate::cron::Schedule::Every { every_ms: 60_000 },
+            "Summarize",
+            SessionTarget::Isolated,
+            None,
+            ExecutionMode::Batch,
+            None,
+            false,
+        )
+        .unwrap();
+        let pending = PendingBatch {
+            provider: "anthropic".into(),
+            batch_id: "msgbatch_gone".into(),
+            model: "claude-batch-test".into(),
+            submitted_at,
+            poll_failures: 0,
+        };
+        set_pending_batch(config, &job.id, Some(&pending)).unwrap();
+        job
+    }
+
+    #[tokio::test]
+    async fn batch_poll_gives_up_after_repeated_failures() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/messages/batches/msgbatch_gone"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("anthropic".into());
+        config.api_key = Some("test-key".into());
+        config.api_url = Some(server.uri());
+        let job = pending_batch_job(&config, Utc::now());
+
+        for attempt in 1..MAX_POLL_FAILURES {
+            poll_pending_batches(&config).await;
+            let stored = cron::get_job(&config, &job.id).unwrap();
+            let pending = stored.pending_batch.expect("batch should stay pending");
+            assert_eq!(pending.poll_failures, attempt);
+            assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+        }
+
+        poll_pending_batches(&config).await;
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "error");
+        assert!(runs[0]
+            .output
+            .as_deref()
+            .unwrap()
+            .contains("failed 5 times"));
+        assert!(cron::get_job(&config, &job.id)
+            .unwrap()
+            .pending_batch
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_poll_fails_batches_past_the_expiry_window() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/messages/batches/msgbatch_gone"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msgbatch_gone",
+                "processing_status": "in_progress"
+            })))
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("anthropic".into());
+        config.api_key = Some("test-key".into());
+        config.api_url = Some(server.uri());
+        let submitted_at = Utc::now() - ChronoDuration::hours(BATCH_EXPIRY_HOURS + 1);
+        let job = pending_batch_job(&config, submitted_at);
+
+        poll_pending_batches(&config).await;
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "error");
+        assert!(runs[0].output.as_deref().unwrap().contains("unfinished"));
+    }
+
+    #[tokio::test]
+    async fn batch_agent_job_with_unsupported_provider_records_failure() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("openrouter".into());
+        let job = cron::add_agent_job(
+            &config,
+            None,
+            crate::cron::Schedule::Every { every_ms: 60_000 },
+            "Summarize",
+            SessionTarget::Isolated,
+            None,
+            ExecutionMode::Batch,
+            None,
+            false,
+        )
+        .unwrap();
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (_, success) = Box::pin(execute_and_persist_job(
+            &config,
+            &security,
+            &job,
+            "batch-fail",
+        ))
+        .await;
+        assert!(!success);
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0]
+            .output
+            .as_deref()
+            .unwrap()
+            .contains("batch execution requires provider"));
+        assert!(cron::get_job(&config, &job.id)
+            .unwrap()
+            .pending_batch
+            .is_none());
+    }
+
     #[tokio::test]
     async fn deliver_if_configured_handles_none_and_invalid_channel() {
         let tmp = TempDir::new().unwrap();
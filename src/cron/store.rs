@@ -1,7 +1,7 @@
 use crate::config::Config;
 use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    CronRun, DeliveryConfig, ExecutionMode, JobType, PendingBatch, Schedule, SessionTarget,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -72,6 +72,7 @@ pub fn add_agent_job(
     prompt: &str,
     session_target: SessionTarget,
     model: Option<String>,
+    execution_mode: ExecutionMode,
     delivery: Option<DeliveryConfig>,
     delete_after_run: bool,
 ) -> Result<CronJob> {
@@ -87,8 +88,8 @@ pub fn add_agent_job(
         conn.execute(
             "INSERT INTO cron_jobs (
                 id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                enabled, delivery, delete_after_run, created_at, next_run
-             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11)",
+                enabled, delivery, delete_after_run, created_at, next_run, execution_mode
+             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11, ?12)",
             params![
                 id,
                 expression,
@@ -101,6 +102,7 @@ pub fn add_agent_job(
                 if delete_after_run { 1 } else { 0 },
                 now.to_rfc3339(),
                 next_run.to_rfc3339(),
+                execution_mode.as_str(),
             ],
         )
         .context("Failed to insert cron agent job")?;
@@ -114,7 +116,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    execution_mode, pending_batch
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -132,7 +135,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    execution_mode, pending_batch
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -165,9 +169,10 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    execution_mode, pending_batch
              FROM cron_jobs
-             WHERE enabled = 1 AND next_run <= ?1
+             WHERE enabled = 1 AND next_run <= ?1 AND pending_batch IS NULL
              ORDER BY next_run ASC
              LIMIT ?2",
         )?;
@@ -182,6 +187,45 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     })
 }
 
+/// Jobs with a batch submission still awaiting provider results.
+pub fn list_pending_batch_jobs(config: &Config) -> Result<Vec<CronJob>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    execution_mode, pending_batch
+             FROM cron_jobs
+             WHERE pending_batch IS NOT NULL
+             ORDER BY next_run ASC",
+        )?;
+
+        let rows = stmt.query_map([], map_cron_job_row)?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            jobs.push(row?);
+        }
+        Ok(jobs)
+    })
+}
+
+/// Record (or clear, with `None`) the batch submission a job is waiting on.
+pub fn set_pending_batch(
+    config: &Config,
+    job_id: &str,
+    pending: Option<&PendingBatch>,
+) -> Result<()> {
+    let encoded = pending.map(serde_json::to_string).transpose()?;
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET pending_batch = ?1 WHERE id = ?2",
+            params![encoded, job_id],
+        )
+        .context("Failed to update cron pending batch")?;
+        Ok(())
+    })
+}
+
 pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<CronJob> {
     let mut job = get_job(config, job_id)?;
     let mut schedule_changed = false;
@@ -213,6 +257,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(target) = patch.session_target {
         job.session_target = target;
     }
+    if let Some(mode) = patch.execution_mode {
+        job.execution_mode = mode;
+    }
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
@@ -226,8 +273,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
+                 next_run = ?12, execution_mode = ?13
+             WHERE id = ?14",
             params![
                 job.expression,
                 job.command,
@@ -241,6 +288,7 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 serde_json::to_string(&job.delivery)?,
                 if job.delete_after_run { 1 } else { 0 },
                 job.next_run.to_rfc3339(),
+                job.execution_mode.as_str(),
                 job.id,
             ],
         )
@@ -309,6 +357,30 @@ pub fn record_run(
     status: &str,
     output: Option<&str>,
     duration_ms: i64,
+) -> Result<()> {
+    record_run_with_cost(
+        config,
+        job_id,
+        started_at,
+        finished_at,
+        status,
+        output,
+        duration_ms,
+        None,
+    )
+}
+
+/// Like [`record_run`], additionally storing the run's provider cost in USD.
+#[allow(clippy::too_many_arguments)]
+pub fn record_run_with_cost(
+    config: &Config,
+    job_id: &str,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    status: &str,
+    output: Option<&str>,
+    duration_ms: i64,
+    cost_usd: Option<f64>,
 ) -> Result<()> {
     let bounded_output = output.map(truncate_cron_output);
     with_connection(config, |conn| {
@@ -318,8 +390,8 @@ pub fn record_run(
         let tx = conn.unchecked_transaction()?;
 
         tx.execute(
-            "INSERT INTO cron_runs (job_id, started_at, finished_at, status, output, duration_ms)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO cron_runs (job_id, started_at, finished_at, status, output, duration_ms, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 job_id,
                 started_at.to_rfc3339(),
@@ -327,6 +399,7 @@ pub fn record_run(
                 status,
                 bounded_output.as_deref(),
                 duration_ms,
+                cost_usd,
             ],
         )
         .context("Failed to insert cron run")?;
@@ -374,7 +447,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     with_connection(config, |conn| {
         let lim = i64::try_from(limit.max(1)).context("Run history limit overflow")?;
         let mut stmt = conn.prepare(
-            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms
+            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms, cost_usd
              FROM cron_runs
              WHERE job_id = ?1
              ORDER BY started_at DESC, id DESC
@@ -392,6 +465,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
                 status: row.get(4)?,
                 output: row.get(5)?,
                 duration_ms: row.get(6)?,
+                cost_usd: row.get(7)?,
             })
         })?;
 
@@ -425,6 +499,14 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let next_run_raw: String = row.get(13)?;
     let last_run_raw: Option<String> = row.get(14)?;
     let created_at_raw: String = row.get(12)?;
+    let pending_batch_raw: Option<String> = row.get(18)?;
+    let pending_batch = pending_batch_raw
+        .as_deref()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(serde_json::from_str::<PendingBatch>)
+        .transpose()
+        .map_err(|e| sql_conversion_error(e.into()))?;
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -436,6 +518,8 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         name: row.get(6)?,
         session_target: SessionTarget::parse(&row.get::<_, String>(7)?),
         model: row.get(8)?,
+        execution_mode: ExecutionMode::parse(&row.get::<_, String>(17)?),
+        pending_batch,
         enabled: row.get::<_, i64>(9)? != 0,
         delivery,
         delete_after_run: row.get::<_, i64>(11)? != 0,
@@ -481,7 +565,16 @@ fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
 }
 
 fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
-    let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
+    add_table_column_if_missing(conn, "cron_jobs", name, sql_type)
+}
+
+fn add_table_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    name: &str,
+    sql_type: &str,
+) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
     let mut rows = stmt.query([])?;
     while let Some(row) = rows.next()? {
         let col_name: String = row.get(1)?;
@@ -496,17 +589,17 @@ fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Resul
     // Tolerate "duplicate column name" errors to handle the race where
     // another process adds the column between our PRAGMA check and ALTER.
     match conn.execute(
-        &format!("ALTER TABLE cron_jobs ADD COLUMN {name} {sql_type}"),
+        &format!("ALTER TABLE {table} ADD COLUMN {name} {sql_type}"),
         [],
     ) {
         Ok(_) => Ok(()),
         Err(rusqlite::Error::SqliteFailure(err, Some(ref msg)))
             if msg.contains("duplicate column name") =>
         {
-            tracing::debug!("Column cron_jobs.{name} already exists (concurrent migration): {err}");
+            tracing::debug!("Column {table}.{name} already exists (concurrent migration): {err}");
             Ok(())
         }
-        Err(e) => Err(e).with_context(|| format!("Failed to add cron_jobs.{name}")),
+        Err(e) => Err(e).with_context(|| format!("Failed to add {table}.{name}")),
     }
 }
 
@@ -539,7 +632,9 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run         TEXT NOT NULL,
             last_run         TEXT,
             last_status      TEXT,
-            last_output      TEXT
+            last_output      TEXT,
+            execution_mode   TEXT NOT NULL DEFAULT 'direct',
+            pending_batch    TEXT
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
 
@@ -551,6 +646,7 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             status      TEXT NOT NULL,
             output      TEXT,
             duration_ms INTEGER,
+            cost_usd    REAL,
             FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
         );
         CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
@@ -568,6 +664,9 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "execution_mode", "TEXT NOT NULL DEFAULT 'direct'")?;
+    add_column_if_missing(&conn, "pending_batch", "TEXT")?;
+    add_table_column_if_missing(&conn, "cron_runs", "cost_usd", "REAL")?;
 
     f(&conn)
 }
@@ -58,6 +58,50 @@ impl SessionTarget {
     }
 }
 
+/// How an agent job's prompt is executed.
+///
+/// `Batch` submits the prompt through the provider's batch API (Anthropic
+/// Message Batches, OpenAI Batch) as a single tool-less completion; results
+/// arrive asynchronously at a discounted price and are collected by the
+/// scheduler on later polls.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionMode {
+    #[default]
+    Direct,
+    Batch,
+}
+
+impl ExecutionMode {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Direct => "direct",
+            Self::Batch => "batch",
+        }
+    }
+
+    pub(crate) fn parse(raw: &str) -> Self {
+        if raw.eq_ignore_ascii_case("batch") {
+            Self::Batch
+        } else {
+            Self::Direct
+        }
+    }
+}
+
+/// Batch submission awaiting results from the provider.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingBatch {
+    /// Batch API the request was submitted to (`anthropic` or `openai`).
+    pub provider: String,
+    pub batch_id: String,
+    pub model: String,
+    pub submitted_at: DateTime<Utc>,
+    /// Consecutive polls that ended in an error.
+    #[serde(default)]
+    pub poll_failures: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum Schedule {
@@ -112,6 +156,10 @@ pub struct CronJob {
     pub job_type: JobType,
     pub session_target: SessionTarget,
     pub model: Option<String>,
+    #[serde(default)]
+    pub execution_mode: ExecutionMode,
+    #[serde(default)]
+    pub pending_batch: Option<PendingBatch>,
     pub enabled: bool,
     pub delivery: DeliveryConfig,
     pub delete_after_run: bool,
@@ -131,6 +179,9 @@ pub struct CronRun {
     pub status: String,
     pub output: Option<String>,
     pub duration_ms: Option<i64>,
+    /// Provider cost of the run in USD, when known (batch runs).
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -143,12 +194,13 @@ pub struct CronJobPatch {
     pub delivery: Option<DeliveryConfig>,
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
+    pub execution_mode: Option<ExecutionMode>,
     pub delete_after_run: Option<bool>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JobType;
+    use super::{ExecutionMode, JobType};
 
     #[test]
     fn job_type_try_from_accepts_known_values_case_insensitive() {
@@ -163,4 +215,12 @@ mod tests {
         assert!(JobType::try_from("").is_err());
         assert!(JobType::try_from("unknown").is_err());
     }
+
+    #[test]
+    fn execution_mode_parse_defaults_to_direct() {
+        assert_eq!(ExecutionMode::parse("batch"), ExecutionMode::Batch);
+        assert_eq!(ExecutionMode::parse("BATCH"), ExecutionMode::Batch);
+        assert_eq!(ExecutionMode::parse("direct"), ExecutionMode::Direct);
+        assert_eq!(ExecutionMode::parse(""), ExecutionMode::Direct);
+    }
 }
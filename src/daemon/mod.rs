@@ -49,7 +49,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    async move { Box::pin(crate::channels::start_channels(cfg)).await }
                 },
             ));
         } else {
@@ -302,6 +302,7 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            group: crate::config::GroupPolicyConfig::default(),
         });
         assert!(has_supervised_channels(&config));
     }
@@ -327,6 +328,8 @@ mod tests {
             allowed_users: vec!["*".into()],
             thread_replies: Some(true),
             mention_only: Some(false),
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
         });
         assert!(has_supervised_channels(&config));
     }
@@ -8,6 +8,7 @@ const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
 const COMMAND_VERSION_PREVIEW_CHARS: usize = 60;
+const TLS_EXPIRY_WARN_DAYS: i64 = 14;
 
 // ── Diagnostic item ──────────────────────────────────────────────
 
@@ -81,6 +82,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
 
     check_config_semantics(config, &mut items);
     check_workspace(config, &mut items);
+    check_gateway_tls(config, &mut items);
     check_daemon_state(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
@@ -650,6 +652,67 @@ fn check_workspace(config: &Config, items: &mut Vec<DiagItem>) {
     check_file_exists(ws, "AGENTS.md", false, cat, items);
 }
 
+// ── Gateway TLS ──────────────────────────────────────────────────
+
+fn check_gateway_tls(config: &Config, items: &mut Vec<DiagItem>) {
+    use crate::gateway::tls;
+
+    let cat = "gateway-tls";
+    let settings = &config.gateway.tls;
+    if !settings.enabled {
+        return;
+    }
+
+    let mut files = vec![("certificate", settings.cert_path.as_str())];
+    if let Some(ca) = settings.client_ca_path.as_deref() {
+        files.push(("client CA", ca));
+    }
+    for (label, raw) in files {
+        let path = tls::resolve_path(raw);
+        let certs = match tls::load_certs(&path) {
+            Ok(certs) => certs,
+            Err(e) => {
+                items.push(DiagItem::error(cat, format!("{label}: {e:#}")));
+                continue;
+            }
+        };
+        match tls::certificate_not_after(certs[0].as_ref()) {
+            Some(not_after) => {
+                items.push(certificate_expiry_item(cat, label, not_after, Utc::now()));
+            }
+            None => items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "{label}: could not read expiry date from {}",
+                    path.display()
+                ),
+            )),
+        }
+    }
+
+    match tls::build_server_config(settings) {
+        Ok(_) => items.push(DiagItem::ok(cat, "certificate and key load")),
+        Err(e) => items.push(DiagItem::error(cat, format!("{e:#}"))),
+    }
+}
+
+fn certificate_expiry_item(
+    cat: &'static str,
+    label: &str,
+    not_after: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> DiagItem {
+    let days = (not_after - now).num_days();
+    let date = not_after.format("%Y-%m-%d");
+    if not_after <= now {
+        DiagItem::error(cat, format!("{label} expired on {date}"))
+    } else if days < TLS_EXPIRY_WARN_DAYS {
+        DiagItem::warn(cat, format!("{label} expires in {days} days ({date})"))
+    } else {
+        DiagItem::ok(cat, format!("{label} valid until {date}"))
+    }
+}
+
 fn check_file_exists(
     base: &Path,
     name: &str,
@@ -964,6 +1027,21 @@ mod tests {
         assert!(invalid_unknown.contains("Unknown provider"));
     }
 
+    #[test]
+    fn certificate_expiry_item_warns_before_expiry() {
+        let now = Utc::now();
+        let expired =
+            certificate_expiry_item("t", "certificate", now - chrono::Duration::days(1), now);
+        assert_eq!(expired.severity, Severity::Error);
+        let soon =
+            certificate_expiry_item("t", "certificate", now + chrono::Duration::days(3), now);
+        assert_eq!(soon.severity, Severity::Warn);
+        assert!(soon.message.contains("expires in"));
+        let fine =
+            certificate_expiry_item("t", "certificate", now + chrono::Duration::days(90), now);
+        assert_eq!(fine.severity, Severity::Ok);
+    }
+
     #[test]
     fn diag_item_icons() {
         assert_eq!(DiagItem::ok("t", "m").icon(), "✅");
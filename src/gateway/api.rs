@@ -1,8 +1,11 @@
 //! REST API handlers for the web dashboard.
 //!
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
+//! Reads need the `read` scope; config, cron and memory changes and the
+//! doctor run need `admin`.
 
 use super::AppState;
+use crate::config::GatewayScope;
 use axum::{
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
@@ -13,33 +16,28 @@ use serde::Deserialize;
 // ── Bearer token auth extractor ─────────────────────────────────
 
 /// Extract and validate bearer token from Authorization header.
-fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
+pub(crate) fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
     headers
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .and_then(|auth| auth.strip_prefix("Bearer "))
 }
 
-/// Verify bearer token against PairingGuard. Returns error response if unauthorized.
-fn require_auth(
+/// Verify the bearer token carries `scope`. Returns error response if not.
+fn require_scope(
     state: &AppState,
     headers: &HeaderMap,
+    scope: GatewayScope,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    if !state.pairing.require_pairing() {
-        return Ok(());
-    }
-
     let token = extract_bearer_token(headers).unwrap_or("");
-    if state.pairing.is_authenticated(token) {
-        Ok(())
-    } else {
-        Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            })),
-        ))
-    }
+    super::authorize(state, token, scope)
+        .map(|_| ())
+        .map_err(|e| {
+            (
+                e.status(),
+                Json(serde_json::json!({ "error": e.message() })),
+            )
+        })
 }
 
 // ── Query parameters ─────────────────────────────────────────────
@@ -71,7 +69,7 @@ pub async fn handle_api_status(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -105,7 +103,7 @@ pub async fn handle_api_config_get(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -139,7 +137,7 @@ pub async fn handle_api_config_put(
     headers: HeaderMap,
     body: String,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -175,7 +173,7 @@ pub async fn handle_api_tools(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -199,7 +197,7 @@ pub async fn handle_api_cron_list(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -236,7 +234,7 @@ pub async fn handle_api_cron_add(
     headers: HeaderMap,
     Json(body): Json<CronAddBody>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -271,7 +269,7 @@ pub async fn handle_api_cron_delete(
     headers: HeaderMap,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -291,7 +289,7 @@ pub async fn handle_api_integrations(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -319,7 +317,7 @@ pub async fn handle_api_doctor(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -356,7 +354,7 @@ pub async fn handle_api_memory_list(
     headers: HeaderMap,
     Query(params): Query<MemoryQuery>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -396,7 +394,7 @@ pub async fn handle_api_memory_store(
     headers: HeaderMap,
     Json(body): Json<MemoryStoreBody>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -431,7 +429,7 @@ pub async fn handle_api_memory_delete(
     headers: HeaderMap,
     Path(key): Path<String>,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Admin) {
         return e.into_response();
     }
 
@@ -452,7 +450,7 @@ pub async fn handle_api_cost(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -485,7 +483,7 @@ pub async fn handle_api_cli_tools(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -499,7 +497,7 @@ pub async fn handle_api_health(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Err(e) = require_auth(&state, &headers) {
+    if let Err(e) = require_scope(&state, &headers, GatewayScope::Read) {
         return e.into_response();
     }
 
@@ -0,0 +1,384 @@
+//! Generic inbound event hooks — `POST /hooks/{name}`.
+//!
+//! Each `[gateway.hooks.<name>]` entry verifies its sender (bearer token,
+//! shared secret, GitHub, GitLab or Stripe-style signatures), renders the
+//! JSON payload into a prompt with its template and queues the prompt as an
+//! async job (see [`super::jobs`]). The answer can be sent to a channel.
+
+use super::jobs::{self, NewJob};
+use super::{authorize, client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::config::{GatewayScope, HookVerification, InboundHookConfig};
+use crate::security::pairing::constant_time_eq;
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::SocketAddr;
+
+/// Maximum age of a Stripe-style signature timestamp
+const STRIPE_TOLERANCE_SECS: i64 = 300;
+/// Maximum characters of `{{payload}}` rendered into a prompt
+const MAX_PAYLOAD_CHARS: usize = 16_000;
+
+const DEFAULT_TEMPLATE: &str =
+    "Inbound event `{{event}}` from hook `{{hook}}`:\n\n```json\n{{payload}}\n```";
+
+/// POST /hooks/{name} — verify, render and queue an inbound event
+pub async fn handle_hook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/hooks/{name} rate limit exceeded");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    let hook = state.config.lock().gateway.hooks.get(&name).cloned();
+    let Some(hook) = hook.filter(|h| h.enabled) else {
+        let err = serde_json::json!({"error": format!("Unknown hook '{name}'")});
+        return (StatusCode::NOT_FOUND, Json(err));
+    };
+
+    if let Err((status, message)) = verify(&state, &hook, &headers, &body) {
+        tracing::warn!("Hook '{name}': rejected — {message}");
+        return (status, Json(serde_json::json!({"error": message})));
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!("Hook '{name}': invalid JSON body: {e}");
+            let err = serde_json::json!({"error": "Invalid JSON body"});
+            return (StatusCode::BAD_REQUEST, Json(err));
+        }
+    };
+
+    let event = event_name(hook.verify, &headers, &payload);
+    if !event_matches(&hook.events, &event) {
+        tracing::debug!("Hook '{name}': ignoring event '{event}'");
+        let body = serde_json::json!({"status": "ignored", "event": event});
+        return (StatusCode::OK, Json(body));
+    }
+
+    let idempotency_key =
+        delivery_id(hook.verify, &headers, &payload).map(|id| format!("hook:{name}:{id}"));
+    if let Some(ref key) = idempotency_key {
+        if !state.idempotency_store.record_if_new(key) {
+            tracing::info!("Hook '{name}': duplicate delivery ignored ({key})");
+            let body = serde_json::json!({"status": "duplicate", "idempotent": true});
+            return (StatusCode::OK, Json(body));
+        }
+    }
+
+    let template = hook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let prompt = render_template(template, &name, &event, &payload, &headers);
+    let source = format!("hook:{name}");
+    jobs::submit(
+        &state,
+        &NewJob {
+            message: &prompt,
+            source: &source,
+            idempotency_key: idempotency_key.as_deref(),
+            deliver_channel: hook.channel.as_deref(),
+            deliver_to: hook.to.as_deref(),
+            ..NewJob::default()
+        },
+    )
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Check the sender against the hook's verification scheme.
+fn verify(
+    state: &AppState,
+    hook: &InboundHookConfig,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(), (StatusCode, String)> {
+    let secret = hook.secret.as_deref().unwrap_or("").trim();
+    let unauthorized = |message: &str| Err((StatusCode::UNAUTHORIZED, message.to_string()));
+    if hook.verify != HookVerification::Bearer && secret.is_empty() {
+        return unauthorized("hook secret is not configured");
+    }
+
+    match hook.verify {
+        HookVerification::Bearer => {
+            let token = headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|auth| auth.strip_prefix("Bearer "))
+                .unwrap_or("");
+            authorize(state, token, GatewayScope::Chat)
+                .map(|_| ())
+                .map_err(|e| (e.status(), e.message()))
+        }
+        HookVerification::SharedSecret => match header_str(headers, &hook.secret_header) {
+            Some(value) if constant_time_eq(value, secret) => Ok(()),
+            _ => unauthorized("invalid or missing shared secret header"),
+        },
+        HookVerification::Github => match header_str(headers, "X-Hub-Signature-256") {
+            Some(sig) if super::verify_whatsapp_signature(secret, body, sig) => Ok(()),
+            _ => unauthorized("invalid or missing X-Hub-Signature-256"),
+        },
+        HookVerification::Gitlab => match header_str(headers, "X-Gitlab-Token") {
+            Some(value) if constant_time_eq(value, secret) => Ok(()),
+            _ => unauthorized("invalid or missing X-Gitlab-Token"),
+        },
+        HookVerification::Stripe => {
+            let Some(sig) = header_str(headers, "Stripe-Signature") else {
+                return unauthorized("missing Stripe-Signature");
+            };
+            if verify_stripe_signature(secret, body, sig, chrono::Utc::now().timestamp()) {
+                Ok(())
+            } else {
+                unauthorized("invalid or expired Stripe-Signature")
+            }
+        }
+    }
+}
+
+/// Verify `t=<ts>,v1=<hex>` where `v1` is HMAC-SHA256 of `"{ts}.{body}"`.
+/// Any matching `v1` is accepted (signing secrets may be rolled).
+pub fn verify_stripe_signature(secret: &str, body: &[u8], header: &str, now: i64) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if (now - timestamp).abs() > STRIPE_TOLERANCE_SECS {
+        return false;
+    }
+
+    signatures.into_iter().any(|sig| {
+        let Ok(expected) = hex::decode(sig) else {
+            return false;
+        };
+        let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+            return false;
+        };
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    })
+}
+
+/// Event name used for filtering and `{{event}}`, e.g. `issues.opened`.
+fn event_name(
+    verify: HookVerification,
+    headers: &HeaderMap,
+    payload: &serde_json::Value,
+) -> String {
+    let str_at = |pointer: &str| payload.pointer(pointer).and_then(|v| v.as_str());
+    let (kind, action) = match verify {
+        HookVerification::Github => (header_str(headers, "X-GitHub-Event"), str_at("/action")),
+        HookVerification::Gitlab => (
+            str_at("/object_kind").or_else(|| header_str(headers, "X-Gitlab-Event")),
+            str_at("/object_attributes/action"),
+        ),
+        HookVerification::Stripe => (str_at("/type"), None),
+        HookVerification::Bearer | HookVerification::SharedSecret => (
+            header_str(headers, "X-Event-Type")
+                .or_else(|| str_at("/type"))
+                .or_else(|| str_at("/event")),
+            None,
+        ),
+    };
+    match (kind, action) {
+        (Some(kind), Some(action)) => format!("{kind}.{action}"),
+        (Some(kind), None) => kind.to_string(),
+        (None, _) => "event".to_string(),
+    }
+}
+
+/// `issues` matches `issues` and `issues.opened`; empty filters match all.
+fn event_matches(filters: &[String], event: &str) -> bool {
+    filters.is_empty()
+        || filters.iter().any(|filter| {
+            let filter = filter.trim();
+            event == filter
+                || event
+                    .strip_prefix(filter)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+}
+
+/// Sender-assigned delivery id, used to drop redeliveries.
+fn delivery_id(
+    verify: HookVerification,
+    headers: &HeaderMap,
+    payload: &serde_json::Value,
+) -> Option<String> {
+    let from_sender = match verify {
+        HookVerification::Github => header_str(headers, "X-GitHub-Delivery"),
+        HookVerification::Gitlab => header_str(headers, "X-Gitlab-Event-UUID"),
+        HookVerification::Stripe => payload.get("id").and_then(|v| v.as_str()),
+        HookVerification::Bearer | HookVerification::SharedSecret => None,
+    };
+    from_sender
+        .or_else(|| header_str(headers, "X-Idempotency-Key"))
+        .map(str::to_string)
+}
+
+/// Replace `{{...}}` placeholders. Unknown placeholders render empty.
+fn render_template(
+    template: &str,
+    hook: &str,
+    event: &str,
+    payload: &serde_json::Value,
+    headers: &HeaderMap,
+) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let key = after[..end].trim();
+        out.push_str(&placeholder(key, hook, event, payload, headers));
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn placeholder(
+    key: &str,
+    hook: &str,
+    event: &str,
+    payload: &serde_json::Value,
+    headers: &HeaderMap,
+) -> String {
+    match key {
+        "hook" => return hook.to_string(),
+        "event" => return event.to_string(),
+        "payload" => {
+            let pretty = serde_json::to_string_pretty(payload).unwrap_or_default();
+            return crate::util::truncate_with_ellipsis(&pretty, MAX_PAYLOAD_CHARS);
+        }
+        _ => {}
+    }
+    if let Some(name) = key.strip_prefix("header.") {
+        return header_str(headers, name).unwrap_or("").to_string();
+    }
+    let Some(path) = key.strip_prefix("payload.") else {
+        return String::new();
+    };
+    let mut value = payload;
+    for segment in path.split('.') {
+        let next = match value {
+            serde_json::Value::Array(items) => {
+                segment.parse::<usize>().ok().and_then(|i| items.get(i))
+            }
+            _ => value.get(segment),
+        };
+        let Some(next) = next else {
+            return String::new();
+        };
+        value = next;
+    }
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => crate::util::truncate_with_ellipsis(&other.to_string(), MAX_PAYLOAD_CHARS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn hmac_hex(secret: &str, data: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(data);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn template_renders_payload_paths_headers_and_event() {
+        let payload = serde_json::json!({
+            "action": "opened",
+            "issue": {"number": 42, "title": "Crash on start", "labels": [{"name": "bug"}]},
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert("X-GitHub-Event", HeaderValue::from_static("issues"));
+
+        let event = event_name(HookVerification::Github, &headers, &payload);
+        assert_eq!(event, "issues.opened");
+        let rendered = render_template(
+            "[{{hook}}/{{ event }}] #{{payload.issue.number}} {{payload.issue.title}} \
+             ({{payload.issue.labels.0.name}}) via {{header.X-GitHub-Event}}{{payload.missing}}",
+            "github",
+            &event,
+            &payload,
+            &headers,
+        );
+        assert_eq!(
+            rendered,
+            "[github/issues.opened] #42 Crash on start (bug) via issues"
+        );
+
+        let default = render_template(DEFAULT_TEMPLATE, "github", &event, &payload, &headers);
+        assert!(default.contains("\"title\": \"Crash on start\""));
+    }
+
+    #[test]
+    fn event_filters_match_kind_or_kind_with_action() {
+        assert!(event_matches(&[], "push"));
+        let filters = vec!["issues".to_string(), "alert.firing".to_string()];
+        assert!(event_matches(&filters, "issues.opened"));
+        assert!(event_matches(&filters, "alert.firing"));
+        assert!(!event_matches(&filters, "issue_comment.created"));
+        assert!(!event_matches(&filters, "alert.resolved"));
+    }
+
+    #[test]
+    fn stripe_signature_checks_hmac_and_timestamp_tolerance() {
+        let body = br#"{"id":"evt_1","type":"invoice.paid"}"#;
+        let now = 1_700_000_000;
+        let mut signed = format!("{now}.").into_bytes();
+        signed.extend_from_slice(body);
+        let sig = hmac_hex("whsec", &signed);
+        let header = format!("t={now},v1=deadbeef,v1={sig}");
+
+        assert!(verify_stripe_signature("whsec", body, &header, now + 10));
+        assert!(!verify_stripe_signature("other", body, &header, now));
+        assert!(!verify_stripe_signature("whsec", b"{}", &header, now));
+        assert!(!verify_stripe_signature(
+            "whsec",
+            body,
+            &header,
+            now + STRIPE_TOLERANCE_SECS + 1
+        ));
+    }
+}
@@ -0,0 +1,784 @@
+//! Asynchronous `/webhook` jobs.
+//!
+//! `POST /webhook` with `"async": true` (or a `callback_url`) queues the
+//! message and returns `202` with a job id instead of holding the request
+//! open for the whole agent run. Jobs run the full tool loop; progress lines
+//! are recorded as events and `GET /api/jobs/{id}` reports status, events and
+//! the final answer. When a callback URL was given, the finished job is POSTed
+//! there with an HMAC-SHA256 signature.
+//!
+//! Jobs live in `{workspace}/gateway/jobs.db`, so they survive a restart:
+//! queued jobs are resumed, while jobs that were running are marked failed
+//! rather than replayed, since their tools may already have had side effects.
+
+use super::AppState;
+use crate::agent::loop_::DRAFT_CLEAR_SENTINEL;
+use crate::config::{Config, GatewayJobsConfig, GatewayScope};
+use crate::security::EgressPolicy;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+/// Header carrying `sha256=<hex>` over `"{timestamp}.{body}"`
+pub const SIGNATURE_HEADER: &str = "X-ZeroClaw-Signature";
+/// Header carrying the Unix timestamp that was signed
+pub const TIMESTAMP_HEADER: &str = "X-ZeroClaw-Timestamp";
+/// Maximum progress events kept per job
+const MAX_EVENTS_PER_JOB: usize = 200;
+
+/// Lifecycle state of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "queued" => Self::Queued,
+            "running" => Self::Running,
+            "succeeded" => Self::Succeeded,
+            _ => Self::Failed,
+        }
+    }
+}
+
+/// A persisted job. The submitted message and idempotency key are not echoed.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub status: JobStatus,
+    /// `webhook`, or `hook:<name>` for inbound event hooks
+    pub source: String,
+    #[serde(skip)]
+    pub message: String,
+    pub callback_url: Option<String>,
+    pub deliver_channel: Option<String>,
+    pub deliver_to: Option<String>,
+    #[serde(skip)]
+    pub idempotency_key: Option<String>,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub callback_status: Option<String>,
+    pub delivery_status: Option<String>,
+}
+
+/// Parameters for a new job
+#[derive(Debug, Default)]
+pub struct NewJob<'a> {
+    pub message: &'a str,
+    /// `webhook`, or `hook:<name>`
+    pub source: &'a str,
+    pub callback_url: Option<&'a str>,
+    pub idempotency_key: Option<&'a str>,
+    /// Channel and recipient that receive the final answer
+    pub deliver_channel: Option<&'a str>,
+    pub deliver_to: Option<&'a str>,
+}
+
+/// A progress line recorded while a job runs
+#[derive(Debug, Clone, Serialize)]
+pub struct JobEvent {
+    pub at: String,
+    pub message: String,
+}
+
+/// SQLite-backed job store plus the concurrency limit for running jobs
+pub struct JobStore {
+    conn: Mutex<Connection>,
+    slots: Arc<Semaphore>,
+    config: GatewayJobsConfig,
+}
+
+const JOB_COLUMNS: &str = "id, status, source, message, callback_url, deliver_channel, deliver_to,
+     idempotency_key, response, error, created_at, started_at, finished_at, callback_status,
+     delivery_status";
+
+fn job_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<JobRecord> {
+    Ok(JobRecord {
+        id: row.get(0)?,
+        status: JobStatus::parse(&row.get::<_, String>(1)?),
+        source: row.get(2)?,
+        message: row.get(3)?,
+        callback_url: row.get(4)?,
+        deliver_channel: row.get(5)?,
+        deliver_to: row.get(6)?,
+        idempotency_key: row.get(7)?,
+        response: row.get(8)?,
+        error: row.get(9)?,
+        created_at: row.get(10)?,
+        started_at: row.get(11)?,
+        finished_at: row.get(12)?,
+        callback_status: row.get(13)?,
+        delivery_status: row.get(14)?,
+    })
+}
+
+impl JobStore {
+    /// Open (or create) `{workspace_dir}/gateway/jobs.db`.
+    pub fn open(workspace_dir: &std::path::Path, config: &GatewayJobsConfig) -> Result<Self> {
+        let db_path = workspace_dir.join("gateway").join("jobs.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create gateway directory: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open jobs DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS gateway_jobs (
+                id              TEXT PRIMARY KEY,
+                status          TEXT NOT NULL,
+                source          TEXT NOT NULL DEFAULT 'webhook',
+                message         TEXT NOT NULL,
+                callback_url    TEXT,
+                deliver_channel TEXT,
+                deliver_to      TEXT,
+                idempotency_key TEXT,
+                response        TEXT,
+                error           TEXT,
+                created_at      TEXT NOT NULL,
+                started_at      TEXT,
+                finished_at     TEXT,
+                callback_status TEXT,
+                delivery_status TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_gateway_jobs_status ON gateway_jobs(status);
+            CREATE INDEX IF NOT EXISTS idx_gateway_jobs_idempotency
+                ON gateway_jobs(idempotency_key);
+
+            CREATE TABLE IF NOT EXISTS gateway_job_events (
+                id      INTEGER PRIMARY KEY AUTOINCREMENT,
+                job_id  TEXT NOT NULL,
+                at      TEXT NOT NULL,
+                message TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_gateway_job_events_job
+                ON gateway_job_events(job_id);",
+        )
+        .context("Failed to initialize jobs schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            slots: Arc::new(Semaphore::new(config.max_concurrent.max(1))),
+            config: config.clone(),
+        })
+    }
+
+    /// Queue a new job.
+    pub fn create(&self, new: &NewJob<'_>) -> Result<JobRecord> {
+        let id = Uuid::new_v4().to_string();
+        let created_at = Utc::now().to_rfc3339();
+        let source = if new.source.is_empty() {
+            "webhook"
+        } else {
+            new.source
+        };
+        self.conn.lock().execute(
+            "INSERT INTO gateway_jobs (id, status, source, message, callback_url, deliver_channel,
+                deliver_to, idempotency_key, created_at)
+             VALUES (?1, 'queued', ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                source,
+                new.message,
+                new.callback_url,
+                new.deliver_channel,
+                new.deliver_to,
+                new.idempotency_key,
+                created_at
+            ],
+        )?;
+        Ok(JobRecord {
+            id,
+            status: JobStatus::Queued,
+            source: source.to_string(),
+            message: new.message.to_string(),
+            callback_url: new.callback_url.map(str::to_string),
+            deliver_channel: new.deliver_channel.map(str::to_string),
+            deliver_to: new.deliver_to.map(str::to_string),
+            idempotency_key: new.idempotency_key.map(str::to_string),
+            response: None,
+            error: None,
+            created_at,
+            started_at: None,
+            finished_at: None,
+            callback_status: None,
+            delivery_status: None,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<JobRecord>> {
+        let conn = self.conn.lock();
+        let job = conn
+            .query_row(
+                &format!("SELECT {JOB_COLUMNS} FROM gateway_jobs WHERE id = ?1"),
+                params![id],
+                job_from_row,
+            )
+            .optional()?;
+        Ok(job)
+    }
+
+    /// Most recent job submitted with `key`.
+    pub fn find_by_idempotency_key(&self, key: &str) -> Result<Option<JobRecord>> {
+        let conn = self.conn.lock();
+        let job = conn
+            .query_row(
+                &format!(
+                    "SELECT {JOB_COLUMNS} FROM gateway_jobs WHERE idempotency_key = ?1
+                     ORDER BY created_at DESC LIMIT 1"
+                ),
+                params![key],
+                job_from_row,
+            )
+            .optional()?;
+        Ok(job)
+    }
+
+    /// Idempotency keys submitted within `ttl`, with their age, for seeding
+    /// the in-memory [`super::IdempotencyStore`] after a restart.
+    pub fn recent_idempotency_keys(&self, ttl: Duration) -> Result<Vec<(String, Duration)>> {
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::zero());
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT idempotency_key, created_at FROM gateway_jobs
+             WHERE idempotency_key IS NOT NULL AND created_at >= ?1",
+        )?;
+        let rows = stmt.query_map(params![cutoff.to_rfc3339()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut keys = Vec::new();
+        for row in rows {
+            let (key, created_at) = row?;
+            let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(&created_at) else {
+                continue;
+            };
+            let age = (now - created_at.with_timezone(&Utc))
+                .to_std()
+                .unwrap_or_default();
+            keys.push((key, age));
+        }
+        Ok(keys)
+    }
+
+    pub fn events(&self, id: &str) -> Result<Vec<JobEvent>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare("SELECT at, message FROM gateway_job_events WHERE job_id = ?1 ORDER BY id")?;
+        let rows = stmt.query_map(params![id], |row| {
+            Ok(JobEvent {
+                at: row.get(0)?,
+                message: row.get(1)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    fn add_event(&self, id: &str, message: &str) -> Result<()> {
+        let conn = self.conn.lock();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM gateway_job_events WHERE job_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        if usize::try_from(count).unwrap_or(usize::MAX) >= MAX_EVENTS_PER_JOB {
+            return Ok(());
+        }
+        conn.execute(
+            "INSERT INTO gateway_job_events (job_id, at, message) VALUES (?1, ?2, ?3)",
+            params![id, Utc::now().to_rfc3339(), message],
+        )?;
+        Ok(())
+    }
+
+    fn mark_running(&self, id: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE gateway_jobs SET status = 'running', started_at = ?2 WHERE id = ?1",
+            params![id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    fn finish(&self, id: &str, outcome: &std::result::Result<String, String>) -> Result<()> {
+        let (status, response, error) = match outcome {
+            Ok(response) => (JobStatus::Succeeded, Some(response.as_str()), None),
+            Err(error) => (JobStatus::Failed, None, Some(error.as_str())),
+        };
+        self.conn.lock().execute(
+            "UPDATE gateway_jobs SET status = ?2, response = ?3, error = ?4, finished_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                status.as_str(),
+                response,
+                error,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn set_callback_status(&self, id: &str, status: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE gateway_jobs SET callback_status = ?2 WHERE id = ?1",
+            params![id, status],
+        )?;
+        Ok(())
+    }
+
+    fn set_delivery_status(&self, id: &str, status: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "UPDATE gateway_jobs SET delivery_status = ?2 WHERE id = ?1",
+            params![id, status],
+        )?;
+        Ok(())
+    }
+
+    /// Fail jobs left running by a previous gateway process and return them
+    /// with every still-queued job, oldest first.
+    fn recover(&self) -> Result<(Vec<JobRecord>, Vec<JobRecord>)> {
+        let interrupted: Vec<String> = {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare("SELECT id FROM gateway_jobs WHERE status = 'running'")?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            ids
+        };
+        let mut failed = Vec::with_capacity(interrupted.len());
+        for id in interrupted {
+            self.finish(&id, &Err("Interrupted by gateway restart".into()))?;
+            if let Some(job) = self.get(&id)? {
+                failed.push(job);
+            }
+        }
+
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {JOB_COLUMNS} FROM gateway_jobs WHERE status = 'queued' ORDER BY created_at"
+        ))?;
+        let queued = stmt
+            .query_map([], job_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok((queued, failed))
+    }
+
+    /// Delete finished jobs older than `retention_hours`.
+    fn prune(&self) -> Result<usize> {
+        let hours = i64::try_from(self.config.retention_hours).unwrap_or(i64::MAX / 3600);
+        let cutoff = (Utc::now() - chrono::Duration::hours(hours)).to_rfc3339();
+        let conn = self.conn.lock();
+        conn.execute(
+            "DELETE FROM gateway_job_events WHERE job_id IN (
+                SELECT id FROM gateway_jobs
+                WHERE status IN ('succeeded', 'failed') AND finished_at < ?1)",
+            params![cutoff],
+        )?;
+        let removed = conn.execute(
+            "DELETE FROM gateway_jobs WHERE status IN ('succeeded', 'failed') AND finished_at < ?1",
+            params![cutoff],
+        )?;
+        Ok(removed)
+    }
+}
+
+/// Check a submitted callback URL against the scheme, the egress policy and
+/// the presence of a signing secret.
+pub(crate) fn validate_callback_url(config: &Config, url: &str) -> std::result::Result<(), String> {
+    if config
+        .gateway
+        .jobs
+        .callback_secret
+        .as_deref()
+        .is_none_or(|s| s.trim().is_empty())
+    {
+        return Err("callback_url requires [gateway.jobs] callback_secret to be configured".into());
+    }
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid callback_url: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("callback_url must use http or https".into());
+    }
+    EgressPolicy::from_config(&config.security.egress)
+        .check_url(url)
+        .map_err(|e| format!("callback_url blocked: {e}"))
+}
+
+/// Queue a job and start it. Returns the `202` response body.
+pub(crate) fn submit(state: &AppState, new: &NewJob<'_>) -> (StatusCode, Json<serde_json::Value>) {
+    let Some(jobs) = state.jobs.as_ref() else {
+        let err = serde_json::json!({"error": "Async jobs are unavailable on this gateway"});
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(err));
+    };
+    match jobs.create(new) {
+        Ok(job) => {
+            tracing::info!(job_id = %job.id, source = %job.source, "Gateway: queued async job");
+            spawn(state.clone(), job.clone());
+            (StatusCode::ACCEPTED, Json(accepted_body(&job, false)))
+        }
+        Err(e) => {
+            tracing::error!("Failed to queue webhook job: {e}");
+            let err = serde_json::json!({"error": "Failed to queue job"});
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
+        }
+    }
+}
+
+/// Body returned when a job is accepted (or matched by idempotency key)
+pub(crate) fn accepted_body(job: &JobRecord, idempotent: bool) -> serde_json::Value {
+    serde_json::json!({
+        "job_id": job.id,
+        "status": job.status,
+        "status_url": format!("/api/jobs/{}", job.id),
+        "idempotent": idempotent,
+    })
+}
+
+/// Resume jobs after a restart: fail interrupted ones, restart queued ones
+/// and prune expired history.
+pub(crate) fn resume(state: &AppState) {
+    let Some(jobs) = state.jobs.as_ref() else {
+        return;
+    };
+    match jobs.prune() {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Pruned {n} finished webhook job(s)"),
+        Err(e) => tracing::warn!("Failed to prune webhook jobs: {e}"),
+    }
+    match jobs.recover() {
+        Ok((queued, interrupted)) => {
+            for job in interrupted {
+                tracing::warn!(job_id = %job.id, "Webhook job interrupted by restart");
+                if job.callback_url.is_some() {
+                    let state = state.clone();
+                    tokio::spawn(async move { deliver_callback(&state, &job).await });
+                }
+            }
+            if !queued.is_empty() {
+                tracing::info!("Resuming {} queued webhook job(s)", queued.len());
+            }
+            for job in queued {
+                spawn(state.clone(), job);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to recover webhook jobs: {e}"),
+    }
+}
+
+fn spawn(state: AppState, job: JobRecord) {
+    tokio::spawn(Box::pin(run(state, job)));
+}
+
+async fn run(state: AppState, job: JobRecord) {
+    let Some(jobs) = state.jobs.clone() else {
+        return;
+    };
+    let Ok(_permit) = Arc::clone(&jobs.slots).acquire_owned().await else {
+        return;
+    };
+    if let Err(e) = jobs.mark_running(&job.id) {
+        tracing::warn!(job_id = %job.id, "Failed to mark job running: {e}");
+    }
+
+    // Progress lines arrive before the clear sentinel; the answer after it
+    // is taken from the return value instead.
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(64);
+    let relay_jobs = Arc::clone(&jobs);
+    let relay_id = job.id.clone();
+    let relay = tokio::spawn(async move {
+        let mut answering = false;
+        while let Some(delta) = rx.recv().await {
+            if delta == DRAFT_CLEAR_SENTINEL {
+                answering = true;
+            }
+            if answering {
+                continue;
+            }
+            for line in delta.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                if let Err(e) = relay_jobs.add_event(&relay_id, line) {
+                    tracing::debug!(job_id = %relay_id, "Failed to record job event: {e}");
+                }
+            }
+        }
+    });
+
+    // Hook jobs run as channel "hook", which `[security.injection]` treats as
+    // untrusted by default.
+    let channel_name = job.source.split(':').next().unwrap_or("webhook");
+    let config = state.config.lock().clone();
+    let outcome = crate::agent::process_conversation(
+        config.clone(),
+        &[],
+        &job.message,
+        channel_name,
+        Some(tx),
+    )
+    .await
+    .map_err(|e| crate::providers::sanitize_api_error(&e.to_string()));
+    let _ = relay.await;
+
+    if let Err(e) = &outcome {
+        tracing::error!(job_id = %job.id, "Webhook job failed: {e}");
+    }
+    if let Err(e) = jobs.finish(&job.id, &outcome) {
+        tracing::error!(job_id = %job.id, "Failed to record job result: {e}");
+    }
+
+    if let (Ok(response), Some(channel), Some(to)) = (
+        &outcome,
+        job.deliver_channel.as_deref(),
+        job.deliver_to.as_deref(),
+    ) {
+        let status =
+            match crate::cron::scheduler::send_to_channel(&config, channel, to, response).await {
+                Ok(()) => "delivered".to_string(),
+                Err(e) => {
+                    tracing::warn!(job_id = %job.id, "Job delivery to {channel} failed: {e}");
+                    format!("failed: {e}")
+                }
+            };
+        if let Err(e) = jobs.set_delivery_status(&job.id, &status) {
+            tracing::warn!(job_id = %job.id, "Failed to record delivery status: {e}");
+        }
+    }
+
+    if job.callback_url.is_some() {
+        match jobs.get(&job.id) {
+            Ok(Some(finished)) => deliver_callback(&state, &finished).await,
+            Ok(None) => {}
+            Err(e) => tracing::warn!(job_id = %job.id, "Failed to reload job: {e}"),
+        }
+    }
+}
+
+/// HMAC-SHA256 of `"{timestamp}.{body}"`, hex-encoded.
+pub fn sign_callback(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+async fn deliver_callback(state: &AppState, job: &JobRecord) {
+    let (Some(jobs), Some(url)) = (state.jobs.as_ref(), job.callback_url.as_deref()) else {
+        return;
+    };
+    let config = state.config.lock().clone();
+    let Some(secret) = config.gateway.jobs.callback_secret.clone() else {
+        let _ = jobs.set_callback_status(&job.id, "failed: no callback_secret configured");
+        return;
+    };
+
+    let status = match send_callback(&config, url, &secret, job).await {
+        Ok(()) => "delivered".to_string(),
+        Err(e) => {
+            tracing::warn!(job_id = %job.id, "Job callback failed: {e}");
+            format!("failed: {e}")
+        }
+    };
+    if let Err(e) = jobs.set_callback_status(&job.id, &status) {
+        tracing::warn!(job_id = %job.id, "Failed to record callback status: {e}");
+    }
+}
+
+async fn send_callback(config: &Config, url: &str, secret: &str, job: &JobRecord) -> Result<()> {
+    let policy = Arc::new(EgressPolicy::from_config(&config.security.egress));
+    policy.check_url(url)?;
+    let client = policy.build_client(
+        "gateway.jobs",
+        config.gateway.jobs.callback_timeout_secs.max(1),
+        10,
+    )?;
+    let body = serde_json::to_string(job)?;
+    let attempts = config.gateway.jobs.callback_max_attempts.max(1);
+
+    let mut last_error = anyhow::anyhow!("no attempts made");
+    for attempt in 1..=attempts {
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_callback(secret, timestamp, &body);
+        let result = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+            .header("X-ZeroClaw-Job-Id", &job.id)
+            .body(body.clone())
+            .send()
+            .await;
+        match result {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => last_error = anyhow::anyhow!("HTTP {}", resp.status()),
+            Err(e) => last_error = anyhow::anyhow!("{e}"),
+        }
+        if attempt < attempts {
+            tokio::time::sleep(Duration::from_secs(1 << attempt.min(5))).await;
+        }
+    }
+    Err(last_error)
+}
+
+/// GET /api/jobs/{id} — job status, progress events and final answer.
+///
+/// Readable with either the `read` or the `chat` scope, so the token that
+/// submitted a job can poll it.
+pub async fn handle_job_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let token = super::api::extract_bearer_token(&headers).unwrap_or("");
+    if let Err(e) = super::authorize_any(&state, token, &[GatewayScope::Read, GatewayScope::Chat]) {
+        let err = serde_json::json!({"error": e.message()});
+        return (e.status(), Json(err));
+    }
+
+    let Some(jobs) = state.jobs.as_ref() else {
+        let err = serde_json::json!({"error": "Async jobs are unavailable on this gateway"});
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(err));
+    };
+    let job = match jobs.get(&id) {
+        Ok(Some(job)) => job,
+        Ok(None) => {
+            let err = serde_json::json!({"error": "Job not found"});
+            return (StatusCode::NOT_FOUND, Json(err));
+        }
+        Err(e) => {
+            tracing::error!("Failed to load job {id}: {e}");
+            let err = serde_json::json!({"error": "Failed to load job"});
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(err));
+        }
+    };
+    let events = jobs.events(&id).unwrap_or_default();
+
+    let mut body = serde_json::to_value(&job).unwrap_or_default();
+    body["events"] = serde_json::to_value(events).unwrap_or_default();
+    (StatusCode::OK, Json(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn jobs_persist_and_interrupted_runs_fail_on_recovery() {
+        let tmp = TempDir::new().unwrap();
+        let config = GatewayJobsConfig::default();
+
+        let (running_id, queued_id) = {
+            let store = JobStore::open(tmp.path(), &config).unwrap();
+            let running = store
+                .create(&NewJob {
+                    message: "deploy",
+                    callback_url: Some("https://ci.example.com/hook"),
+                    idempotency_key: Some("k1"),
+                    ..NewJob::default()
+                })
+                .unwrap();
+            store.mark_running(&running.id).unwrap();
+            store
+                .add_event(&running.id, "⏳ shell: cargo test")
+                .unwrap();
+            let queued = store
+                .create(&NewJob {
+                    message: "lint",
+                    source: "hook:github",
+                    ..NewJob::default()
+                })
+                .unwrap();
+            (running.id, queued.id)
+        };
+
+        // Reopen as a restarted gateway would.
+        let store = JobStore::open(tmp.path(), &config).unwrap();
+        let (queued, interrupted) = store.recover().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].id, queued_id);
+        assert_eq!(queued[0].message, "lint");
+        assert_eq!(queued[0].source, "hook:github");
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].status, JobStatus::Failed);
+        assert!(interrupted[0].error.as_deref().unwrap().contains("restart"));
+
+        assert_eq!(store.events(&running_id).unwrap().len(), 1);
+        let by_key = store.find_by_idempotency_key("k1").unwrap().unwrap();
+        assert_eq!(by_key.id, running_id);
+        let keys = store
+            .recent_idempotency_keys(Duration::from_secs(300))
+            .unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, "k1");
+
+        store.finish(&queued_id, &Ok("done".into())).unwrap();
+        let finished = store.get(&queued_id).unwrap().unwrap();
+        assert_eq!(finished.status, JobStatus::Succeeded);
+        assert_eq!(finished.response.as_deref(), Some("done"));
+        let json = serde_json::to_value(&finished).unwrap();
+        assert_eq!(json["status"], "succeeded");
+        assert!(json.get("message").is_none());
+    }
+
+    #[test]
+    fn callback_signature_covers_timestamp_and_body() {
+        let sig = sign_callback("secret", 1_700_000_000, "{\"id\":\"a\"}");
+        assert_eq!(sig.len(), 64);
+        assert_eq!(
+            sig,
+            sign_callback("secret", 1_700_000_000, "{\"id\":\"a\"}")
+        );
+        assert_ne!(
+            sig,
+            sign_callback("secret", 1_700_000_001, "{\"id\":\"a\"}")
+        );
+        assert_ne!(sig, sign_callback("other", 1_700_000_000, "{\"id\":\"a\"}"));
+    }
+
+    #[test]
+    fn callback_url_requires_secret_and_passes_egress_policy() {
+        let mut config = Config::default();
+        assert!(
+            validate_callback_url(&config, "https://ci.example.com/hook")
+                .unwrap_err()
+                .contains("callback_secret")
+        );
+
+        config.gateway.jobs.callback_secret = Some("s3cret".into());
+        assert!(validate_callback_url(&config, "https://ci.example.com/hook").is_ok());
+        assert!(validate_callback_url(&config, "ftp://ci.example.com/hook").is_err());
+        assert!(validate_callback_url(&config, "http://169.254.169.254/latest").is_err());
+    }
+}
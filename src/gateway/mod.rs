@@ -8,17 +8,26 @@
 //! - Header sanitization (handled by axum/hyper)
 
 pub mod api;
+pub mod hooks;
+pub mod jobs;
+pub mod openai;
+pub mod sessions;
 pub mod sse;
 pub mod static_files;
+pub mod tls;
+pub mod tokens;
 pub mod ws;
 
-use crate::channels::{Channel, LinqChannel, NextcloudTalkChannel, SendMessage, WhatsAppChannel};
-use crate::config::Config;
+use crate::channels::traits::ChannelMessage;
+use crate::channels::{
+    group, Channel, LinqChannel, NextcloudTalkChannel, SendMessage, SlackChannel, WhatsAppChannel,
+};
+use crate::config::{Config, GatewayScope, GroupPolicyConfig};
 use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
-use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
+use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard, TokenGrant};
 use crate::security::SecurityPolicy;
 use crate::tools;
 use crate::tools::traits::ToolSpec;
@@ -30,10 +39,11 @@ use axum::{
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
     routing::{delete, get, post, put},
+    serve::ListenerExt,
     Router,
 };
 use parking_lot::Mutex;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -68,6 +78,10 @@ fn nextcloud_talk_memory_key(msg: &crate::channels::traits::ChannelMessage) -> S
     format!("nextcloud_talk_{}_{}", msg.sender, msg.id)
 }
 
+fn slack_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("slack_{}_{}", msg.sender, msg.id)
+}
+
 fn hash_webhook_secret(value: &str) -> String {
     use sha2::{Digest, Sha256};
 
@@ -104,7 +118,13 @@ impl SlidingWindowRateLimiter {
     }
 
     fn allow(&self, key: &str) -> bool {
-        if self.limit_per_window == 0 {
+        self.allow_with_limit(key, self.limit_per_window)
+    }
+
+    /// Like [`allow`](Self::allow) with a caller-supplied limit, for keys
+    /// that carry their own budget (named gateway tokens).
+    fn allow_with_limit(&self, key: &str, limit_per_window: u32) -> bool {
+        if limit_per_window == 0 {
             return true;
         }
 
@@ -139,7 +159,7 @@ impl SlidingWindowRateLimiter {
         let entry = requests.entry(key.to_owned()).or_default();
         entry.retain(|instant| *instant > cutoff);
 
-        if entry.len() >= self.limit_per_window as usize {
+        if entry.len() >= limit_per_window as usize {
             return false;
         }
 
@@ -152,6 +172,8 @@ impl SlidingWindowRateLimiter {
 pub struct GatewayRateLimiter {
     pair: SlidingWindowRateLimiter,
     webhook: SlidingWindowRateLimiter,
+    /// Per named token; each token supplies its own limit.
+    token: SlidingWindowRateLimiter,
 }
 
 impl GatewayRateLimiter {
@@ -160,9 +182,14 @@ impl GatewayRateLimiter {
         Self {
             pair: SlidingWindowRateLimiter::new(pair_per_minute, window, max_keys),
             webhook: SlidingWindowRateLimiter::new(webhook_per_minute, window, max_keys),
+            token: SlidingWindowRateLimiter::new(0, window, max_keys),
         }
     }
 
+    fn allow_token(&self, name: &str, per_minute: u32) -> bool {
+        self.token.allow_with_limit(name, per_minute)
+    }
+
     fn allow_pair(&self, key: &str) -> bool {
         self.pair.allow(key)
     }
@@ -212,6 +239,18 @@ impl IdempotencyStore {
         keys.insert(key.to_owned(), now);
         true
     }
+
+    /// Record a key first seen `age` ago (used to restore keys after a restart).
+    fn seed(&self, key: &str, age: Duration) {
+        if age >= self.ttl {
+            return;
+        }
+        let seen_at = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+        let mut keys = self.keys.lock();
+        if keys.len() < self.max_keys {
+            keys.entry(key.to_owned()).or_insert(seen_at);
+        }
+    }
 }
 
 fn parse_client_ip(value: &str) -> Option<IpAddr> {
@@ -271,6 +310,84 @@ fn normalize_max_keys(configured: usize, fallback: usize) -> usize {
     }
 }
 
+/// Why [`authorize`] refused a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AuthError {
+    /// Missing, unknown or expired token
+    Unauthorized,
+    /// Valid token without the required scope
+    Forbidden(GatewayScope),
+    /// The token's own rate limit is exhausted
+    RateLimited,
+}
+
+impl AuthError {
+    pub(crate) fn status(self) -> StatusCode {
+        match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    pub(crate) fn message(self) -> String {
+        match self {
+            Self::Unauthorized => {
+                "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+                    .into()
+            }
+            Self::Forbidden(scope) => format!("Forbidden — this token lacks the '{scope}' scope"),
+            Self::RateLimited => "Too many requests for this token. Please retry later.".into(),
+        }
+    }
+}
+
+/// Authenticate a bearer token, check that it carries `scope`, and charge
+/// the request against the token's own rate limit.
+pub(crate) fn authorize(
+    state: &AppState,
+    token: &str,
+    scope: GatewayScope,
+) -> Result<TokenGrant, AuthError> {
+    let grant = state
+        .pairing
+        .authenticate(token)
+        .ok_or(AuthError::Unauthorized)?;
+    if !grant.allows(scope) {
+        tracing::warn!(
+            token = grant.name.as_deref().unwrap_or("paired"),
+            "Gateway: rejected — token lacks '{scope}' scope"
+        );
+        return Err(AuthError::Forbidden(scope));
+    }
+    if let Some(name) = grant.name.as_deref() {
+        if !state
+            .rate_limiter
+            .allow_token(name, grant.rate_limit_per_minute)
+        {
+            tracing::warn!(token = name, "Gateway: token rate limit exceeded");
+            return Err(AuthError::RateLimited);
+        }
+    }
+    Ok(grant)
+}
+
+/// Like [`authorize`], for endpoints open to several scopes: the first scope
+/// the token holds is checked (and charged), falling back to `scopes[0]`.
+pub(crate) fn authorize_any(
+    state: &AppState,
+    token: &str,
+    scopes: &[GatewayScope],
+) -> Result<TokenGrant, AuthError> {
+    let fallback = scopes.first().copied().unwrap_or(GatewayScope::Admin);
+    let scope = state
+        .pairing
+        .authenticate(token)
+        .and_then(|grant| scopes.iter().copied().find(|s| grant.allows(*s)))
+        .unwrap_or(fallback);
+    authorize(state, token, scope)
+}
+
 /// Shared state for all axum handlers
 #[derive(Clone)]
 pub struct AppState {
@@ -295,6 +412,12 @@ pub struct AppState {
     pub nextcloud_talk: Option<Arc<NextcloudTalkChannel>>,
     /// Nextcloud Talk webhook secret for signature verification
     pub nextcloud_talk_webhook_secret: Option<Arc<str>>,
+    /// Slack channel for the Events API (`None` unless a signing secret is set)
+    pub slack: Option<Arc<SlackChannel>>,
+    /// Slack signing secret for `X-Slack-Signature` verification
+    pub slack_signing_secret: Option<Arc<str>>,
+    /// Reply policy state for Slack rooms handled over the Events API
+    pub slack_rooms: Arc<SlackRooms>,
     /// Observability backend for metrics scraping
     pub observer: Arc<dyn crate::observability::Observer>,
     /// Registered tool specs (for web dashboard tools page)
@@ -303,18 +426,26 @@ pub struct AppState {
     pub cost_tracker: Option<Arc<CostTracker>>,
     /// SSE broadcast channel for real-time events
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// Persistent store for async webhook jobs (`None` if it failed to open)
+    pub jobs: Option<Arc<jobs::JobStore>>,
+    /// Persistent store for REST sessions (`None` if it failed to open)
+    pub sessions: Option<Arc<sessions::SessionStore>>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
 #[allow(clippy::too_many_lines)]
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
-    // ── Security: refuse public bind without tunnel or explicit opt-in ──
-    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
+    // ── Security: refuse public bind without TLS, tunnel or explicit opt-in ──
+    let tls_enabled = config.gateway.tls.enabled;
+    if is_public_bind(host)
+        && config.tunnel.provider == "none"
+        && !tls_enabled
+        && !config.gateway.allow_public_bind
     {
         anyhow::bail!(
             "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
-             Fix: use --host 127.0.0.1 (default), configure a tunnel, or set\n\
-             [gateway] allow_public_bind = true in config.toml (NOT recommended)."
+             Fix: use --host 127.0.0.1 (default), configure a tunnel, enable [gateway.tls],\n\
+             or set [gateway] allow_public_bind = true in config.toml (NOT recommended)."
         );
     }
     let config_state = Arc::new(Mutex::new(config.clone()));
@@ -330,6 +461,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
+    let scheme = if tls_enabled { "https" } else { "http" };
+    if tls_enabled {
+        // Fail fast on a bad certificate, before providers and channels start.
+        tls::build_server_config(&config.gateway.tls)
+            .context("Invalid [gateway.tls] configuration")?;
+    }
 
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
@@ -340,7 +477,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             auth_profile_override: None,
             zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
             secrets_encrypt: config.secrets.encrypt,
-            reasoning_enabled: config.runtime.reasoning_enabled,
+            reasoning: providers::reasoning::ReasoningSettings::from_runtime(&config.runtime),
         },
     )?);
     let model = config
@@ -356,10 +493,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     )?);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
-    let security = Arc::new(SecurityPolicy::from_config(
-        &config.autonomy,
-        &config.workspace_dir,
-    ));
+    let security = Arc::new(
+        SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+            .with_egress(&config.security.egress),
+    );
 
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
@@ -508,11 +645,48 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
             .map(Arc::from);
 
+    // Slack signing secret for the Events API
+    // Priority: environment variable > config file
+    let slack_signing_secret: Option<Arc<str>> = std::env::var("ZEROCLAW_SLACK_SIGNING_SECRET")
+        .ok()
+        .and_then(|secret| {
+            let secret = secret.trim();
+            (!secret.is_empty()).then(|| secret.to_owned())
+        })
+        .or_else(|| {
+            config.channels_config.slack.as_ref().and_then(|sl| {
+                sl.signing_secret
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|secret| !secret.is_empty())
+                    .map(ToOwned::to_owned)
+            })
+        })
+        .map(Arc::from);
+
+    // Slack channel for the Events API (only with a signing secret)
+    let slack_channel: Option<Arc<SlackChannel>> = config
+        .channels_config
+        .slack
+        .as_ref()
+        .filter(|_| slack_signing_secret.is_some())
+        .map(|sl| {
+            Arc::new(SlackChannel::new(
+                sl.bot_token.clone(),
+                sl.channel_id.clone(),
+                sl.allowed_users.clone(),
+            ))
+        });
+
     // ── Pairing guard ──────────────────────────────────────
-    let pairing = Arc::new(PairingGuard::new(
-        config.gateway.require_pairing,
-        &config.gateway.paired_tokens,
-    ));
+    let pairing = Arc::new(
+        PairingGuard::new(
+            // A verified client certificate replaces the pairing token.
+            config.gateway.require_pairing && !tls::client_cert_auth(&config.gateway.tls),
+            &config.gateway.paired_tokens,
+        )
+        .with_scoped_tokens(&config.gateway.tokens),
+    );
     let rate_limit_max_keys = normalize_max_keys(
         config.gateway.rate_limit_max_keys,
         RATE_LIMIT_MAX_KEYS_DEFAULT,
@@ -531,6 +705,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         idempotency_max_keys,
     ));
 
+    // ── Async webhook jobs (persisted across restarts) ──
+    let jobs = match jobs::JobStore::open(&config.workspace_dir, &config.gateway.jobs) {
+        Ok(store) => {
+            let ttl = Duration::from_secs(config.gateway.idempotency_ttl_secs.max(1));
+            for (key, age) in store.recent_idempotency_keys(ttl).unwrap_or_default() {
+                idempotency_store.seed(&key, age);
+            }
+            Some(Arc::new(store))
+        }
+        Err(e) => {
+            tracing::warn!("Async webhook jobs disabled: {e}");
+            None
+        }
+    };
+    let sessions = match sessions::SessionStore::open(&config.workspace_dir) {
+        Ok(store) => Some(Arc::new(store)),
+        Err(e) => {
+            tracing::warn!("REST sessions disabled: {e}");
+            None
+        }
+    };
+
     // ── Tunnel ────────────────────────────────────────────────
     let tunnel = crate::tunnel::create_tunnel(&config.tunnel)?;
     let mut tunnel_url: Option<String> = None;
@@ -549,13 +745,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         }
     }
 
-    println!("🦀 ZeroClaw Gateway listening on http://{display_addr}");
+    println!("🦀 ZeroClaw Gateway listening on {scheme}://{display_addr}");
     if let Some(ref url) = tunnel_url {
         println!("  🌐 Public URL: {url}");
     }
-    println!("  🌐 Web Dashboard: http://{display_addr}/");
+    println!("  🌐 Web Dashboard: {scheme}://{display_addr}/");
+    if tls::client_cert_auth(&config.gateway.tls) {
+        println!("  🔒 Mutual TLS: client certificate required (replaces pairing)");
+    }
     println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
+    println!("  GET  /api/jobs/{{id}} — async webhook job status (\"async\": true)");
+    let mut hook_names: Vec<&String> = config
+        .gateway
+        .hooks
+        .iter()
+        .filter(|(_, hook)| hook.enabled)
+        .map(|(name, _)| name)
+        .collect();
+    hook_names.sort();
+    for name in hook_names {
+        println!("  POST /hooks/{name} — inbound event hook");
+    }
     if whatsapp_channel.is_some() {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
@@ -566,8 +777,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if nextcloud_talk_channel.is_some() {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
+    if slack_channel.is_some() {
+        println!("  POST /slack/events — Slack Events API and interactivity");
+    }
     println!("  GET  /api/*     — REST API (bearer token required)");
+    println!("  POST /api/sessions — multi-turn REST sessions");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    println!("  POST /v1/chat/completions — OpenAI-compatible chat (GET /v1/models)");
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
     if let Some(code) = pairing.pairing_code() {
@@ -616,17 +832,36 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         linq_signing_secret,
         nextcloud_talk: nextcloud_talk_channel,
         nextcloud_talk_webhook_secret,
+        slack: slack_channel,
+        slack_signing_secret,
+        slack_rooms: Arc::new(SlackRooms::default()),
         observer: broadcast_observer,
         tools_registry,
         cost_tracker,
         event_tx,
+        jobs,
+        sessions,
     };
+    jobs::resume(&state);
 
     // Config PUT needs larger body limit (1MB)
     let config_put_router = Router::new()
         .route("/api/config", put(api::handle_api_config_put))
         .layer(RequestBodyLimitLayer::new(1_048_576));
 
+    // OpenAI-compatible chat: conversations need a larger body limit (1MB)
+    let openai_router = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(openai::handle_chat_completions),
+        )
+        .layer(RequestBodyLimitLayer::new(openai::MAX_CHAT_BODY_SIZE));
+
+    // Inbound event hooks: provider payloads (e.g. GitHub) often exceed 64KB
+    let hooks_router = Router::new()
+        .route("/hooks/{name}", post(hooks::handle_hook))
+        .layer(RequestBodyLimitLayer::new(1_048_576));
+
     // Build router with middleware
     let app = Router::new()
         // ── Existing routes ──
@@ -638,6 +873,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/slack/events", post(handle_slack_events))
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
@@ -653,14 +889,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/jobs/{id}", get(jobs::handle_job_status))
+        .route(
+            "/api/sessions",
+            get(sessions::handle_list).post(sessions::handle_create),
+        )
+        .route(
+            "/api/sessions/{id}",
+            get(sessions::handle_get).delete(sessions::handle_delete),
+        )
+        .route("/api/sessions/{id}/messages", post(sessions::handle_send))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
+        // ── OpenAI-compatible API ──
+        .route("/v1/models", get(openai::handle_models))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        .merge(openai_router)
+        .merge(hooks_router)
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -671,11 +921,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .fallback(get(static_files::handle_spa_fallback));
 
     // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+    if tls_enabled {
+        let listener = tls::TlsListener::new(listener, &config.gateway.tls)?;
+        axum::serve(listener.tap_io(|_| {}), make_service).await?;
+    } else {
+        axum::serve(listener, make_service).await?;
+    }
 
     Ok(())
 }
@@ -831,13 +1083,26 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    Box::pin(crate::agent::process_message(config, message)).await
 }
 
 /// Webhook request body
 #[derive(serde::Deserialize)]
 pub struct WebhookBody {
     pub message: String,
+    /// Queue the message as a job and return `202` with a job id
+    #[serde(default, rename = "async")]
+    pub run_async: bool,
+    /// POST the finished job here, signed with `[gateway.jobs] callback_secret`
+    /// (implies `async`)
+    #[serde(default)]
+    pub callback_url: Option<String>,
+}
+
+impl WebhookBody {
+    fn is_async(&self) -> bool {
+        self.run_async || self.callback_url.is_some()
+    }
 }
 
 /// POST /webhook — main webhook endpoint
@@ -858,20 +1123,18 @@ async fn handle_webhook(
         return (StatusCode::TOO_MANY_REQUESTS, Json(err));
     }
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
+    // ── Bearer token auth (pairing / scoped tokens) ──
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if let Err(e) = authorize(&state, token, GatewayScope::Chat) {
+        if e == AuthError::Unauthorized {
             tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
         }
+        let err = serde_json::json!({"error": e.message()});
+        return (e.status(), Json(err));
     }
 
     // ── Webhook secret auth (optional, additional layer) ──
@@ -904,15 +1167,34 @@ async fn handle_webhook(
         }
     };
 
+    if let Some(ref url) = webhook_body.callback_url {
+        let config = state.config.lock().clone();
+        if let Err(e) = jobs::validate_callback_url(&config, url) {
+            tracing::warn!("Webhook: rejected callback_url — {e}");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e})),
+            );
+        }
+    }
+
     // ── Idempotency (optional) ──
-    if let Some(idempotency_key) = headers
+    let idempotency_key = headers
         .get("X-Idempotency-Key")
         .and_then(|v| v.to_str().ok())
         .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
+        .filter(|value| !value.is_empty());
+    if let Some(idempotency_key) = idempotency_key {
         if !state.idempotency_store.record_if_new(idempotency_key) {
             tracing::info!("Webhook duplicate ignored (idempotency key: {idempotency_key})");
+            let existing_job = state
+                .jobs
+                .as_ref()
+                .filter(|_| webhook_body.is_async())
+                .and_then(|jobs| jobs.find_by_idempotency_key(idempotency_key).ok().flatten());
+            if let Some(job) = existing_job {
+                return (StatusCode::ACCEPTED, Json(jobs::accepted_body(&job, true)));
+            }
             let body = serde_json::json!({
                 "status": "duplicate",
                 "idempotent": true,
@@ -932,6 +1214,19 @@ async fn handle_webhook(
             .await;
     }
 
+    if webhook_body.is_async() {
+        return jobs::submit(
+            &state,
+            &jobs::NewJob {
+                message,
+                source: "webhook",
+                callback_url: webhook_body.callback_url.as_deref().map(str::trim),
+                idempotency_key,
+                ..jobs::NewJob::default()
+            },
+        );
+    }
+
     let provider_label = state
         .config
         .lock()
@@ -1135,13 +1430,33 @@ async fn handle_whatsapp_message(
     };
 
     // Parse messages from the webhook payload
-    let messages = wa.parse_webhook_payload(&payload);
+    let mut messages = wa.parse_webhook_payload(&payload);
+    let (transcription, tts, workspace_dir) = {
+        let config = state.config.lock();
+        (
+            config.transcription.clone(),
+            config.tts.clone(),
+            config.workspace_dir.clone(),
+        )
+    };
+    if transcription.enabled {
+        messages.extend(wa.transcribe_voice_notes(&payload, &transcription).await);
+    }
 
     if messages.is_empty() {
         // Acknowledge the webhook even if no messages (could be status updates)
         return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
     }
 
+    // Linked users' `voice_replies` preference overrides the TTS defaults.
+    let identities = if tts.enabled && !messages.is_empty() {
+        crate::channels::IdentityRegistry::open(&workspace_dir)
+            .map_err(|e| tracing::warn!("Identity registry unavailable: {e}"))
+            .ok()
+    } else {
+        None
+    };
+
     // Process each message
     for msg in &messages {
         tracing::info!(
@@ -1163,11 +1478,20 @@ async fn handle_whatsapp_message(
             Ok(response) => {
                 // Send reply via WhatsApp
                 if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
+                    .send(&SendMessage::new(&response, &msg.reply_target))
                     .await
                 {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
                 }
+                let voice_preference = identities
+                    .as_ref()
+                    .and_then(|registry| whatsapp_voice_preference(registry, msg));
+                if let Err(e) =
+                    send_whatsapp_voice_reply(wa, msg, &tts, voice_preference.as_deref(), &response)
+                        .await
+                {
+                    tracing::warn!("Failed to send WhatsApp voice reply: {e}");
+                }
             }
             Err(e) => {
                 tracing::error!("LLM error for WhatsApp message: {e:#}");
@@ -1185,6 +1509,56 @@ async fn handle_whatsapp_message(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// The sender's `voice_replies` preference, if they are a linked user.
+/// Group messages use the TTS defaults, as on the channel path.
+fn whatsapp_voice_preference(
+    registry: &crate::channels::IdentityRegistry,
+    msg: &crate::channels::traits::ChannelMessage,
+) -> Option<String> {
+    if msg.group.is_some() {
+        return None;
+    }
+    let lookup = registry
+        .resolve(&msg.channel, &msg.sender)
+        .and_then(|user| {
+            user.map(|user_id| registry.preferences(&user_id))
+                .transpose()
+        });
+    match lookup {
+        Ok(preferences) => preferences?
+            .get(crate::channels::tts::VOICE_REPLIES_PREFERENCE)
+            .cloned(),
+        Err(e) => {
+            tracing::warn!("Failed to load WhatsApp voice preference: {e}");
+            None
+        }
+    }
+}
+
+/// Follow a WhatsApp text reply with synthesized speech when `[tts]` or the
+/// sender's `voice_replies` preference asks for it.
+async fn send_whatsapp_voice_reply(
+    wa: &WhatsAppChannel,
+    msg: &crate::channels::traits::ChannelMessage,
+    tts: &crate::config::TtsConfig,
+    voice_preference: Option<&str>,
+    response: &str,
+) -> anyhow::Result<()> {
+    if !crate::channels::tts::wants_voice_reply(tts, "whatsapp", msg.voice, voice_preference) {
+        return Ok(());
+    }
+    let Some(text) = crate::channels::tts::speech_text(response, tts.max_chars) else {
+        tracing::debug!(
+            channel = "whatsapp",
+            "Skipping voice reply: nothing to speak or longer than tts.max_chars"
+        );
+        return Ok(());
+    };
+    let audio = crate::channels::tts::synthesize_speech(&text, tts).await?;
+    wa.send_voice_reply(&SendMessage::new(text, &msg.reply_target), &audio)
+        .await
+}
+
 /// POST /linq — incoming message webhook (iMessage/RCS/SMS via Linq)
 async fn handle_linq_webhook(
     State(state): State<AppState>,
@@ -1395,6 +1769,187 @@ async fn handle_nextcloud_talk_webhook(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// Unaddressed lines kept per Slack room for the next reply.
+const MAX_SLACK_ROOM_BACKLOG: usize = 50;
+
+/// Group handling for Slack rooms reached through the Events API.
+///
+/// Applies the same `[channels_config.slack.group]` reply policy and per-room
+/// reply cap as the channel runtime. Gateway turns carry no stored history,
+/// so room lines that get no reply are kept here and prepended, attributed,
+/// to the next turn the agent answers in that room.
+#[derive(Default)]
+pub struct SlackRooms {
+    rate_limiter: group::RoomRateLimiter,
+    backlog: Mutex<HashMap<String, VecDeque<String>>>,
+}
+
+impl SlackRooms {
+    /// Turn content for `msg` if it should get a reply, otherwise record it
+    /// in the room backlog and return `None`.
+    fn admit(&self, policy: &GroupPolicyConfig, msg: &ChannelMessage) -> Option<String> {
+        let Some(room) = msg.group.as_ref() else {
+            return Some(msg.content.clone());
+        };
+        let room_key = group::room_history_key(msg, room);
+        let line = group::attributed_content(room, &msg.content);
+
+        let admitted = group::triggers_reply(policy, room, &msg.content) && {
+            let allowed = self.rate_limiter.try_acquire(
+                &room_key,
+                policy.max_replies_per_minute,
+                Instant::now(),
+            );
+            if !allowed {
+                tracing::info!(
+                    room = %room.room,
+                    "Slack room reply rate limit reached; recording message without reply"
+                );
+            }
+            allowed
+        };
+
+        let mut backlog = self.backlog.lock();
+        if admitted {
+            let mut lines: Vec<String> =
+                backlog.remove(&room_key).map(Vec::from).unwrap_or_default();
+            lines.push(line);
+            Some(lines.join("\n"))
+        } else {
+            let lines = backlog.entry(room_key).or_default();
+            lines.push_back(line);
+            while lines.len() > MAX_SLACK_ROOM_BACKLOG {
+                lines.pop_front();
+            }
+            None
+        }
+    }
+}
+
+/// POST /slack/events — Slack Events API and interactivity webhook.
+///
+/// Answers the `url_verification` challenge, then acknowledges right away
+/// and handles the message in the background: Slack retries any request
+/// not answered within three seconds.
+async fn handle_slack_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let (Some(slack), Some(signing_secret)) =
+        (state.slack.clone(), state.slack_signing_secret.clone())
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Slack Events API not configured"})),
+        );
+    };
+
+    // ── Security: Verify Slack request signature ──
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string()
+    };
+    if !crate::channels::slack::verify_slack_signature(
+        &signing_secret,
+        &header("X-Slack-Request-Timestamp"),
+        &body,
+        &header("X-Slack-Signature"),
+        chrono::Utc::now().timestamp(),
+    ) {
+        tracing::warn!("Slack Events API signature verification failed");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid signature"})),
+        );
+    }
+
+    // Interactivity posts `payload=<json>` as a form; events are JSON.
+    let is_form = header("Content-Type").starts_with("application/x-www-form-urlencoded");
+    let payload = if is_form {
+        String::from_utf8_lossy(&body)
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("payload="))
+            .and_then(|raw| {
+                urlencoding::decode(&raw.replace('+', " "))
+                    .ok()
+                    .map(|v| v.into_owned())
+            })
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+    } else {
+        serde_json::from_slice::<serde_json::Value>(&body).ok()
+    };
+    let Some(payload) = payload else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid payload"})),
+        );
+    };
+
+    if payload.get("type").and_then(|t| t.as_str()) == Some("url_verification") {
+        let challenge = payload.get("challenge").cloned().unwrap_or_default();
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({ "challenge": challenge })),
+        );
+    }
+
+    let message = if is_form {
+        slack.parse_interaction(&payload)
+    } else {
+        slack.parse_event_callback(&payload)
+    };
+    let Some(msg) = message else {
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    };
+
+    tracing::info!(
+        "Slack message from {}: {}",
+        msg.sender,
+        truncate_with_ellipsis(&msg.content, 50)
+    );
+    let policy = state
+        .config
+        .lock()
+        .channels_config
+        .slack
+        .as_ref()
+        .map(|sl| sl.group.clone())
+        .unwrap_or_default();
+    let turn = state.slack_rooms.admit(&policy, &msg);
+    tokio::spawn(async move {
+        if state.auto_save {
+            let key = slack_memory_key(&msg);
+            let _ = state
+                .mem
+                .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                .await;
+        }
+        let Some(turn) = turn else {
+            return;
+        };
+
+        let reply = match Box::pin(run_gateway_chat_with_tools(&state, &turn)).await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!("LLM error for Slack message: {e:#}");
+                "Sorry, I couldn't process your message right now.".to_string()
+            }
+        };
+        if let Err(e) = slack
+            .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await
+        {
+            tracing::error!("Failed to send Slack reply: {e}");
+        }
+    });
+
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1472,10 +2027,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1520,10 +2080,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer,
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1722,12 +2287,42 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
+            voice: false,
         };
 
         let key = whatsapp_memory_key(&msg);
         assert_eq!(key, "whatsapp_+1234567890_wamid-123");
     }
 
+    #[test]
+    fn whatsapp_voice_preference_reads_linked_user() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let registry = crate::channels::IdentityRegistry::open(tmp.path()).unwrap();
+        registry.link("alice", "whatsapp", "+1234567890").unwrap();
+        registry
+            .set_preference("alice", "voice_replies", "always")
+            .unwrap();
+        let mut msg = ChannelMessage {
+            id: "wamid-1".into(),
+            sender: "+1234567890".into(),
+            reply_target: "+1234567890".into(),
+            content: "hello".into(),
+            channel: "whatsapp".into(),
+            timestamp: 1,
+            thread_ts: None,
+            group: None,
+            voice: false,
+        };
+
+        assert_eq!(
+            whatsapp_voice_preference(&registry, &msg).as_deref(),
+            Some("always")
+        );
+        msg.sender = "+1999".into();
+        assert!(whatsapp_voice_preference(&registry, &msg).is_none());
+    }
+
     #[derive(Default)]
     struct MockMemory;
 
@@ -1885,10 +2480,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -1896,6 +2496,8 @@ mod tests {
 
         let body = Ok(Json(WebhookBody {
             message: "hello".into(),
+            run_async: false,
+            callback_url: None,
         }));
         let first = handle_webhook(
             State(state.clone()),
@@ -1909,6 +2511,8 @@ mod tests {
 
         let body = Ok(Json(WebhookBody {
             message: "hello".into(),
+            run_async: false,
+            callback_url: None,
         }));
         let second = handle_webhook(State(state), test_connect_info(), headers, body)
             .await
@@ -1922,6 +2526,325 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn async_webhook_queues_job_and_dedupes_by_idempotency_key() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        let jobs = jobs::JobStore::open(tmp.path(), &config.gateway.jobs).unwrap();
+
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: Some(Arc::new(jobs)),
+            sessions: None,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Idempotency-Key", HeaderValue::from_static("ci-run-42"));
+        let async_body = || {
+            Ok(Json(WebhookBody {
+                message: "run the release checklist".into(),
+                run_async: true,
+                callback_url: None,
+            }))
+        };
+
+        let first = handle_webhook(
+            State(state.clone()),
+            test_connect_info(),
+            headers.clone(),
+            async_body(),
+        )
+        .await
+        .into_response();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        let payload = first.into_body().collect().await.unwrap().to_bytes();
+        let first: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        let job_id = first["job_id"].as_str().unwrap().to_string();
+        assert_eq!(first["status_url"], format!("/api/jobs/{job_id}"));
+        assert_eq!(first["idempotent"], false);
+
+        let second = handle_webhook(
+            State(state.clone()),
+            test_connect_info(),
+            headers,
+            async_body(),
+        )
+        .await
+        .into_response();
+        assert_eq!(second.status(), StatusCode::ACCEPTED);
+        let payload = second.into_body().collect().await.unwrap().to_bytes();
+        let second: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(second["job_id"], job_id.as_str());
+        assert_eq!(second["idempotent"], true);
+
+        let status = jobs::handle_job_status(
+            State(state.clone()),
+            HeaderMap::new(),
+            axum::extract::Path(job_id),
+        )
+        .await
+        .into_response();
+        assert_eq!(status.status(), StatusCode::OK);
+
+        // No callback secret configured, so callback URLs are refused up front.
+        let rejected = handle_webhook(
+            State(state),
+            test_connect_info(),
+            HeaderMap::new(),
+            Ok(Json(WebhookBody {
+                message: "hi".into(),
+                run_async: false,
+                callback_url: Some("https://ci.example.com/hook".into()),
+            })),
+        )
+        .await
+        .into_response();
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn github_hook_verifies_signature_filters_events_and_drops_redeliveries() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.gateway.hooks.insert(
+            "github".into(),
+            crate::config::InboundHookConfig {
+                enabled: true,
+                verify: crate::config::HookVerification::Github,
+                secret: Some("gh-secret".into()),
+                secret_header: "X-Webhook-Secret".into(),
+                events: vec!["issues.opened".into()],
+                template: Some("Triage: {{payload.issue.title}}".into()),
+                channel: None,
+                to: None,
+            },
+        );
+        let jobs_store = jobs::JobStore::open(tmp.path(), &config.gateway.jobs).unwrap();
+        let jobs_store = Arc::new(jobs_store);
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: Some(Arc::clone(&jobs_store)),
+            sessions: None,
+        };
+
+        let post = |action: &str, signature: Option<String>| {
+            let body = format!(r#"{{"action":"{action}","issue":{{"title":"Crash on start"}}}}"#);
+            let mut headers = HeaderMap::new();
+            headers.insert("X-GitHub-Event", HeaderValue::from_static("issues"));
+            headers.insert("X-GitHub-Delivery", HeaderValue::from_str(action).unwrap());
+            let signature = signature.unwrap_or_else(|| {
+                let mut mac = Hmac::<Sha256>::new_from_slice(b"gh-secret").unwrap();
+                mac.update(body.as_bytes());
+                format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+            });
+            headers.insert(
+                "X-Hub-Signature-256",
+                HeaderValue::from_str(&signature).unwrap(),
+            );
+            hooks::handle_hook(
+                State(state.clone()),
+                test_connect_info(),
+                axum::extract::Path("github".to_string()),
+                headers,
+                Bytes::from(body),
+            )
+        };
+
+        let forged = post("opened", Some(format!("sha256={}", "0".repeat(64))))
+            .await
+            .into_response();
+        assert_eq!(forged.status(), StatusCode::UNAUTHORIZED);
+
+        let ignored = post("closed", None).await.into_response();
+        assert_eq!(ignored.status(), StatusCode::OK);
+        let payload = ignored.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["status"], "ignored");
+
+        let accepted = post("opened", None).await.into_response();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        let payload = accepted.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        let job = jobs_store
+            .get(parsed["job_id"].as_str().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(job.source, "hook:github");
+        assert_eq!(job.message, "Triage: Crash on start");
+
+        let redelivered = post("opened", None).await.into_response();
+        assert_eq!(redelivered.status(), StatusCode::OK);
+        let payload = redelivered.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["status"], "duplicate");
+
+        let unknown = hooks::handle_hook(
+            State(state.clone()),
+            test_connect_info(),
+            axum::extract::Path("gitlab".to_string()),
+            HeaderMap::new(),
+            Bytes::from_static(b"{}"),
+        )
+        .await
+        .into_response();
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn session_endpoints_create_list_fetch_and_delete() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let session_store = Arc::new(sessions::SessionStore::open(tmp.path()).unwrap());
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: Some(Arc::clone(&session_store)),
+        };
+        let create = |name: &str| {
+            sessions::handle_create(
+                State(state.clone()),
+                HeaderMap::new(),
+                Some(Json(sessions::CreateSessionBody {
+                    name: Some(name.into()),
+                })),
+            )
+        };
+
+        let created = create("support").await.into_response();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let payload = created.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+        assert_eq!(created["name"], "support");
+
+        let conflict = create("support").await.into_response();
+        assert_eq!(conflict.status(), StatusCode::CONFLICT);
+
+        session_store
+            .record_turn(&id, "hello", "hi there", &[])
+            .unwrap();
+        let fetched = sessions::handle_get(
+            State(state.clone()),
+            HeaderMap::new(),
+            axum::extract::Path(id.clone()),
+        )
+        .await
+        .into_response();
+        assert_eq!(fetched.status(), StatusCode::OK);
+        let payload = fetched.into_body().collect().await.unwrap().to_bytes();
+        let fetched: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(fetched["message_count"], 2);
+        assert_eq!(fetched["messages"][1]["content"], "hi there");
+
+        let empty = sessions::handle_send(
+            State(state.clone()),
+            HeaderMap::new(),
+            axum::extract::Path(id.clone()),
+            Ok(Json(sessions::SendMessageBody {
+                message: "   ".into(),
+            })),
+        )
+        .await
+        .into_response();
+        assert_eq!(empty.status(), StatusCode::BAD_REQUEST);
+
+        let deleted = sessions::handle_delete(
+            State(state.clone()),
+            HeaderMap::new(),
+            axum::extract::Path(id.clone()),
+        )
+        .await
+        .into_response();
+        assert_eq!(deleted.status(), StatusCode::OK);
+
+        let listed = sessions::handle_list(State(state), HeaderMap::new())
+            .await
+            .into_response();
+        let payload = listed.into_body().collect().await.unwrap().to_bytes();
+        let listed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(listed["sessions"].as_array().unwrap().len(), 0);
+    }
+
     #[tokio::test]
     async fn webhook_autosave_stores_distinct_keys_per_request() {
         let provider_impl = Arc::new(MockProvider::default());
@@ -1948,16 +2871,23 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let headers = HeaderMap::new();
 
         let body1 = Ok(Json(WebhookBody {
             message: "hello one".into(),
+            run_async: false,
+            callback_url: None,
         }));
         let first = handle_webhook(
             State(state.clone()),
@@ -1971,6 +2901,8 @@ mod tests {
 
         let body2 = Ok(Json(WebhookBody {
             message: "hello two".into(),
+            run_async: false,
+            callback_url: None,
         }));
         let second = handle_webhook(State(state), test_connect_info(), headers, body2)
             .await
@@ -1985,6 +2917,106 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 2);
     }
 
+    #[tokio::test]
+    async fn scoped_tokens_are_limited_to_their_scopes_and_rate() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+        let (read_token, read_hash) = crate::security::pairing::issue_token();
+        let (chat_token, chat_hash) = crate::security::pairing::issue_token();
+        let scoped = |name: &str, hash: String, scope: GatewayScope, limit: u32| {
+            crate::config::GatewayTokenConfig {
+                name: name.into(),
+                token_hash: hash,
+                scopes: vec![scope],
+                expires_at: None,
+                rate_limit_per_minute: limit,
+                created_at: None,
+            }
+        };
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &[]).with_scoped_tokens(&[
+                scoped("monitoring", read_hash, GatewayScope::Read, 0),
+                scoped("frontend", chat_hash, GatewayScope::Chat, 1),
+            ])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
+        };
+        let bearer = |token: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::AUTHORIZATION,
+                format!("Bearer {token}").parse().unwrap(),
+            );
+            headers
+        };
+
+        // Read-only token: dashboard reads pass, writes and chat are forbidden.
+        let response = api::handle_api_tools(State(state.clone()), bearer(&read_token))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response =
+            api::handle_api_config_put(State(state.clone()), bearer(&read_token), String::new())
+                .await
+                .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let webhook = |headers: HeaderMap| {
+            handle_webhook(
+                State(state.clone()),
+                test_connect_info(),
+                headers,
+                Ok(Json(WebhookBody {
+                    message: "hello".into(),
+                    run_async: false,
+                    callback_url: None,
+                })),
+            )
+        };
+        let response = webhook(bearer(&read_token)).await.into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = openai::handle_models(State(state.clone()), bearer(&read_token)).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // Chat token: webhook works once, then its own rate limit applies.
+        let response = webhook(bearer(&chat_token)).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = webhook(bearer(&chat_token)).await.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let response = api::handle_api_tools(State(state.clone()), bearer(&chat_token))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = webhook(bearer("zc_unknown")).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn webhook_secret_hash_is_deterministic_and_nonempty() {
         let secret_a = generate_test_secret();
@@ -2023,10 +3055,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let response = handle_webhook(
@@ -2035,6 +3072,8 @@ mod tests {
             HeaderMap::new(),
             Ok(Json(WebhookBody {
                 message: "hello".into(),
+                run_async: false,
+                callback_url: None,
             })),
         )
         .await
@@ -2070,10 +3109,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2088,6 +3132,8 @@ mod tests {
             headers,
             Ok(Json(WebhookBody {
                 message: "hello".into(),
+                run_async: false,
+                callback_url: None,
             })),
         )
         .await
@@ -2122,10 +3168,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2137,6 +3188,8 @@ mod tests {
             headers,
             Ok(Json(WebhookBody {
                 message: "hello".into(),
+                run_async: false,
+                callback_url: None,
             })),
         )
         .await
@@ -2179,10 +3232,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -2232,10 +3290,15 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: Some(channel),
             nextcloud_talk_webhook_secret: Some(Arc::from(secret)),
+            slack: None,
+            slack_signing_secret: None,
+            slack_rooms: Arc::new(SlackRooms::default()),
             observer: Arc::new(crate::observability::NoopObserver),
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2255,6 +3318,132 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn slack_events_verify_signature_and_answer_challenge() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let secret = generate_test_secret();
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            slack: Some(Arc::new(SlackChannel::new(
+                "xoxb-fake".into(),
+                None,
+                vec!["*".into()],
+            ))),
+            slack_signing_secret: Some(Arc::from(secret.as_str())),
+            slack_rooms: Arc::new(SlackRooms::default()),
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            jobs: None,
+            sessions: None,
+        };
+
+        let body = r#"{"type":"url_verification","challenge":"abc123"}"#;
+        let post = |signature: String| {
+            let ts = chrono::Utc::now().timestamp();
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "X-Slack-Request-Timestamp",
+                HeaderValue::from_str(&ts.to_string()).unwrap(),
+            );
+            let signature = if signature.is_empty() {
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+                mac.update(format!("v0:{ts}:{body}").as_bytes());
+                format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+            } else {
+                signature
+            };
+            headers.insert(
+                "X-Slack-Signature",
+                HeaderValue::from_str(&signature).unwrap(),
+            );
+            handle_slack_events(State(state.clone()), headers, Bytes::from(body))
+        };
+
+        let rejected = post("v0=deadbeef".into()).await.into_response();
+        assert_eq!(rejected.status(), StatusCode::UNAUTHORIZED);
+
+        let accepted = post(String::new()).await.into_response();
+        assert_eq!(accepted.status(), StatusCode::OK);
+        let payload = accepted.into_body().collect().await.unwrap().to_bytes();
+        let payload: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(payload["challenge"], "abc123");
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn slack_rooms_apply_group_policy_backlog_and_rate_limit() {
+        use crate::channels::traits::GroupContext;
+        use crate::config::GroupReplyMode;
+
+        let msg = |content: &str, sender_name: &str, mentioned: bool, room: bool| ChannelMessage {
+            id: "1".into(),
+            sender: "U1".into(),
+            reply_target: "C1".into(),
+            content: content.into(),
+            channel: "slack".into(),
+            timestamp: 1,
+            thread_ts: None,
+            group: room.then(|| GroupContext {
+                room: "C1".into(),
+                sender_name: sender_name.into(),
+                mentioned,
+                replied_to_bot: false,
+            }),
+            voice: false,
+        };
+        let policy = GroupPolicyConfig {
+            reply: GroupReplyMode::Mention,
+            keywords: Vec::new(),
+            max_replies_per_minute: 1,
+        };
+        let rooms = SlackRooms::default();
+
+        // Direct messages bypass the room policy.
+        assert_eq!(
+            rooms
+                .admit(&policy, &msg("hi", "", false, false))
+                .as_deref(),
+            Some("hi")
+        );
+
+        assert_eq!(
+            rooms.admit(&policy, &msg("lunch?", "alice", false, true)),
+            None
+        );
+        assert_eq!(
+            rooms
+                .admit(&policy, &msg("what did alice ask?", "bob", true, true))
+                .as_deref(),
+            Some("[alice] lunch?\n[bob] what did alice ask?")
+        );
+
+        // Second mention within the minute is recorded but not answered.
+        assert_eq!(rooms.admit(&policy, &msg("again", "bob", true, true)), None);
+        assert_eq!(rooms.backlog.lock()["slack_room_C1"].len(), 1);
+    }
+
     // ══════════════════════════════════════════════════════════
     // WhatsApp Signature Verification Tests (CWE-345 Prevention)
     // ══════════════════════════════════════════════════════════
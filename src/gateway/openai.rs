@@ -0,0 +1,446 @@
+//! OpenAI-compatible chat API.
+//!
+//! `POST /v1/chat/completions` runs the request through the full agent (tools,
+//! memory, security policy) and answers in OpenAI format, optionally as an SSE
+//! stream. `GET /v1/models` lists the models a client may ask for: the
+//! configured default and every `[[model_routes]]` hint as `hint:<name>`.
+//!
+//! Both routes need a bearer token with the `chat` scope. Client-side `tools`
+//! are ignored; the agent uses its own tool registry.
+
+use super::{authorize, client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::config::{Config, GatewayScope};
+use crate::providers::ChatMessage;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Maximum request body for chat completions (conversations outgrow the
+/// gateway-wide 64KB limit).
+pub const MAX_CHAT_BODY_SIZE: usize = 1_048_576;
+
+/// Model id that always maps to the configured default model.
+const DEFAULT_MODEL_ALIAS: &str = "zeroclaw";
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    #[serde(default)]
+    pub model: Option<String>,
+    pub messages: Vec<RequestMessage>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestMessage {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+}
+
+/// OpenAI message content: a plain string or a list of typed parts.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text {
+        text: String,
+    },
+    ImageUrl {
+        image_url: ImageUrl,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageUrl {
+    pub url: String,
+}
+
+impl MessageContent {
+    /// Flatten to agent text; images become `[IMAGE:<url>]` markers.
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Parts(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(text.clone()),
+                    ContentPart::ImageUrl { image_url } => {
+                        Some(format!("[IMAGE:{}]", image_url.url))
+                    }
+                    ContentPart::Unsupported => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+fn openai_error(status: StatusCode, kind: &str, message: impl Into<String>) -> Response {
+    let body = serde_json::json!({
+        "error": {
+            "message": message.into(),
+            "type": kind,
+        }
+    });
+    (status, Json(body)).into_response()
+}
+
+fn check_auth(state: &AppState, headers: &HeaderMap) -> Result<(), Box<Response>> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    authorize(state, token, GatewayScope::Chat)
+        .map(|_| ())
+        .map_err(|e| {
+            let kind = if e.status() == StatusCode::TOO_MANY_REQUESTS {
+                "rate_limit_error"
+            } else {
+                "authentication_error"
+            };
+            Box::new(openai_error(e.status(), kind, e.message()))
+        })
+}
+
+/// Model ids this gateway accepts, default first.
+fn available_models(config: &Config, default_model: &str) -> Vec<String> {
+    let mut models = vec![DEFAULT_MODEL_ALIAS.to_string(), default_model.to_string()];
+    models.extend(
+        config
+            .model_routes
+            .iter()
+            .map(|route| format!("hint:{}", route.hint)),
+    );
+    models.dedup();
+    models
+}
+
+/// Resolve a requested model id to the model the agent should use.
+fn resolve_model(config: &Config, default_model: &str, requested: Option<&str>) -> Option<String> {
+    let requested = requested.map(str::trim).filter(|m| !m.is_empty());
+    match requested {
+        None | Some(DEFAULT_MODEL_ALIAS) => Some(default_model.to_string()),
+        Some(model) => available_models(config, default_model)
+            .into_iter()
+            .any(|m| m == model)
+            .then(|| model.to_string()),
+    }
+}
+
+/// Split the request into earlier turns and the final user message.
+fn split_conversation(messages: &[RequestMessage]) -> Option<(Vec<ChatMessage>, String)> {
+    let last_user = messages.iter().rposition(|m| m.role == "user")?;
+    let text_of = |m: &RequestMessage| m.content.as_ref().map(MessageContent::to_text);
+    let message = text_of(&messages[last_user]).filter(|t| !t.trim().is_empty())?;
+    let prior = messages[..last_user]
+        .iter()
+        .filter_map(|m| {
+            let role = match m.role.as_str() {
+                "system" | "developer" => "system",
+                "user" => "user",
+                "assistant" => "assistant",
+                _ => return None,
+            };
+            let content = text_of(m).filter(|t| !t.is_empty())?;
+            Some(ChatMessage {
+                role: role.to_string(),
+                content,
+            })
+        })
+        .collect();
+    Some((prior, message))
+}
+
+/// GET /v1/models — models available to OpenAI clients
+pub async fn handle_models(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Err(response) = check_auth(&state, &headers) {
+        return *response;
+    }
+    let config = state.config.lock().clone();
+    let data: Vec<serde_json::Value> = available_models(&config, &state.model)
+        .into_iter()
+        .map(|id| {
+            serde_json::json!({
+                "id": id,
+                "object": "model",
+                "created": 0,
+                "owned_by": "zeroclaw",
+            })
+        })
+        .collect();
+    Json(serde_json::json!({ "object": "list", "data": data })).into_response()
+}
+
+/// POST /v1/chat/completions — OpenAI-format chat through the full agent
+pub async fn handle_chat_completions(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<ChatCompletionRequest>, axum::extract::rejection::JsonRejection>,
+) -> Response {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/v1/chat/completions rate limit exceeded");
+        return openai_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate_limit_error",
+            format!("Too many requests. Retry in {RATE_LIMIT_WINDOW_SECS}s."),
+        );
+    }
+    if let Err(response) = check_auth(&state, &headers) {
+        return *response;
+    }
+
+    let Json(request) = match body {
+        Ok(body) => body,
+        Err(e) => {
+            return openai_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_request_error",
+                format!("Invalid request body: {e}"),
+            )
+        }
+    };
+
+    let mut config = state.config.lock().clone();
+    let Some(model) = resolve_model(&config, &state.model, request.model.as_deref()) else {
+        return openai_error(
+            StatusCode::NOT_FOUND,
+            "invalid_request_error",
+            format!(
+                "The model '{}' does not exist; see GET /v1/models",
+                request.model.unwrap_or_default()
+            ),
+        );
+    };
+    let Some((prior, message)) = split_conversation(&request.messages) else {
+        return openai_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            "messages must contain a non-empty user message",
+        );
+    };
+
+    config.default_model = Some(model.clone());
+    if let Some(temperature) = request.temperature {
+        config.default_temperature = temperature.clamp(0.0, 2.0);
+    }
+    let response_model = request
+        .model
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or(model);
+    let id = format!("chatcmpl-{}", Uuid::new_v4().simple());
+    let created = chrono::Utc::now().timestamp();
+
+    if request.stream {
+        return stream_completion(config, prior, message, id, created, response_model);
+    }
+
+    match crate::agent::process_conversation(config, &prior, &message, "openai", None).await {
+        Ok(content) => Json(serde_json::json!({
+            "id": id,
+            "object": "chat.completion",
+            "created": created,
+            "model": response_model,
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop",
+            }],
+        }))
+        .into_response(),
+        Err(e) => {
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            tracing::error!("/v1/chat/completions agent error: {sanitized}");
+            openai_error(StatusCode::INTERNAL_SERVER_ERROR, "server_error", sanitized)
+        }
+    }
+}
+
+fn chunk(
+    id: &str,
+    created: i64,
+    model: &str,
+    delta: serde_json::Value,
+    finish: Option<&str>,
+) -> Event {
+    let body = serde_json::json!({
+        "id": id,
+        "object": "chat.completion.chunk",
+        "created": created,
+        "model": model,
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": finish }],
+    });
+    Event::default().data(body.to_string())
+}
+
+/// Run the agent in the background and relay the final answer as
+/// `chat.completion.chunk` events. Tool progress lines are not forwarded.
+fn stream_completion(
+    config: Config,
+    prior: Vec<ChatMessage>,
+    message: String,
+    id: String,
+    created: i64,
+    model: String,
+) -> Response {
+    let (event_tx, event_rx) = tokio::sync::mpsc::channel::<Event>(64);
+
+    tokio::spawn(async move {
+        let _ = event_tx
+            .send(chunk(
+                &id,
+                created,
+                &model,
+                serde_json::json!({ "role": "assistant", "content": "" }),
+                None,
+            ))
+            .await;
+
+        let (delta_tx, mut delta_rx) = tokio::sync::mpsc::channel::<String>(64);
+        let relay_tx = event_tx.clone();
+        let (relay_id, relay_model) = (id.clone(), model.clone());
+        let relay = tokio::spawn(async move {
+            let mut answering = false;
+            while let Some(delta) = delta_rx.recv().await {
+                if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
+                    answering = true;
+                    continue;
+                }
+                if answering {
+                    let event = chunk(
+                        &relay_id,
+                        created,
+                        &relay_model,
+                        serde_json::json!({ "content": delta }),
+                        None,
+                    );
+                    if relay_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let result =
+            crate::agent::process_conversation(config, &prior, &message, "openai", Some(delta_tx))
+                .await;
+        let _ = relay.await;
+
+        match result {
+            Ok(_) => {
+                let _ = event_tx
+                    .send(chunk(
+                        &id,
+                        created,
+                        &model,
+                        serde_json::json!({}),
+                        Some("stop"),
+                    ))
+                    .await;
+            }
+            Err(e) => {
+                let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+                tracing::error!("/v1/chat/completions stream error: {sanitized}");
+                let body = serde_json::json!({
+                    "error": { "message": sanitized, "type": "server_error" }
+                });
+                let _ = event_tx.send(Event::default().data(body.to_string())).await;
+            }
+        }
+        let _ = event_tx.send(Event::default().data("[DONE]")).await;
+    });
+
+    Sse::new(ReceiverStream::new(event_rx).map(Ok::<_, Infallible>))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn split_conversation_keeps_history_and_last_user_message() {
+        let req = request(serde_json::json!({
+            "model": "zeroclaw",
+            "messages": [
+                {"role": "system", "content": "Answer tersely."},
+                {"role": "user", "content": "Hi"},
+                {"role": "assistant", "content": "Hello!"},
+                {"role": "tool", "content": "ignored"},
+                {"role": "user", "content": [
+                    {"type": "text", "text": "What is this?"},
+                    {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+                    {"type": "input_audio", "input_audio": {}}
+                ]}
+            ]
+        }));
+        let (prior, message) = split_conversation(&req.messages).unwrap();
+        let roles: Vec<&str> = prior.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant"]);
+        assert_eq!(message, "What is this?\n[IMAGE:https://example.com/a.png]");
+    }
+
+    #[test]
+    fn split_conversation_requires_user_message() {
+        let req = request(serde_json::json!({
+            "messages": [{"role": "system", "content": "x"}]
+        }));
+        assert!(split_conversation(&req.messages).is_none());
+    }
+
+    #[test]
+    fn resolve_model_accepts_alias_default_and_route_hints() {
+        let mut config = Config::default();
+        config.model_routes.push(crate::config::ModelRouteConfig {
+            hint: "fast".into(),
+            provider: "groq".into(),
+            model: "llama-3.3-70b".into(),
+            api_key: None,
+        });
+        let default = "anthropic/claude-sonnet-4";
+        assert_eq!(resolve_model(&config, default, None).unwrap(), default);
+        assert_eq!(
+            resolve_model(&config, default, Some("zeroclaw")).unwrap(),
+            default
+        );
+        assert_eq!(
+            resolve_model(&config, default, Some("hint:fast")).unwrap(),
+            "hint:fast"
+        );
+        assert!(resolve_model(&config, default, Some("gpt-4o")).is_none());
+        assert_eq!(
+            available_models(&config, default),
+            ["zeroclaw", default, "hint:fast"]
+        );
+    }
+}
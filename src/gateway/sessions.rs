@@ -0,0 +1,517 @@
+//! REST sessions — multi-turn conversations over HTTP.
+//!
+//! `/api/sessions` creates, lists, reads, continues and deletes sessions.
+//! Each session keeps two histories in `{workspace}/gateway/sessions.db`:
+//! the full transcript shown to clients, and the agent context that is
+//! compacted and trimmed like an interactive session. Autosaved memories
+//! carry the session id and are forgotten with the session.
+
+use super::{authorize, authorize_any, AppState};
+use crate::config::GatewayScope;
+use crate::memory::MemoryCategory;
+use crate::providers::ChatMessage;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use chrono::Utc;
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Maximum length of a session name
+const MAX_SESSION_NAME_CHARS: usize = 128;
+
+/// Session metadata
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub message_count: usize,
+}
+
+/// One transcript entry
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMessage {
+    pub role: String,
+    pub content: String,
+    pub at: String,
+}
+
+/// SQLite-backed session store
+pub struct SessionStore {
+    conn: Mutex<Connection>,
+    /// Sessions with a turn in flight
+    busy: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Marks a session busy until dropped
+pub struct TurnGuard {
+    id: String,
+    busy: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Drop for TurnGuard {
+    fn drop(&mut self) {
+        self.busy.lock().remove(&self.id);
+    }
+}
+
+const SESSION_COLUMNS: &str = "s.id, s.name, s.created_at, s.updated_at,
+     (SELECT COUNT(*) FROM gateway_session_messages m WHERE m.session_id = s.id)";
+
+fn session_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionInfo> {
+    Ok(SessionInfo {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        created_at: row.get(2)?,
+        updated_at: row.get(3)?,
+        message_count: usize::try_from(row.get::<_, i64>(4)?).unwrap_or(0),
+    })
+}
+
+impl SessionStore {
+    /// Open (or create) `{workspace_dir}/gateway/sessions.db`.
+    pub fn open(workspace_dir: &std::path::Path) -> Result<Self> {
+        let db_path = workspace_dir.join("gateway").join("sessions.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create gateway directory: {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open sessions DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA foreign_keys = ON;
+             CREATE TABLE IF NOT EXISTS gateway_sessions (
+                id         TEXT PRIMARY KEY,
+                name       TEXT UNIQUE,
+                context    TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS gateway_session_messages (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                role       TEXT NOT NULL,
+                content    TEXT NOT NULL,
+                at         TEXT NOT NULL,
+                FOREIGN KEY (session_id) REFERENCES gateway_sessions(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_gateway_session_messages_session
+                ON gateway_session_messages(session_id);",
+        )
+        .context("Failed to initialize sessions schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            busy: Arc::new(Mutex::new(HashSet::new())),
+        })
+    }
+
+    /// Create a session. Returns `Ok(None)` if `name` is already taken.
+    pub fn create(&self, name: Option<&str>) -> Result<Option<SessionInfo>> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let conn = self.conn.lock();
+        match conn.execute(
+            "INSERT INTO gateway_sessions (id, name, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?3)",
+            params![id, name, now],
+        ) {
+            Ok(_) => {}
+            Err(rusqlite::Error::SqliteFailure(err, _))
+                if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        }
+        Ok(Some(SessionInfo {
+            id,
+            name: name.map(str::to_string),
+            created_at: now.clone(),
+            updated_at: now,
+            message_count: 0,
+        }))
+    }
+
+    /// All sessions, most recently active first.
+    pub fn list(&self) -> Result<Vec<SessionInfo>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SESSION_COLUMNS} FROM gateway_sessions s ORDER BY s.updated_at DESC"
+        ))?;
+        let sessions = stmt
+            .query_map([], session_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(sessions)
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<SessionInfo>> {
+        let conn = self.conn.lock();
+        let session = conn
+            .query_row(
+                &format!("SELECT {SESSION_COLUMNS} FROM gateway_sessions s WHERE s.id = ?1"),
+                params![id],
+                session_from_row,
+            )
+            .optional()?;
+        Ok(session)
+    }
+
+    /// Full transcript, oldest first.
+    pub fn messages(&self, id: &str) -> Result<Vec<SessionMessage>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT role, content, at FROM gateway_session_messages
+             WHERE session_id = ?1 ORDER BY id",
+        )?;
+        let messages = stmt
+            .query_map(params![id], |row| {
+                Ok(SessionMessage {
+                    role: row.get(0)?,
+                    content: row.get(1)?,
+                    at: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(messages)
+    }
+
+    /// Compacted agent context for the next turn.
+    pub fn context(&self, id: &str) -> Result<Vec<ChatMessage>> {
+        let raw: Option<String> = self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT context FROM gateway_sessions WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match raw {
+            Some(raw) => Ok(serde_json::from_str(&raw).unwrap_or_default()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Append a completed exchange and replace the agent context.
+    pub fn record_turn(
+        &self,
+        id: &str,
+        user: &str,
+        assistant: &str,
+        context: &[ChatMessage],
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let context = serde_json::to_string(context)?;
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        for (role, content) in [("user", user), ("assistant", assistant)] {
+            tx.execute(
+                "INSERT INTO gateway_session_messages (session_id, role, content, at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, role, content, now],
+            )?;
+        }
+        tx.execute(
+            "UPDATE gateway_sessions SET context = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, context, now],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Delete a session and its transcript. Returns false if it did not exist.
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .lock()
+            .execute("DELETE FROM gateway_sessions WHERE id = ?1", params![id])?;
+        Ok(removed > 0)
+    }
+
+    /// Claim a session for one turn; `None` if a turn is already running.
+    pub fn begin_turn(&self, id: &str) -> Option<TurnGuard> {
+        if !self.busy.lock().insert(id.to_string()) {
+            return None;
+        }
+        Some(TurnGuard {
+            id: id.to_string(),
+            busy: Arc::clone(&self.busy),
+        })
+    }
+}
+
+// ── Handlers ─────────────────────────────────────────────────────
+
+#[derive(Deserialize, Default)]
+pub struct CreateSessionBody {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SendMessageBody {
+    pub message: String,
+}
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+fn error(status: StatusCode, message: impl Into<String>) -> ApiError {
+    (status, Json(serde_json::json!({ "error": message.into() })))
+}
+
+fn require(state: &AppState, headers: &HeaderMap, scopes: &[GatewayScope]) -> Result<(), ApiError> {
+    let token = super::api::extract_bearer_token(headers).unwrap_or("");
+    let result = match scopes {
+        [scope] => authorize(state, token, *scope),
+        _ => authorize_any(state, token, scopes),
+    };
+    result
+        .map(|_| ())
+        .map_err(|e| error(e.status(), e.message()))
+}
+
+fn store(state: &AppState) -> Result<&SessionStore, ApiError> {
+    state.sessions.as_deref().ok_or_else(|| {
+        error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Sessions are unavailable on this gateway",
+        )
+    })
+}
+
+fn internal(context: &str, e: &anyhow::Error) -> ApiError {
+    tracing::error!("{context}: {e}");
+    error(StatusCode::INTERNAL_SERVER_ERROR, context)
+}
+
+/// POST /api/sessions — create a session (`{"name": "..."}` optional)
+pub async fn handle_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Option<Json<CreateSessionBody>>,
+) -> Result<impl IntoResponse, ApiError> {
+    require(&state, &headers, &[GatewayScope::Chat])?;
+    let sessions = store(&state)?;
+
+    let name = body
+        .and_then(|Json(body)| body.name)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if name
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_SESSION_NAME_CHARS)
+    {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            format!("Session name must be at most {MAX_SESSION_NAME_CHARS} characters"),
+        ));
+    }
+
+    match sessions.create(name.as_deref()) {
+        Ok(Some(session)) => Ok((StatusCode::CREATED, Json(session))),
+        Ok(None) => Err(error(
+            StatusCode::CONFLICT,
+            "A session with this name already exists",
+        )),
+        Err(e) => Err(internal("Failed to create session", &e)),
+    }
+}
+
+/// GET /api/sessions — list sessions
+pub async fn handle_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    require(&state, &headers, &[GatewayScope::Read, GatewayScope::Chat])?;
+    let sessions = store(&state)?
+        .list()
+        .map_err(|e| internal("Failed to list sessions", &e))?;
+    Ok(Json(serde_json::json!({ "sessions": sessions })))
+}
+
+/// GET /api/sessions/{id} — session metadata and full transcript
+pub async fn handle_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    require(&state, &headers, &[GatewayScope::Read, GatewayScope::Chat])?;
+    let sessions = store(&state)?;
+    let session = sessions
+        .get(&id)
+        .map_err(|e| internal("Failed to load session", &e))?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "Session not found"))?;
+    let messages = sessions
+        .messages(&id)
+        .map_err(|e| internal("Failed to load session messages", &e))?;
+
+    let mut body = serde_json::to_value(&session).unwrap_or_default();
+    body["messages"] = serde_json::to_value(messages).unwrap_or_default();
+    Ok(Json(body))
+}
+
+/// POST /api/sessions/{id}/messages — run one turn and return the answer
+pub async fn handle_send(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Result<Json<SendMessageBody>, axum::extract::rejection::JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    require(&state, &headers, &[GatewayScope::Chat])?;
+    let sessions = store(&state)?;
+    let Ok(Json(body)) = body else {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "Invalid JSON body. Expected: {\"message\": \"...\"}",
+        ));
+    };
+    let message = body.message.trim();
+    if message.is_empty() {
+        return Err(error(StatusCode::BAD_REQUEST, "message must not be empty"));
+    }
+
+    if sessions
+        .get(&id)
+        .map_err(|e| internal("Failed to load session", &e))?
+        .is_none()
+    {
+        return Err(error(StatusCode::NOT_FOUND, "Session not found"));
+    }
+    let Some(_turn) = sessions.begin_turn(&id) else {
+        return Err(error(
+            StatusCode::CONFLICT,
+            "A message is already being processed for this session",
+        ));
+    };
+
+    let mut context = sessions
+        .context(&id)
+        .map_err(|e| internal("Failed to load session context", &e))?;
+    let config = state.config.lock().clone();
+    let response = crate::agent::process_session_turn(config, &id, &mut context, message)
+        .await
+        .map_err(|e| {
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            tracing::error!(session_id = %id, "Session turn failed: {sanitized}");
+            error(StatusCode::INTERNAL_SERVER_ERROR, "LLM request failed")
+        })?;
+
+    sessions
+        .record_turn(&id, message, &response, &context)
+        .map_err(|e| internal("Failed to save session turn", &e))?;
+
+    Ok(Json(serde_json::json!({
+        "session_id": id,
+        "response": response,
+    })))
+}
+
+/// DELETE /api/sessions/{id} — delete a session and its autosaved memories
+pub async fn handle_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    require(&state, &headers, &[GatewayScope::Chat])?;
+    let sessions = store(&state)?;
+    let Some(_turn) = sessions.begin_turn(&id) else {
+        return Err(error(
+            StatusCode::CONFLICT,
+            "A message is still being processed for this session",
+        ));
+    };
+    let deleted = sessions
+        .delete(&id)
+        .map_err(|e| internal("Failed to delete session", &e))?;
+    if !deleted {
+        return Err(error(StatusCode::NOT_FOUND, "Session not found"));
+    }
+
+    let mut forgotten = 0usize;
+    if let Ok(entries) = state
+        .mem
+        .list(Some(&MemoryCategory::Conversation), Some(&id))
+        .await
+    {
+        for entry in entries {
+            if state.mem.forget(&entry.key).await.unwrap_or(false) {
+                forgotten += 1;
+            }
+        }
+    }
+
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "forgotten_memories": forgotten,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sessions_persist_transcript_and_context_until_deleted() {
+        let tmp = TempDir::new().unwrap();
+        let id = {
+            let store = SessionStore::open(tmp.path()).unwrap();
+            let session = store.create(Some("support")).unwrap().unwrap();
+            assert!(store.create(Some("support")).unwrap().is_none());
+            assert!(store.create(None).unwrap().is_some());
+
+            let context = vec![
+                ChatMessage::assistant("[Compaction summary]\n- user is Ana"),
+                ChatMessage::user("what's my name?"),
+                ChatMessage::assistant("Ana"),
+            ];
+            store
+                .record_turn(&session.id, "what's my name?", "Ana", &context)
+                .unwrap();
+            session.id
+        };
+
+        // Reopen as after a page reload / gateway restart.
+        let store = SessionStore::open(tmp.path()).unwrap();
+        let session = store.get(&id).unwrap().unwrap();
+        assert_eq!(session.name.as_deref(), Some("support"));
+        assert_eq!(session.message_count, 2);
+        assert_eq!(store.list().unwrap().len(), 2);
+
+        let messages = store.messages(&id).unwrap();
+        assert_eq!(messages[0].role, "user");
+        assert_eq!(messages[1].content, "Ana");
+        let context = store.context(&id).unwrap();
+        assert_eq!(context.len(), 3);
+        assert!(context[0].content.starts_with("[Compaction summary]"));
+
+        assert!(store.delete(&id).unwrap());
+        assert!(store.get(&id).unwrap().is_none());
+        assert!(store.messages(&id).unwrap().is_empty());
+        assert!(!store.delete(&id).unwrap());
+    }
+
+    #[test]
+    fn only_one_turn_runs_per_session() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::open(tmp.path()).unwrap();
+        let guard = store.begin_turn("s1").unwrap();
+        assert!(store.begin_turn("s1").is_none());
+        assert!(store.begin_turn("s2").is_some());
+        drop(guard);
+        assert!(store.begin_turn("s1").is_some());
+    }
+}
@@ -3,9 +3,10 @@
 //! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
 
 use super::AppState;
+use crate::config::GatewayScope;
 use axum::{
     extract::State,
-    http::{header, HeaderMap, StatusCode},
+    http::{header, HeaderMap},
     response::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
@@ -21,20 +22,18 @@ pub async fn handle_sse_events(
     headers: HeaderMap,
 ) -> impl IntoResponse {
     // Auth check
-    if state.pairing.require_pairing() {
-        let token = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .and_then(|auth| auth.strip_prefix("Bearer "))
-            .unwrap_or("");
-
-        if !state.pairing.is_authenticated(token) {
-            return (
-                StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide Authorization: Bearer <token>",
-            )
-                .into_response();
-        }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if let Err(e) = super::authorize(&state, token, GatewayScope::Read) {
+        let message = if e == super::AuthError::Unauthorized {
+            "Unauthorized — provide Authorization: Bearer <token>".to_string()
+        } else {
+            e.message()
+        };
+        return (e.status(), message).into_response();
     }
 
     let rx = state.event_tx.subscribe();
@@ -0,0 +1,187 @@
+//! `zeroclaw gateway tokens` — manage named, scoped gateway API tokens.
+//!
+//! Tokens live in `[[gateway.tokens]]` as SHA-256 hashes; the plaintext is
+//! printed once on creation. The running gateway loads them at startup.
+
+use crate::config::{Config, GatewayScope, GatewayTokenConfig};
+use crate::security::pairing::issue_token;
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+
+pub async fn handle_command(command: crate::GatewayTokenCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::GatewayTokenCommands::Create {
+            name,
+            scopes,
+            expires_in_days,
+            rate_limit,
+        } => {
+            let mut updated = config.clone();
+            let token = create_token(&mut updated, &name, &scopes, expires_in_days, rate_limit)?;
+            updated.save().await?;
+
+            let entry = updated
+                .gateway
+                .tokens
+                .iter()
+                .find(|t| t.name == name.trim())
+                .expect("token was just added");
+            println!("✅ Created gateway token '{}'", entry.name);
+            println!("   Scopes:  {}", format_scopes(&entry.scopes));
+            if let Some(expires_at) = &entry.expires_at {
+                println!("   Expires: {expires_at}");
+            }
+            if entry.rate_limit_per_minute > 0 {
+                println!(
+                    "   Limit:   {} requests/minute",
+                    entry.rate_limit_per_minute
+                );
+            }
+            println!();
+            println!("   {token}");
+            println!();
+            println!("   Save this token now — it is not stored and cannot be shown again.");
+            println!("   Restart the gateway to apply.");
+            Ok(())
+        }
+        crate::GatewayTokenCommands::List => {
+            if config.gateway.tokens.is_empty() {
+                println!("No named gateway tokens.");
+                println!("Create one with: zeroclaw gateway tokens create <name> --scopes read");
+                return Ok(());
+            }
+            let now = Utc::now();
+            println!("Gateway tokens ({}):", config.gateway.tokens.len());
+            for token in &config.gateway.tokens {
+                let expiry = match token.expires_at.as_deref() {
+                    None => "never expires".to_string(),
+                    Some(raw) => match chrono::DateTime::parse_from_rfc3339(raw) {
+                        Ok(at) if at <= now => format!("EXPIRED {raw}"),
+                        Ok(_) => format!("expires {raw}"),
+                        Err(_) => format!("invalid expiry {raw}"),
+                    },
+                };
+                let limit = if token.rate_limit_per_minute == 0 {
+                    "unlimited".to_string()
+                } else {
+                    format!("{}/min", token.rate_limit_per_minute)
+                };
+                println!(
+                    "  {:<20} scopes={:<16} {limit:<10} {expiry}",
+                    token.name,
+                    format_scopes(&token.scopes)
+                );
+            }
+            Ok(())
+        }
+        crate::GatewayTokenCommands::Revoke { name } => {
+            let mut updated = config.clone();
+            revoke_token(&mut updated, &name)?;
+            updated.save().await?;
+            println!("✅ Revoked gateway token '{}'", name.trim());
+            println!("   Restart the gateway to apply.");
+            Ok(())
+        }
+    }
+}
+
+/// Add a named token to `config` and return its plaintext.
+fn create_token(
+    config: &mut Config,
+    name: &str,
+    scopes: &[String],
+    expires_in_days: Option<u32>,
+    rate_limit_per_minute: u32,
+) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Token name must not be empty");
+    }
+    if config.gateway.tokens.iter().any(|t| t.name == name) {
+        bail!("A gateway token named '{name}' already exists; revoke it first");
+    }
+    let mut parsed: Vec<GatewayScope> = Vec::new();
+    for scope in scopes {
+        let scope: GatewayScope = scope.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+        if !parsed.contains(&scope) {
+            parsed.push(scope);
+        }
+    }
+    if parsed.is_empty() {
+        bail!("At least one scope is required (chat, read, admin)");
+    }
+
+    let now = Utc::now();
+    let (token, token_hash) = issue_token();
+    config.gateway.tokens.push(GatewayTokenConfig {
+        name: name.to_string(),
+        token_hash,
+        scopes: parsed,
+        expires_at: expires_in_days
+            .map(|days| (now + Duration::days(i64::from(days))).to_rfc3339()),
+        rate_limit_per_minute,
+        created_at: Some(now.to_rfc3339()),
+    });
+    Ok(token)
+}
+
+fn revoke_token(config: &mut Config, name: &str) -> Result<()> {
+    let name = name.trim();
+    let before = config.gateway.tokens.len();
+    config.gateway.tokens.retain(|t| t.name != name);
+    if config.gateway.tokens.len() == before {
+        bail!("No gateway token named '{name}'");
+    }
+    Ok(())
+}
+
+fn format_scopes(scopes: &[GatewayScope]) -> String {
+    scopes
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::PairingGuard;
+
+    #[test]
+    fn created_token_authenticates_with_its_scopes_until_revoked() {
+        let mut config = Config::default();
+        let token = create_token(
+            &mut config,
+            "monitoring",
+            &["read".into(), "read".into()],
+            Some(30),
+            10,
+        )
+        .unwrap();
+
+        let entry = &config.gateway.tokens[0];
+        assert_eq!(entry.scopes, vec![GatewayScope::Read]);
+        assert_ne!(entry.token_hash, token);
+        assert!(entry.expires_at.is_some());
+        config.validate().unwrap();
+
+        let guard = PairingGuard::new(true, &[]).with_scoped_tokens(&config.gateway.tokens);
+        let grant = guard.authenticate(&token).unwrap();
+        assert!(grant.allows(GatewayScope::Read));
+        assert!(!grant.allows(GatewayScope::Chat));
+
+        assert!(create_token(&mut config, "monitoring", &["chat".into()], None, 0).is_err());
+        revoke_token(&mut config, "monitoring").unwrap();
+        assert!(config.gateway.tokens.is_empty());
+        assert!(revoke_token(&mut config, "monitoring").is_err());
+    }
+
+    #[test]
+    fn create_rejects_unknown_scope() {
+        let mut config = Config::default();
+        let err = create_token(&mut config, "x", &["write".into()], None, 0).unwrap_err();
+        assert!(err.to_string().contains("unknown gateway scope"));
+        assert!(config.gateway.tokens.is_empty());
+    }
+}
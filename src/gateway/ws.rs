@@ -10,6 +10,7 @@
 //! ```
 
 use super::AppState;
+use crate::config::GatewayScope;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -32,15 +33,14 @@ pub async fn handle_ws_chat(
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Auth via query param (browser WebSocket limitation)
-    if state.pairing.require_pairing() {
-        let token = params.token.as_deref().unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide ?token=<bearer_token>",
-            )
-                .into_response();
-        }
+    let token = params.token.as_deref().unwrap_or("");
+    if let Err(e) = super::authorize(&state, token, GatewayScope::Chat) {
+        let message = if e == super::AuthError::Unauthorized {
+            "Unauthorized — provide ?token=<bearer_token>".to_string()
+        } else {
+            e.message()
+        };
+        return (e.status(), message).into_response();
     }
 
     ws.on_upgrade(move |socket| handle_socket(socket, state))
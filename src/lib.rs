@@ -136,6 +136,35 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// Link a channel sender to a canonical user
+    #[command(long_about = "\
+Link a channel sender to a canonical user.
+
+Linked senders share conversation history, memory, model routes and \
+preferences across channels. Allowlists may list `user:<id>` to admit \
+every identity linked to that user. Senders can also link themselves \
+from chat with `/link`.
+
+Examples:
+  zeroclaw channel link-identity alice telegram 123456789
+  zeroclaw channel link-identity alice email alice@example.com")]
+    LinkIdentity {
+        /// Canonical user ID (letters, digits, '_', '-', '.')
+        user: String,
+        /// Channel name (telegram, slack, email, ...)
+        channel: String,
+        /// Sender ID as the channel reports it
+        sender: String,
+    },
+    /// Remove a channel sender's identity link
+    UnlinkIdentity {
+        /// Channel name (telegram, slack, email, ...)
+        channel: String,
+        /// Sender ID as the channel reports it
+        sender: String,
+    },
+    /// List canonical users with their linked identities and preferences
+    Identities,
 }
 
 /// Skills management subcommands
@@ -324,6 +353,81 @@ pub enum MemoryCommands {
     },
 }
 
+/// Audit log subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditCommands {
+    /// Check signatures and chain continuity across the audit log and its rotated files
+    Verify,
+    /// Show audit events filtered by actor, tool and time
+    Query {
+        /// Actor channel, user id or username
+        #[arg(long)]
+        actor: Option<String>,
+        /// Tool name (e.g. shell)
+        #[arg(long)]
+        tool: Option<String>,
+        /// Only events at or after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Only events at or before this RFC 3339 timestamp
+        #[arg(long)]
+        until: Option<String>,
+        /// Maximum number of (newest) events to show; 0 shows all
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        /// Print raw JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Gateway management subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GatewayCommands {
+    /// Manage named, scoped gateway API tokens
+    Tokens {
+        #[command(subcommand)]
+        tokens_command: GatewayTokenCommands,
+    },
+}
+
+/// Gateway API token subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GatewayTokenCommands {
+    /// Create a named token and print it once
+    #[command(long_about = "\
+Create a named gateway API token.
+
+The token is printed once; only its SHA-256 hash is written to \
+config.toml. Scopes: chat (/webhook, /ws/chat), read (GET /api/*, \
+/api/events), admin (everything). Restart the gateway to apply.
+
+Examples:
+  zeroclaw gateway tokens create frontend --scopes chat
+  zeroclaw gateway tokens create monitoring --scopes read --expires-in-days 90 --rate-limit 30
+  zeroclaw gateway tokens create ops --scopes admin")]
+    Create {
+        /// Unique token name
+        name: String,
+        /// Comma-separated scopes: chat, read, admin
+        #[arg(long, value_delimiter = ',', required = true)]
+        scopes: Vec<String>,
+        /// Expire the token after this many days
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+        /// Max requests per minute for this token; 0 = unlimited
+        #[arg(long, default_value = "0")]
+        rate_limit: u32,
+    },
+    /// List named tokens (hashes are never shown)
+    List,
+    /// Revoke a named token
+    Revoke {
+        /// Token name
+        name: String,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
@@ -87,8 +87,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    AuditCommands, ChannelCommands, CronCommands, GatewayCommands, GatewayTokenCommands,
+    HardwareCommands, IntegrationCommands, MigrateCommands, PeripheralCommands, ServiceCommands,
+    SkillCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -196,8 +197,14 @@ Examples:
   zeroclaw gateway                  # use config defaults
   zeroclaw gateway -p 8080          # listen on port 8080
   zeroclaw gateway --host 0.0.0.0   # bind to all interfaces
-  zeroclaw gateway -p 0             # random available port")]
+  zeroclaw gateway -p 0             # random available port
+  zeroclaw gateway tokens create monitoring --scopes read
+  zeroclaw gateway tokens list
+  zeroclaw gateway tokens revoke monitoring")]
     Gateway {
+        #[command(subcommand)]
+        gateway_command: Option<GatewayCommands>,
+
         /// Port to listen on (use 0 for random available port); defaults to config gateway.port
         #[arg(short, long)]
         port: Option<u16>,
@@ -384,6 +391,25 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// Verify and query the security audit log
+    #[command(long_about = "\
+Verify and query the security audit log.
+
+With [security.audit] sign_events = true, every event is signed with \
+an HMAC key derived from the secret store and chained to the previous \
+event. 'verify' walks the active log and its rotated files and reports \
+edited events, gaps and broken links.
+
+Examples:
+  zeroclaw audit verify
+  zeroclaw audit query --tool shell --limit 20
+  zeroclaw audit query --actor telegram --since 2025-01-15T00:00:00Z
+  zeroclaw audit query --until 2025-01-16T00:00:00Z --json")]
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -696,7 +722,16 @@ async fn main() -> Result<()> {
         .await
         .map(|_| ()),
 
-        Commands::Gateway { port, host } => {
+        Commands::Gateway {
+            gateway_command: Some(GatewayCommands::Tokens { tokens_command }),
+            ..
+        } => gateway::tokens::handle_command(tokens_command, &config).await,
+
+        Commands::Gateway {
+            gateway_command: None,
+            port,
+            host,
+        } => {
             let port = port.unwrap_or(config.gateway.port);
             let host = host.unwrap_or_else(|| config.gateway.host.clone());
             if port == 0 {
@@ -899,6 +934,10 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Audit { audit_command } => {
+            security::audit::handle_command(audit_command, &config)
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1,11 +1,13 @@
 use crate::config::schema::{
-    default_nostr_relays, DingTalkConfig, IrcConfig, LarkReceiveMode, LinqConfig,
-    NextcloudTalkConfig, NostrConfig, QQConfig, SignalConfig, StreamMode, WhatsAppConfig,
+    default_nostr_relays, DingTalkConfig, GroupPolicyConfig, IrcConfig, LarkReceiveMode,
+    LinqConfig, NextcloudTalkConfig, NostrConfig, QQConfig, SignalConfig, StreamMode,
+    WhatsAppConfig,
 };
 use crate::config::{
     AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
     HeartbeatConfig, IMessageConfig, LarkConfig, MatrixConfig, MemoryConfig, ObservabilityConfig,
-    RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig, WebhookConfig,
+    RuntimeConfig, SecretsConfig, SecurityConfig, SlackConfig, StorageConfig, TelegramConfig,
+    WebhookConfig,
 };
 use crate::hardware::{self, HardwareConfig};
 use crate::memory::{
@@ -137,6 +139,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
+        security: SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
@@ -166,6 +169,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
     };
 
     println!(
@@ -484,6 +488,7 @@ async fn run_quick_setup_with_home(
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
+        security: SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
@@ -513,6 +518,7 @@ async fn run_quick_setup_with_home(
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
     };
 
     config.save().await?;
@@ -3382,6 +3388,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     draft_update_interval_ms: 1000,
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    group: GroupPolicyConfig::default(),
                 });
             }
             ChannelMenuChoice::Discord => {
@@ -3481,6 +3488,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    group: GroupPolicyConfig::default(),
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -3602,12 +3610,14 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     } else {
                         Some(app_token)
                     },
+                    signing_secret: None,
                     channel_id: if channel.is_empty() {
                         None
                     } else {
                         Some(channel)
                     },
                     allowed_users,
+                    group: GroupPolicyConfig::default(),
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -3764,6 +3774,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     device_id: detected_device_id,
                     room_id,
                     allowed_users,
+                    group: GroupPolicyConfig::default(),
                 });
             }
             ChannelMenuChoice::Signal => {
@@ -4270,6 +4281,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(sasl_password.trim().to_string())
                     },
                     verify_tls: Some(verify_tls),
+                    group: GroupPolicyConfig::default(),
                 });
             }
             ChannelMenuChoice::Webhook => {
@@ -6872,6 +6884,8 @@ mod tests {
             allowed_users: vec!["*".into()],
             thread_replies: Some(true),
             mention_only: Some(false),
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
         });
         assert!(has_launchable_channels(&channels));
 
@@ -1,15 +1,20 @@
+use crate::providers::reasoning::{ReasoningSettings, MIN_THINKING_BUDGET_TOKENS};
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, ReasoningContent, ResponseSchema, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+/// Output tokens reserved for the visible reply on top of the thinking budget.
+const THINKING_REPLY_TOKENS: u32 = 4096;
+
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
+    reasoning: ReasoningSettings,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,6 +56,26 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<NativeToolChoice<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<NativeThinking>,
+}
+
+/// Extended thinking request option.
+#[derive(Debug, Serialize)]
+struct NativeThinking {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    budget_tokens: u32,
+}
+
+/// Forces a specific tool (used to emulate structured output).
+#[derive(Debug, Serialize)]
+struct NativeToolChoice<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'a str,
 }
 
 #[derive(Debug, Serialize)]
@@ -83,6 +108,10 @@ enum NativeContentOut {
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
     },
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
 }
 
 #[derive(Debug, Serialize)]
@@ -133,11 +162,37 @@ struct NativeChatResponse {
 }
 
 #[derive(Debug, Deserialize)]
-struct AnthropicUsage {
+pub(crate) struct AnthropicUsage {
     #[serde(default)]
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    /// Anthropic reports cache reads/writes separately from `input_tokens`;
+    /// fold them back in so `input_tokens` is the full prompt size.
+    pub(crate) fn into_token_usage(self) -> TokenUsage {
+        let cached = self
+            .cache_read_input_tokens
+            .unwrap_or(0)
+            .saturating_add(self.cache_creation_input_tokens.unwrap_or(0));
+        let input_tokens = match self.input_tokens {
+            Some(base) => Some(base.saturating_add(cached)),
+            None if cached > 0 => Some(cached),
+            None => None,
+        };
+        TokenUsage {
+            input_tokens,
+            output_tokens: self.output_tokens,
+            cache_read_tokens: self.cache_read_input_tokens,
+            cache_write_tokens: self.cache_creation_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -152,6 +207,12 @@ struct NativeContentIn {
     name: Option<String>,
     #[serde(default)]
     input: Option<serde_json::Value>,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    data: Option<String>,
 }
 
 impl AnthropicProvider {
@@ -170,10 +231,26 @@ impl AnthropicProvider {
                 .filter(|k| !k.is_empty())
                 .map(ToString::to_string),
             base_url,
+            reasoning: ReasoningSettings::default(),
         }
     }
 
-    fn is_setup_token(token: &str) -> bool {
+    /// Apply configured reasoning settings (extended thinking budget per model).
+    pub fn with_reasoning(mut self, reasoning: ReasoningSettings) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
+    /// Extended thinking option for `model`, if a budget is configured.
+    fn thinking_for(&self, model: &str) -> Option<NativeThinking> {
+        let budget = self.reasoning.resolve(model).budget_tokens?;
+        Some(NativeThinking {
+            kind: "enabled",
+            budget_tokens: budget.max(MIN_THINKING_BUDGET_TOKENS),
+        })
+    }
+
+    pub(crate) fn is_setup_token(token: &str) -> bool {
         token.starts_with("sk-ant-oat01-")
     }
 
@@ -210,7 +287,9 @@ impl AnthropicProvider {
                     | NativeContentOut::ToolResult { cache_control, .. } => {
                         *cache_control = Some(CacheControl::ephemeral());
                     }
-                    NativeContentOut::ToolUse { .. } => {}
+                    NativeContentOut::ToolUse { .. }
+                    | NativeContentOut::Thinking { .. }
+                    | NativeContentOut::RedactedThinking { .. } => {}
                 }
             }
         }
@@ -239,13 +318,50 @@ impl AnthropicProvider {
         Some(native_tools)
     }
 
+    /// Anthropic has no JSON-schema response mode; emulate it by forcing a
+    /// single tool whose input schema is the requested response schema.
+    fn structured_output_tool(
+        schema: &ResponseSchema,
+    ) -> (NativeToolSpec<'_>, NativeToolChoice<'_>) {
+        (
+            NativeToolSpec {
+                name: &schema.name,
+                description: "Return the final answer as structured data matching this schema.",
+                input_schema: &schema.schema,
+                cache_control: None,
+            },
+            NativeToolChoice {
+                kind: "tool",
+                name: &schema.name,
+            },
+        )
+    }
+
+    /// Move the forced structured-output tool call into the response text.
+    fn unwrap_structured_output(response: &mut ProviderChatResponse, schema: &ResponseSchema) {
+        if let Some(index) = response
+            .tool_calls
+            .iter()
+            .position(|call| call.name == schema.name)
+        {
+            let call = response.tool_calls.remove(index);
+            response.text = Some(call.arguments);
+        }
+    }
+
     fn parse_assistant_tool_call_message(content: &str) -> Option<Vec<NativeContentOut>> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_calls = value
             .get("tool_calls")
             .and_then(|v| serde_json::from_value::<Vec<ProviderToolCall>>(v.clone()).ok())?;
 
-        let mut blocks = Vec::new();
+        // Signed thinking blocks must precede tool_use blocks verbatim.
+        let mut blocks: Vec<NativeContentOut> = value
+            .get("reasoning")
+            .and_then(|r| r.get("provider_blocks"))
+            .and_then(serde_json::Value::as_array)
+            .map(|items| items.iter().filter_map(Self::thinking_block_out).collect())
+            .unwrap_or_default();
         if let Some(text) = value
             .get("content")
             .and_then(serde_json::Value::as_str)
@@ -270,6 +386,25 @@ impl AnthropicProvider {
         Some(blocks)
     }
 
+    fn thinking_block_out(block: &serde_json::Value) -> Option<NativeContentOut> {
+        let field = |name: &str| {
+            block
+                .get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)
+        };
+        match block.get("type").and_then(serde_json::Value::as_str)? {
+            "thinking" => Some(NativeContentOut::Thinking {
+                thinking: field("thinking").unwrap_or_default(),
+                signature: field("signature")?,
+            }),
+            "redacted_thinking" => Some(NativeContentOut::RedactedThinking {
+                data: field("data")?,
+            }),
+            _ => None,
+        }
+    }
+
     fn parse_tool_result_message(content: &str) -> Option<NativeMessage> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_use_id = value
@@ -371,11 +506,9 @@ impl AnthropicProvider {
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
+        let mut reasoning = ReasoningContent::default();
 
-        let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
-        });
+        let usage = response.usage.map(AnthropicUsage::into_token_usage);
 
         for block in response.content {
             match block.kind.as_str() {
@@ -400,6 +533,26 @@ impl AnthropicProvider {
                         arguments: arguments.to_string(),
                     });
                 }
+                "thinking" => {
+                    let thinking = block.thinking.unwrap_or_default();
+                    if !reasoning.text.is_empty() {
+                        reasoning.text.push_str("\n\n");
+                    }
+                    reasoning.text.push_str(thinking.trim());
+                    reasoning.provider_blocks.push(serde_json::json!({
+                        "type": "thinking",
+                        "thinking": thinking,
+                        "signature": block.signature.unwrap_or_default(),
+                    }));
+                }
+                "redacted_thinking" => {
+                    if let Some(data) = block.data {
+                        reasoning.provider_blocks.push(serde_json::json!({
+                            "type": "redacted_thinking",
+                            "data": data,
+                        }));
+                    }
+                }
                 _ => {}
             }
         }
@@ -412,6 +565,7 @@ impl AnthropicProvider {
             },
             tool_calls,
             usage,
+            reasoning: reasoning.into_option(),
         }
     }
 
@@ -484,13 +638,35 @@ impl Provider for AnthropicProvider {
             Self::apply_cache_to_last_message(&mut messages);
         }
 
+        let (tools, tool_choice) = match request.response_schema {
+            Some(schema) => {
+                let (tool, choice) = Self::structured_output_tool(schema);
+                (Some(vec![tool]), Some(choice))
+            }
+            None => (Self::convert_tools(request.tools), None),
+        };
+
+        // Forced tool use (structured output) is incompatible with thinking.
+        let thinking = if tool_choice.is_none() {
+            self.thinking_for(model)
+        } else {
+            None
+        };
+        // Thinking requires temperature 1 and room for the reply beyond the budget.
+        let (max_tokens, temperature) = match &thinking {
+            Some(t) => (t.budget_tokens + THINKING_REPLY_TOKENS, 1.0),
+            None => (4096, temperature),
+        };
+
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens,
             system: system_prompt,
             messages,
             temperature,
-            tools: Self::convert_tools(request.tools),
+            tools,
+            tool_choice,
+            thinking,
         };
 
         let req = self
@@ -506,13 +682,21 @@ impl Provider for AnthropicProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let mut parsed = Self::parse_native_response(native_response);
+        if let Some(schema) = request.response_schema {
+            Self::unwrap_structured_output(&mut parsed, schema);
+        }
+        Ok(parsed)
     }
 
     fn supports_native_tools(&self) -> bool {
         true
     }
 
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -556,6 +740,7 @@ impl Provider for AnthropicProvider {
             } else {
                 Some(&tool_specs)
             },
+            response_schema: None,
         };
         self.chat(request, model, temperature).await
     }
@@ -1155,6 +1340,8 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            tool_choice: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -1244,6 +1431,7 @@ mod tests {
         let provider = AnthropicProvider {
             credential: Some("test-key".to_string()),
             base_url: format!("http://{addr}"),
+            reasoning: ReasoningSettings::default(),
         };
 
         // Multi-turn conversation: system → user (Go code) → assistant (code response) → user (follow-up)
@@ -1343,6 +1531,66 @@ mod tests {
         let usage = result.usage.unwrap();
         assert_eq!(usage.input_tokens, Some(300));
         assert_eq!(usage.output_tokens, Some(75));
+        assert!(usage.cache_read_tokens.is_none());
+        assert!(usage.cache_write_tokens.is_none());
+    }
+
+    #[test]
+    fn native_response_parses_cache_usage() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "Hello"}],
+            "usage": {
+                "input_tokens": 50,
+                "output_tokens": 10,
+                "cache_creation_input_tokens": 200,
+                "cache_read_input_tokens": 4000
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let result = AnthropicProvider::parse_native_response(resp);
+        let usage = result.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(4250));
+        assert_eq!(usage.cache_read_tokens, Some(4000));
+        assert_eq!(usage.cache_write_tokens, Some(200));
+        assert_eq!(usage.uncached_input_tokens(), Some(50));
+    }
+
+    #[test]
+    fn structured_output_forces_schema_tool() {
+        let schema = ResponseSchema::new("verdict", serde_json::json!({"type": "object"}));
+        let (tool, choice) = AnthropicProvider::structured_output_tool(&schema);
+        let req = NativeChatRequest {
+            model: "claude-3-opus".to_string(),
+            max_tokens: 1024,
+            system: None,
+            messages: vec![],
+            temperature: 0.0,
+            tools: Some(vec![tool]),
+            tool_choice: Some(choice),
+            thinking: None,
+        };
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["tool_choice"]["type"], "tool");
+        assert_eq!(json["tool_choice"]["name"], "verdict");
+        assert_eq!(json["tools"][0]["name"], "verdict");
+        assert_eq!(json["tools"][0]["input_schema"]["type"], "object");
+    }
+
+    #[test]
+    fn structured_output_tool_call_becomes_text() {
+        let json = r#"{
+            "content": [
+                {"type": "tool_use", "id": "t1", "name": "verdict", "input": {"ok": true}}
+            ]
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let mut result = AnthropicProvider::parse_native_response(resp);
+        let schema = ResponseSchema::new("verdict", serde_json::json!({"type": "object"}));
+        AnthropicProvider::unwrap_structured_output(&mut result, &schema);
+
+        assert!(result.tool_calls.is_empty());
+        assert_eq!(result.text.as_deref(), Some(r#"{"ok":true}"#));
     }
 
     #[test]
@@ -1352,4 +1600,74 @@ mod tests {
         let result = AnthropicProvider::parse_native_response(resp);
         assert!(result.usage.is_none());
     }
+
+    #[test]
+    fn native_response_separates_thinking_blocks() {
+        let json = r#"{
+            "content": [
+                {"type": "thinking", "thinking": "Check the file first.", "signature": "sig-1"},
+                {"type": "redacted_thinking", "data": "opaque"},
+                {"type": "text", "text": "Let me look."},
+                {"type": "tool_use", "id": "t1", "name": "file_read", "input": {"path": "a"}}
+            ]
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let result = AnthropicProvider::parse_native_response(resp);
+
+        assert_eq!(result.text.as_deref(), Some("Let me look."));
+        let reasoning = result.reasoning.unwrap();
+        assert_eq!(reasoning.text, "Check the file first.");
+        assert_eq!(reasoning.provider_blocks.len(), 2);
+        assert_eq!(reasoning.provider_blocks[0]["signature"], "sig-1");
+        assert_eq!(reasoning.provider_blocks[1]["type"], "redacted_thinking");
+    }
+
+    #[test]
+    fn convert_messages_replays_signed_thinking_before_tool_use() {
+        let history = serde_json::json!({
+            "content": null,
+            "tool_calls": [{"id": "t1", "name": "shell", "arguments": "{}"}],
+            "reasoning": {
+                "text": "Run ls.",
+                "provider_blocks": [
+                    {"type": "thinking", "thinking": "Run ls.", "signature": "sig-1"},
+                    {"type": "redacted_thinking", "data": "opaque"}
+                ]
+            }
+        });
+        let messages = vec![ChatMessage::assistant(history.to_string())];
+        let (_, native) = AnthropicProvider::convert_messages(&messages);
+
+        let json = serde_json::to_value(&native[0].content).unwrap();
+        assert_eq!(json[0]["type"], "thinking");
+        assert_eq!(json[0]["signature"], "sig-1");
+        assert_eq!(json[1]["type"], "redacted_thinking");
+        assert_eq!(json[2]["type"], "tool_use");
+    }
+
+    #[test]
+    fn thinking_uses_configured_budget_per_model() {
+        let mut settings = ReasoningSettings {
+            budget_tokens: Some(200),
+            ..ReasoningSettings::default()
+        };
+        settings.models.insert(
+            "claude-opus-4".into(),
+            crate::config::ModelReasoningConfig {
+                budget_tokens: Some(16_000),
+                ..Default::default()
+            },
+        );
+        let provider = AnthropicProvider::new(Some("key")).with_reasoning(settings);
+
+        // Budgets below the API minimum are raised to it.
+        let thinking = provider.thinking_for("claude-sonnet-4").unwrap();
+        assert_eq!(thinking.budget_tokens, MIN_THINKING_BUDGET_TOKENS);
+        let thinking = provider.thinking_for("claude-opus-4").unwrap();
+        assert_eq!(thinking.budget_tokens, 16_000);
+
+        assert!(AnthropicProvider::new(Some("key"))
+            .thinking_for("claude-sonnet-4")
+            .is_none());
+    }
 }
@@ -371,7 +371,15 @@ struct InferenceConfig {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ToolConfig {
-    tools: Vec<ToolDefinition>,
+    tools: Vec<ToolEntry>,
+}
+
+/// Tool list entries: either `{"toolSpec": {...}}` or `{"cachePoint": {...}}`.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum ToolEntry {
+    Spec(ToolDefinition),
+    CachePoint(CachePointWrapper),
 }
 
 #[derive(Debug, Serialize)]
@@ -414,6 +422,32 @@ struct BedrockUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_write_input_tokens: Option<u64>,
+}
+
+impl BedrockUsage {
+    /// Converse reports cache reads/writes separately from `inputTokens`;
+    /// fold them back in so `input_tokens` is the full prompt size.
+    fn into_token_usage(self) -> TokenUsage {
+        let cached = self
+            .cache_read_input_tokens
+            .unwrap_or(0)
+            .saturating_add(self.cache_write_input_tokens.unwrap_or(0));
+        let input_tokens = match self.input_tokens {
+            Some(base) => Some(base.saturating_add(cached)),
+            None if cached > 0 => Some(cached),
+            None => None,
+        };
+        TokenUsage {
+            input_tokens,
+            output_tokens: self.output_tokens,
+            cache_read_tokens: self.cache_read_input_tokens,
+            cache_write_tokens: self.cache_write_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -521,6 +555,17 @@ impl BedrockProvider {
         messages.iter().filter(|m| m.role != "system").count() > 4
     }
 
+    /// Models that accept `cachePoint` blocks in system and messages.
+    /// Others (Llama, Mistral, Titan, ...) reject them with a ValidationException.
+    fn supports_prompt_caching(model: &str) -> bool {
+        model.contains("anthropic.claude") || model.contains("amazon.nova")
+    }
+
+    /// Models that also accept a `cachePoint` in the tool list (Nova does not).
+    fn supports_tool_caching(model: &str) -> bool {
+        model.contains("anthropic.claude")
+    }
+
     // ── Message conversion ──────────────────────────────────────
 
     fn convert_messages(
@@ -720,36 +765,96 @@ impl BedrockProvider {
 
     // ── Tool conversion ─────────────────────────────────────────
 
-    fn convert_tools_to_converse(tools: Option<&[ToolSpec]>) -> Option<ToolConfig> {
+    fn convert_tools_to_converse(
+        tools: Option<&[ToolSpec]>,
+        cache_tools: bool,
+    ) -> Option<ToolConfig> {
         let items = tools?;
         if items.is_empty() {
             return None;
         }
-        let tool_defs: Vec<ToolDefinition> = items
+        let mut tool_defs: Vec<ToolEntry> = items
             .iter()
-            .map(|tool| ToolDefinition {
-                tool_spec: ToolSpecDef {
-                    name: tool.name.clone(),
-                    description: tool.description.clone(),
-                    input_schema: InputSchema {
-                        json: tool.parameters.clone(),
+            .map(|tool| {
+                ToolEntry::Spec(ToolDefinition {
+                    tool_spec: ToolSpecDef {
+                        name: tool.name.clone(),
+                        description: tool.description.clone(),
+                        input_schema: InputSchema {
+                            json: tool.parameters.clone(),
+                        },
                     },
-                },
+                })
             })
             .collect();
+        // Tool definitions are identical across turns, so cache them.
+        if cache_tools {
+            tool_defs.push(ToolEntry::CachePoint(CachePointWrapper {
+                cache_point: CachePoint::default_cache(),
+            }));
+        }
         Some(ToolConfig { tools: tool_defs })
     }
 
+    // ── Request building ────────────────────────────────────────
+
+    /// Converse request for a chat turn, with cache points where `model` takes them.
+    fn build_converse_request(
+        request: &ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> ConverseRequest {
+        let (system_blocks, mut converse_messages) = Self::convert_messages(request.messages);
+
+        // Apply cachePoint to system if large, or when tools are cached too so
+        // the whole tools + system prefix can be reused.
+        let prompt_caching = Self::supports_prompt_caching(model);
+        let cache_tools = Self::supports_tool_caching(model)
+            && request.tools.is_some_and(|tools| !tools.is_empty());
+        let system = system_blocks.map(|mut blocks| {
+            let has_large_system = blocks
+                .iter()
+                .any(|b| matches!(b, SystemBlock::Text(tb) if Self::should_cache_system(&tb.text)));
+            if prompt_caching && (has_large_system || cache_tools) {
+                blocks.push(SystemBlock::CachePoint(CachePointWrapper {
+                    cache_point: CachePoint::default_cache(),
+                }));
+            }
+            blocks
+        });
+
+        // Apply cachePoint to last message if conversation is long.
+        if prompt_caching && Self::should_cache_conversation(request.messages) {
+            if let Some(last_msg) = converse_messages.last_mut() {
+                last_msg
+                    .content
+                    .push(ContentBlock::CachePointBlock(CachePointWrapper {
+                        cache_point: CachePoint::default_cache(),
+                    }));
+            }
+        }
+
+        let tool_config = Self::convert_tools_to_converse(request.tools, cache_tools);
+
+        ConverseRequest {
+            system,
+            messages: converse_messages,
+            inference_config: Some(InferenceConfig {
+                max_tokens: DEFAULT_MAX_TOKENS,
+                temperature,
+            }),
+            tool_config,
+        }
+    }
+
     // ── Response parsing ────────────────────────────────────────
 
     fn parse_converse_response(response: ConverseResponse) -> ProviderChatResponse {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
+        let mut reasoning_parts = Vec::new();
 
-        let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
-        });
+        let usage = response.usage.map(BedrockUsage::into_token_usage);
 
         if let Some(output) = response.output {
             if let Some(message) = output.message {
@@ -770,7 +875,14 @@ impl BedrockProvider {
                                 });
                             }
                         }
-                        ResponseContentBlock::Other(_) => {}
+                        ResponseContentBlock::Other(value) => {
+                            if let Some(text) = value
+                                .pointer("/reasoningContent/reasoningText/text")
+                                .and_then(serde_json::Value::as_str)
+                            {
+                                reasoning_parts.push(text.to_string());
+                            }
+                        }
                     }
                 }
             }
@@ -784,6 +896,9 @@ impl BedrockProvider {
             },
             tool_calls,
             usage,
+            reasoning: super::reasoning::merge_reasoning_text(
+                reasoning_parts.iter().map(String::as_str),
+            ),
         }
     }
 
@@ -883,6 +998,7 @@ impl Provider for BedrockProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: false,
         }
     }
 
@@ -919,7 +1035,7 @@ impl Provider for BedrockProvider {
             let mut blocks = vec![SystemBlock::Text(TextBlock {
                 text: text.to_string(),
             })];
-            if Self::should_cache_system(text) {
+            if Self::supports_prompt_caching(model) && Self::should_cache_system(text) {
                 blocks.push(SystemBlock::CachePoint(CachePointWrapper {
                     cache_point: CachePoint::default_cache(),
                 }));
@@ -957,43 +1073,7 @@ impl Provider for BedrockProvider {
     ) -> anyhow::Result<ProviderChatResponse> {
         let credentials = self.resolve_credentials().await?;
 
-        let (system_blocks, mut converse_messages) = Self::convert_messages(request.messages);
-
-        // Apply cachePoint to system if large.
-        let system = system_blocks.map(|mut blocks| {
-            let has_large_system = blocks
-                .iter()
-                .any(|b| matches!(b, SystemBlock::Text(tb) if Self::should_cache_system(&tb.text)));
-            if has_large_system {
-                blocks.push(SystemBlock::CachePoint(CachePointWrapper {
-                    cache_point: CachePoint::default_cache(),
-                }));
-            }
-            blocks
-        });
-
-        // Apply cachePoint to last message if conversation is long.
-        if Self::should_cache_conversation(request.messages) {
-            if let Some(last_msg) = converse_messages.last_mut() {
-                last_msg
-                    .content
-                    .push(ContentBlock::CachePointBlock(CachePointWrapper {
-                        cache_point: CachePoint::default_cache(),
-                    }));
-            }
-        }
-
-        let tool_config = Self::convert_tools_to_converse(request.tools);
-
-        let converse_request = ConverseRequest {
-            system,
-            messages: converse_messages,
-            inference_config: Some(InferenceConfig {
-                max_tokens: DEFAULT_MAX_TOKENS,
-                temperature,
-            }),
-            tool_config,
-        };
+        let converse_request = Self::build_converse_request(&request, model, temperature);
 
         let response = self
             .send_converse_request(&credentials, model, &converse_request)
@@ -1327,17 +1407,80 @@ mod tests {
             description: "Run commands".to_string(),
             parameters: serde_json::json!({"type": "object", "properties": {"command": {"type": "string"}}}),
         }];
-        let config = BedrockProvider::convert_tools_to_converse(Some(&tools));
+        let config = BedrockProvider::convert_tools_to_converse(Some(&tools), true);
         assert!(config.is_some());
         let config = config.unwrap();
-        assert_eq!(config.tools.len(), 1);
-        assert_eq!(config.tools[0].tool_spec.name, "shell");
+        assert_eq!(config.tools.len(), 2);
+        assert!(matches!(&config.tools[0], ToolEntry::Spec(def) if def.tool_spec.name == "shell"));
+        assert!(matches!(config.tools[1], ToolEntry::CachePoint(_)));
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["tools"][1]["cachePoint"]["type"], "default");
+
+        let uncached = BedrockProvider::convert_tools_to_converse(Some(&tools), false).unwrap();
+        assert_eq!(uncached.tools.len(), 1);
+    }
+
+    #[test]
+    fn cache_points_only_for_models_that_support_them() {
+        let claude = "us.anthropic.claude-3-5-sonnet-20241022-v2:0";
+        assert!(BedrockProvider::supports_prompt_caching(claude));
+        assert!(BedrockProvider::supports_tool_caching(claude));
+
+        let nova = "amazon.nova-pro-v1:0";
+        assert!(BedrockProvider::supports_prompt_caching(nova));
+        assert!(!BedrockProvider::supports_tool_caching(nova));
+
+        for model in [
+            "meta.llama3-1-70b-instruct-v1:0",
+            "mistral.mistral-large-2407-v1:0",
+            "amazon.titan-text-express-v1",
+        ] {
+            assert!(!BedrockProvider::supports_prompt_caching(model));
+            assert!(!BedrockProvider::supports_tool_caching(model));
+        }
+    }
+
+    #[test]
+    fn converse_request_omits_cache_points_for_uncached_models() {
+        let tools = vec![ToolSpec {
+            name: "shell".to_string(),
+            description: "Run commands".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let mut messages = vec![ChatMessage::system("a".repeat(4000))];
+        messages.extend((0..6).map(|i| ChatMessage::user(format!("turn {i}"))));
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+            response_schema: None,
+        };
+
+        let llama = BedrockProvider::build_converse_request(
+            &request,
+            "meta.llama3-1-70b-instruct-v1:0",
+            0.7,
+        );
+        let json = serde_json::to_string(&llama).unwrap();
+        assert!(!json.contains("cachePoint"));
+
+        let claude = BedrockProvider::build_converse_request(
+            &request,
+            "anthropic.claude-3-5-sonnet-20241022-v2:0",
+            0.7,
+        );
+        let json = serde_json::to_value(&claude).unwrap();
+        assert_eq!(
+            json["toolConfig"]["tools"][1]["cachePoint"]["type"],
+            "default"
+        );
+        assert_eq!(json["system"][1]["cachePoint"]["type"], "default");
     }
 
     #[test]
     fn convert_tools_to_converse_empty_returns_none() {
-        assert!(BedrockProvider::convert_tools_to_converse(Some(&[])).is_none());
-        assert!(BedrockProvider::convert_tools_to_converse(None).is_none());
+        assert!(BedrockProvider::convert_tools_to_converse(Some(&[]), true).is_none());
+        assert!(BedrockProvider::convert_tools_to_converse(None, true).is_none());
     }
 
     // ── Serde tests ─────────────────────────────────────────────
@@ -1487,6 +1630,26 @@ mod tests {
         assert_eq!(usage.output_tokens, Some(100));
     }
 
+    #[test]
+    fn converse_response_parses_cache_usage() {
+        let json = r#"{
+            "output": {"message": {"role": "assistant", "content": [{"text": {"text": "Hello"}}]}},
+            "usage": {
+                "inputTokens": 40,
+                "outputTokens": 12,
+                "cacheReadInputTokens": 3000,
+                "cacheWriteInputTokens": 0
+            }
+        }"#;
+        let resp: ConverseResponse = serde_json::from_str(json).unwrap();
+        let result = BedrockProvider::parse_converse_response(resp);
+        let usage = result.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(3040));
+        assert_eq!(usage.cache_read_tokens, Some(3000));
+        assert_eq!(usage.cache_write_tokens, Some(0));
+        assert_eq!(usage.uncached_input_tokens(), Some(40));
+    }
+
     #[test]
     fn converse_response_parses_without_usage() {
         let json = r#"{"output": {"message": {"role": "assistant", "content": []}}}"#;
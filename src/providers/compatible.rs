@@ -5,7 +5,7 @@
 use crate::multimodal;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
+    Provider, ReasoningContent, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
     ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
@@ -352,6 +352,17 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+    /// DeepSeek-style cache hit counter (reported instead of `prompt_tokens_details`).
+    #[serde(default)]
+    prompt_cache_hit_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -364,23 +375,7 @@ struct Choice {
 /// in the `content` field rather than a separate `reasoning_content` field.
 /// The resulting `<think>` tags must be stripped before returning to the user.
 fn strip_think_tags(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut rest = s;
-    loop {
-        if let Some(start) = rest.find("<think>") {
-            result.push_str(&rest[..start]);
-            if let Some(end) = rest[start..].find("</think>") {
-                rest = &rest[start + end + "</think>".len()..];
-            } else {
-                // Unclosed tag: drop the rest to avoid leaking partial reasoning.
-                break;
-            }
-        } else {
-            result.push_str(rest);
-            break;
-        }
-    }
-    result.trim().to_string()
+    super::reasoning::split_think_tags(s).0
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -429,6 +424,22 @@ impl ResponseMessage {
             .map(|c| strip_think_tags(c))
             .filter(|c| !c.is_empty())
     }
+
+    /// Reasoning kept out of the reply: inline `<think>` blocks, plus
+    /// `reasoning_content` when it did not already serve as the reply.
+    fn reasoning(&self) -> Option<ReasoningContent> {
+        let (visible, inline) = self
+            .content
+            .as_deref()
+            .map(super::reasoning::split_think_tags)
+            .unwrap_or_default();
+        let separate = if visible.is_empty() {
+            None
+        } else {
+            self.reasoning_content.as_deref()
+        };
+        super::reasoning::merge_reasoning_text(separate.into_iter().chain([inline.as_str()]))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1006,6 +1017,7 @@ impl OpenAiCompatibleProvider {
 
     fn parse_native_response(message: ResponseMessage) -> ProviderChatResponse {
         let text = message.effective_content_optional();
+        let reasoning = message.reasoning();
         let tool_calls = message
             .tool_calls
             .unwrap_or_default()
@@ -1036,6 +1048,7 @@ impl OpenAiCompatibleProvider {
             text,
             tool_calls,
             usage: None,
+            reasoning,
         }
     }
 
@@ -1067,6 +1080,7 @@ impl Provider for OpenAiCompatibleProvider {
         crate::providers::traits::ProviderCapabilities {
             native_tool_calling: true,
             vision: self.supports_vision,
+            structured_output: false,
         }
     }
 
@@ -1366,6 +1380,7 @@ impl Provider for OpenAiCompatibleProvider {
                     text: Some(text),
                     tool_calls: vec![],
                     usage: None,
+                    reasoning: None,
                 });
             }
         };
@@ -1379,6 +1394,11 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = chat_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u
+                .prompt_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .or(u.prompt_cache_hit_tokens),
+            cache_write_tokens: None,
         });
         let choice = chat_response
             .choices
@@ -1387,6 +1407,7 @@ impl Provider for OpenAiCompatibleProvider {
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
 
         let text = choice.message.effective_content_optional();
+        let reasoning = choice.message.reasoning();
         let tool_calls = choice
             .message
             .tool_calls
@@ -1408,6 +1429,7 @@ impl Provider for OpenAiCompatibleProvider {
             text,
             tool_calls,
             usage,
+            reasoning,
         })
     }
 
@@ -1459,6 +1481,7 @@ impl Provider for OpenAiCompatibleProvider {
                             text: Some(text),
                             tool_calls: vec![],
                             usage: None,
+                            reasoning: None,
                         })
                         .map_err(|responses_err| {
                             anyhow::anyhow!(
@@ -1487,6 +1510,7 @@ impl Provider for OpenAiCompatibleProvider {
                     text: Some(text),
                     tool_calls: vec![],
                     usage: None,
+                    reasoning: None,
                 });
             }
 
@@ -1498,6 +1522,7 @@ impl Provider for OpenAiCompatibleProvider {
                         text: Some(text),
                         tool_calls: vec![],
                         usage: None,
+                        reasoning: None,
                     })
                     .map_err(|responses_err| {
                         anyhow::anyhow!(
@@ -1514,6 +1539,11 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u
+                .prompt_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .or(u.prompt_cache_hit_tokens),
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -2551,6 +2581,30 @@ mod tests {
         assert_eq!(msg.effective_content(), "Normal response");
     }
 
+    #[test]
+    fn native_response_moves_think_tags_and_reasoning_content_to_reasoning() {
+        let json = r#"{"choices":[{"message":{"content":"<think>inline plan</think>Answer","reasoning_content":"Separate plan"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let message = resp.choices.into_iter().next().unwrap().message;
+        let result = OpenAiCompatibleProvider::parse_native_response(message);
+        assert_eq!(result.text.as_deref(), Some("Answer"));
+        assert_eq!(
+            result.reasoning.unwrap().text,
+            "Separate plan\n\ninline plan"
+        );
+    }
+
+    #[test]
+    fn native_response_reasoning_content_fallback_is_not_duplicated() {
+        let json =
+            r#"{"choices":[{"message":{"content":"","reasoning_content":"Only reasoning"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let message = resp.choices.into_iter().next().unwrap().message;
+        let result = OpenAiCompatibleProvider::parse_native_response(message);
+        assert_eq!(result.text.as_deref(), Some("Only reasoning"));
+        assert!(result.reasoning.is_none());
+    }
+
     #[test]
     fn reasoning_content_used_when_content_only_think_tags() {
         let json = r#"{"choices":[{"message":{"content":"<think>secret</think>","reasoning_content":"Fallback text"}}]}"#;
@@ -2634,6 +2688,18 @@ mod tests {
         assert_eq!(usage.completion_tokens, Some(60));
     }
 
+    #[test]
+    fn api_response_parses_deepseek_cache_hits() {
+        let json = r#"{
+            "choices": [{"message": {"content": "Hello"}}],
+            "usage": {"prompt_tokens": 900, "completion_tokens": 5, "prompt_cache_hit_tokens": 768}
+        }"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.prompt_cache_hit_tokens, Some(768));
+        assert!(usage.prompt_tokens_details.is_none());
+    }
+
     #[test]
     fn api_response_parses_without_usage() {
         let json = r#"{"choices": [{"message": {"content": "Hello"}}]}"#;
@@ -353,6 +353,8 @@ impl CopilotProvider {
         let usage = api_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let choice = api_response
             .choices
@@ -378,6 +380,7 @@ impl CopilotProvider {
             text: choice.message.content,
             tool_calls,
             usage,
+            reasoning: None,
         })
     }
 
@@ -5,7 +5,11 @@
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, ChatResponse, Provider, TokenUsage};
+use crate::providers::reasoning::ReasoningSettings;
+use crate::providers::traits::{
+    ChatMessage, ChatResponse, Provider, ReasoningContent, ResponseSchema, TokenUsage,
+};
+use crate::tools::schema::SchemaCleanr;
 use async_trait::async_trait;
 use directories::UserDirs;
 use reqwest::Client;
@@ -23,6 +27,8 @@ pub struct GeminiProvider {
     auth_service: Option<AuthService>,
     /// Override profile name for managed auth.
     auth_profile_override: Option<String>,
+    /// Thinking budget settings, resolved per model.
+    reasoning: ReasoningSettings,
 }
 
 /// Mutable OAuth token state — supports runtime refresh for long-lived processes.
@@ -143,6 +149,20 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+    #[serde(rename = "thinkingConfig", skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<ThinkingConfig>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct ThinkingConfig {
+    #[serde(rename = "thinkingBudget", skip_serializing_if = "Option::is_none")]
+    thinking_budget: Option<u32>,
+    #[serde(rename = "includeThoughts")]
+    include_thoughts: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -161,6 +181,8 @@ struct GeminiUsageMetadata {
     prompt_token_count: Option<u64>,
     #[serde(default, rename = "candidatesTokenCount")]
     candidates_token_count: Option<u64>,
+    #[serde(default, rename = "cachedContentTokenCount")]
+    cached_content_token_count: Option<u64>,
 }
 
 /// Response envelope for the internal cloudcode-pa API.
@@ -201,26 +223,34 @@ impl CandidateContent {
     /// Returns the non-thinking text, falling back to thinking text only when
     /// no non-thinking content is available.
     fn effective_text(self) -> Option<String> {
+        self.into_text_and_reasoning().0
+    }
+
+    /// Like [`Self::effective_text`], but also returns the thinking parts as
+    /// reasoning when a real answer is present.
+    fn into_text_and_reasoning(self) -> (Option<String>, Option<ReasoningContent>) {
         let mut answer_parts: Vec<String> = Vec::new();
-        let mut first_thinking: Option<String> = None;
+        let mut thinking_parts: Vec<String> = Vec::new();
 
         for part in self.parts {
             if let Some(text) = part.text {
                 if text.is_empty() {
                     continue;
                 }
-                if !part.thought {
+                if part.thought {
+                    thinking_parts.push(text);
+                } else {
                     answer_parts.push(text);
-                } else if first_thinking.is_none() {
-                    first_thinking = Some(text);
                 }
             }
         }
 
         if answer_parts.is_empty() {
-            first_thinking
+            (thinking_parts.into_iter().next(), None)
         } else {
-            Some(answer_parts.join(""))
+            let reasoning =
+                super::reasoning::merge_reasoning_text(thinking_parts.iter().map(String::as_str));
+            (Some(answer_parts.join("")), reasoning)
         }
     }
 }
@@ -408,6 +438,7 @@ impl GeminiProvider {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            reasoning: ReasoningSettings::default(),
         }
     }
 
@@ -481,6 +512,7 @@ impl GeminiProvider {
                 None
             },
             auth_profile_override: profile_override,
+            reasoning: ReasoningSettings::default(),
         }
     }
 
@@ -883,13 +915,35 @@ impl GeminiProvider {
 }
 
 impl GeminiProvider {
+    /// Apply configured reasoning settings (thinking budget per model).
+    pub fn with_reasoning(mut self, reasoning: ReasoningSettings) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
+    fn thinking_config_for(&self, model: &str) -> Option<ThinkingConfig> {
+        let resolved = self.reasoning.resolve(model);
+        match (resolved.enabled, resolved.budget_tokens) {
+            (Some(false), _) => Some(ThinkingConfig {
+                thinking_budget: Some(0),
+                include_thoughts: false,
+            }),
+            (Some(true), budget) | (None, budget @ Some(_)) => Some(ThinkingConfig {
+                thinking_budget: budget,
+                include_thoughts: true,
+            }),
+            (None, None) => None,
+        }
+    }
+
     async fn send_generate_content(
         &self,
         contents: Vec<Content>,
         system_instruction: Option<Content>,
         model: &str,
         temperature: f64,
-    ) -> anyhow::Result<(String, Option<TokenUsage>)> {
+        response_schema: Option<&ResponseSchema>,
+    ) -> anyhow::Result<(String, Option<TokenUsage>, Option<ReasoningContent>)> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Gemini API key not found. Options:\n\
@@ -938,6 +992,10 @@ impl GeminiProvider {
             generation_config: GenerationConfig {
                 temperature,
                 max_output_tokens: 8192,
+                response_mime_type: response_schema.map(|_| "application/json".to_string()),
+                response_schema: response_schema
+                    .map(|schema| SchemaCleanr::clean_for_gemini(schema.schema.clone())),
+                thinking_config: self.thinking_config_for(model),
             },
         };
 
@@ -1080,21 +1138,28 @@ impl GeminiProvider {
         let usage = result.usage_metadata.map(|u| TokenUsage {
             input_tokens: u.prompt_token_count,
             output_tokens: u.candidates_token_count,
+            cache_read_tokens: u.cached_content_token_count,
+            cache_write_tokens: None,
         });
 
-        let text = result
+        let (text, reasoning) = result
             .candidates
             .and_then(|c| c.into_iter().next())
             .and_then(|c| c.content)
-            .and_then(|c| c.effective_text())
-            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))?;
+            .map(CandidateContent::into_text_and_reasoning)
+            .unwrap_or_default();
+        let text = text.ok_or_else(|| anyhow::anyhow!("No response from Gemini"))?;
 
-        Ok((text, usage))
+        Ok((text, usage, reasoning))
     }
 }
 
 #[async_trait]
 impl Provider for GeminiProvider {
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -1116,8 +1181,8 @@ impl Provider for GeminiProvider {
             }],
         }];
 
-        let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+        let (text, _usage, _reasoning) = self
+            .send_generate_content(contents, system_instruction, model, temperature, None)
             .await?;
         Ok(text)
     }
@@ -1168,8 +1233,8 @@ impl Provider for GeminiProvider {
             })
         };
 
-        let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+        let (text, _usage, _reasoning) = self
+            .send_generate_content(contents, system_instruction, model, temperature, None)
             .await?;
         Ok(text)
     }
@@ -1213,14 +1278,21 @@ impl Provider for GeminiProvider {
             })
         };
 
-        let (text, usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+        let (text, usage, reasoning) = self
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                temperature,
+                request.response_schema,
+            )
             .await?;
 
         Ok(ChatResponse {
             text: Some(text),
             tool_calls: Vec::new(),
             usage,
+            reasoning,
         })
     }
 
@@ -1275,6 +1347,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            reasoning: ReasoningSettings::default(),
         }
     }
 
@@ -1399,6 +1472,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -1440,6 +1516,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -1484,6 +1563,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -1521,6 +1603,9 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config: None,
             },
         };
 
@@ -1531,6 +1616,33 @@ mod tests {
         assert!(!json.contains("\"system_instruction\""));
         assert!(json.contains("\"temperature\":0.7"));
         assert!(json.contains("\"maxOutputTokens\":8192"));
+        assert!(!json.contains("responseSchema"));
+    }
+
+    #[test]
+    fn generation_config_serializes_response_schema() {
+        let config = GenerationConfig {
+            temperature: 0.0,
+            max_output_tokens: 1024,
+            response_mime_type: Some("application/json".to_string()),
+            response_schema: Some(SchemaCleanr::clean_for_gemini(serde_json::json!({
+                "type": "object",
+                "properties": {"name": {"type": "string", "minLength": 1}},
+                "additionalProperties": false
+            }))),
+            thinking_config: None,
+        };
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["responseMimeType"], "application/json");
+        assert_eq!(
+            json["responseSchema"]["properties"]["name"]["type"],
+            "string"
+        );
+        assert!(json["responseSchema"].get("additionalProperties").is_none());
+        assert!(json["responseSchema"]["properties"]["name"]
+            .get("minLength")
+            .is_none());
     }
 
     #[test]
@@ -1550,6 +1662,9 @@ mod tests {
                 generation_config: Some(GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
+                    response_mime_type: None,
+                    response_schema: None,
+                    thinking_config: None,
                 }),
             },
         };
@@ -1941,4 +2056,42 @@ mod tests {
         let resp: GenerateContentResponse = serde_json::from_str(json).unwrap();
         assert!(resp.usage_metadata.is_none());
     }
+
+    #[test]
+    fn thought_parts_become_reasoning_when_answer_present() {
+        let json = r#"{
+            "candidates": [{"content": {"parts": [
+                {"thought": true, "text": "Consider the options."},
+                {"text": "Pick B."}
+            ]}}]
+        }"#;
+        let resp: GenerateContentResponse = serde_json::from_str(json).unwrap();
+        let content = resp.candidates.unwrap().remove(0).content.unwrap();
+        let (text, reasoning) = content.into_text_and_reasoning();
+        assert_eq!(text.as_deref(), Some("Pick B."));
+        assert_eq!(reasoning.unwrap().text, "Consider the options.");
+    }
+
+    #[test]
+    fn thinking_config_follows_reasoning_settings() {
+        let provider = test_provider(None).with_reasoning(ReasoningSettings {
+            budget_tokens: Some(2048),
+            ..ReasoningSettings::default()
+        });
+        let config = provider.thinking_config_for("gemini-2.5-pro").unwrap();
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["thinkingBudget"], 2048);
+        assert_eq!(json["includeThoughts"], true);
+
+        let disabled = test_provider(None).with_reasoning(ReasoningSettings {
+            enabled: Some(false),
+            ..ReasoningSettings::default()
+        });
+        let config = disabled.thinking_config_for("gemini-2.5-flash").unwrap();
+        assert_eq!(config.thinking_budget, Some(0));
+
+        assert!(test_provider(None)
+            .thinking_config_for("gemini-2.5-pro")
+            .is_none());
+    }
 }
@@ -25,15 +25,17 @@ pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
+pub mod reasoning;
 pub mod reliable;
 pub mod router;
+pub mod structured;
 pub mod telnyx;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ReasoningContent, ResponseSchema, ToolCall, ToolResultMessage,
 };
 
 use crate::auth::AuthService;
@@ -674,7 +676,7 @@ pub struct ProviderRuntimeOptions {
     pub auth_profile_override: Option<String>,
     pub zeroclaw_dir: Option<PathBuf>,
     pub secrets_encrypt: bool,
-    pub reasoning_enabled: Option<bool>,
+    pub reasoning: reasoning::ReasoningSettings,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -683,7 +685,7 @@ impl Default for ProviderRuntimeOptions {
             auth_profile_override: None,
             zeroclaw_dir: None,
             secrets_encrypt: true,
-            reasoning_enabled: None,
+            reasoning: reasoning::ReasoningSettings::default(),
         }
     }
 }
@@ -786,7 +788,10 @@ pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::E
 /// For MiniMax, OAuth mode supports `api_key = "minimax-oauth"`, resolving credentials from
 /// `MINIMAX_OAUTH_TOKEN` first, then `MINIMAX_API_KEY`, and finally
 /// `MINIMAX_OAUTH_REFRESH_TOKEN` (automatic access-token refresh).
-fn resolve_provider_credential(name: &str, credential_override: Option<&str>) -> Option<String> {
+pub(crate) fn resolve_provider_credential(
+    name: &str,
+    credential_override: Option<&str>,
+) -> Option<String> {
     let mut minimax_oauth_placeholder_requested = false;
 
     if let Some(raw_override) = credential_override {
@@ -953,15 +958,20 @@ fn create_provider_with_url_and_options(
     let key = resolved_credential.as_ref().map(String::as_str);
     match name {
         // ── Primary providers (custom implementations) ───────
-        "openrouter" => Ok(Box::new(openrouter::OpenRouterProvider::new(key))),
-        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
-        "openai" => Ok(Box::new(openai::OpenAiProvider::with_base_url(api_url, key))),
+        "openrouter" => Ok(Box::new(
+            openrouter::OpenRouterProvider::new(key).with_reasoning(options.reasoning.clone()),
+        )),
+        "anthropic" => Ok(Box::new(
+            anthropic::AnthropicProvider::new(key).with_reasoning(options.reasoning.clone()),
+        )),
+        "openai" => Ok(Box::new(
+            openai::OpenAiProvider::with_base_url(api_url, key)
+                .with_reasoning(options.reasoning.clone()),
+        )),
         // Ollama uses api_url for custom base URL (e.g. remote Ollama instance)
-        "ollama" => Ok(Box::new(ollama::OllamaProvider::new_with_reasoning(
-            api_url,
-            key,
-            options.reasoning_enabled,
-        ))),
+        "ollama" => Ok(Box::new(
+            ollama::OllamaProvider::new(api_url, key).with_reasoning(options.reasoning.clone()),
+        )),
         "gemini" | "google" | "google-gemini" => {
             let state_dir = options
                 .zeroclaw_dir
@@ -973,11 +983,14 @@ fn create_provider_with_url_and_options(
                     )
                 });
             let auth_service = AuthService::new(&state_dir, options.secrets_encrypt);
-            Ok(Box::new(gemini::GeminiProvider::new_with_auth(
-                key,
-                auth_service,
-                options.auth_profile_override.clone(),
-            )))
+            Ok(Box::new(
+                gemini::GeminiProvider::new_with_auth(
+                    key,
+                    auth_service,
+                    options.auth_profile_override.clone(),
+                )
+                .with_reasoning(options.reasoning.clone()),
+            ))
         }
         "telnyx" => Ok(Box::new(telnyx::TelnyxProvider::new(key))),
 
@@ -1214,10 +1227,10 @@ fn create_provider_with_url_and_options(
                 "Anthropic-custom provider",
                 "anthropic-custom:https://your-api.com",
             )?;
-            Ok(Box::new(anthropic::AnthropicProvider::with_base_url(
-                key,
-                Some(&base_url),
-            )))
+            Ok(Box::new(
+                anthropic::AnthropicProvider::with_base_url(key, Some(&base_url))
+                    .with_reasoning(options.reasoning.clone()),
+            ))
         }
 
         _ => anyhow::bail!(
@@ -1,6 +1,8 @@
 use crate::multimodal;
+use crate::providers::reasoning::{self, ReasoningSettings};
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    ChatMessage, ChatResponse, Provider, ProviderCapabilities, ReasoningContent, TokenUsage,
+    ToolCall,
 };
 use async_trait::async_trait;
 use reqwest::Client;
@@ -10,7 +12,7 @@ use std::collections::HashMap;
 pub struct OllamaProvider {
     base_url: String,
     api_key: Option<String>,
-    reasoning_enabled: Option<bool>,
+    reasoning: ReasoningSettings,
 }
 
 // ─── Request Structures ───────────────────────────────────────────────────────
@@ -25,6 +27,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// JSON schema constraining the reply (structured output).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -126,10 +131,19 @@ impl OllamaProvider {
         Self {
             base_url: Self::normalize_base_url(base_url.unwrap_or("http://localhost:11434")),
             api_key,
-            reasoning_enabled,
+            reasoning: ReasoningSettings {
+                enabled: reasoning_enabled,
+                ..ReasoningSettings::default()
+            },
         }
     }
 
+    /// Apply configured reasoning settings (`think` is resolved per model).
+    pub fn with_reasoning(mut self, reasoning: ReasoningSettings) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
     fn is_local_endpoint(&self) -> bool {
         reqwest::Url::parse(&self.base_url)
             .ok()
@@ -168,20 +182,107 @@ impl OllamaProvider {
         serde_json::from_str(arguments).unwrap_or_else(|_| serde_json::json!({}))
     }
 
+    /// Split the reply into visible text and reasoning: the `thinking` field
+    /// plus any inline `<think>` blocks (e.g. deepseek-r1 without `think`).
+    fn split_reasoning(message: &ResponseMessage) -> (String, Option<ReasoningContent>) {
+        let (visible, inline) = reasoning::split_think_tags(&message.content);
+        let reasoning = reasoning::merge_reasoning_text(
+            message
+                .thinking
+                .as_deref()
+                .into_iter()
+                .chain(std::iter::once(inline.as_str())),
+        );
+        (visible, reasoning)
+    }
+
+    /// Reply used when the model stopped after reasoning without producing output.
+    fn incomplete_reasoning_reply(thinking: &str) -> String {
+        tracing::warn!(
+            "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
+            thinking.chars().take(100).collect::<String>()
+        );
+        format!(
+            "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
+            thinking.chars().take(200).collect::<String>()
+        )
+    }
+
+    fn parse_chat_response(&self, response: ApiChatResponse) -> ChatResponse {
+        let usage =
+            (response.prompt_eval_count.is_some() || response.eval_count.is_some()).then(|| {
+                TokenUsage {
+                    input_tokens: response.prompt_eval_count,
+                    output_tokens: response.eval_count,
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                }
+            });
+        let (content, reasoning) = Self::split_reasoning(&response.message);
+
+        // Native tool calls returned by the model.
+        if !response.message.tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = response
+                .message
+                .tool_calls
+                .iter()
+                .map(|tc| {
+                    let (name, args) = self.extract_tool_name_and_args(tc);
+                    ToolCall {
+                        id: tc
+                            .id
+                            .clone()
+                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                        name,
+                        arguments: serde_json::to_string(&args)
+                            .unwrap_or_else(|_| "{}".to_string()),
+                    }
+                })
+                .collect();
+            return ChatResponse {
+                text: (!content.is_empty()).then_some(content),
+                tool_calls,
+                usage,
+                reasoning,
+            };
+        }
+
+        // Plain text response.
+        if content.is_empty() {
+            if let Some(reasoning) = reasoning {
+                return ChatResponse {
+                    text: Some(Self::incomplete_reasoning_reply(&reasoning.text)),
+                    tool_calls: vec![],
+                    usage,
+                    reasoning: Some(reasoning),
+                };
+            }
+            tracing::warn!("Ollama returned empty content with no tool calls");
+        }
+        ChatResponse {
+            text: Some(content),
+            tool_calls: vec![],
+            usage,
+            reasoning,
+        }
+    }
+
     fn build_chat_request(
         &self,
         messages: Vec<Message>,
         model: &str,
         temperature: f64,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
     ) -> ChatRequest {
         ChatRequest {
             model: model.to_string(),
             messages,
             stream: false,
             options: Options { temperature },
-            think: self.reasoning_enabled,
+            think: self.reasoning.resolve(model).enabled,
             tools: tools.map(|t| t.to_vec()),
+            format: format.cloned(),
         }
     }
 
@@ -321,8 +422,9 @@ impl OllamaProvider {
         temperature: f64,
         should_auth: bool,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
+        let request = self.build_chat_request(messages, model, temperature, tools, format);
 
         let url = format!("{}/api/chat", self.base_url);
 
@@ -460,6 +562,7 @@ impl Provider for OllamaProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -494,7 +597,14 @@ impl Provider for OllamaProvider {
         });
 
         let response = self
-            .send_request(messages, &normalized_model, temperature, should_auth, None)
+            .send_request(
+                messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                None,
+                None,
+            )
             .await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
@@ -506,20 +616,12 @@ impl Provider for OllamaProvider {
             return Ok(self.format_tool_calls_for_loop(&response.message.tool_calls));
         }
 
-        // Plain text response
-        let content = response.message.content;
+        let (content, reasoning) = Self::split_reasoning(&response.message);
 
-        // Handle edge case: model returned only "thinking" with no content or tool calls
+        // Handle edge case: model returned only reasoning with no content or tool calls
         if content.is_empty() {
-            if let Some(thinking) = &response.message.thinking {
-                tracing::warn!(
-                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
-                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
-                );
-                return Ok(format!(
-                    "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
-                    if thinking.len() > 200 { &thinking[..200] } else { thinking }
-                ));
+            if let Some(reasoning) = reasoning {
+                return Ok(Self::incomplete_reasoning_reply(&reasoning.text));
             }
             tracing::warn!("Ollama returned empty content with no tool calls");
         }
@@ -544,6 +646,7 @@ impl Provider for OllamaProvider {
                 temperature,
                 should_auth,
                 None,
+                None,
             )
             .await?;
 
@@ -556,22 +659,12 @@ impl Provider for OllamaProvider {
             return Ok(self.format_tool_calls_for_loop(&response.message.tool_calls));
         }
 
-        // Plain text response
-        let content = response.message.content;
+        let (content, reasoning) = Self::split_reasoning(&response.message);
 
-        // Handle edge case: model returned only "thinking" with no content or tool calls
-        // This is a model quirk - it stopped after reasoning without producing output
+        // Handle edge case: model returned only reasoning with no content or tool calls
         if content.is_empty() {
-            if let Some(thinking) = &response.message.thinking {
-                tracing::warn!(
-                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
-                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
-                );
-                // Return a message indicating the model's thought process but no action
-                return Ok(format!(
-                    "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
-                    if thinking.len() > 200 { &thinking[..200] } else { thinking }
-                ));
+            if let Some(reasoning) = reasoning {
+                return Ok(Self::incomplete_reasoning_reply(&reasoning.text));
             }
             tracing::warn!("Ollama returned empty content with no tool calls");
         }
@@ -601,73 +694,11 @@ impl Provider for OllamaProvider {
                 temperature,
                 should_auth,
                 tools_opt,
+                None,
             )
             .await?;
 
-        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
-            Some(TokenUsage {
-                input_tokens: response.prompt_eval_count,
-                output_tokens: response.eval_count,
-            })
-        } else {
-            None
-        };
-
-        // Native tool calls returned by the model.
-        if !response.message.tool_calls.is_empty() {
-            let tool_calls: Vec<ToolCall> = response
-                .message
-                .tool_calls
-                .iter()
-                .map(|tc| {
-                    let (name, args) = self.extract_tool_name_and_args(tc);
-                    ToolCall {
-                        id: tc
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                        name,
-                        arguments: serde_json::to_string(&args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                    }
-                })
-                .collect();
-            let text = if response.message.content.is_empty() {
-                None
-            } else {
-                Some(response.message.content)
-            };
-            return Ok(ChatResponse {
-                text,
-                tool_calls,
-                usage,
-            });
-        }
-
-        // Plain text response.
-        let content = response.message.content;
-        if content.is_empty() {
-            if let Some(thinking) = &response.message.thinking {
-                tracing::warn!(
-                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
-                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
-                );
-                return Ok(ChatResponse {
-                    text: Some(format!(
-                        "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
-                        if thinking.len() > 200 { &thinking[..200] } else { thinking }
-                    )),
-                    tool_calls: vec![],
-                    usage,
-                });
-            }
-            tracing::warn!("Ollama returned empty content with no tool calls");
-        }
-        Ok(ChatResponse {
-            text: Some(content),
-            tool_calls: vec![],
-            usage,
-        })
+        Ok(self.parse_chat_response(response))
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -705,15 +736,35 @@ impl Provider for OllamaProvider {
             }
         }
 
-        // No tools — fall back to plain text chat.
-        let text = self
-            .chat_with_history(request.messages, model, temperature)
+        // Structured output — pass the schema through Ollama's `format` field.
+        if let Some(schema) = request.response_schema {
+            let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+            let response = self
+                .send_request(
+                    self.convert_messages(request.messages),
+                    &normalized_model,
+                    temperature,
+                    should_auth,
+                    None,
+                    Some(&schema.schema),
+                )
+                .await?;
+            return Ok(self.parse_chat_response(response));
+        }
+
+        // No tools — plain chat, keeping reasoning separate from the reply.
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+        let response = self
+            .send_request(
+                self.convert_messages(request.messages),
+                &normalized_model,
+                temperature,
+                should_auth,
+                None,
+                None,
+            )
             .await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: vec![],
-            usage: None,
-        })
+        Ok(self.parse_chat_response(response))
     }
 }
 
@@ -819,6 +870,7 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
@@ -839,12 +891,35 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
         assert_eq!(json.get("think"), Some(&serde_json::json!(false)));
     }
 
+    #[test]
+    fn request_includes_format_for_structured_output() {
+        let provider = OllamaProvider::new(None, None);
+        let schema = serde_json::json!({"type": "object", "required": ["ok"]});
+        let request = provider.build_chat_request(
+            vec![Message {
+                role: "user".to_string(),
+                content: Some("hello".to_string()),
+                images: None,
+                tool_calls: None,
+                tool_name: None,
+            }],
+            "llama3",
+            0.0,
+            None,
+            Some(&schema),
+        );
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json.get("format"), Some(&schema));
+    }
+
     #[test]
     fn response_deserializes() {
         let json = r#"{"message":{"role":"assistant","content":"Hello from Ollama!"}}"#;
@@ -1050,4 +1125,45 @@ mod tests {
         assert!(resp.prompt_eval_count.is_none());
         assert!(resp.eval_count.is_none());
     }
+
+    #[test]
+    fn parse_chat_response_strips_inline_think_tags() {
+        let p = OllamaProvider::new(None, None);
+        let json = r#"{"message":{"role":"assistant","content":"<think>plan it</think>\nHello!"}}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let result = p.parse_chat_response(resp);
+        assert_eq!(result.text.as_deref(), Some("Hello!"));
+        assert_eq!(result.reasoning.unwrap().text, "plan it");
+    }
+
+    #[test]
+    fn parse_chat_response_keeps_thinking_field_as_reasoning() {
+        let p = OllamaProvider::new(None, None);
+        let json =
+            r#"{"message":{"role":"assistant","content":"hello","thinking":"internal reasoning"}}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let result = p.parse_chat_response(resp);
+        assert_eq!(result.text.as_deref(), Some("hello"));
+        assert_eq!(result.reasoning.unwrap().text, "internal reasoning");
+    }
+
+    #[test]
+    fn request_think_resolves_per_model() {
+        let mut settings = ReasoningSettings {
+            enabled: Some(true),
+            ..ReasoningSettings::default()
+        };
+        settings.models.insert(
+            "llama3".into(),
+            crate::config::ModelReasoningConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        );
+        let p = OllamaProvider::new(None, None).with_reasoning(settings);
+        let req = p.build_chat_request(vec![], "qwen3", 0.7, None, None);
+        assert_eq!(req.think, Some(true));
+        let req = p.build_chat_request(vec![], "llama3", 0.7, None, None);
+        assert_eq!(req.think, Some(false));
+    }
 }
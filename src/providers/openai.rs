@@ -1,6 +1,7 @@
+use crate::providers::reasoning::ReasoningSettings;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, ReasoningContent, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize};
 pub struct OpenAiProvider {
     base_url: String,
     credential: Option<String>,
+    reasoning: ReasoningSettings,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +64,11 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    /// Reasoning effort for o-series / reasoning models (`low` | `medium` | `high`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -131,6 +138,14 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -156,6 +171,15 @@ impl NativeResponseMessage {
             _ => self.reasoning_content.clone(),
         }
     }
+
+    /// Reasoning reported alongside a non-empty reply (otherwise it already
+    /// served as the reply via `effective_content`).
+    fn reasoning(&self) -> Option<ReasoningContent> {
+        match (&self.content, &self.reasoning_content) {
+            (Some(c), Some(r)) if !c.is_empty() => ReasoningContent::from_text(r).into_option(),
+            _ => None,
+        }
+    }
 }
 
 impl OpenAiProvider {
@@ -171,9 +195,16 @@ impl OpenAiProvider {
                 .map(|u| u.trim_end_matches('/').to_string())
                 .unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
             credential: credential.map(ToString::to_string),
+            reasoning: ReasoningSettings::default(),
         }
     }
 
+    /// Apply configured reasoning settings (`reasoning_effort` per model).
+    pub fn with_reasoning(mut self, reasoning: ReasoningSettings) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         tools.map(|items| {
             items
@@ -259,6 +290,7 @@ impl OpenAiProvider {
 
     fn parse_native_response(message: NativeResponseMessage) -> ProviderChatResponse {
         let text = message.effective_content();
+        let reasoning = message.reasoning();
         let tool_calls = message
             .tool_calls
             .unwrap_or_default()
@@ -274,6 +306,7 @@ impl OpenAiProvider {
             text,
             tool_calls,
             usage: None,
+            reasoning,
         }
     }
 
@@ -354,6 +387,10 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request
+                .response_schema
+                .map(super::structured::openai_response_format),
+            reasoning_effort: self.reasoning.resolve(model).effort,
         };
 
         let response = self
@@ -372,6 +409,8 @@ impl Provider for OpenAiProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -388,6 +427,10 @@ impl Provider for OpenAiProvider {
         true
     }
 
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -417,6 +460,8 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            reasoning_effort: self.reasoning.resolve(model).effort,
         };
 
         let response = self
@@ -435,6 +480,8 @@ impl Provider for OpenAiProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -713,10 +760,68 @@ mod tests {
         assert_eq!(usage.completion_tokens, Some(50));
     }
 
+    #[test]
+    fn native_response_parses_cached_prompt_tokens() {
+        let json = r#"{
+            "choices": [{"message": {"content": "Hello"}}],
+            "usage": {
+                "prompt_tokens": 2048,
+                "completion_tokens": 12,
+                "prompt_tokens_details": {"cached_tokens": 1920}
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage.unwrap();
+        assert_eq!(
+            usage.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            Some(1920)
+        );
+    }
+
     #[test]
     fn native_response_parses_without_usage() {
         let json = r#"{"choices": [{"message": {"content": "Hello"}}]}"#;
         let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
         assert!(resp.usage.is_none());
     }
+
+    #[test]
+    fn native_response_keeps_reasoning_separate_from_answer() {
+        let json =
+            r#"{"choices":[{"message":{"content":"Real answer","reasoning_content":"Because."}}]}"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let message = resp.choices.into_iter().next().unwrap().message;
+        let result = OpenAiProvider::parse_native_response(message);
+        assert_eq!(result.text.as_deref(), Some("Real answer"));
+        assert_eq!(result.reasoning.unwrap().text, "Because.");
+    }
+
+    #[test]
+    fn native_request_serializes_reasoning_effort() {
+        let provider = OpenAiProvider::new(Some("key")).with_reasoning(ReasoningSettings {
+            effort: Some("high".into()),
+            ..ReasoningSettings::default()
+        });
+        let req = NativeChatRequest {
+            model: "o3".to_string(),
+            messages: vec![],
+            temperature: 1.0,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            reasoning_effort: provider.reasoning.resolve("o3").effort,
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["reasoning_effort"], "high");
+
+        let req = NativeChatRequest {
+            reasoning_effort: OpenAiProvider::new(Some("key"))
+                .reasoning
+                .resolve("o3")
+                .effort,
+            ..req
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("reasoning_effort").is_none());
+    }
 }
@@ -1,7 +1,8 @@
 use crate::multimodal;
+use crate::providers::reasoning::ReasoningSettings;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, ReasoningContent, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize};
 
 pub struct OpenRouterProvider {
     credential: Option<String>,
+    reasoning: ReasoningSettings,
 }
 
 #[derive(Debug, Serialize)]
@@ -68,6 +70,21 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning: Option<NativeReasoning>,
+}
+
+/// OpenRouter unified reasoning option (mapped to each upstream's own control).
+#[derive(Debug, Serialize)]
+struct NativeReasoning {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -123,6 +140,14 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -134,6 +159,9 @@ struct NativeChoice {
 struct NativeResponseMessage {
     #[serde(default)]
     content: Option<String>,
+    /// Reasoning text returned by thinking models.
+    #[serde(default)]
+    reasoning: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<NativeToolCall>>,
 }
@@ -142,7 +170,39 @@ impl OpenRouterProvider {
     pub fn new(credential: Option<&str>) -> Self {
         Self {
             credential: credential.map(ToString::to_string),
+            reasoning: ReasoningSettings::default(),
+        }
+    }
+
+    /// Apply configured reasoning settings (effort / budget per model).
+    pub fn with_reasoning(mut self, reasoning: ReasoningSettings) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
+    fn reasoning_for(&self, model: &str) -> Option<NativeReasoning> {
+        let resolved = self.reasoning.resolve(model);
+        if resolved.enabled == Some(false) {
+            return Some(NativeReasoning {
+                effort: None,
+                max_tokens: None,
+                exclude: Some(true),
+            });
         }
+        if resolved.effort.is_none() && resolved.budget_tokens.is_none() {
+            return None;
+        }
+        // OpenRouter accepts either an effort level or a token budget, not both.
+        let max_tokens = resolved
+            .effort
+            .is_none()
+            .then_some(resolved.budget_tokens)
+            .flatten();
+        Some(NativeReasoning {
+            effort: resolved.effort,
+            max_tokens,
+            exclude: None,
+        })
     }
 
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
@@ -276,6 +336,9 @@ impl OpenRouterProvider {
             text: message.content,
             tool_calls,
             usage: None,
+            reasoning: message
+                .reasoning
+                .and_then(|r| ReasoningContent::from_text(r).into_option()),
         }
     }
 
@@ -290,6 +353,7 @@ impl Provider for OpenRouterProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -433,6 +497,10 @@ impl Provider for OpenRouterProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request
+                .response_schema
+                .map(super::structured::openai_response_format),
+            reasoning: self.reasoning_for(model),
         };
 
         let response = self
@@ -456,6 +524,8 @@ impl Provider for OpenRouterProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -527,6 +597,8 @@ impl Provider for OpenRouterProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            reasoning: self.reasoning_for(model),
         };
 
         let response = self
@@ -550,6 +622,8 @@ impl Provider for OpenRouterProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -790,6 +864,7 @@ mod tests {
                     arguments: r#"{"path":"test.txt"}"#.into(),
                 },
             }]),
+            reasoning: None,
         };
 
         let response = OpenRouterProvider::parse_native_response(message);
@@ -886,4 +961,35 @@ mod tests {
         let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
         assert!(resp.usage.is_none());
     }
+
+    #[test]
+    fn reasoning_option_prefers_effort_over_budget() {
+        let provider = OpenRouterProvider::new(Some("key")).with_reasoning(ReasoningSettings {
+            effort: Some("low".into()),
+            budget_tokens: Some(4000),
+            ..ReasoningSettings::default()
+        });
+        let json = serde_json::to_value(provider.reasoning_for("openai/o3")).unwrap();
+        assert_eq!(json, serde_json::json!({"effort": "low"}));
+
+        let budget_only = OpenRouterProvider::new(Some("key")).with_reasoning(ReasoningSettings {
+            budget_tokens: Some(4000),
+            ..ReasoningSettings::default()
+        });
+        let json = serde_json::to_value(budget_only.reasoning_for("anthropic/claude")).unwrap();
+        assert_eq!(json, serde_json::json!({"max_tokens": 4000}));
+
+        assert!(OpenRouterProvider::new(Some("key"))
+            .reasoning_for("openai/o3")
+            .is_none());
+    }
+
+    #[test]
+    fn parse_native_response_captures_reasoning() {
+        let json = r#"{"content": "Answer", "reasoning": "Thought it through."}"#;
+        let message: NativeResponseMessage = serde_json::from_str(json).unwrap();
+        let result = OpenRouterProvider::parse_native_response(message);
+        assert_eq!(result.text.as_deref(), Some("Answer"));
+        assert_eq!(result.reasoning.unwrap().text, "Thought it through.");
+    }
 }
@@ -0,0 +1,216 @@
+//! Reasoning ("thinking") helpers shared by all providers.
+//!
+//! Providers surface model reasoning in different shapes: Anthropic signed
+//! `thinking` blocks, OpenAI-compatible `reasoning_content`, Ollama `thinking`,
+//! Gemini `thought` parts, and DeepSeek-R1 style inline `<think>` tags. This
+//! module resolves the configured effort/budget per model, splits inline
+//! `<think>` blocks out of reply text, and renders reasoning for channels.
+
+use super::traits::ReasoningContent;
+use crate::config::{ModelReasoningConfig, ReasoningDisplay, RuntimeConfig};
+use std::collections::HashMap;
+
+/// Characters of reasoning kept by [`ReasoningDisplay::Summary`].
+const REASONING_SUMMARY_CHARS: usize = 280;
+
+/// Minimum thinking budget accepted by Anthropic.
+pub const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
+/// Reasoning settings handed to providers at construction time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReasoningSettings {
+    pub enabled: Option<bool>,
+    pub effort: Option<String>,
+    pub budget_tokens: Option<u32>,
+    pub models: HashMap<String, ModelReasoningConfig>,
+}
+
+impl ReasoningSettings {
+    pub fn from_runtime(runtime: &RuntimeConfig) -> Self {
+        Self {
+            enabled: runtime.reasoning_enabled,
+            effort: runtime.reasoning_effort.clone(),
+            budget_tokens: runtime.reasoning_budget_tokens,
+            models: runtime.reasoning_models.clone(),
+        }
+    }
+
+    /// Effective settings for `model`: per-model overrides win, unset fields
+    /// fall back to the global values.
+    pub fn resolve(&self, model: &str) -> ModelReasoningConfig {
+        let model_override = self.models.get(model);
+        let enabled = model_override.and_then(|m| m.enabled).or(self.enabled);
+        if enabled == Some(false) {
+            return ModelReasoningConfig {
+                enabled,
+                effort: None,
+                budget_tokens: None,
+            };
+        }
+        ModelReasoningConfig {
+            enabled,
+            effort: model_override
+                .and_then(|m| m.effort.clone())
+                .or_else(|| self.effort.clone())
+                .map(|e| e.trim().to_ascii_lowercase())
+                .filter(|e| !e.is_empty()),
+            budget_tokens: model_override
+                .and_then(|m| m.budget_tokens)
+                .or(self.budget_tokens)
+                .filter(|b| *b > 0),
+        }
+    }
+}
+
+/// Split inline `<think>...</think>` blocks out of model output.
+///
+/// Returns `(visible_text, reasoning_text)`, both trimmed. An unclosed
+/// `<think>` tag swallows the rest of the output so partial reasoning never
+/// leaks into the reply.
+pub fn split_think_tags(s: &str) -> (String, String) {
+    let mut visible = String::with_capacity(s.len());
+    let mut reasoning: Vec<&str> = Vec::new();
+    let mut rest = s;
+    loop {
+        if let Some(start) = rest.find("<think>") {
+            visible.push_str(&rest[..start]);
+            let body = &rest[start + "<think>".len()..];
+            if let Some(end) = body.find("</think>") {
+                reasoning.push(body[..end].trim());
+                rest = &body[end + "</think>".len()..];
+            } else {
+                reasoning.push(body.trim());
+                break;
+            }
+        } else {
+            visible.push_str(rest);
+            break;
+        }
+    }
+    let reasoning = reasoning
+        .into_iter()
+        .filter(|r| !r.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (visible.trim().to_string(), reasoning)
+}
+
+/// Combine reasoning fragments (separate field plus inline tags) into one value.
+pub fn merge_reasoning_text<'a>(
+    parts: impl IntoIterator<Item = &'a str>,
+) -> Option<ReasoningContent> {
+    let text = parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ReasoningContent::from_text(text).into_option()
+}
+
+/// Render a reply for a channel according to its reasoning display mode.
+pub fn render_with_reasoning(
+    display: ReasoningDisplay,
+    reasoning: Option<&ReasoningContent>,
+    reply: &str,
+) -> String {
+    let Some(text) = reasoning
+        .map(|r| r.text.trim())
+        .filter(|text| !text.is_empty())
+    else {
+        return reply.to_string();
+    };
+    match display {
+        ReasoningDisplay::Hide => reply.to_string(),
+        ReasoningDisplay::Summary => {
+            let first_paragraph = text.split("\n\n").next().unwrap_or(text).trim();
+            let excerpt =
+                crate::util::truncate_with_ellipsis(first_paragraph, REASONING_SUMMARY_CHARS);
+            format!("\u{1f4ad} {excerpt}\n\n{reply}")
+        }
+        ReasoningDisplay::Show => format!("\u{1f4ad} Reasoning:\n{text}\n\n{reply}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ReasoningSettings {
+        let mut models = HashMap::new();
+        models.insert(
+            "claude-sonnet-4".to_string(),
+            ModelReasoningConfig {
+                enabled: None,
+                effort: None,
+                budget_tokens: Some(8000),
+            },
+        );
+        models.insert(
+            "o3-mini".to_string(),
+            ModelReasoningConfig {
+                enabled: Some(false),
+                ..ModelReasoningConfig::default()
+            },
+        );
+        ReasoningSettings {
+            enabled: Some(true),
+            effort: Some(" High ".into()),
+            budget_tokens: Some(2048),
+            models,
+        }
+    }
+
+    #[test]
+    fn resolve_prefers_model_override_and_falls_back_to_globals() {
+        let resolved = settings().resolve("claude-sonnet-4");
+        assert_eq!(resolved.enabled, Some(true));
+        assert_eq!(resolved.budget_tokens, Some(8000));
+        assert_eq!(resolved.effort.as_deref(), Some("high"));
+
+        let fallback = settings().resolve("gpt-5");
+        assert_eq!(fallback.budget_tokens, Some(2048));
+    }
+
+    #[test]
+    fn resolve_disabled_model_clears_effort_and_budget() {
+        let resolved = settings().resolve("o3-mini");
+        assert_eq!(resolved.enabled, Some(false));
+        assert!(resolved.effort.is_none());
+        assert!(resolved.budget_tokens.is_none());
+    }
+
+    #[test]
+    fn split_think_tags_separates_reasoning_from_reply() {
+        let (visible, reasoning) =
+            split_think_tags("<think>step one</think>Hello <think>step two</think>world");
+        assert_eq!(visible, "Hello world");
+        assert_eq!(reasoning, "step one\n\nstep two");
+    }
+
+    #[test]
+    fn split_think_tags_unclosed_block_goes_to_reasoning() {
+        let (visible, reasoning) = split_think_tags("answer<think>partial");
+        assert_eq!(visible, "answer");
+        assert_eq!(reasoning, "partial");
+    }
+
+    #[test]
+    fn render_with_reasoning_respects_display_mode() {
+        let reasoning = ReasoningContent::from_text("First idea.\n\nSecond idea.");
+        assert_eq!(
+            render_with_reasoning(ReasoningDisplay::Hide, Some(&reasoning), "Hi"),
+            "Hi"
+        );
+        let summary = render_with_reasoning(ReasoningDisplay::Summary, Some(&reasoning), "Hi");
+        assert!(summary.contains("First idea."));
+        assert!(!summary.contains("Second idea."));
+        assert!(summary.ends_with("Hi"));
+        let full = render_with_reasoning(ReasoningDisplay::Show, Some(&reasoning), "Hi");
+        assert!(full.contains("Second idea."));
+        assert_eq!(
+            render_with_reasoning(ReasoningDisplay::Show, None, "Hi"),
+            "Hi"
+        );
+    }
+}
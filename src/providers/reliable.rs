@@ -550,6 +550,16 @@ impl Provider for ReliableProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn supports_structured_output(&self) -> bool {
+        // A fallback provider without native support would skip the
+        // prompt-guided schema instructions, so require it everywhere.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, provider)| provider.supports_structured_output())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -678,6 +688,7 @@ impl Provider for ReliableProvider {
                     let req = ChatRequest {
                         messages: request.messages,
                         tools: request.tools,
+                        response_schema: request.response_schema,
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
@@ -1670,6 +1681,7 @@ mod tests {
                 text: Some(self.response_text.to_string()),
                 tool_calls: self.tool_calls.clone(),
                 usage: None,
+                reasoning: None,
             })
         }
     }
@@ -1701,6 +1713,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1737,6 +1750,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1808,6 +1822,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
         };
         let err = provider
             .chat(request, "test", 0.0)
@@ -1862,6 +1877,7 @@ mod tests {
                 text: Some(self.response_text.to_string()),
                 tool_calls: vec![],
                 usage: None,
+                reasoning: None,
             })
         }
     }
@@ -1923,6 +1939,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
         };
         let result = provider.chat(request, "claude-opus", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("ok from sonnet"));
@@ -1971,6 +1988,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_schema: None,
         };
         let result = provider.chat(request, "test", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("from fallback"));
@@ -0,0 +1,305 @@
+//! Structured-output helpers shared by all providers.
+//!
+//! [`Provider::chat_structured`](super::traits::Provider::chat_structured) uses
+//! these to turn a model reply into a schema-conforming JSON value:
+//!
+//! 1. Build prompt instructions for providers without native schema support
+//! 2. Extract the JSON payload from the reply (bare, fenced, or wrapped in prose)
+//! 3. Validate it against the supported JSON Schema subset
+//! 4. Build retry feedback when validation fails
+//!
+//! The validator covers the keywords structured-output schemas use in
+//! practice: `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties: false`, `items`, `minItems`/`maxItems` and
+//! `anyOf`/`oneOf`. Unknown keywords are ignored.
+
+use super::traits::ResponseSchema;
+use serde_json::Value;
+
+/// Total attempts (first try plus retries with error feedback).
+pub const MAX_STRUCTURED_ATTEMPTS: usize = 3;
+
+/// Prompt-guided instructions for providers without native structured output.
+pub fn schema_instructions(schema: &ResponseSchema) -> String {
+    let rendered =
+        serde_json::to_string_pretty(&schema.schema).unwrap_or_else(|_| "{}".to_string());
+    format!(
+        "## Response Format\n\n\
+         Reply with a single JSON value that conforms to the `{}` JSON Schema below. \
+         Output only the JSON: no prose, no markdown fences.\n\n{rendered}",
+        schema.name
+    )
+}
+
+/// OpenAI-style `response_format` payload (also used by OpenRouter).
+pub fn openai_response_format(schema: &ResponseSchema) -> Value {
+    serde_json::json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": schema.name,
+            "schema": schema.schema,
+            "strict": schema.strict,
+        }
+    })
+}
+
+/// Feedback message sent back to the model after an invalid reply.
+pub fn retry_feedback(error: &str) -> String {
+    format!(
+        "Your previous reply did not match the required JSON schema: {error}\n\
+         Reply again with only the corrected JSON value."
+    )
+}
+
+/// Extract and validate the JSON payload of a model reply.
+pub fn parse_and_validate(raw: &str, schema: &Value) -> Result<Value, String> {
+    let value = extract_json(raw).ok_or_else(|| "reply does not contain valid JSON".to_string())?;
+    validate(&value, schema, "$")?;
+    Ok(value)
+}
+
+/// Find the JSON value in a reply: the whole text, a fenced code block, or
+/// the outermost `{...}` / `[...]` span.
+pub fn extract_json(raw: &str) -> Option<Value> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+
+    if let Some(start) = trimmed.find("```") {
+        let after_fence = &trimmed[start + 3..];
+        let body_start = after_fence.find('\n').map_or(0, |i| i + 1);
+        let body = &after_fence[body_start..];
+        if let Some(end) = body.find("```") {
+            if let Ok(value) = serde_json::from_str(body[..end].trim()) {
+                return Some(value);
+            }
+        }
+    }
+
+    for (open, close) in [('{', '}'), ('[', ']')] {
+        if let (Some(start), Some(end)) = (trimmed.find(open), trimmed.rfind(close)) {
+            if start < end {
+                if let Ok(value) = serde_json::from_str(&trimmed[start..=end]) {
+                    return Some(value);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        _ => true,
+    }
+}
+
+/// Validate `value` against `schema`, reporting the first mismatch with its
+/// JSON path (e.g. `$.items[2].name`).
+pub fn validate(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    match schema.get("type") {
+        Some(Value::String(expected)) if !type_matches(value, expected) => {
+            return Err(format!("{path}: expected {expected}"));
+        }
+        Some(Value::Array(options)) => {
+            let matched = options
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|expected| type_matches(value, expected));
+            if !matched {
+                return Err(format!(
+                    "{path}: expected one of {}",
+                    Value::Array(options.clone())
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            return Err(format!(
+                "{path}: value {value} is not one of {}",
+                schema["enum"]
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return Err(format!("{path}: expected constant {expected}"));
+        }
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            if !variants.iter().any(|v| validate(value, v, path).is_ok()) {
+                return Err(format!("{path}: does not match any allowed variant"));
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    return Err(format!("{path}: missing required field `{field}`"));
+                }
+            }
+        }
+
+        if let Some(properties) = properties {
+            for (field, field_schema) in properties {
+                if let Some(field_value) = object.get(field) {
+                    validate(field_value, field_schema, &format!("{path}.{field}"))?;
+                }
+            }
+        }
+
+        if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+            if let Some(extra) = object
+                .keys()
+                .find(|k| properties.is_none_or(|p| !p.contains_key(*k)))
+            {
+                return Err(format!("{path}: unexpected field `{extra}`"));
+            }
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                return Err(format!("{path}: expected at least {min} items"));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if (items.len() as u64) > max {
+                return Err(format!("{path}: expected at most {max} items"));
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                validate(item, item_schema, &format!("{path}[{index}]"))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn verdict_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "verdict": {"type": "string", "enum": ["pass", "fail"]},
+                "score": {"type": "integer"},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["verdict", "score"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn extract_json_accepts_bare_fenced_and_wrapped_replies() {
+        let bare = extract_json(r#"{"a": 1}"#).unwrap();
+        assert_eq!(bare["a"], 1);
+
+        let fenced = extract_json("Here you go:\n```json\n{\"a\": 2}\n```\nDone.").unwrap();
+        assert_eq!(fenced["a"], 2);
+
+        let wrapped = extract_json("Sure! {\"a\": 3} Hope that helps.").unwrap();
+        assert_eq!(wrapped["a"], 3);
+
+        let array = extract_json("Result: [1, 2, 3]").unwrap();
+        assert_eq!(array, json!([1, 2, 3]));
+
+        assert!(extract_json("no json here").is_none());
+        assert!(extract_json("   ").is_none());
+    }
+
+    #[test]
+    fn validate_accepts_conforming_value() {
+        let value = json!({"verdict": "pass", "score": 9, "tags": ["fast"]});
+        assert!(validate(&value, &verdict_schema(), "$").is_ok());
+    }
+
+    #[test]
+    fn validate_reports_path_of_first_mismatch() {
+        let missing = json!({"verdict": "pass"});
+        let err = validate(&missing, &verdict_schema(), "$").unwrap_err();
+        assert!(err.contains("missing required field `score`"));
+
+        let wrong_enum = json!({"verdict": "maybe", "score": 1});
+        let err = validate(&wrong_enum, &verdict_schema(), "$").unwrap_err();
+        assert!(err.starts_with("$.verdict"));
+
+        let wrong_item = json!({"verdict": "pass", "score": 1, "tags": ["ok", 5]});
+        let err = validate(&wrong_item, &verdict_schema(), "$").unwrap_err();
+        assert!(err.starts_with("$.tags[1]: expected string"));
+
+        let extra = json!({"verdict": "pass", "score": 1, "note": "x"});
+        let err = validate(&extra, &verdict_schema(), "$").unwrap_err();
+        assert!(err.contains("unexpected field `note`"));
+    }
+
+    #[test]
+    fn validate_handles_integer_and_union_types() {
+        assert!(validate(&json!(1.5), &json!({"type": "integer"}), "$").is_err());
+        assert!(validate(&json!(3), &json!({"type": "number"}), "$").is_ok());
+        assert!(validate(&json!(null), &json!({"type": ["string", "null"]}), "$").is_ok());
+        assert!(validate(
+            &json!("x"),
+            &json!({"anyOf": [{"type": "integer"}, {"type": "string"}]}),
+            "$"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn parse_and_validate_rejects_prose_only_reply() {
+        let err = parse_and_validate("I think it passed.", &verdict_schema()).unwrap_err();
+        assert!(err.contains("valid JSON"));
+    }
+
+    #[test]
+    fn openai_response_format_wraps_schema() {
+        let schema = ResponseSchema::new("verdict", verdict_schema()).strict();
+        let format = openai_response_format(&schema);
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["name"], "verdict");
+        assert_eq!(format["json_schema"]["strict"], true);
+        assert_eq!(format["json_schema"]["schema"], verdict_schema());
+    }
+
+    #[test]
+    fn schema_instructions_embed_name_and_schema() {
+        let schema = ResponseSchema::new("verdict", verdict_schema());
+        let text = schema_instructions(&schema);
+        assert!(text.contains("`verdict`"));
+        assert!(text.contains("\"required\""));
+    }
+}
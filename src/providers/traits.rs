@@ -50,10 +50,57 @@ pub struct ToolCall {
 }
 
 /// Raw token counts from a single LLM API response.
-#[derive(Debug, Clone, Default)]
+///
+/// `input_tokens` is the full prompt size. When the provider reports prompt
+/// caching, `cache_read_tokens` and `cache_write_tokens` are the portions of
+/// `input_tokens` that were served from or written to the cache.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
+    /// Prompt tokens served from the provider's prompt cache.
+    pub cache_read_tokens: Option<u64>,
+    /// Prompt tokens written to the provider's prompt cache.
+    pub cache_write_tokens: Option<u64>,
+}
+
+impl TokenUsage {
+    /// Prompt tokens billed at the regular (uncached) input rate.
+    pub fn uncached_input_tokens(&self) -> Option<u64> {
+        self.input_tokens.map(|total| {
+            total
+                .saturating_sub(self.cache_read_tokens.unwrap_or(0))
+                .saturating_sub(self.cache_write_tokens.unwrap_or(0))
+        })
+    }
+}
+
+/// Reasoning ("thinking") emitted by the model alongside its reply.
+///
+/// `text` is the human-readable reasoning, if the provider exposes it.
+/// `provider_blocks` holds provider-native blocks that must be sent back
+/// verbatim on the next request of a tool loop (e.g. Anthropic signed
+/// `thinking` / `redacted_thinking` blocks).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReasoningContent {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_blocks: Vec<serde_json::Value>,
+}
+
+impl ReasoningContent {
+    pub fn from_text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            provider_blocks: Vec::new(),
+        }
+    }
+
+    /// `None` when there is neither readable text nor anything to replay.
+    pub fn into_option(self) -> Option<Self> {
+        (!self.text.trim().is_empty() || !self.provider_blocks.is_empty()).then_some(self)
+    }
 }
 
 /// An LLM response that may contain text, tool calls, or both.
@@ -65,6 +112,8 @@ pub struct ChatResponse {
     pub tool_calls: Vec<ToolCall>,
     /// Token usage reported by the provider, if available.
     pub usage: Option<TokenUsage>,
+    /// Reasoning content, kept separate from `text` so it never leaks into replies.
+    pub reasoning: Option<ReasoningContent>,
 }
 
 impl ChatResponse {
@@ -79,11 +128,45 @@ impl ChatResponse {
     }
 }
 
+/// JSON schema a structured-output reply must conform to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Short identifier for the schema (OpenAI `json_schema.name`, Anthropic forced tool name).
+    pub name: String,
+    /// JSON Schema object describing the expected reply.
+    pub schema: serde_json::Value,
+    /// Ask providers that support it to enforce the schema strictly.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+impl ResponseSchema {
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            strict: false,
+        }
+    }
+
+    /// Request strict schema enforcement where the provider supports it.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
 /// Request payload for provider chat calls.
 #[derive(Debug, Clone, Copy)]
 pub struct ChatRequest<'a> {
     pub messages: &'a [ChatMessage],
     pub tools: Option<&'a [ToolSpec]>,
+    /// Constrain the reply to a JSON schema (structured output).
+    ///
+    /// Providers with native support map this to their own request option;
+    /// others ignore it. Use [`Provider::chat_structured`] for a validated
+    /// result with prompt-guided fallback.
+    pub response_schema: Option<&'a ResponseSchema>,
 }
 
 /// A tool result to feed back to the LLM.
@@ -103,6 +186,10 @@ pub enum ConversationMessage {
     AssistantToolCalls {
         text: Option<String>,
         tool_calls: Vec<ToolCall>,
+        /// Reasoning that preceded the tool calls; replayed to providers
+        /// that require it (Anthropic extended thinking).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reasoning: Option<ReasoningContent>,
     },
     /// Results of tool executions, fed back to the LLM.
     ToolResults(Vec<ToolResultMessage>),
@@ -225,6 +312,9 @@ pub struct ProviderCapabilities {
     pub native_tool_calling: bool,
     /// Whether the provider supports vision / image inputs.
     pub vision: bool,
+    /// Whether the provider can natively constrain replies to a JSON schema
+    /// (`ChatRequest::response_schema`).
+    pub structured_output: bool,
 }
 
 /// Provider-specific tool payload formats.
@@ -333,19 +423,7 @@ pub trait Provider: Send + Sync {
                     }
                 };
                 let mut modified_messages = request.messages.to_vec();
-
-                // Inject tool instructions into an existing system message.
-                // If none exists, prepend one to the conversation.
-                if let Some(system_message) =
-                    modified_messages.iter_mut().find(|m| m.role == "system")
-                {
-                    if !system_message.content.is_empty() {
-                        system_message.content.push_str("\n\n");
-                    }
-                    system_message.content.push_str(&tool_instructions);
-                } else {
-                    modified_messages.insert(0, ChatMessage::system(tool_instructions));
-                }
+                inject_system_instructions(&mut modified_messages, &tool_instructions);
 
                 let text = self
                     .chat_with_history(&modified_messages, model, temperature)
@@ -354,6 +432,7 @@ pub trait Provider: Send + Sync {
                     text: Some(text),
                     tool_calls: Vec::new(),
                     usage: None,
+                    reasoning: None,
                 });
             }
         }
@@ -365,6 +444,7 @@ pub trait Provider: Send + Sync {
             text: Some(text),
             tool_calls: Vec::new(),
             usage: None,
+            reasoning: None,
         })
     }
 
@@ -378,6 +458,65 @@ pub trait Provider: Send + Sync {
         self.capabilities().vision
     }
 
+    /// Whether provider natively supports JSON-schema constrained replies.
+    fn supports_structured_output(&self) -> bool {
+        self.capabilities().structured_output
+    }
+
+    /// Chat constrained to a JSON schema, returning the parsed and validated value.
+    ///
+    /// Providers with native structured output receive the schema through
+    /// `ChatRequest::response_schema`; for the rest the schema is injected into
+    /// the system prompt. Replies that fail to parse or validate are retried
+    /// with the validation error fed back to the model.
+    async fn chat_structured(
+        &self,
+        messages: &[ChatMessage],
+        schema: &ResponseSchema,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<serde_json::Value> {
+        let mut conversation = messages.to_vec();
+        if !self.supports_structured_output() {
+            inject_system_instructions(
+                &mut conversation,
+                &super::structured::schema_instructions(schema),
+            );
+        }
+
+        let mut last_error = String::new();
+        for _ in 0..super::structured::MAX_STRUCTURED_ATTEMPTS {
+            let response = self
+                .chat(
+                    ChatRequest {
+                        messages: &conversation,
+                        tools: None,
+                        response_schema: Some(schema),
+                    },
+                    model,
+                    temperature,
+                )
+                .await?;
+            let raw = response.text_or_empty().to_string();
+
+            match super::structured::parse_and_validate(&raw, &schema.schema) {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    tracing::debug!(schema = %schema.name, "Structured output rejected: {error}");
+                    conversation.push(ChatMessage::assistant(raw));
+                    conversation.push(ChatMessage::user(super::structured::retry_feedback(&error)));
+                    last_error = error;
+                }
+            }
+        }
+
+        anyhow::bail!(
+            "Structured output for schema '{}' failed validation after {} attempts: {last_error}",
+            schema.name,
+            super::structured::MAX_STRUCTURED_ATTEMPTS
+        )
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -399,6 +538,7 @@ pub trait Provider: Send + Sync {
             text: Some(text),
             tool_calls: Vec::new(),
             usage: None,
+            reasoning: None,
         })
     }
 
@@ -442,6 +582,19 @@ pub trait Provider: Send + Sync {
     }
 }
 
+/// Append instructions to the first system message, or prepend one if the
+/// conversation has none.
+fn inject_system_instructions(messages: &mut Vec<ChatMessage>, instructions: &str) {
+    if let Some(system_message) = messages.iter_mut().find(|m| m.role == "system") {
+        if !system_message.content.is_empty() {
+            system_message.content.push_str("\n\n");
+        }
+        system_message.content.push_str(instructions);
+    } else {
+        messages.insert(0, ChatMessage::system(instructions));
+    }
+}
+
 /// Build tool instructions text for prompt-guided tool calling.
 ///
 /// Generates a formatted text block describing available tools and how to
@@ -487,6 +640,7 @@ mod tests {
             ProviderCapabilities {
                 native_tool_calling: true,
                 vision: true,
+                structured_output: true,
             }
         }
 
@@ -523,6 +677,7 @@ mod tests {
             text: None,
             tool_calls: vec![],
             usage: None,
+            reasoning: None,
         };
         assert!(!empty.has_tool_calls());
         assert_eq!(empty.text_or_empty(), "");
@@ -535,6 +690,7 @@ mod tests {
                 arguments: "{}".into(),
             }],
             usage: None,
+            reasoning: None,
         };
         assert!(with_tools.has_tool_calls());
         assert_eq!(with_tools.text_or_empty(), "Let me check");
@@ -555,12 +711,33 @@ mod tests {
             usage: Some(TokenUsage {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
             }),
+            reasoning: None,
         };
         assert_eq!(resp.usage.as_ref().unwrap().input_tokens, Some(100));
         assert_eq!(resp.usage.as_ref().unwrap().output_tokens, Some(50));
     }
 
+    #[test]
+    fn token_usage_uncached_input_excludes_cache_segments() {
+        let usage = TokenUsage {
+            input_tokens: Some(1000),
+            output_tokens: Some(20),
+            cache_read_tokens: Some(700),
+            cache_write_tokens: Some(200),
+        };
+        assert_eq!(usage.uncached_input_tokens(), Some(100));
+
+        let no_cache = TokenUsage {
+            input_tokens: Some(50),
+            ..TokenUsage::default()
+        };
+        assert_eq!(no_cache.uncached_input_tokens(), Some(50));
+        assert_eq!(TokenUsage::default().uncached_input_tokens(), None);
+    }
+
     #[test]
     fn tool_call_serialization() {
         let tc = ToolCall {
@@ -599,14 +776,17 @@ mod tests {
         let caps1 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps2 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps3 = ProviderCapabilities {
             native_tool_calling: false,
             vision: false,
+            structured_output: false,
         };
 
         assert_eq!(caps1, caps2);
@@ -766,6 +946,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -783,6 +964,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: None,
+            response_schema: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -883,6 +1065,7 @@ mod tests {
                 ChatMessage::system("BASE_SYSTEM_PROMPT"),
             ],
             tools: Some(&tools),
+            response_schema: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -905,6 +1088,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -927,6 +1111,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_schema: None,
         };
 
         let err = provider.chat(request, "model", 0.7).await.unwrap_err();
@@ -934,4 +1119,118 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    // Provider that replays scripted replies and records the system prompt.
+    struct ScriptedProvider {
+        replies: std::sync::Mutex<Vec<String>>,
+        seen_system: std::sync::Mutex<Vec<String>>,
+        seen_schema: std::sync::Mutex<Vec<bool>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: std::sync::Mutex::new(
+                    replies.iter().rev().map(ToString::to_string).collect(),
+                ),
+                seen_system: std::sync::Mutex::new(Vec::new()),
+                seen_schema: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            unreachable!("chat is overridden")
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            if let Some(system) = request.messages.iter().find(|m| m.role == "system") {
+                self.seen_system
+                    .lock()
+                    .unwrap()
+                    .push(system.content.clone());
+            }
+            self.seen_schema
+                .lock()
+                .unwrap()
+                .push(request.response_schema.is_some());
+            let reply = self.replies.lock().unwrap().pop().unwrap_or_default();
+            Ok(ChatResponse {
+                text: Some(reply),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning: None,
+            })
+        }
+    }
+
+    fn answer_schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "answer",
+            serde_json::json!({
+                "type": "object",
+                "properties": {"answer": {"type": "integer"}},
+                "required": ["answer"]
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn chat_structured_injects_schema_for_prompt_guided_providers() {
+        let provider = ScriptedProvider::new(&["Sure! {\"answer\": 42}"]);
+        let value = provider
+            .chat_structured(
+                &[ChatMessage::system("BASE"), ChatMessage::user("6 * 7?")],
+                &answer_schema(),
+                "model",
+                0.0,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(value["answer"], 42);
+        let systems = provider.seen_system.lock().unwrap();
+        assert!(systems[0].starts_with("BASE"));
+        assert!(systems[0].contains("Response Format"));
+        assert!(provider.seen_schema.lock().unwrap()[0]);
+    }
+
+    #[tokio::test]
+    async fn chat_structured_retries_with_validation_feedback() {
+        let provider =
+            ScriptedProvider::new(&["not json", r#"{"answer": "x"}"#, r#"{"answer": 7}"#]);
+        let value = provider
+            .chat_structured(&[ChatMessage::user("q")], &answer_schema(), "model", 0.0)
+            .await
+            .unwrap();
+
+        assert_eq!(value["answer"], 7);
+        assert_eq!(provider.seen_schema.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn chat_structured_fails_after_max_attempts() {
+        let provider = ScriptedProvider::new(&["a", "b", "c", r#"{"answer": 1}"#]);
+        let err = provider
+            .chat_structured(&[ChatMessage::user("q")], &answer_schema(), "model", 0.0)
+            .await
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("failed validation after 3 attempts"));
+    }
 }
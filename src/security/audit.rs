@@ -1,15 +1,43 @@
 //! Audit logging for security events
+//!
+//! Events are appended as JSON lines. With `sign_events = true` every event
+//! carries a sequence number, the signature of the previous event and its own
+//! HMAC-SHA256 signature (key derived from the [`SecretStore`] master key), so
+//! edits, deletions and reordering anywhere in the log — including across
+//! rotated files — are detected by [`verify_chain`].
+//!
+//! Appends hold an advisory lock on `<log_path>.lock`, so several processes
+//! (daemon, CLI agent, cron) sharing one log still extend a single chain.
 
-use crate::config::AuditConfig;
-use anyhow::Result;
+use crate::config::{AuditConfig, Config};
+use crate::security::SecretStore;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Key-derivation context for audit-log signatures
+const AUDIT_KEY_CONTEXT: &str = "zeroclaw-audit-log-v1";
+
+/// Rotated files kept next to the active log (`audit.log.1.log` is newest)
+const MAX_ROTATED_FILES: usize = 10;
+
+/// Bytes read from the end of a log file to recover the chain head
+const TAIL_SCAN_BYTES: u64 = 64 * 1024;
+
+/// Longest tool-argument summary stored in a tool-call event
+const MAX_ARGS_SUMMARY_CHARS: usize = 512;
+
+/// Serializes appends so concurrent loggers in one process extend a single chain
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
 /// Audit event types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -67,6 +95,18 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    /// Tool that performed the action (e.g. "shell")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Position in the signed chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    /// Signature of the previous event in the chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_signature: Option<String>,
+    /// Hex HMAC-SHA256 over every other field of the event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 impl AuditEvent {
@@ -84,9 +124,19 @@ impl AuditEvent {
                 rate_limit_remaining: None,
                 sandbox_backend: None,
             },
+            tool: None,
+            seq: None,
+            prev_signature: None,
+            signature: None,
         }
     }
 
+    /// Set the tool that performed the action
+    pub fn with_tool(mut self, tool: &str) -> Self {
+        self.tool = Some(tool.to_string());
+        self
+    }
+
     /// Set the actor
     pub fn with_actor(
         mut self,
@@ -136,6 +186,28 @@ impl AuditEvent {
         self
     }
 
+    /// Event for one tool call, with its arguments scrubbed and summarized.
+    /// Calls refused by security policy are recorded as policy violations.
+    pub fn tool_call(tool: &str, args: &serde_json::Value, approved: bool, allowed: bool) -> Self {
+        let event_type = if !allowed {
+            AuditEventType::PolicyViolation
+        } else if tool.starts_with("file_") || tool == "glob_search" {
+            AuditEventType::FileAccess
+        } else {
+            AuditEventType::CommandExecution
+        };
+        let mut event = Self::new(event_type)
+            .with_actor("agent".to_string(), None, None)
+            .with_tool(tool);
+        event.action = Some(Action {
+            command: Some(summarize_args(args)),
+            risk_level: None,
+            approved,
+            allowed,
+        });
+        event
+    }
+
     /// Set security context
     pub fn with_security(mut self, sandbox_backend: Option<String>) -> Self {
         self.security.sandbox_backend = sandbox_backend;
@@ -147,7 +219,8 @@ impl AuditEvent {
 pub struct AuditLogger {
     log_path: PathBuf,
     config: AuditConfig,
-    buffer: Mutex<Vec<AuditEvent>>,
+    /// Present when `sign_events` is enabled
+    signing_key: Option<Vec<u8>>,
 }
 
 /// Structured command execution details for audit logging.
@@ -166,10 +239,15 @@ impl AuditLogger {
     /// Create a new audit logger
     pub fn new(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Self> {
         let log_path = zeroclaw_dir.join(&config.log_path);
+        let signing_key = if config.enabled && config.sign_events {
+            Some(audit_signing_key(&zeroclaw_dir)?)
+        } else {
+            None
+        };
         Ok(Self {
             log_path,
             config,
-            buffer: Mutex::new(Vec::new()),
+            signing_key,
         })
     }
 
@@ -179,11 +257,17 @@ impl AuditLogger {
             return Ok(());
         }
 
+        let _guard = WRITE_LOCK.lock();
+        let _file_lock = FileLock::acquire(&lock_path(&self.log_path))?;
+
         // Check log size and rotate if needed
         self.rotate_if_needed()?;
 
-        // Serialize and write
-        let line = serde_json::to_string(event)?;
+        // Serialize (and sign) then write
+        let line = match &self.signing_key {
+            Some(key) => self.signed_line(key, event)?,
+            None => serde_json::to_string(event)?,
+        };
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -195,6 +279,35 @@ impl AuditLogger {
         Ok(())
     }
 
+    /// [`AuditLogger::log`] on the blocking pool, for use from async code.
+    pub async fn log_async(self: &Arc<Self>, event: AuditEvent) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let logger = Arc::clone(self);
+        tokio::task::spawn_blocking(move || logger.log(&event))
+            .await
+            .context("Audit log task failed")?
+    }
+
+    /// Chain `event` onto the current head and serialize it with its signature.
+    fn signed_line(&self, key: &[u8], event: &AuditEvent) -> Result<String> {
+        let (seq, prev_signature) = match chain_head(&self.log_path)? {
+            Some((seq, signature)) => (seq + 1, Some(signature)),
+            None => (0, None),
+        };
+
+        let mut signed = event.clone();
+        signed.seq = Some(seq);
+        signed.prev_signature = prev_signature;
+        signed.signature = None;
+
+        let mut value = serde_json::to_value(&signed)?;
+        let signature = sign_value(key, &value)?;
+        value["signature"] = serde_json::Value::String(signature);
+        Ok(serde_json::to_string(&value)?)
+    }
+
     /// Log a command execution event.
     pub fn log_command_event(&self, entry: CommandExecutionLog<'_>) -> Result<()> {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -246,18 +359,422 @@ impl AuditLogger {
 
     /// Rotate the log file
     fn rotate(&self) -> Result<()> {
-        for i in (1..10).rev() {
-            let old_name = format!("{}.{}.log", self.log_path.display(), i);
-            let new_name = format!("{}.{}.log", self.log_path.display(), i + 1);
-            let _ = std::fs::rename(&old_name, &new_name);
+        for i in (1..MAX_ROTATED_FILES).rev() {
+            let _ = std::fs::rename(
+                rotated_path(&self.log_path, i),
+                rotated_path(&self.log_path, i + 1),
+            );
         }
 
-        let rotated = format!("{}.1.log", self.log_path.display());
-        std::fs::rename(&self.log_path, &rotated)?;
+        std::fs::rename(&self.log_path, rotated_path(&self.log_path, 1))?;
         Ok(())
     }
 }
 
+/// Derive the audit signing key from the secret store in `zeroclaw_dir`.
+pub fn audit_signing_key(zeroclaw_dir: &Path) -> Result<Vec<u8>> {
+    SecretStore::new(zeroclaw_dir, true)
+        .derive_key(AUDIT_KEY_CONTEXT)
+        .context("Failed to load audit signing key")
+}
+
+fn lock_path(log_path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.lock", log_path.display()))
+}
+
+/// Exclusive advisory lock on a file, released when dropped.
+struct FileLock {
+    _file: std::fs::File,
+}
+
+impl FileLock {
+    fn acquire(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit lock {}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+            // SAFETY: `file` owns a valid descriptor for the duration of the call.
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+                return Err(std::io::Error::last_os_error())
+                    .with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+/// Tool arguments as compact JSON with credentials redacted, truncated for the log.
+fn summarize_args(args: &serde_json::Value) -> String {
+    let scrubbed = crate::agent::loop_::scrub_credentials(&args.to_string());
+    crate::util::truncate_with_ellipsis(&scrubbed, MAX_ARGS_SUMMARY_CHARS)
+}
+
+tokio::task_local! {
+    /// Whether the tool call running on this task was approved by a person.
+    static TOOL_CALL_APPROVED: bool;
+}
+
+/// Run a tool call with its approval decision visible to the audit log.
+pub async fn with_tool_approval<F: std::future::Future>(approved: bool, call: F) -> F::Output {
+    TOOL_CALL_APPROVED.scope(approved, call).await
+}
+
+/// Approval decision recorded by [`with_tool_approval`]; `false` outside one.
+pub fn tool_call_approved() -> bool {
+    TOOL_CALL_APPROVED
+        .try_with(|approved| *approved)
+        .unwrap_or(false)
+}
+
+fn rotated_path(log_path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{index}.log", log_path.display()))
+}
+
+/// Existing log files, oldest first (rotated files, then the active log).
+pub fn log_files(log_path: &Path) -> Vec<PathBuf> {
+    (1..=MAX_ROTATED_FILES)
+        .rev()
+        .map(|i| rotated_path(log_path, i))
+        .chain(std::iter::once(log_path.to_path_buf()))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// HMAC over the event JSON with the `signature` field removed.
+fn sign_value(key: &[u8], value: &serde_json::Value) -> Result<String> {
+    Ok(hex::encode(event_mac(key, value)?.finalize().into_bytes()))
+}
+
+fn event_mac(key: &[u8], value: &serde_json::Value) -> Result<Hmac<Sha256>> {
+    let mut unsigned = value.clone();
+    if let Some(map) = unsigned.as_object_mut() {
+        map.remove("signature");
+    }
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key).context("Audit signing key has an invalid length")?;
+    mac.update(serde_json::to_string(&unsigned)?.as_bytes());
+    Ok(mac)
+}
+
+/// Last non-empty line of a file, reading at most [`TAIL_SCAN_BYTES`].
+fn read_last_line(path: &Path) -> Result<Option<String>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL_SCAN_BYTES)))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes)
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(str::to_string))
+}
+
+/// `(seq, signature)` of the newest signed event, looking past a fresh
+/// rotation into the previous file so the chain continues across files.
+fn chain_head(log_path: &Path) -> Result<Option<(u64, String)>> {
+    for path in [log_path.to_path_buf(), rotated_path(log_path, 1)] {
+        let Some(line) = read_last_line(&path)? else {
+            continue;
+        };
+        let value: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => return Ok(None),
+        };
+        let seq = value.get("seq").and_then(serde_json::Value::as_u64);
+        let signature = value.get("signature").and_then(serde_json::Value::as_str);
+        return Ok(seq.zip(signature.map(str::to_string)));
+    }
+    Ok(None)
+}
+
+/// Outcome of [`verify_chain`]
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub files: usize,
+    pub events: usize,
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+    /// Human-readable problems (`file:line: reason`); empty when intact
+    pub problems: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty() && self.events > 0
+    }
+}
+
+/// Verify signatures, sequence numbers and chain links across the active log
+/// and its rotated files.
+///
+/// A chain that starts above `seq 0` is not a problem by itself: the oldest
+/// files may have been rotated away. Truncating the newest events cannot be
+/// detected from the log alone.
+pub fn verify_chain(log_path: &Path, key: &[u8]) -> Result<VerifyReport> {
+    let mut report = VerifyReport::default();
+    let mut previous: Option<(u64, String)> = None;
+
+    for path in log_files(log_path) {
+        report.files += 1;
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let location = format!("{name}:{}", index + 1);
+            report.events += 1;
+
+            let value: serde_json::Value = match serde_json::from_str(line) {
+                Ok(value) => value,
+                Err(e) => {
+                    report
+                        .problems
+                        .push(format!("{location}: not valid JSON ({e})"));
+                    previous = None;
+                    continue;
+                }
+            };
+
+            let Some(signature) = value.get("signature").and_then(serde_json::Value::as_str) else {
+                report
+                    .problems
+                    .push(format!("{location}: event is not signed"));
+                previous = None;
+                continue;
+            };
+            let signature_valid = hex::decode(signature).is_ok_and(|bytes| {
+                event_mac(key, &value).is_ok_and(|mac| mac.verify_slice(&bytes).is_ok())
+            });
+            if !signature_valid {
+                report.problems.push(format!(
+                    "{location}: signature mismatch (event was modified)"
+                ));
+            }
+
+            let Some(seq) = value.get("seq").and_then(serde_json::Value::as_u64) else {
+                report
+                    .problems
+                    .push(format!("{location}: missing sequence number"));
+                previous = None;
+                continue;
+            };
+            let prev_signature = value
+                .get("prev_signature")
+                .and_then(serde_json::Value::as_str);
+
+            match &previous {
+                Some((prev_seq, prev_sig)) => {
+                    if seq != prev_seq + 1 {
+                        report.problems.push(format!(
+                            "{location}: sequence gap (expected {}, found {seq})",
+                            prev_seq + 1
+                        ));
+                    }
+                    if prev_signature != Some(prev_sig.as_str()) {
+                        report.problems.push(format!(
+                            "{location}: chain broken (prev_signature does not match the previous event)"
+                        ));
+                    }
+                }
+                None if report.first_seq.is_none() => report.first_seq = Some(seq),
+                None => {}
+            }
+
+            report.last_seq = Some(seq);
+            previous = Some((seq, signature.to_string()));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Filters for [`query_events`]
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Matches the actor's channel, user id or username (case-insensitive)
+    pub actor: Option<String>,
+    pub tool: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Return at most this many of the newest matches (0 = all)
+    pub limit: usize,
+}
+
+impl AuditQuery {
+    fn matches(&self, event: &AuditEvent) -> bool {
+        if let Some(actor) = &self.actor {
+            let Some(a) = &event.actor else {
+                return false;
+            };
+            let hit = [Some(&a.channel), a.user_id.as_ref(), a.username.as_ref()]
+                .into_iter()
+                .flatten()
+                .any(|v| v.eq_ignore_ascii_case(actor));
+            if !hit {
+                return false;
+            }
+        }
+        if let Some(tool) = &self.tool {
+            if !event
+                .tool
+                .as_deref()
+                .is_some_and(|t| t.eq_ignore_ascii_case(tool))
+            {
+                return false;
+            }
+        }
+        if self.since.is_some_and(|since| event.timestamp < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| event.timestamp > until) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Read events across the active and rotated logs, oldest first.
+/// Lines that do not parse are skipped (`verify_chain` reports them).
+pub fn query_events(log_path: &Path, query: &AuditQuery) -> Result<Vec<AuditEvent>> {
+    let mut events = Vec::new();
+    for path in log_files(log_path) {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        events.extend(
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<AuditEvent>(line).ok())
+                .filter(|event| query.matches(event)),
+        );
+    }
+    if query.limit > 0 && events.len() > query.limit {
+        events.drain(..events.len() - query.limit);
+    }
+    Ok(events)
+}
+
+fn audit_log_path(config: &Config) -> Result<(PathBuf, PathBuf)> {
+    let zeroclaw_dir = config
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")?
+        .to_path_buf();
+    let log_path = zeroclaw_dir.join(&config.security.audit.log_path);
+    Ok((zeroclaw_dir, log_path))
+}
+
+fn parse_time_filter(flag: &str, value: Option<String>) -> Result<Option<DateTime<Utc>>> {
+    value
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(raw.trim())
+                .map(|t| t.with_timezone(&Utc))
+                .with_context(|| format!("--{flag} must be an RFC 3339 timestamp, got '{raw}'"))
+        })
+        .transpose()
+}
+
+/// Handle `zeroclaw audit` subcommands
+pub fn handle_command(command: crate::AuditCommands, config: &Config) -> Result<()> {
+    let (zeroclaw_dir, log_path) = audit_log_path(config)?;
+
+    match command {
+        crate::AuditCommands::Verify => {
+            let key = audit_signing_key(&zeroclaw_dir)?;
+            let report = verify_chain(&log_path, &key)?;
+            if report.events == 0 {
+                anyhow::bail!("No audit events found at {}", log_path.display());
+            }
+            let range = match (report.first_seq, report.last_seq) {
+                (Some(first), Some(last)) => format!(" (seq {first}..={last})"),
+                _ => String::new(),
+            };
+            if report.is_intact() {
+                println!(
+                    "✅ Audit log intact: {} events across {} file(s){range}",
+                    report.events, report.files
+                );
+                if report.first_seq.is_some_and(|seq| seq > 0) {
+                    println!("   Earlier events were rotated out of retention.");
+                }
+                Ok(())
+            } else {
+                println!(
+                    "❌ Audit log verification failed: {} problem(s) in {} events{range}",
+                    report.problems.len(),
+                    report.events
+                );
+                for problem in &report.problems {
+                    println!("   {problem}");
+                }
+                anyhow::bail!("Audit log integrity check failed")
+            }
+        }
+        crate::AuditCommands::Query {
+            actor,
+            tool,
+            since,
+            until,
+            limit,
+            json,
+        } => {
+            let query = AuditQuery {
+                actor,
+                tool,
+                since: parse_time_filter("since", since)?,
+                until: parse_time_filter("until", until)?,
+                limit,
+            };
+            let events = query_events(&log_path, &query)?;
+            if events.is_empty() {
+                println!("No matching audit events.");
+                return Ok(());
+            }
+            for event in &events {
+                if json {
+                    println!("{}", serde_json::to_string(event)?);
+                    continue;
+                }
+                let event_type = serde_json::to_value(&event.event_type)?;
+                let actor = event.actor.as_ref().map_or("-", |a| a.channel.as_str());
+                let (command, allowed) = event.action.as_ref().map_or(("-", true), |a| {
+                    (a.command.as_deref().unwrap_or("-"), a.allowed)
+                });
+                let outcome = match (&event.result, allowed) {
+                    (_, false) => "denied",
+                    (Some(r), _) if r.success => "ok",
+                    (Some(_), _) => "failed",
+                    (None, _) => "-",
+                };
+                println!(
+                    "{} {:<18} actor={actor} tool={} {outcome}: {command}",
+                    event
+                        .timestamp
+                        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                    event_type.as_str().unwrap_or("unknown"),
+                    event.tool.as_deref().unwrap_or("-"),
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,6 +817,23 @@ mod tests {
         assert_eq!(action.risk_level, Some("low".to_string()));
     }
 
+    #[tokio::test]
+    async fn tool_call_event_redacts_credentials_and_records_approval() {
+        let args =
+            serde_json::json!({"url": "https://api.example.com", "api_key": "sk-live-123456789"});
+        let approved = with_tool_approval(true, async { tool_call_approved() }).await;
+        let event = AuditEvent::tool_call("http_request", &args, approved, false);
+
+        let action = event.action.as_ref().unwrap();
+        let command = action.command.as_deref().unwrap();
+        assert!(!command.contains("sk-live-123456789"));
+        assert!(command.contains("[REDACTED]"));
+        assert!(action.approved);
+        assert!(!action.allowed);
+        assert!(matches!(event.event_type, AuditEventType::PolicyViolation));
+        assert!(!tool_call_approved());
+    }
+
     #[test]
     fn audit_event_serializes_to_json() {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -420,4 +954,183 @@ mod tests {
         );
         Ok(())
     }
+
+    // ── Signed hash chain ───────────────────────────────────
+
+    fn signed_logger(tmp: &TempDir, max_size_mb: u32) -> Result<AuditLogger> {
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb,
+            sign_events: true,
+            ..Default::default()
+        };
+        AuditLogger::new(config, tmp.path().to_path_buf())
+    }
+
+    fn log_shell(logger: &AuditLogger, channel: &str, command: &str) -> Result<()> {
+        logger.log(
+            &AuditEvent::new(AuditEventType::CommandExecution)
+                .with_actor(channel.to_string(), None, None)
+                .with_action(command.to_string(), "low".to_string(), false, true)
+                .with_tool("shell"),
+        )
+    }
+
+    fn key(tmp: &TempDir) -> Result<Vec<u8>> {
+        audit_signing_key(tmp.path())
+    }
+
+    #[test]
+    fn signed_events_form_verifiable_chain() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = signed_logger(&tmp, 10)?;
+        for command in ["ls", "pwd", "whoami"] {
+            log_shell(&logger, "cli", command)?;
+        }
+
+        let log_path = tmp.path().join("audit.log");
+        let report = verify_chain(&log_path, &key(&tmp)?)?;
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.events, 3);
+        assert_eq!(report.first_seq, Some(0));
+        assert_eq!(report.last_seq, Some(2));
+
+        let content = std::fs::read_to_string(&log_path)?;
+        let second: AuditEvent = serde_json::from_str(content.lines().nth(1).unwrap())?;
+        let first: AuditEvent = serde_json::from_str(content.lines().next().unwrap())?;
+        assert_eq!(second.prev_signature, first.signature);
+        Ok(())
+    }
+
+    #[test]
+    fn verify_detects_edited_event() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = signed_logger(&tmp, 10)?;
+        log_shell(&logger, "cli", "ls")?;
+        log_shell(&logger, "cli", "cat notes.txt")?;
+
+        let log_path = tmp.path().join("audit.log");
+        let tampered = std::fs::read_to_string(&log_path)?.replace("cat notes.txt", "echo hi");
+        std::fs::write(&log_path, tampered)?;
+
+        let report = verify_chain(&log_path, &key(&tmp)?)?;
+        assert!(!report.is_intact());
+        assert!(report.problems[0].contains("audit.log:2: signature mismatch"));
+        Ok(())
+    }
+
+    #[test]
+    fn verify_detects_deleted_event() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = signed_logger(&tmp, 10)?;
+        for command in ["ls", "rm -rf build", "pwd"] {
+            log_shell(&logger, "cli", command)?;
+        }
+
+        let log_path = tmp.path().join("audit.log");
+        let content = std::fs::read_to_string(&log_path)?;
+        let kept: Vec<&str> = content.lines().filter(|l| !l.contains("rm -rf")).collect();
+        std::fs::write(&log_path, kept.join("\n") + "\n")?;
+
+        let report = verify_chain(&log_path, &key(&tmp)?)?;
+        assert!(report
+            .problems
+            .iter()
+            .any(|p| p.contains("sequence gap (expected 1, found 2)")));
+        assert!(report.problems.iter().any(|p| p.contains("chain broken")));
+        Ok(())
+    }
+
+    #[test]
+    fn chain_continues_across_rotated_files() -> Result<()> {
+        let tmp = TempDir::new()?;
+        // max_size_mb = 0 rotates before every write, so each event lands in its own file
+        let logger = signed_logger(&tmp, 0)?;
+        for command in ["ls", "pwd", "whoami"] {
+            log_shell(&logger, "cli", command)?;
+        }
+
+        let log_path = tmp.path().join("audit.log");
+        assert_eq!(log_files(&log_path).len(), 3);
+        let report = verify_chain(&log_path, &key(&tmp)?)?;
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.last_seq, Some(2));
+
+        // Dropping a middle file breaks the chain
+        std::fs::remove_file(rotated_path(&log_path, 1))?;
+        let report = verify_chain(&log_path, &key(&tmp)?)?;
+        assert!(report.problems[0].contains("sequence gap"));
+        Ok(())
+    }
+
+    #[test]
+    fn verify_flags_unsigned_events() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+        log_shell(&logger, "cli", "ls")?;
+
+        let report = verify_chain(&tmp.path().join("audit.log"), &key(&tmp)?)?;
+        assert!(report.problems[0].ends_with("event is not signed"));
+        Ok(())
+    }
+
+    #[test]
+    fn query_filters_by_actor_tool_and_time() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let logger = signed_logger(&tmp, 10)?;
+        log_shell(&logger, "telegram", "ls")?;
+        log_shell(&logger, "cli", "pwd")?;
+        logger.log(
+            &AuditEvent::new(AuditEventType::FileAccess)
+                .with_actor("telegram".to_string(), None, None)
+                .with_tool("file_read"),
+        )?;
+        let log_path = tmp.path().join("audit.log");
+
+        let by_actor = query_events(
+            &log_path,
+            &AuditQuery {
+                actor: Some("Telegram".into()),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(by_actor.len(), 2);
+
+        let by_tool = query_events(
+            &log_path,
+            &AuditQuery {
+                actor: Some("telegram".into()),
+                tool: Some("shell".into()),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(by_tool.len(), 1);
+        assert_eq!(
+            by_tool[0].action.as_ref().unwrap().command.as_deref(),
+            Some("ls")
+        );
+
+        let future = query_events(
+            &log_path,
+            &AuditQuery {
+                since: Some(Utc::now() + chrono::Duration::hours(1)),
+                ..Default::default()
+            },
+        )?;
+        assert!(future.is_empty());
+
+        let newest = query_events(
+            &log_path,
+            &AuditQuery {
+                limit: 1,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(newest[0].tool.as_deref(), Some("file_read"));
+        Ok(())
+    }
 }
@@ -34,7 +34,7 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
             #[cfg(target_os = "linux")]
             {
                 if let Ok(sandbox) = super::firejail::FirejailSandbox::new() {
-                    return Arc::new(sandbox);
+                    return Arc::new(sandbox.with_resource_limits(&config.resources));
                 }
             }
             tracing::warn!(
@@ -59,20 +59,20 @@ pub fn create_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
         }
         SandboxBackend::Docker => {
             if let Ok(sandbox) = super::docker::DockerSandbox::new() {
-                return Arc::new(sandbox);
+                return Arc::new(sandbox.with_resource_limits(&config.resources));
             }
             tracing::warn!("Docker requested but not available, falling back to application-layer");
             Arc::new(super::traits::NoopSandbox)
         }
         SandboxBackend::Auto | SandboxBackend::None => {
             // Auto-detect best available
-            detect_best_sandbox()
+            detect_best_sandbox(config)
         }
     }
 }
 
 /// Auto-detect the best available sandbox
-fn detect_best_sandbox() -> Arc<dyn Sandbox> {
+fn detect_best_sandbox(config: &SecurityConfig) -> Arc<dyn Sandbox> {
     #[cfg(target_os = "linux")]
     {
         // Try Landlock first (native, no dependencies)
@@ -87,7 +87,7 @@ fn detect_best_sandbox() -> Arc<dyn Sandbox> {
         // Try Firejail second (user-space tool)
         if let Ok(sandbox) = super::firejail::FirejailSandbox::probe() {
             tracing::info!("Firejail sandbox enabled");
-            return Arc::new(sandbox);
+            return Arc::new(sandbox.with_resource_limits(&config.resources));
         }
     }
 
@@ -106,7 +106,7 @@ fn detect_best_sandbox() -> Arc<dyn Sandbox> {
     // Docker is heavy but works everywhere if docker is installed
     if let Ok(sandbox) = super::docker::DockerSandbox::probe() {
         tracing::info!("Docker sandbox enabled");
-        return Arc::new(sandbox);
+        return Arc::new(sandbox.with_resource_limits(&config.resources));
     }
 
     // Fallback: application-layer security only
@@ -121,7 +121,7 @@ mod tests {
 
     #[test]
     fn detect_best_sandbox_returns_something() {
-        let sandbox = detect_best_sandbox();
+        let sandbox = detect_best_sandbox(&SecurityConfig::default());
         // Should always return at least NoopSandbox
         assert!(sandbox.is_available());
     }
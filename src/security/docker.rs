@@ -1,5 +1,6 @@
 //! Docker sandbox (container isolation)
 
+use crate::config::ResourceLimitsConfig;
 use crate::security::traits::Sandbox;
 use std::process::Command;
 
@@ -7,12 +8,14 @@ use std::process::Command;
 #[derive(Debug, Clone)]
 pub struct DockerSandbox {
     image: String,
+    resources: ResourceLimitsConfig,
 }
 
 impl Default for DockerSandbox {
     fn default() -> Self {
         Self {
             image: "alpine:latest".to_string(),
+            resources: ResourceLimitsConfig::default(),
         }
     }
 }
@@ -31,7 +34,10 @@ impl DockerSandbox {
 
     pub fn with_image(image: String) -> std::io::Result<Self> {
         if Self::is_installed() {
-            Ok(Self { image })
+            Ok(Self {
+                image,
+                ..Self::default()
+            })
         } else {
             Err(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
@@ -40,6 +46,12 @@ impl DockerSandbox {
         }
     }
 
+    /// Apply these resource limits to the container
+    pub fn with_resource_limits(mut self, resources: &ResourceLimitsConfig) -> Self {
+        self.resources = resources.clone();
+        self
+    }
+
     pub fn probe() -> std::io::Result<Self> {
         Self::new()
     }
@@ -62,16 +74,8 @@ impl Sandbox for DockerSandbox {
             .collect();
 
         let mut docker_cmd = Command::new("docker");
-        docker_cmd.args([
-            "run",
-            "--rm",
-            "--memory",
-            "512m",
-            "--cpus",
-            "1.0",
-            "--network",
-            "none",
-        ]);
+        docker_cmd.args(["run", "--rm", "--cpus", "1.0", "--network", "none"]);
+        docker_cmd.args(crate::security::limits::docker_args(&self.resources));
         docker_cmd.arg(&self.image);
         docker_cmd.arg(&program);
         docker_cmd.args(&args);
@@ -159,8 +163,8 @@ mod tests {
             "must include --memory limit"
         );
         assert!(
-            args.contains(&"512m".to_string()),
-            "memory limit must be 512m"
+            args.contains(&"2048m".to_string()),
+            "memory limit must follow the default max_memory_mb"
         );
         assert!(
             args.contains(&"--cpus".to_string()),
@@ -169,6 +173,27 @@ mod tests {
         assert!(args.contains(&"1.0".to_string()), "CPU limit must be 1.0");
     }
 
+    #[test]
+    fn docker_wrap_command_passes_resource_limits() {
+        let resources = ResourceLimitsConfig {
+            max_memory_mb: 1024,
+            max_subprocesses: 32,
+            ..ResourceLimitsConfig::default()
+        };
+        let sandbox = DockerSandbox::default().with_resource_limits(&resources);
+        let mut cmd = Command::new("echo");
+        sandbox.wrap_command(&mut cmd).unwrap();
+
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+
+        assert!(args.contains(&"1024m".to_string()));
+        assert!(args.contains(&"--pids-limit".to_string()));
+        assert!(args.contains(&"32".to_string()));
+    }
+
     #[test]
     fn docker_wrap_command_preserves_original_command() {
         let sandbox = DockerSandbox::default();
@@ -199,6 +224,7 @@ mod tests {
     fn docker_wrap_command_uses_custom_image() {
         let sandbox = DockerSandbox {
             image: "ubuntu:22.04".to_string(),
+            ..DockerSandbox::default()
         };
         let mut cmd = Command::new("echo");
         sandbox.wrap_command(&mut cmd).unwrap();
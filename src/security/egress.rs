@@ -0,0 +1,673 @@
+//! Network egress policy for agent-initiated traffic
+//!
+//! A single [`EgressPolicy`] (built from `[security.egress]` and carried on
+//! [`SecurityPolicy`](super::SecurityPolicy)) decides which hosts and addresses
+//! tools may reach. It is enforced at three layers:
+//!
+//! - **Pre-flight** — [`EgressPolicy::check_url`] runs before a tool sends a
+//!   request or hands a URL to a browser.
+//! - **Connect time** — [`EgressPolicy::apply_to_builder`] installs a DNS
+//!   resolver that checks every resolved address, so a permitted name that
+//!   resolves (or re-resolves) to a private or metadata address is refused
+//!   (DNS rebinding), and re-checks every redirect hop.
+//! - **Shell** — [`EgressPolicy::check_command`] rejects commands that name a
+//!   metadata endpoint or a denied host/CIDR, and with `shell_network = false`
+//!   [`isolate_network`] runs native shell commands without any network.
+//!
+//! Cloud instance-metadata endpoints are always denied, whatever the allow
+//! lists say.
+
+use crate::config::EgressConfig;
+use anyhow::{anyhow, bail, Result};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Cloud instance-metadata host names
+const METADATA_HOSTS: &[&str] = &[
+    "metadata",
+    "metadata.google.internal",
+    "metadata.goog",
+    "instance-data",
+    "instance-data.ec2.internal",
+];
+
+/// Cloud instance-metadata addresses
+const METADATA_ADDRS: &[IpAddr] = &[
+    IpAddr::V4(Ipv4Addr::new(169, 254, 169, 254)), // AWS, GCP, Azure, OCI, DigitalOcean
+    IpAddr::V4(Ipv4Addr::new(169, 254, 170, 2)),   // AWS ECS task metadata
+    IpAddr::V4(Ipv4Addr::new(100, 100, 100, 200)), // Alibaba Cloud
+    IpAddr::V4(Ipv4Addr::new(192, 0, 0, 192)),     // Oracle Cloud (legacy)
+    IpAddr::V6(Ipv6Addr::new(0xfd00, 0xec2, 0, 0, 0, 0, 0, 0x254)), // AWS IMDS over IPv6
+];
+
+/// Maximum redirect hops followed by policy-wrapped clients
+const MAX_REDIRECTS: usize = 10;
+
+/// An IP network in CIDR notation (`10.0.0.0/8`, `fd00::/8`, or a bare address)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, canonical_ip(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (raw, None),
+        };
+        let addr = addr
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map(canonical_ip)
+            .map_err(|_| anyhow!("invalid CIDR '{raw}'"))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .ok_or_else(|| anyhow!("invalid CIDR prefix in '{raw}'"))?,
+            None => max,
+        };
+        Ok(Self { addr, prefix })
+    }
+}
+
+/// Central allow/deny decision for outbound connections made by tools
+#[derive(Debug, Clone)]
+pub struct EgressPolicy {
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+    allowed_cidrs: Vec<Cidr>,
+    denied_cidrs: Vec<Cidr>,
+    block_private_networks: bool,
+    shell_network: bool,
+}
+
+impl Default for EgressPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            allowed_cidrs: Vec::new(),
+            denied_cidrs: Vec::new(),
+            block_private_networks: true,
+            shell_network: true,
+        }
+    }
+}
+
+impl EgressPolicy {
+    /// Build from `[security.egress]`. Invalid CIDRs are rejected by
+    /// `Config::validate`; any that slip through are skipped with a warning.
+    pub fn from_config(config: &EgressConfig) -> Self {
+        Self {
+            allowed_hosts: normalize_patterns(&config.allowed_hosts),
+            denied_hosts: normalize_patterns(&config.denied_hosts),
+            allowed_cidrs: parse_cidrs("allowed_cidrs", &config.allowed_cidrs),
+            denied_cidrs: parse_cidrs("denied_cidrs", &config.denied_cidrs),
+            block_private_networks: config.block_private_networks,
+            shell_network: config.shell_network,
+        }
+    }
+
+    /// Whether shell commands may use the network at all
+    pub fn shell_network(&self) -> bool {
+        self.shell_network
+    }
+
+    /// Check a host name or IP literal without resolving it.
+    pub fn check_host(&self, host: &str) -> Result<()> {
+        let host = normalize_host(host);
+        if host.is_empty() {
+            bail!("Egress policy: missing host");
+        }
+        if METADATA_HOSTS.contains(&host.as_str()) {
+            bail!("Egress policy blocked {host}: cloud metadata endpoint");
+        }
+        if matches_any(&host, &self.denied_hosts) {
+            bail!("Egress policy blocked {host}: listed in security.egress.denied_hosts");
+        }
+
+        let ip = host.parse::<IpAddr>().ok().or_else(|| {
+            // `localhost` never needs DNS to land on loopback
+            (host == "localhost" || host.ends_with(".localhost"))
+                .then_some(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        });
+        let listed = self.allowed_hosts.is_empty()
+            || matches_any(&host, &self.allowed_hosts)
+            || ip.is_some_and(|ip| self.allowed_cidrs.iter().any(|c| c.contains(ip)));
+        if !listed {
+            bail!("Egress policy blocked {host}: not in security.egress.allowed_hosts");
+        }
+
+        match ip {
+            Some(ip) => self.check_ip(ip),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a concrete address (literal or resolved).
+    pub fn check_ip(&self, ip: IpAddr) -> Result<()> {
+        let ip = canonical_ip(ip);
+        if METADATA_ADDRS.contains(&ip) {
+            bail!("Egress policy blocked {ip}: cloud metadata endpoint");
+        }
+        if self.denied_cidrs.iter().any(|c| c.contains(ip)) {
+            bail!("Egress policy blocked {ip}: listed in security.egress.denied_cidrs");
+        }
+        if self.allowed_cidrs.iter().any(|c| c.contains(ip)) {
+            return Ok(());
+        }
+        if self.block_private_networks && is_non_global(ip) {
+            bail!("Egress policy blocked {ip}: private or non-routable address");
+        }
+        Ok(())
+    }
+
+    /// Check the host of a URL. Numeric hosts are normalized first, so
+    /// `http://2130706433/` is checked as `127.0.0.1`.
+    pub fn check_url(&self, url: &str) -> Result<()> {
+        let parsed =
+            reqwest::Url::parse(url.trim()).map_err(|e| anyhow!("Invalid URL '{url}': {e}"))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| anyhow!("URL '{url}' has no host"))?;
+        self.check_host(host)
+    }
+
+    /// Reject shell commands that name a metadata endpoint or a denied host.
+    ///
+    /// Only the always-on metadata block, `denied_hosts` and `denied_cidrs`
+    /// apply here: commands routinely mention loopback and private addresses
+    /// (`grep localhost /etc/hosts`, dev servers binding `0.0.0.0`), so the
+    /// allow lists and `block_private_networks` are left to the HTTP tools.
+    /// Best effort: only hosts written literally in the command are seen. Use
+    /// `shell_network = false` to take the network away from shell commands.
+    pub fn check_command(&self, command: &str) -> Result<()> {
+        let tokens = command.split(|c: char| {
+            c.is_whitespace() || matches!(c, '"' | '\'' | ';' | '|' | '&' | '(' | ')' | '<' | '>')
+        });
+        for token in tokens.filter(|t| !t.is_empty()) {
+            if let Some(idx) = token.find("://") {
+                // Start at the scheme, skipping prefixes like `--url=` or `URL=`
+                let start = token[..idx]
+                    .rfind(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')))
+                    .map_or(0, |i| i + 1);
+                if let Some(host) = reqwest::Url::parse(&token[start..])
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                {
+                    self.check_shell_host(&host)?;
+                }
+                continue;
+            }
+
+            // `--host=addr`, `user@host:path`, `host:port/path`, `host/path`
+            let token = token.split_once('=').map_or(token, |(_, value)| value);
+            let authority = token.rsplit_once('@').map_or(token, |(_, host)| host);
+            let authority = authority.split('/').next().unwrap_or_default();
+            let host = bare_host(authority);
+            if host.parse::<IpAddr>().is_ok() {
+                self.check_shell_host(host)?;
+                continue;
+            }
+            // A bare `metadata` word is too common in commands to block
+            let host = normalize_host(host);
+            if host.contains('.') || !METADATA_HOSTS.contains(&host.as_str()) {
+                self.check_shell_host(&host)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Metadata and deny-list checks for a host seen in a shell command.
+    fn check_shell_host(&self, host: &str) -> Result<()> {
+        let host = normalize_host(host);
+        if METADATA_HOSTS.contains(&host.as_str()) {
+            bail!("Egress policy blocked {host}: cloud metadata endpoint");
+        }
+        if matches_any(&host, &self.denied_hosts) {
+            bail!("Egress policy blocked {host}: listed in security.egress.denied_hosts");
+        }
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            let ip = canonical_ip(ip);
+            if METADATA_ADDRS.contains(&ip) {
+                bail!("Egress policy blocked {ip}: cloud metadata endpoint");
+            }
+            if self.denied_cidrs.iter().any(|c| c.contains(ip)) {
+                bail!("Egress policy blocked {ip}: listed in security.egress.denied_cidrs");
+            }
+        }
+        Ok(())
+    }
+
+    /// Install the policy on a reqwest client: every resolved address and
+    /// every redirect target is checked before a connection is made.
+    pub fn apply_to_builder(
+        self: &Arc<Self>,
+        builder: reqwest::ClientBuilder,
+    ) -> reqwest::ClientBuilder {
+        let resolver = EgressResolver {
+            policy: Arc::clone(self),
+            exempt_hosts: runtime_proxy_hosts(),
+        };
+        let redirects = Arc::clone(self);
+        builder
+            .dns_resolver(Arc::new(resolver))
+            .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    return attempt.error(format!("too many redirects (max {MAX_REDIRECTS})"));
+                }
+                match redirects.check_url(attempt.url().as_str()) {
+                    Ok(()) => attempt.follow(),
+                    Err(e) => attempt.error(e.to_string()),
+                }
+            }))
+    }
+
+    /// Build a tool HTTP client with the runtime proxy for `service_key`,
+    /// the given timeouts and this policy applied.
+    pub fn build_client(
+        self: &Arc<Self>,
+        service_key: &str,
+        timeout_secs: u64,
+        connect_timeout_secs: u64,
+    ) -> Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(timeout_secs))
+            .connect_timeout(std::time::Duration::from_secs(connect_timeout_secs));
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, service_key);
+        Ok(self.apply_to_builder(builder).build()?)
+    }
+}
+
+/// DNS resolver that refuses names whose addresses violate the policy
+struct EgressResolver {
+    policy: Arc<EgressPolicy>,
+    /// Configured proxy hosts, which must stay reachable even on loopback
+    exempt_hosts: Vec<String>,
+}
+
+impl reqwest::dns::Resolve for EgressResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let policy = Arc::clone(&self.policy);
+        let host = normalize_host(name.as_str());
+        let exempt = self.exempt_hosts.contains(&host);
+        Box::pin(async move {
+            if !exempt {
+                policy.check_host(&host)?;
+            }
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if !exempt {
+                // Any disallowed answer rejects the name: a rebinding server only
+                // needs one private address in the set to win a race
+                for addr in &addrs {
+                    policy
+                        .check_ip(addr.ip())
+                        .map_err(|e| anyhow!("{host} resolves to {}: {e}", addr.ip()))?;
+                }
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Run a command in a fresh, empty network namespace (loopback only, down).
+///
+/// Tries a plain network namespace first (needs `CAP_SYS_ADMIN`), then an
+/// unprivileged user + network namespace. Spawning fails if neither works, so
+/// `shell_network = false` never silently runs with network access.
+#[cfg(target_os = "linux")]
+pub fn isolate_network(cmd: &mut tokio::process::Command) -> std::io::Result<()> {
+    // SAFETY: the closure only calls the async-signal-safe `unshare(2)`
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::unshare(libc::CLONE_NEWNET) == 0
+                || libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) == 0
+            {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Network namespaces are Linux-only; refuse rather than run with network.
+#[cfg(not(target_os = "linux"))]
+pub fn isolate_network(_cmd: &mut tokio::process::Command) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "security.egress.shell_network = false requires Linux network namespaces",
+    ))
+}
+
+fn canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(IpAddr::V6(v6), IpAddr::V4),
+        v4 @ IpAddr::V4(_) => v4,
+    }
+}
+
+/// Returns true if the address is not globally routable.
+fn is_non_global(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || (a == 100 && (64..=127).contains(&b)) // Shared address space (RFC 6598)
+                || a >= 240 // Reserved
+                || a == 0 // "This network"
+                || (a == 192 && b == 0 && (c == 0 || c == 2)) // IETF assignments + TEST-NET-1
+                || (a == 198 && b == 51) // Documentation
+                || (a == 203 && b == 0) // Documentation
+                || (a == 198 && (18..=19).contains(&b)) // Benchmarking
+        }
+        IpAddr::V6(v6) => {
+            let segs = v6.segments();
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (segs[0] & 0xfe00) == 0xfc00 // Unique-local
+                || (segs[0] & 0xffc0) == 0xfe80 // Link-local
+                || (segs[0] == 0x2001 && segs[1] == 0x0db8) // Documentation
+        }
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// Host part of `host`, `host:port`, `[v6]:port` or a bare IPv6 literal.
+fn bare_host(authority: &str) -> &str {
+    if let Some(rest) = authority.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or_default();
+    }
+    if authority.parse::<Ipv6Addr>().is_ok() {
+        return authority;
+    }
+    authority.split(':').next().unwrap_or_default()
+}
+
+/// Normalize host patterns: `https://Example.com:443/x` → `example.com`,
+/// `*.example.com` and `*` are kept as wildcards.
+fn normalize_patterns(patterns: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = patterns
+        .iter()
+        .filter_map(|raw| {
+            let raw = raw.trim().to_ascii_lowercase();
+            let raw = raw.split_once("://").map_or(raw.as_str(), |(_, rest)| rest);
+            let authority = raw.split('/').next().unwrap_or_default();
+            let host = normalize_host(bare_host(authority));
+            (!host.is_empty()).then_some(host)
+        })
+        .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// `example.com` matches itself and subdomains, `*.example.com` only
+/// subdomains, `*` everything.
+fn matches_any(host: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| {
+        if pattern == "*" {
+            return true;
+        }
+        let (domain, subdomains_only) = match pattern.strip_prefix("*.") {
+            Some(domain) => (domain, true),
+            None => (pattern.as_str(), false),
+        };
+        (!subdomains_only && host == domain)
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+fn parse_cidrs(field: &str, values: &[String]) -> Vec<Cidr> {
+    values
+        .iter()
+        .filter_map(|value| match value.parse() {
+            Ok(cidr) => Some(cidr),
+            Err(e) => {
+                tracing::warn!("Ignoring security.egress.{field} entry: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+fn runtime_proxy_hosts() -> Vec<String> {
+    let proxy = crate::config::runtime_proxy_config();
+    if !proxy.enabled {
+        return Vec::new();
+    }
+    [proxy.http_proxy, proxy.https_proxy, proxy.all_proxy]
+        .into_iter()
+        .flatten()
+        .filter_map(|url| {
+            reqwest::Url::parse(url.trim())
+                .ok()
+                .and_then(|url| url.host_str().map(normalize_host))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(configure: impl FnOnce(&mut EgressConfig)) -> EgressPolicy {
+        let mut config = EgressConfig::default();
+        configure(&mut config);
+        EgressPolicy::from_config(&config)
+    }
+
+    #[test]
+    fn cidr_parsing_and_matching() {
+        let net: Cidr = "10.20.0.0/16".parse().unwrap();
+        assert!(net.contains("10.20.3.4".parse().unwrap()));
+        assert!(!net.contains("10.21.0.1".parse().unwrap()));
+        assert!(net.contains("::ffff:10.20.0.9".parse().unwrap()));
+
+        let single: Cidr = "fd00::1".parse().unwrap();
+        assert!(single.contains("fd00::1".parse().unwrap()));
+        assert!(!single.contains("fd00::2".parse().unwrap()));
+
+        let all: Cidr = "0.0.0.0/0".parse().unwrap();
+        assert!(all.contains("8.8.8.8".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("example.com/8".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn default_policy_blocks_private_and_metadata() {
+        let policy = EgressPolicy::default();
+        assert!(policy.check_url("https://example.com/").is_ok());
+        assert!(policy
+            .check_url("http://169.254.169.254/latest/meta-data/")
+            .is_err());
+        assert!(policy
+            .check_url("http://metadata.google.internal/")
+            .is_err());
+        assert!(policy.check_url("http://[fd00:ec2::254]/").is_err());
+        assert!(policy.check_url("http://localhost:8080/").is_err());
+        assert!(policy.check_url("http://10.0.0.5/").is_err());
+        assert!(policy.check_url("http://[::ffff:192.168.1.1]/").is_err());
+        // Alternate numeric spellings are normalized before the check
+        assert!(policy.check_url("http://2130706433/").is_err());
+        assert!(policy.check_url("http://0x7f.1/").is_err());
+    }
+
+    #[test]
+    fn metadata_stays_blocked_when_private_ranges_are_allowed() {
+        let policy = policy(|c| {
+            c.block_private_networks = false;
+            c.allowed_cidrs = vec!["169.254.0.0/16".into()];
+        });
+        assert!(policy.check_url("http://10.0.0.5/").is_ok());
+        assert!(policy.check_url("http://169.254.1.1/").is_ok());
+        assert!(policy.check_url("http://169.254.169.254/").is_err());
+        assert!(policy.check_host("metadata.goog").is_err());
+    }
+
+    #[test]
+    fn allowed_cidrs_open_specific_private_ranges() {
+        let policy = policy(|c| c.allowed_cidrs = vec!["10.20.0.0/16".into()]);
+        assert!(policy.check_ip("10.20.1.1".parse().unwrap()).is_ok());
+        assert!(policy.check_ip("10.30.1.1".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn host_allow_and_deny_lists() {
+        let policy = policy(|c| {
+            c.allowed_hosts = vec!["https://Example.com/".into(), "*.rust-lang.org".into()];
+            c.denied_hosts = vec!["private.example.com".into()];
+        });
+        assert!(policy.check_host("example.com").is_ok());
+        assert!(policy.check_host("api.example.com").is_ok());
+        assert!(policy.check_host("docs.rust-lang.org").is_ok());
+        assert!(policy.check_host("rust-lang.org").is_err());
+        assert!(policy.check_host("other.com").is_err());
+        assert!(policy.check_host("private.example.com").is_err());
+        assert!(policy.check_host("x.private.example.com").is_err());
+        assert!(policy.check_host("8.8.8.8").is_err());
+    }
+
+    #[test]
+    fn denied_cidrs_block_public_addresses() {
+        let policy = policy(|c| c.denied_cidrs = vec!["203.0.0.0/8".into(), "8.8.8.8".into()]);
+        assert!(policy.check_ip("8.8.8.8".parse().unwrap()).is_err());
+        assert!(policy.check_ip("8.8.4.4".parse().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn check_command_finds_literal_targets() {
+        let policy = EgressPolicy::default();
+        assert!(policy
+            .check_command("curl -s http://169.254.169.254/latest/meta-data/")
+            .is_err());
+        assert!(policy.check_command("curl 169.254.169.254/latest").is_err());
+        assert!(policy
+            .check_command("wget -qO- metadata.google.internal")
+            .is_err());
+        assert!(policy
+            .check_command("curl --url=http://[fd00:ec2::254]/latest")
+            .is_err());
+        assert!(policy
+            .check_command("curl https://example.com | head")
+            .is_ok());
+        assert!(policy.check_command("git status && ls -la src/").is_ok());
+        assert!(policy.check_command("echo 1.2.3").is_ok());
+        assert!(policy.check_command("cat metadata/notes.txt").is_ok());
+    }
+
+    #[test]
+    fn check_command_allows_loopback_and_private_addresses() {
+        let policy = EgressPolicy::default();
+        for command in [
+            "grep localhost /etc/hosts",
+            "curl 127.0.0.1:8080",
+            "python -m http.server --bind 127.0.0.1",
+            "npm run dev -- --host 0.0.0.0",
+            "curl 'http://localhost:9000'",
+            "ssh root@192.168.0.10",
+        ] {
+            assert!(policy.check_command(command).is_ok(), "{command}");
+        }
+    }
+
+    #[test]
+    fn check_command_applies_deny_lists() {
+        let policy = policy(|c| {
+            c.allowed_hosts = vec!["github.com".into()];
+            c.denied_hosts = vec!["*.evil.example".into()];
+            c.denied_cidrs = vec!["10.0.0.0/8".into()];
+        });
+        assert!(policy
+            .check_command("git clone https://github.com/org/repo")
+            .is_ok());
+        assert!(policy.check_command("curl https://other.example/x").is_ok());
+        assert!(policy
+            .check_command("curl https://api.evil.example/x")
+            .is_err());
+        assert!(policy.check_command("wget cdn.evil.example/file").is_err());
+        assert!(policy.check_command("ssh admin@10.1.2.3").is_err());
+    }
+
+    #[tokio::test]
+    async fn client_refuses_names_resolving_to_private_addresses() {
+        let policy = Arc::new(EgressPolicy::default());
+        let client = policy
+            .apply_to_builder(reqwest::Client::builder())
+            .build()
+            .unwrap();
+        // `localhost` is refused before any connection attempt
+        let err = client.get("http://localhost:1/").send().await.unwrap_err();
+        assert!(
+            format!("{err:?}").contains("Egress policy blocked"),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn isolate_network_leaves_only_loopback() {
+        let mut cmd = tokio::process::Command::new("cat");
+        cmd.arg("/proc/net/dev");
+        isolate_network(&mut cmd).unwrap();
+        let Ok(output) = cmd.output().await else {
+            // Namespaces unavailable here (e.g. nested container without userns)
+            return;
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let interfaces: Vec<&str> = stdout
+            .lines()
+            .skip(2)
+            .filter_map(|line| line.split(':').next())
+            .map(str::trim)
+            .collect();
+        assert_eq!(interfaces, vec!["lo"]);
+    }
+}
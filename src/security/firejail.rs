@@ -2,18 +2,21 @@
 //!
 //! Firejail is a SUID sandbox program that Linux applications use to sandbox themselves.
 
+use crate::config::ResourceLimitsConfig;
 use crate::security::traits::Sandbox;
 use std::process::Command;
 
 /// Firejail sandbox backend for Linux
 #[derive(Debug, Clone, Default)]
-pub struct FirejailSandbox;
+pub struct FirejailSandbox {
+    resources: ResourceLimitsConfig,
+}
 
 impl FirejailSandbox {
     /// Create a new Firejail sandbox
     pub fn new() -> std::io::Result<Self> {
         if Self::is_installed() {
-            Ok(Self)
+            Ok(Self::default())
         } else {
             Err(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
@@ -27,6 +30,12 @@ impl FirejailSandbox {
         Self::new()
     }
 
+    /// Apply these resource limits inside the sandbox
+    pub fn with_resource_limits(mut self, resources: &ResourceLimitsConfig) -> Self {
+        self.resources = resources.clone();
+        self
+    }
+
     /// Check if firejail is installed
     fn is_installed() -> bool {
         Command::new("firejail")
@@ -59,6 +68,7 @@ impl Sandbox for FirejailSandbox {
             "--noprofile",    // Skip profile loading
             "--quiet",        // Suppress warnings
         ]);
+        firejail_cmd.args(crate::security::limits::firejail_args(&self.resources));
 
         // Add the original command
         firejail_cmd.arg(&program);
@@ -88,12 +98,13 @@ mod tests {
 
     #[test]
     fn firejail_sandbox_name() {
-        assert_eq!(FirejailSandbox.name(), "firejail");
+        assert_eq!(FirejailSandbox::default().name(), "firejail");
     }
 
     #[test]
     fn firejail_description_mentions_dependency() {
-        let desc = FirejailSandbox.description();
+        let sandbox = FirejailSandbox::default();
+        let desc = sandbox.description();
         assert!(desc.contains("firejail"));
     }
 
@@ -112,7 +123,7 @@ mod tests {
 
     #[test]
     fn firejail_wrap_command_prepends_firejail() {
-        let sandbox = FirejailSandbox;
+        let sandbox = FirejailSandbox::default();
         let mut cmd = Command::new("echo");
         cmd.arg("test");
 
@@ -130,7 +141,7 @@ mod tests {
 
     #[test]
     fn firejail_wrap_command_includes_all_security_flags() {
-        let sandbox = FirejailSandbox;
+        let sandbox = FirejailSandbox::default();
         let mut cmd = Command::new("echo");
         cmd.arg("test");
         sandbox.wrap_command(&mut cmd).unwrap();
@@ -166,9 +177,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn firejail_wrap_command_passes_resource_limits() {
+        let resources = ResourceLimitsConfig {
+            max_memory_mb: 256,
+            max_cpu_time_seconds: 30,
+            ..ResourceLimitsConfig::default()
+        };
+        let sandbox = FirejailSandbox::default().with_resource_limits(&resources);
+        let mut cmd = Command::new("echo");
+        sandbox.wrap_command(&mut cmd).unwrap();
+
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+
+        assert!(args.contains(&"--rlimit-cpu=30".to_string()));
+        assert!(!args.iter().any(|arg| arg.starts_with("--rlimit-as")));
+    }
+
     #[test]
     fn firejail_wrap_command_preserves_original_command() {
-        let sandbox = FirejailSandbox;
+        let sandbox = FirejailSandbox::default();
         let mut cmd = Command::new("ls");
         cmd.arg("-la");
         cmd.arg("/workspace");
@@ -0,0 +1,457 @@
+//! Per-command resource limits.
+//!
+//! Applies [`ResourceLimitsConfig`] to every child process the agent spawns
+//! (shell tool, cron shell jobs, skill scripts run through the shell tool):
+//!
+//! - **rlimits** (Unix): `RLIMIT_CPU` for CPU time, set in the child between
+//!   `fork` and `exec`. Off by default, since a release build easily spends
+//!   minutes of CPU.
+//! - **cgroup v2** (Linux): when the daemon runs in a delegated cgroup with the
+//!   `memory` and `pids` controllers enabled in `cgroup.subtree_control`, each
+//!   command gets its own leaf cgroup with `memory.max` and `pids.max`. This
+//!   caps the whole process tree rather than individual processes, and is the
+//!   only place `max_memory_mb` and `max_subprocesses` are enforced natively.
+//!   `RLIMIT_DATA`/`RLIMIT_AS` count reserved address space, which JVM, Node
+//!   and Go runtimes reserve generously, and `RLIMIT_NPROC` is per-user.
+//!
+//! The defaults (2 GiB of memory, 4096 tasks) stop a runaway build from taking
+//! the host down while leaving room for `cargo`, `make -j` or `node`, whose
+//! threads all count against `pids.max`.
+//!
+//! After the command exits, [`CommandLimits::violation`] reports whether it
+//! was killed (or throttled) for exceeding a limit.
+
+use crate::config::ResourceLimitsConfig;
+use std::fmt;
+use std::process::ExitStatus;
+
+/// Container memory cap used when `max_memory_mb` is set to 0.
+const DEFAULT_DOCKER_MEMORY: &str = "512m";
+
+/// A limit the command ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// Killed after exceeding `max_cpu_time_seconds`
+    CpuTime(u64),
+    /// OOM-killed inside its cgroup after exceeding `max_memory_mb`
+    Memory(u32),
+    /// A fork/clone was refused because `max_subprocesses` was reached
+    Subprocesses(u32),
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CpuTime(secs) => {
+                write!(f, "command killed: exceeded CPU time limit ({secs}s)")
+            }
+            Self::Memory(mb) => write!(f, "command killed: exceeded memory limit ({mb} MB)"),
+            Self::Subprocesses(max) => {
+                write!(f, "command hit the subprocess limit ({max} tasks)")
+            }
+        }
+    }
+}
+
+/// Limits attached to one spawned command.
+///
+/// Keep this alive until the child has exited: dropping it kills anything
+/// left in the command's cgroup and removes the cgroup.
+#[derive(Debug)]
+pub struct CommandLimits {
+    config: ResourceLimitsConfig,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<cgroup::Leaf>,
+}
+
+impl CommandLimits {
+    /// Attach limits to `cmd` before it is spawned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if a cgroup was created but cannot be
+    /// configured; without cgroup support only the CPU rlimit applies.
+    pub fn apply(
+        cmd: &mut tokio::process::Command,
+        config: &ResourceLimitsConfig,
+    ) -> std::io::Result<Self> {
+        #[cfg(target_os = "linux")]
+        let cgroup = cgroup::Leaf::create(config)?;
+
+        #[cfg(unix)]
+        {
+            let cpu_secs = config.max_cpu_time_seconds;
+            #[cfg(target_os = "linux")]
+            let procs_fd = cgroup.as_ref().map(cgroup::Leaf::procs_fd);
+
+            // SAFETY: the closure only calls async-signal-safe functions
+            // (setrlimit, write) and does not allocate.
+            unsafe {
+                cmd.pre_exec(move || {
+                    #[cfg(target_os = "linux")]
+                    if let Some(fd) = procs_fd {
+                        // "0" moves the calling process into the cgroup.
+                        if libc::write(fd, b"0".as_ptr().cast(), 1) != 1 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                    if cpu_secs > 0 {
+                        // Soft limit sends SIGXCPU; the hard limit one second
+                        // later is a SIGKILL backstop.
+                        let limit = libc::rlimit {
+                            rlim_cur: cpu_secs as libc::rlim_t,
+                            rlim_max: cpu_secs.saturating_add(1) as libc::rlim_t,
+                        };
+                        check_rc(libc::setrlimit(libc::RLIMIT_CPU, &raw const limit))?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+
+        Ok(Self {
+            config: config.clone(),
+            #[cfg(target_os = "linux")]
+            cgroup,
+        })
+    }
+
+    /// Inspect the exit status (and cgroup events) for a limit violation.
+    pub fn violation(&self, status: ExitStatus) -> Option<LimitViolation> {
+        #[cfg(target_os = "linux")]
+        if let Some(leaf) = &self.cgroup {
+            if self.config.memory_monitoring
+                && self.config.max_memory_mb > 0
+                && leaf.event_count("memory.events", "oom_kill") > 0
+            {
+                return Some(LimitViolation::Memory(self.config.max_memory_mb));
+            }
+        }
+
+        if self.config.max_cpu_time_seconds > 0 && killed_by_cpu_limit(status) {
+            return Some(LimitViolation::CpuTime(self.config.max_cpu_time_seconds));
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(leaf) = &self.cgroup {
+            if self.config.max_subprocesses > 0 && leaf.event_count("pids.events", "max") > 0 {
+                return Some(LimitViolation::Subprocesses(self.config.max_subprocesses));
+            }
+        }
+
+        None
+    }
+}
+
+/// Turn a libc return code into an `io::Result`.
+#[cfg(unix)]
+fn check_rc(rc: libc::c_int) -> std::io::Result<()> {
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// SIGXCPU either kills the child directly or, when it ran under `sh -c`,
+/// shows up as the shell's `128 + SIGXCPU` exit code.
+fn killed_by_cpu_limit(status: ExitStatus) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal() == Some(libc::SIGXCPU) || status.code() == Some(128 + libc::SIGXCPU)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        false
+    }
+}
+
+/// `firejail` flags equivalent to the configured limits.
+///
+/// Memory and task caps are left to the command's cgroup: firejail only
+/// offers `--rlimit-as` and `--rlimit-nproc`, with the problems described in
+/// the module docs.
+pub fn firejail_args(config: &ResourceLimitsConfig) -> Vec<String> {
+    let mut args = Vec::new();
+    if config.max_cpu_time_seconds > 0 {
+        args.push(format!("--rlimit-cpu={}", config.max_cpu_time_seconds));
+    }
+    args
+}
+
+/// `docker run` flags equivalent to the configured limits.
+///
+/// Containers keep the historical `--memory 512m` cap when `max_memory_mb`
+/// is set to 0.
+pub fn docker_args(config: &ResourceLimitsConfig) -> Vec<String> {
+    let mut args = vec!["--memory".to_string()];
+    if config.max_memory_mb > 0 {
+        args.push(format!("{}m", config.max_memory_mb));
+    } else {
+        args.push(DEFAULT_DOCKER_MEMORY.to_string());
+    }
+    if config.max_cpu_time_seconds > 0 {
+        args.push("--ulimit".to_string());
+        args.push(format!(
+            "cpu={}:{}",
+            config.max_cpu_time_seconds,
+            config.max_cpu_time_seconds.saturating_add(1)
+        ));
+    }
+    if config.max_subprocesses > 0 {
+        args.push("--pids-limit".to_string());
+        args.push(config.max_subprocesses.to_string());
+    }
+    args
+}
+
+#[cfg(target_os = "linux")]
+mod cgroup {
+    use super::ResourceLimitsConfig;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::OnceLock;
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// The daemon's own cgroup, if it can host per-command children.
+    fn parent() -> Option<&'static Path> {
+        static PARENT: OnceLock<Option<PathBuf>> = OnceLock::new();
+        PARENT
+            .get_or_init(|| {
+                let own = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+                let relative = own.lines().find_map(|line| line.strip_prefix("0::"))?;
+                let dir = Path::new(CGROUP_ROOT).join(relative.trim_start_matches('/'));
+                let controllers =
+                    std::fs::read_to_string(dir.join("cgroup.subtree_control")).ok()?;
+                let enabled: Vec<&str> = controllers.split_whitespace().collect();
+                if enabled.contains(&"memory") && enabled.contains(&"pids") {
+                    tracing::debug!("Per-command cgroups enabled under {}", dir.display());
+                    Some(dir)
+                } else {
+                    None
+                }
+            })
+            .as_deref()
+    }
+
+    /// A leaf cgroup holding a single command's process tree.
+    #[derive(Debug)]
+    pub(super) struct Leaf {
+        path: PathBuf,
+        procs: File,
+    }
+
+    impl Leaf {
+        pub(super) fn create(config: &ResourceLimitsConfig) -> std::io::Result<Option<Self>> {
+            let Some(parent) = parent() else {
+                return Ok(None);
+            };
+
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            let path = parent.join(format!("zeroclaw-cmd-{}-{id}", std::process::id()));
+            if let Err(e) = std::fs::create_dir(&path) {
+                tracing::debug!("Cannot create command cgroup {}: {e}", path.display());
+                return Ok(None);
+            }
+
+            let configure = || -> std::io::Result<File> {
+                if config.max_memory_mb > 0 {
+                    let bytes = u64::from(config.max_memory_mb) * 1024 * 1024;
+                    std::fs::write(path.join("memory.max"), bytes.to_string())?;
+                    // Without this the limit can be dodged by swapping.
+                    let _ = std::fs::write(path.join("memory.swap.max"), "0");
+                }
+                if config.max_subprocesses > 0 {
+                    std::fs::write(path.join("pids.max"), config.max_subprocesses.to_string())?;
+                }
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .open(path.join("cgroup.procs"))
+            };
+
+            match configure() {
+                Ok(procs) => Ok(Some(Self { path, procs })),
+                Err(e) => {
+                    let _ = std::fs::remove_dir(&path);
+                    Err(e)
+                }
+            }
+        }
+
+        pub(super) fn procs_fd(&self) -> i32 {
+            self.procs.as_raw_fd()
+        }
+
+        pub(super) fn path(&self) -> &Path {
+            &self.path
+        }
+
+        /// Read a counter such as `oom_kill` from `memory.events`.
+        pub(super) fn event_count(&self, file: &str, key: &str) -> u64 {
+            std::fs::read_to_string(self.path.join(file))
+                .ok()
+                .and_then(|events| {
+                    events.lines().find_map(|line| {
+                        let (name, value) = line.split_once(' ')?;
+                        if name == key {
+                            value.trim().parse().ok()
+                        } else {
+                            None
+                        }
+                    })
+                })
+                .unwrap_or(0)
+        }
+    }
+
+    /// Remove an emptied cgroup, waiting for killed tasks to be reaped.
+    fn remove(path: &Path) {
+        for _ in 0..50 {
+            match std::fs::remove_dir(path) {
+                Err(e) if e.raw_os_error() == Some(libc::EBUSY) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                _ => return,
+            }
+        }
+        tracing::warn!("Failed to remove command cgroup {}", path.display());
+    }
+
+    impl Drop for Leaf {
+        fn drop(&mut self) {
+            // Kill stragglers (backgrounded grandchildren) so the cgroup can
+            // be removed. `cgroup.kill` needs Linux 5.14+.
+            let _ = std::fs::write(self.path.join("cgroup.kill"), "1");
+            if std::fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            // The killed tasks take a moment to exit; wait for them off the
+            // async workers.
+            let path = std::mem::take(&mut self.path);
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => drop(handle.spawn_blocking(move || remove(&path))),
+                Err(_) => remove(&path),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(memory_mb: u32, cpu_secs: u64, subprocesses: u32) -> ResourceLimitsConfig {
+        ResourceLimitsConfig {
+            max_memory_mb: memory_mb,
+            max_cpu_time_seconds: cpu_secs,
+            max_subprocesses: subprocesses,
+            memory_monitoring: true,
+        }
+    }
+
+    #[test]
+    fn violation_messages_name_the_limit() {
+        assert!(LimitViolation::CpuTime(5)
+            .to_string()
+            .contains("CPU time limit (5s)"));
+        assert!(LimitViolation::Memory(256)
+            .to_string()
+            .contains("memory limit (256 MB)"));
+        assert!(LimitViolation::Subprocesses(4)
+            .to_string()
+            .contains("subprocess limit"));
+    }
+
+    #[test]
+    fn firejail_args_skip_zero_limits() {
+        assert!(firejail_args(&limits(512, 0, 10)).is_empty());
+        assert_eq!(firejail_args(&limits(512, 30, 10)), vec!["--rlimit-cpu=30"]);
+    }
+
+    #[test]
+    fn docker_args_map_all_limits() {
+        let args = docker_args(&limits(256, 30, 8));
+        assert_eq!(
+            args,
+            vec![
+                "--memory",
+                "256m",
+                "--ulimit",
+                "cpu=30:31",
+                "--pids-limit",
+                "8"
+            ]
+        );
+    }
+
+    #[test]
+    fn docker_args_keep_default_memory_cap() {
+        let args = docker_args(&limits(0, 0, 0));
+        assert_eq!(args, vec!["--memory", "512m"]);
+    }
+
+    #[test]
+    fn defaults_cap_memory_and_tasks_but_not_cpu_time() {
+        let defaults = ResourceLimitsConfig::default();
+        assert_eq!(defaults.max_memory_mb, 2048);
+        assert_eq!(defaults.max_cpu_time_seconds, 0);
+        assert_eq!(defaults.max_subprocesses, 4096);
+        assert_eq!(
+            docker_args(&defaults),
+            vec!["--memory", "2048m", "--pids-limit", "4096"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn rlimits_are_applied_to_child() {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("ulimit -t; ulimit -d");
+        let limits = CommandLimits::apply(&mut cmd, &limits(64, 7, 0)).unwrap();
+        let output = cmd.output().await.unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        // Memory is only capped through the cgroup, never RLIMIT_DATA.
+        assert_eq!(lines, vec!["7", "unlimited"]);
+        assert_eq!(limits.violation(output.status), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn parallel_command_succeeds_under_default_limits() {
+        // Stand-in for `make -j` / `cargo build`: many concurrent tasks and
+        // no CPU-time cap.
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c")
+            .arg("for i in $(seq 64); do sleep 0.2 & done; wait; ulimit -t; ulimit -d");
+        let limits = CommandLimits::apply(&mut cmd, &ResourceLimitsConfig::default()).unwrap();
+        let output = cmd.output().await.unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec!["unlimited", "unlimited"]
+        );
+        assert_eq!(limits.violation(output.status), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cpu_limit_kill_is_reported() {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("while :; do :; done");
+        let limits = CommandLimits::apply(&mut cmd, &limits(0, 1, 0)).unwrap();
+        let status = tokio::time::timeout(std::time::Duration::from_secs(20), cmd.status())
+            .await
+            .expect("CPU limit should stop the loop")
+            .unwrap();
+        assert_eq!(limits.violation(status), Some(LimitViolation::CpuTime(1)));
+    }
+}
@@ -9,8 +9,13 @@
 //! OS-level isolation is provided through the [`Sandbox`] trait defined in
 //! [`traits`], with pluggable backends including Docker, Firejail, Bubblewrap,
 //! and Landlock. The [`create_sandbox`] function selects the best available
-//! backend at runtime. An [`AuditLogger`] records security-relevant events for
-//! forensic review.
+//! backend at runtime. [`CommandLimits`] applies the configured CPU, memory
+//! and subprocess limits to every spawned command, and [`EgressPolicy`] decides
+//! which hosts and addresses tool traffic and shell commands may reach.
+//! [`InjectionGuard`] tags and screens untrusted tool output before it reaches
+//! the model. An [`AuditLogger`] records
+//! security-relevant events for forensic review, optionally as an HMAC-signed
+//! hash chain that `zeroclaw audit verify` can check.
 //!
 //! # Extension
 //!
@@ -23,10 +28,13 @@ pub mod audit;
 pub mod bubblewrap;
 pub mod detect;
 pub mod docker;
+pub mod egress;
 #[cfg(target_os = "linux")]
 pub mod firejail;
+pub mod injection;
 #[cfg(feature = "sandbox-landlock")]
 pub mod landlock;
+pub mod limits;
 pub mod pairing;
 pub mod policy;
 pub mod secrets;
@@ -37,6 +45,12 @@ pub use audit::{AuditEvent, AuditEventType, AuditLogger};
 #[allow(unused_imports)]
 pub use detect::create_sandbox;
 #[allow(unused_imports)]
+pub use egress::EgressPolicy;
+#[allow(unused_imports)]
+pub use injection::InjectionGuard;
+#[allow(unused_imports)]
+pub use limits::{CommandLimits, LimitViolation};
+#[allow(unused_imports)]
 pub use pairing::PairingGuard;
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
@@ -6,8 +6,12 @@
 // that must be sent on all subsequent requests via `Authorization: Bearer <token>`.
 //
 // Already-paired tokens are persisted in config so restarts don't require
-// re-pairing.
+// re-pairing. Paired tokens have full access; named tokens from
+// `[[gateway.tokens]]` carry scopes, an optional expiry and a per-token
+// rate limit.
 
+use crate::config::{GatewayScope, GatewayTokenConfig};
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
@@ -24,6 +28,43 @@ const MAX_TRACKED_CLIENTS: usize = 1024;
 /// Per-client failed attempt counter with optional lockout timestamp.
 type FailedAttempts = HashMap<String, (u32, Option<Instant>)>;
 
+/// A named token loaded from `[[gateway.tokens]]`.
+#[derive(Debug, Clone)]
+struct ScopedToken {
+    name: String,
+    hash: String,
+    scopes: Vec<GatewayScope>,
+    expires_at: Option<DateTime<Utc>>,
+    rate_limit_per_minute: u32,
+}
+
+/// What an authenticated bearer token may do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenGrant {
+    /// Token name; `None` for paired tokens and when pairing is disabled.
+    pub name: Option<String>,
+    pub scopes: Vec<GatewayScope>,
+    /// Per-token request limit (0 = unlimited).
+    pub rate_limit_per_minute: u32,
+}
+
+impl TokenGrant {
+    fn full_access() -> Self {
+        Self {
+            name: None,
+            scopes: vec![GatewayScope::Admin],
+            rate_limit_per_minute: 0,
+        }
+    }
+
+    /// Whether this grant covers `scope`. `admin` covers everything.
+    pub fn allows(&self, scope: GatewayScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == GatewayScope::Admin)
+    }
+}
+
 /// Manages pairing state for the gateway.
 ///
 /// Bearer tokens are stored as SHA-256 hashes to prevent plaintext exposure
@@ -40,6 +81,8 @@ pub struct PairingGuard {
     paired_tokens: Arc<Mutex<HashSet<String>>>,
     /// Brute-force protection: per-client failed attempt counter + lockout time.
     failed_attempts: Arc<Mutex<FailedAttempts>>,
+    /// Named, scoped tokens from config (fixed for the process lifetime).
+    scoped_tokens: Arc<Vec<ScopedToken>>,
 }
 
 impl PairingGuard {
@@ -72,9 +115,42 @@ impl PairingGuard {
             pairing_code: Arc::new(Mutex::new(code)),
             paired_tokens: Arc::new(Mutex::new(tokens)),
             failed_attempts: Arc::new(Mutex::new(HashMap::new())),
+            scoped_tokens: Arc::new(Vec::new()),
         }
     }
 
+    /// Accept the named tokens from `[[gateway.tokens]]`. Entries with an
+    /// unparseable expiry are skipped (`Config::validate` rejects them).
+    pub fn with_scoped_tokens(mut self, tokens: &[GatewayTokenConfig]) -> Self {
+        let scoped = tokens
+            .iter()
+            .filter_map(|t| {
+                let expires_at = match t.expires_at.as_deref() {
+                    Some(raw) => match DateTime::parse_from_rfc3339(raw) {
+                        Ok(at) => Some(at.with_timezone(&Utc)),
+                        Err(e) => {
+                            tracing::warn!(
+                                "Skipping gateway token '{}': bad expires_at: {e}",
+                                t.name
+                            );
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+                Some(ScopedToken {
+                    name: t.name.clone(),
+                    hash: t.token_hash.to_ascii_lowercase(),
+                    scopes: t.scopes.clone(),
+                    expires_at,
+                    rate_limit_per_minute: t.rate_limit_per_minute,
+                })
+            })
+            .collect();
+        self.scoped_tokens = Arc::new(scoped);
+        self
+    }
+
     /// The one-time pairing code (only set when no tokens exist yet).
     pub fn pairing_code(&self) -> Option<String> {
         self.pairing_code.lock().clone()
@@ -166,12 +242,32 @@ impl PairingGuard {
 
     /// Check if a bearer token is valid (compares against stored hashes).
     pub fn is_authenticated(&self, token: &str) -> bool {
+        self.authenticate(token).is_some()
+    }
+
+    /// Resolve a bearer token to its grant. Paired tokens (and every request
+    /// when pairing is disabled) get full access; named tokens get their
+    /// configured scopes until they expire.
+    pub fn authenticate(&self, token: &str) -> Option<TokenGrant> {
         if !self.require_pairing {
-            return true;
+            return Some(TokenGrant::full_access());
         }
         let hashed = hash_token(token);
-        let tokens = self.paired_tokens.lock();
-        tokens.contains(&hashed)
+        if self.paired_tokens.lock().contains(&hashed) {
+            return Some(TokenGrant::full_access());
+        }
+        let scoped = self
+            .scoped_tokens
+            .iter()
+            .find(|t| constant_time_eq(&t.hash, &hashed))?;
+        if scoped.expires_at.is_some_and(|at| at <= Utc::now()) {
+            return None;
+        }
+        Some(TokenGrant {
+            name: Some(scoped.name.clone()),
+            scopes: scoped.scopes.clone(),
+            rate_limit_per_minute: scoped.rate_limit_per_minute,
+        })
     }
 
     /// Returns true if the gateway is already paired (has at least one token).
@@ -188,7 +284,7 @@ impl PairingGuard {
 }
 
 /// Generate a 6-digit numeric pairing code using cryptographically secure randomness.
-fn generate_code() -> String {
+pub(crate) fn generate_code() -> String {
     // UUID v4 uses getrandom (backed by /dev/urandom on Linux, BCryptGenRandom
     // on Windows) — a CSPRNG. We extract 4 bytes from it for a uniform random
     // number in [0, 1_000_000).
@@ -222,6 +318,13 @@ fn generate_token() -> String {
     format!("zc_{}", hex::encode(bytes))
 }
 
+/// Generate a bearer token and its storage hash (for named gateway tokens).
+pub fn issue_token() -> (String, String) {
+    let token = generate_token();
+    let hash = hash_token(&token);
+    (token, hash)
+}
+
 /// SHA-256 hash a bearer token for storage. Returns lowercase hex.
 fn hash_token(token: &str) -> String {
     format!("{:x}", Sha256::digest(token.as_bytes()))
@@ -366,6 +469,51 @@ mod tests {
         assert!(!guard.is_authenticated("wrong"));
     }
 
+    // ── Scoped tokens ────────────────────────────────────────
+
+    fn scoped(name: &str, hash: String, scopes: Vec<GatewayScope>) -> GatewayTokenConfig {
+        GatewayTokenConfig {
+            name: name.into(),
+            token_hash: hash,
+            scopes,
+            expires_at: None,
+            rate_limit_per_minute: 0,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    async fn scoped_token_grants_only_its_scopes() {
+        let (token, hash) = issue_token();
+        let mut config = scoped("frontend", hash, vec![GatewayScope::Chat]);
+        config.rate_limit_per_minute = 20;
+        let guard = PairingGuard::new(true, &["zc_admin".into()]).with_scoped_tokens(&[config]);
+
+        let grant = guard
+            .authenticate(&token)
+            .expect("scoped token should authenticate");
+        assert_eq!(grant.name.as_deref(), Some("frontend"));
+        assert_eq!(grant.rate_limit_per_minute, 20);
+        assert!(grant.allows(GatewayScope::Chat));
+        assert!(!grant.allows(GatewayScope::Read));
+        assert!(!grant.allows(GatewayScope::Admin));
+
+        let admin = guard.authenticate("zc_admin").expect("paired token");
+        assert!(admin.name.is_none());
+        assert!(admin.allows(GatewayScope::Read));
+        assert!(admin.allows(GatewayScope::Chat));
+    }
+
+    #[test]
+    async fn expired_scoped_token_is_rejected() {
+        let (token, hash) = issue_token();
+        let mut config = scoped("old", hash, vec![GatewayScope::Read]);
+        config.expires_at = Some("2001-01-01T00:00:00Z".into());
+        let guard = PairingGuard::new(true, &[]).with_scoped_tokens(&[config]);
+        assert!(guard.authenticate(&token).is_none());
+        assert!(!guard.is_authenticated(&token));
+    }
+
     // ── Token hashing ────────────────────────────────────────
 
     #[test]
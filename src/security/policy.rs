@@ -1,7 +1,9 @@
+use crate::security::EgressPolicy;
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
 /// How much autonomy the agent has
@@ -91,6 +93,8 @@ pub struct SecurityPolicy {
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    /// Network egress policy shared by every tool that makes connections
+    pub egress: Arc<EgressPolicy>,
     pub tracker: ActionTracker,
 }
 
@@ -143,6 +147,7 @@ impl Default for SecurityPolicy {
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            egress: Arc::new(EgressPolicy::default()),
             tracker: ActionTracker::new(),
         }
     }
@@ -536,6 +541,10 @@ impl SecurityPolicy {
             return Err(format!("Command not allowed by security policy: {command}"));
         }
 
+        self.egress
+            .check_command(command)
+            .map_err(|e| e.to_string())?;
+
         let risk = self.command_risk_level(command);
 
         if risk == CommandRiskLevel::High {
@@ -837,9 +846,17 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            egress: Arc::new(EgressPolicy::default()),
             tracker: ActionTracker::new(),
         }
     }
+
+    /// Replace the default egress policy (private networks and metadata
+    /// endpoints blocked) with one built from `[security.egress]`.
+    pub fn with_egress(mut self, egress: &crate::config::EgressConfig) -> Self {
+        self.egress = Arc::new(EgressPolicy::from_config(egress));
+        self
+    }
 }
 
 #[cfg(test)]
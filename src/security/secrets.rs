@@ -157,6 +157,21 @@ impl SecretStore {
             .context("Decrypted legacy secret is not valid UTF-8 — wrong key or corrupt data")
     }
 
+    /// Derive a purpose-bound 256-bit key from the store's master key
+    /// (`HMAC-SHA256(master, context)`), creating the master key if needed.
+    ///
+    /// Works whether or not secret encryption is enabled, so features such as
+    /// audit-log signing can share the same key file.
+    pub fn derive_key(&self, context: &str) -> Result<Vec<u8>> {
+        use hmac::{Hmac, Mac};
+
+        let master = self.load_or_create_key()?;
+        let mut mac = <Hmac<sha2::Sha256> as Mac>::new_from_slice(&master)
+            .context("Secret key has an invalid length")?;
+        mac.update(context.as_bytes());
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
     /// Check if a value is already encrypted (current or legacy format).
     pub fn is_encrypted(value: &str) -> bool {
         value.starts_with("enc2:") || value.starts_with("enc:")
@@ -848,4 +863,14 @@ mod tests {
             "Key file must be owner-only (0600)"
         );
     }
+
+    #[test]
+    fn derive_key_is_stable_and_context_bound() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), false);
+        let audit = store.derive_key("audit-log").unwrap();
+        assert_eq!(audit.len(), 32);
+        assert_eq!(audit, store.derive_key("audit-log").unwrap());
+        assert_ne!(audit, store.derive_key("other").unwrap());
+    }
 }
@@ -433,6 +433,9 @@ impl BrowserTool {
             anyhow::bail!("Host '{host}' not in browser.allowed_domains");
         }
 
+        // The browser resolves names itself, so only the pre-flight check applies here
+        self.security.egress.check_url(url)?;
+
         Ok(())
     }
 
@@ -49,6 +49,8 @@ impl BrowserOpenTool {
             anyhow::bail!("Host '{host}' is not in browser.allowed_domains");
         }
 
+        self.security.egress.check_url(url)?;
+
         Ok(url.to_string())
     }
 }
@@ -54,8 +54,8 @@ impl ComposioTool {
         }
     }
 
-    fn client(&self) -> Client {
-        crate::config::build_runtime_proxy_client_with_timeouts("tool.composio", 60, 10)
+    fn client(&self) -> anyhow::Result<Client> {
+        self.security.egress.build_client("tool.composio", 60, 10)
     }
 
     /// List available Composio apps/actions for the authenticated user.
@@ -82,7 +82,7 @@ impl ComposioTool {
     async fn list_actions_v3(&self, app_name: Option<&str>) -> anyhow::Result<Vec<ComposioAction>> {
         let url = format!("{COMPOSIO_API_BASE_V3}/tools");
         let req = self
-            .client()
+            .client()?
             .get(&url)
             .header("x-api-key", &self.api_key)
             .query(&Self::build_list_actions_v3_query(app_name));
@@ -107,7 +107,7 @@ impl ComposioTool {
         }
 
         let resp = self
-            .client()
+            .client()?
             .get(&url)
             .header("x-api-key", &self.api_key)
             .send()
@@ -132,7 +132,7 @@ impl ComposioTool {
         entity_id: Option<&str>,
     ) -> anyhow::Result<Vec<ComposioConnectedAccount>> {
         let url = format!("{COMPOSIO_API_BASE_V3}/connected_accounts");
-        let mut req = self.client().get(&url).header("x-api-key", &self.api_key);
+        let mut req = self.client()?.get(&url).header("x-api-key", &self.api_key);
 
         req = req.query(&[
             ("limit", "50"),
@@ -342,7 +342,7 @@ impl ComposioTool {
         ensure_https(&url)?;
 
         let resp = self
-            .client()
+            .client()?
             .post(&url)
             .header("x-api-key", &self.api_key)
             .json(&body)
@@ -378,7 +378,7 @@ impl ComposioTool {
         }
 
         let resp = self
-            .client()
+            .client()?
             .post(&url)
             .header("x-api-key", &self.api_key)
             .json(&body)
@@ -450,7 +450,7 @@ impl ComposioTool {
         });
 
         let resp = self
-            .client()
+            .client()?
             .post(&url)
             .header("x-api-key", &self.api_key)
             .json(&body)
@@ -487,7 +487,7 @@ impl ComposioTool {
         });
 
         let resp = self
-            .client()
+            .client()?
             .post(&url)
             .header("x-api-key", &self.api_key)
             .json(&body)
@@ -515,7 +515,7 @@ impl ComposioTool {
         let url = format!("{COMPOSIO_API_BASE_V3}/auth_configs");
 
         let resp = self
-            .client()
+            .client()?
             .get(&url)
             .header("x-api-key", &self.api_key)
             .query(&[
@@ -1,6 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{self, DeliveryConfig, ExecutionMode, JobType, Schedule, SessionTarget};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -71,6 +71,11 @@ impl Tool for CronAddTool {
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
+                "execution_mode": {
+                    "type": "string",
+                    "enum": ["direct", "batch"],
+                    "description": "Agent jobs only: 'batch' submits the prompt through the provider batch API (anthropic/openai) for discounted, asynchronous results"
+                },
                 "delivery": { "type": "object" },
                 "delete_after_run": { "type": "boolean" },
                 "approved": {
@@ -204,6 +209,20 @@ impl Tool for CronAddTool {
                     .and_then(serde_json::Value::as_str)
                     .map(str::to_string);
 
+                let execution_mode = match args.get("execution_mode") {
+                    Some(v) => match serde_json::from_value::<ExecutionMode>(v.clone()) {
+                        Ok(mode) => mode,
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid execution_mode: {e}")),
+                            });
+                        }
+                    },
+                    None => ExecutionMode::Direct,
+                };
+
                 let delivery = match args.get("delivery") {
                     Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
                         Ok(cfg) => Some(cfg),
@@ -229,6 +248,7 @@ impl Tool for CronAddTool {
                     prompt,
                     session_target,
                     model,
+                    execution_mode,
                     delivery,
                     delete_after_run,
                 )
@@ -27,6 +27,8 @@ struct RunView {
     status: String,
     output: Option<String>,
     duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost_usd: Option<f64>,
 }
 
 #[async_trait]
@@ -87,6 +89,7 @@ impl Tool for CronRunsTool {
                         status: run.status,
                         output: run.output.map(|out| truncate(&out, MAX_RUN_OUTPUT_CHARS)),
                         duration_ms: run.duration_ms,
+                        cost_usd: run.cost_usd,
                     })
                     .collect();
 
@@ -4,7 +4,7 @@ use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
-use crate::security::SecurityPolicy;
+use crate::security::{InjectionGuard, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -33,6 +33,8 @@ pub struct DelegateTool {
     parent_tools: Arc<Vec<Arc<dyn Tool>>>,
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
+    /// Inherited prompt-injection guard for sub-agent loops.
+    injection_guard: Option<Arc<InjectionGuard>>,
 }
 
 impl DelegateTool {
@@ -63,6 +65,7 @@ impl DelegateTool {
             depth: 0,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            injection_guard: None,
         }
     }
 
@@ -99,6 +102,7 @@ impl DelegateTool {
             depth,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            injection_guard: None,
         }
     }
 
@@ -113,6 +117,12 @@ impl DelegateTool {
         self.multimodal_config = config;
         self
     }
+
+    /// Attach the prompt-injection guard for sub-agent tool loops.
+    pub fn with_injection_guard(mut self, guard: Option<Arc<InjectionGuard>>) -> Self {
+        self.injection_guard = guard;
+        self
+    }
 }
 
 #[async_trait]
@@ -411,6 +421,9 @@ impl DelegateTool {
                 None,
                 None,
                 &[],
+                crate::config::ReasoningDisplay::Hide,
+                self.injection_guard.as_deref(),
+                None,
             ),
         )
         .await;
@@ -601,6 +614,7 @@ mod tests {
                     text: Some("done".to_string()),
                     tool_calls: Vec::new(),
                     usage: None,
+                    reasoning: None,
                 })
             } else {
                 Ok(ChatResponse {
@@ -611,6 +625,7 @@ mod tests {
                         arguments: "{\"value\":\"ping\"}".to_string(),
                     }],
                     usage: None,
+                    reasoning: None,
                 })
             }
         }
@@ -644,6 +659,7 @@ mod tests {
                     arguments: "{\"value\":\"x\"}".to_string(),
                 }],
                 usage: None,
+                reasoning: None,
             })
         }
     }
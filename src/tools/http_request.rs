@@ -60,6 +60,8 @@ impl HttpRequestTool {
             anyhow::bail!("Host '{host}' is not in http_request.allowed_domains");
         }
 
+        self.security.egress.check_url(url)?;
+
         Ok(url.to_string())
     }
 
@@ -122,9 +124,13 @@ impl HttpRequestTool {
         };
         let builder = reqwest::Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
-            .connect_timeout(Duration::from_secs(10))
-            .redirect(reqwest::redirect::Policy::none());
+            .connect_timeout(Duration::from_secs(10));
         let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.http_request");
+        let builder = self
+            .security
+            .egress
+            .apply_to_builder(builder)
+            .redirect(reqwest::redirect::Policy::none());
         let client = builder.build()?;
 
         let mut request = client.request(method, url);
@@ -523,6 +529,23 @@ mod tests {
         assert!(err.contains("allowed_domains"));
     }
 
+    #[test]
+    fn validate_applies_shared_egress_policy() {
+        let security = Arc::new(SecurityPolicy::default().with_egress(
+            &crate::config::EgressConfig {
+                denied_hosts: vec!["internal.example.com".into()],
+                ..Default::default()
+            },
+        ));
+        let tool = HttpRequestTool::new(security, vec!["example.com".into()], 1_000_000, 30);
+        assert!(tool.validate_url("https://example.com/").is_ok());
+        let err = tool
+            .validate_url("https://internal.example.com/admin")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("denied_hosts"));
+    }
+
     #[test]
     fn validate_rejects_localhost() {
         let tool = test_tool(vec!["localhost"]);
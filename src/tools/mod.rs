@@ -39,6 +39,7 @@ pub mod image_info;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
+pub mod mqtt_publish;
 pub mod pdf_read;
 pub mod proxy_config;
 pub mod pushover;
@@ -72,6 +73,7 @@ pub use image_info::ImageInfoTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
+pub use mqtt_publish::MqttPublishTool;
 pub use pdf_read::PdfReadTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
@@ -88,7 +90,8 @@ pub use web_search_tool::WebSearchTool;
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
-use crate::security::SecurityPolicy;
+use crate::security::audit::AuditEvent;
+use crate::security::{AuditLogger, SecurityPolicy};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -123,6 +126,66 @@ impl Tool for ArcDelegatingTool {
     }
 }
 
+/// Records every call of the wrapped tool in the audit log.
+struct AuditedTool {
+    inner: Arc<dyn Tool>,
+    audit: Arc<AuditLogger>,
+}
+
+impl AuditedTool {
+    fn wrap(inner: Arc<dyn Tool>, audit: &Arc<AuditLogger>) -> Arc<dyn Tool> {
+        Arc::new(Self {
+            inner,
+            audit: audit.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for AuditedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let started = std::time::Instant::now();
+        let result = self.inner.execute(args.clone()).await;
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let (success, error) = match &result {
+            Ok(outcome) => (outcome.success, outcome.error.clone()),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        let allowed = !error.as_deref().is_some_and(is_policy_denial);
+        let event = AuditEvent::tool_call(
+            self.name(),
+            &args,
+            crate::security::audit::tool_call_approved(),
+            allowed,
+        )
+        .with_result(success, None, duration_ms, error);
+        if let Err(e) = self.audit.log_async(event).await {
+            tracing::warn!("Failed to write audit event: {e}");
+        }
+        result
+    }
+}
+
+/// Does a tool error report a refusal by the security policy?
+fn is_policy_denial(error: &str) -> bool {
+    const PREFIXES: [&str; 3] = ["Action blocked", "Rate limit exceeded", "Security policy"];
+    PREFIXES.iter().any(|prefix| error.starts_with(prefix))
+        || error.contains("not allowed by security policy")
+        || error.contains("allowed_domains")
+}
+
 fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
     tools.into_iter().map(ArcDelegatingTool::boxed).collect()
 }
@@ -193,8 +256,30 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let mut shell = ShellTool::new(security.clone(), runtime)
+        .with_resource_limits(root_config.security.resources.clone());
+    let audit = if root_config.security.audit.enabled {
+        root_config.config_path.parent().and_then(|zeroclaw_dir| {
+            match AuditLogger::new(
+                root_config.security.audit.clone(),
+                zeroclaw_dir.to_path_buf(),
+            ) {
+                Ok(logger) => Some(Arc::new(logger)),
+                Err(e) => {
+                    tracing::warn!("Audit logging disabled: {e}");
+                    None
+                }
+            }
+        })
+    } else {
+        None
+    };
+    if let Some(audit) = &audit {
+        shell = shell.with_audit_logger(audit.clone());
+    }
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(shell),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -258,12 +343,25 @@ pub fn all_tools_with_runtime(
 
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
-        tool_arcs.push(Arc::new(WebSearchTool::new(
-            root_config.web_search.provider.clone(),
-            root_config.web_search.brave_api_key.clone(),
-            root_config.web_search.max_results,
-            root_config.web_search.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            WebSearchTool::new(
+                root_config.web_search.provider.clone(),
+                root_config.web_search.brave_api_key.clone(),
+                root_config.web_search.max_results,
+                root_config.web_search.timeout_secs,
+            )
+            .with_egress(security.egress.clone()),
+        ));
+    }
+
+    // MQTT publishing, limited to the configured topic filters
+    if let Some(mqtt) = root_config.channels_config.mqtt.as_ref() {
+        if !mqtt.publish_topics.is_empty() {
+            tool_arcs.push(Arc::new(MqttPublishTool::new(
+                mqtt.clone(),
+                security.clone(),
+            )));
+        }
     }
 
     // PDF extraction (feature-gated at compile time via rag-pdf)
@@ -283,6 +381,20 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // Shell writes its own, richer events; every other tool call is recorded here.
+    if let Some(audit) = &audit {
+        tool_arcs = tool_arcs
+            .into_iter()
+            .map(|tool| {
+                if tool.name() == "shell" {
+                    tool
+                } else {
+                    AuditedTool::wrap(tool, audit)
+                }
+            })
+            .collect();
+    }
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -305,12 +417,22 @@ pub fn all_tools_with_runtime(
                     .parent()
                     .map(std::path::PathBuf::from),
                 secrets_encrypt: root_config.secrets.encrypt,
-                reasoning_enabled: root_config.runtime.reasoning_enabled,
+                reasoning: crate::providers::reasoning::ReasoningSettings::from_runtime(
+                    &root_config.runtime,
+                ),
             },
         )
         .with_parent_tools(parent_tools)
-        .with_multimodal_config(root_config.multimodal.clone());
-        tool_arcs.push(Arc::new(delegate_tool));
+        .with_multimodal_config(root_config.multimodal.clone())
+        .with_injection_guard(
+            crate::security::InjectionGuard::from_config(&root_config.security.injection)
+                .map(Arc::new),
+        );
+        let delegate_tool: Arc<dyn Tool> = Arc::new(delegate_tool);
+        tool_arcs.push(match &audit {
+            Some(audit) => AuditedTool::wrap(delegate_tool, audit),
+            None => delegate_tool,
+        });
     }
 
     boxed_registry_from_arcs(tool_arcs)
@@ -375,6 +497,107 @@ mod tests {
         assert!(names.contains(&"schedule"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
+        assert!(!names.contains(&"mqtt_publish"));
+    }
+
+    #[test]
+    fn all_tools_includes_mqtt_publish_when_topics_configured() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        cfg.channels_config.mqtt = Some(crate::config::MqttConfig {
+            host: "broker.local".into(),
+            port: None,
+            tls: false,
+            verify_tls: None,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            client_id: None,
+            username: None,
+            password: None,
+            protocol: crate::config::MqttProtocol::V311,
+            topics: vec![],
+            response_topic: None,
+            qos: 1,
+            keep_alive_secs: 30,
+            publish_topics: vec!["home/#".into()],
+        });
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert!(names.contains(&"mqtt_publish"));
+    }
+
+    #[tokio::test]
+    async fn all_tools_record_non_shell_calls_in_audit_log() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        cfg.security.audit.enabled = true;
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let file_read = tools.iter().find(|t| t.name() == "file_read").unwrap();
+        file_read
+            .execute(serde_json::json!({"path": "missing.txt"}))
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let event: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(event["tool"], "file_read");
+        assert_eq!(event["event_type"], "file_access");
+        assert!(event["action"]["command"]
+            .as_str()
+            .unwrap()
+            .contains("missing.txt"));
+        assert_eq!(event["action"]["approved"], false);
+        assert_eq!(event["action"]["allowed"], true);
+    }
+
+    #[test]
+    fn policy_denials_are_recognised_in_tool_errors() {
+        assert!(is_policy_denial("Action blocked: autonomy is read-only"));
+        assert!(is_policy_denial(
+            "Path not allowed by security policy: /etc"
+        ));
+        assert!(!is_policy_denial("Failed to read file: not found"));
     }
 
     #[test]
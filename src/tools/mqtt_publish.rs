@@ -0,0 +1,203 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MqttConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Publish a message to the configured MQTT broker.
+///
+/// Topics are restricted to the `publish_topics` filters in
+/// `[channels_config.mqtt]`.
+pub struct MqttPublishTool {
+    config: MqttConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl MqttPublishTool {
+    pub fn new(config: MqttConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn topic_allowed(&self, topic: &str) -> bool {
+        self.config
+            .publish_topics
+            .iter()
+            .any(|filter| crate::channels::mqtt::topic_matches(filter, topic))
+    }
+}
+
+#[async_trait]
+impl Tool for MqttPublishTool {
+    fn name(&self) -> &str {
+        "mqtt_publish"
+    }
+
+    fn description(&self) -> &str {
+        "Publish a message to an MQTT topic on the configured broker (e.g. to control home-automation devices)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {
+                    "type": "string",
+                    "description": format!(
+                        "Topic to publish to. Allowed filters: {}",
+                        self.config.publish_topics.join(", ")
+                    )
+                },
+                "payload": {
+                    "type": "string",
+                    "description": "Message payload (text or JSON)"
+                },
+                "retain": {
+                    "type": "boolean",
+                    "description": "Ask the broker to retain the message for new subscribers (default: false)"
+                }
+            },
+            "required": ["topic", "payload"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        let topic = args
+            .get("topic")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'topic' parameter"))?;
+        let payload = args
+            .get("payload")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'payload' parameter"))?;
+        let retain = args
+            .get("retain")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if topic.contains(['+', '#']) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Topic must not contain wildcards".into()),
+            });
+        }
+        if !self.topic_allowed(topic) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Topic '{topic}' is not in publish_topics")),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        match crate::channels::mqtt::publish_once(&self.config, topic, payload.as_bytes(), retain)
+            .await
+        {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Published {} bytes to {topic}", payload.len()),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("MQTT publish failed: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn config(publish_topics: Vec<String>) -> MqttConfig {
+        MqttConfig {
+            host: "127.0.0.1".into(),
+            port: Some(1),
+            tls: false,
+            verify_tls: None,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            client_id: None,
+            username: None,
+            password: None,
+            protocol: crate::config::MqttProtocol::V311,
+            topics: vec![],
+            response_topic: None,
+            qos: 1,
+            keep_alive_secs: 30,
+            publish_topics,
+        }
+    }
+
+    fn tool(publish_topics: Vec<String>) -> MqttPublishTool {
+        MqttPublishTool::new(config(publish_topics), Arc::new(SecurityPolicy::default()))
+    }
+
+    #[test]
+    fn mqtt_publish_tool_name_and_schema() {
+        let tool = tool(vec!["home/#".into()]);
+        assert_eq!(tool.name(), "mqtt_publish");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["topic"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("home/#"));
+        assert_eq!(schema["required"], json!(["topic", "payload"]));
+    }
+
+    #[tokio::test]
+    async fn mqtt_publish_rejects_topics_outside_filters() {
+        let result = tool(vec!["home/+/set".into()])
+            .execute(json!({"topic": "office/lamp/set", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not in publish_topics"));
+    }
+
+    #[tokio::test]
+    async fn mqtt_publish_rejects_wildcard_topics() {
+        let result = tool(vec!["home/#".into()])
+            .execute(json!({"topic": "home/#", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn mqtt_publish_blocked_when_read_only() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = MqttPublishTool::new(config(vec!["home/#".into()]), security);
+        let result = tool
+            .execute(json!({"topic": "home/lamp", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
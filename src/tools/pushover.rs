@@ -173,11 +173,11 @@ impl Tool for PushoverTool {
             form = form.text("sound", sound);
         }
 
-        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        let client = self.security.egress.build_client(
             "tool.pushover",
             PUSHOVER_REQUEST_TIMEOUT_SECS,
             10,
-        );
+        )?;
         let response = client.post(PUSHOVER_API_URL).multipart(form).send().await?;
 
         let status = response.status();
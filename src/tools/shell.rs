@@ -1,11 +1,15 @@
 use super::traits::{Tool, ToolResult};
+use crate::config::ResourceLimitsConfig;
 use crate::runtime::RuntimeAdapter;
-use crate::security::SecurityPolicy;
+use crate::security::policy::CommandRiskLevel;
+use crate::security::{
+    egress, AuditEvent, AuditEventType, AuditLogger, CommandLimits, SecurityPolicy,
+};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Maximum shell command execution time before kill.
 const SHELL_TIMEOUT_SECS: u64 = 60;
@@ -21,11 +25,58 @@ const SAFE_ENV_VARS: &[&str] = &[
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
+    resources: ResourceLimitsConfig,
+    audit: Option<Arc<AuditLogger>>,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security,
+            runtime,
+            resources: ResourceLimitsConfig::default(),
+            audit: None,
+        }
+    }
+
+    /// Apply `[security.resources]` limits to every command
+    pub fn with_resource_limits(mut self, resources: ResourceLimitsConfig) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Record every executed or denied command in the audit log
+    pub fn with_audit_logger(mut self, audit: Arc<AuditLogger>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// `outcome` is `(success, duration_ms)` for executed commands, `None` when denied by policy
+    async fn audit(&self, command: &str, approved: bool, outcome: Option<(bool, u64)>) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let risk = match self.security.command_risk_level(command) {
+            CommandRiskLevel::Low => "low",
+            CommandRiskLevel::Medium => "medium",
+            CommandRiskLevel::High => "high",
+        };
+        let event = match outcome {
+            Some((success, duration_ms)) => AuditEvent::new(AuditEventType::CommandExecution)
+                .with_action(command.to_string(), risk.to_string(), approved, true)
+                .with_result(success, None, duration_ms, None),
+            None => AuditEvent::new(AuditEventType::PolicyViolation).with_action(
+                command.to_string(),
+                risk.to_string(),
+                approved,
+                false,
+            ),
+        }
+        .with_actor("agent".to_string(), None, None)
+        .with_tool("shell");
+        if let Err(e) = audit.log_async(event).await {
+            tracing::warn!("Failed to write audit event: {e}");
+        }
     }
 }
 
@@ -106,6 +157,7 @@ impl Tool for ShellTool {
         match self.security.validate_command_execution(command, approved) {
             Ok(_) => {}
             Err(reason) => {
+                self.audit(command, approved, None).await;
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -146,8 +198,34 @@ impl Tool for ShellTool {
             }
         }
 
+        let limits = match CommandLimits::apply(&mut cmd, &self.resources) {
+            Ok(limits) => limits,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to apply resource limits: {e}")),
+                });
+            }
+        };
+        cmd.kill_on_drop(true);
+        if !self.security.egress.shell_network() && self.runtime.name() == "native" {
+            if let Err(e) = egress::isolate_network(&mut cmd) {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to isolate network: {e}")),
+                });
+            }
+        }
+
+        let started = Instant::now();
         let result =
             tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        let success = matches!(&result, Ok(Ok(output)) if output.status.success());
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.audit(command, approved, Some((success, duration_ms)))
+            .await;
 
         match result {
             Ok(Ok(output)) => {
@@ -164,6 +242,14 @@ impl Tool for ShellTool {
                     stderr.push_str("\n... [stderr truncated at 1MB]");
                 }
 
+                if let Some(violation) = limits.violation(output.status) {
+                    tracing::warn!(command, "{violation}");
+                    if !stderr.is_empty() {
+                        stderr.push('\n');
+                    }
+                    stderr.push_str(&violation.to_string());
+                }
+
                 Ok(ToolResult {
                     success: output.status.success(),
                     output: stdout,
@@ -360,6 +446,110 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_applies_resource_limits() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["ulimit".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool =
+            ShellTool::new(security, test_runtime()).with_resource_limits(ResourceLimitsConfig {
+                max_cpu_time_seconds: 7,
+                ..ResourceLimitsConfig::default()
+            });
+
+        let result = tool
+            .execute(json!({"command": "ulimit -t"}))
+            .await
+            .expect("ulimit command should succeed");
+        assert!(result.success);
+        assert_eq!(result.output.trim(), "7");
+    }
+
+    #[tokio::test]
+    async fn shell_blocks_metadata_endpoint() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            allowed_commands: vec!["curl".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = ShellTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"command": "curl -s http://169.254.169.254/latest/meta-data/"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("cloud metadata endpoint"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn shell_without_network_runs_in_empty_namespace() {
+        let security = Arc::new(
+            SecurityPolicy {
+                autonomy: AutonomyLevel::Full,
+                workspace_dir: std::env::temp_dir(),
+                allowed_commands: vec!["cat".into()],
+                ..SecurityPolicy::default()
+            }
+            .with_egress(&crate::config::EgressConfig {
+                shell_network: false,
+                ..Default::default()
+            }),
+        );
+        let tool = ShellTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"command": "cat /proc/net/dev"}))
+            .await
+            .unwrap();
+        if !result.success {
+            // Namespaces unavailable in this environment; the command must not run
+            assert!(result.output.is_empty());
+            return;
+        }
+        let interfaces = result.output.lines().skip(2).count();
+        assert_eq!(interfaces, 1, "only loopback expected:\n{}", result.output);
+    }
+
+    #[tokio::test]
+    async fn shell_records_executed_and_denied_commands_in_audit_log() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let audit = crate::config::AuditConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let logger = Arc::new(AuditLogger::new(audit, tmp.path().to_path_buf()).unwrap());
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime())
+            .with_audit_logger(logger);
+
+        tool.execute(json!({"command": "echo audited"}))
+            .await
+            .unwrap();
+        tool.execute(json!({"command": "rm -rf /"})).await.unwrap();
+
+        let content = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let events: Vec<AuditEvent> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].tool.as_deref(), Some("shell"));
+        assert!(events[0].result.as_ref().unwrap().success);
+        assert!(!events[1].action.as_ref().unwrap().allowed);
+        assert!(matches!(
+            events[1].event_type,
+            AuditEventType::PolicyViolation
+        ));
+    }
+
     #[tokio::test]
     async fn shell_preserves_path_and_home() {
         let tool = ShellTool::new(test_security_with_env_cmd(), test_runtime());
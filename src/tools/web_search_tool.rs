@@ -1,7 +1,9 @@
 use super::traits::{Tool, ToolResult};
+use crate::security::EgressPolicy;
 use async_trait::async_trait;
 use regex::Regex;
 use serde_json::json;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Web search tool for searching the internet.
@@ -11,6 +13,7 @@ pub struct WebSearchTool {
     brave_api_key: Option<String>,
     max_results: usize,
     timeout_secs: u64,
+    egress: Arc<EgressPolicy>,
 }
 
 impl WebSearchTool {
@@ -25,17 +28,24 @@ impl WebSearchTool {
             brave_api_key,
             max_results: max_results.clamp(1, 10),
             timeout_secs: timeout_secs.max(1),
+            egress: Arc::new(EgressPolicy::default()),
         }
     }
 
+    /// Apply the shared `[security.egress]` policy to search requests
+    pub fn with_egress(mut self, egress: Arc<EgressPolicy>) -> Self {
+        self.egress = egress;
+        self
+    }
+
     async fn search_duckduckgo(&self, query: &str) -> anyhow::Result<String> {
         let encoded_query = urlencoding::encode(query);
         let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
 
-        let client = reqwest::Client::builder()
+        let builder = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .build()?;
+            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+        let client = self.egress.apply_to_builder(builder).build()?;
 
         let response = client.get(&search_url).send().await?;
 
@@ -110,9 +120,8 @@ impl WebSearchTool {
             encoded_query, self.max_results
         );
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .build()?;
+        let builder = reqwest::Client::builder().timeout(Duration::from_secs(self.timeout_secs));
+        let client = self.egress.apply_to_builder(builder).build()?;
 
         let response = client
             .get(&search_url)
@@ -65,6 +65,7 @@ impl Provider for MockProvider {
                 text: Some("done".into()),
                 tool_calls: vec![],
                 usage: None,
+                reasoning: None,
             });
         }
         Ok(guard.remove(0))
@@ -190,6 +191,7 @@ impl Provider for RecordingProvider {
                 text: Some("done".into()),
                 tool_calls: vec![],
                 usage: None,
+                reasoning: None,
             });
         }
         Ok(guard.remove(0))
@@ -238,6 +240,7 @@ fn text_response(text: &str) -> ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
         usage: None,
+        reasoning: None,
     }
 }
 
@@ -246,6 +249,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
         usage: None,
+        reasoning: None,
     }
 }
 
@@ -370,6 +374,7 @@ async fn e2e_xml_dispatcher_tool_call() {
             ),
             tool_calls: vec![],
             usage: None,
+            reasoning: None,
         },
         text_response("XML tool executed"),
     ]));